@@ -0,0 +1,61 @@
+// libFuzzer target for the field script readers (FieldScript). Each input is
+// tried both as an LZS blob and as a decompressed field; every script slot is
+// walked the way the randomizer's passes walk them. Any out-of-bounds read,
+// bad length or runaway walk aborts.
+//
+//   cmake -DGOLDSAUCER_FUZZ=ON -DCMAKE_CXX_COMPILER=clang++ ..
+//   ./field_script_fuzz -max_total_time=600 corpus/
+//
+// A few vanilla fields (decompressed and raw) make a good starting corpus.
+
+#include "FieldScript.h"
+
+#include <QSet>
+#include <cstdint>
+#include <cstdlib>
+#include <cstring>
+
+namespace {
+
+void walkScripts(const QByteArray& field)
+{
+    FieldScript::ScriptRange range;
+    if (!FieldScript::scriptRange(field, range)) return;
+    if (range.dataStart < 0 || range.offsetTable + 64 * range.entityCount > field.size()
+        || range.walkEnd > field.size())
+        abort();
+
+    QSet<quint16> seen;
+    for (int e = 0; e < range.entityCount; ++e) {
+        quint16 slot[32];
+        memcpy(slot, field.constData() + range.offsetTable + 64 * e, 64);
+        for (int s = 0; s < 32; ++s) {
+            if (seen.contains(slot[s])) continue;
+            seen.insert(slot[s]);
+            int pos = range.dataStart + slot[s];
+            while (pos < range.walkEnd) {
+                const int len = FieldScript::opcodeLength(field, pos, range.walkEnd);
+                if (len <= 0) break;
+                if (pos + len > field.size()) abort();
+                pos += len;   // len >= 1, so the walk always ends
+            }
+        }
+    }
+}
+
+} // namespace
+
+extern "C" int LLVMFuzzerTestOneInput(const uint8_t* data, size_t size)
+{
+    const QByteArray input(reinterpret_cast<const char*>(data), static_cast<int>(size));
+
+    // Opcode lengths at every position, with bounds past the buffer too
+    for (int pos = 0; pos < input.size(); ++pos) {
+        const int len = FieldScript::opcodeLength(input, pos, input.size() + 64);
+        if (len == 0 || pos + len > input.size()) abort();
+    }
+
+    walkScripts(input);
+    walkScripts(FieldScript::lzsDecompress(input));
+    return 0;
+}
@@ -0,0 +1,299 @@
+#include "ArmorRandomizer.h"
+#include "Randomizer.h"
+#include "Config.h"
+#include "GameFileLocator.h"
+#include "KernelBinValidator.h"
+#include "FieldPickupRandomizer_ff7tk.h"
+#include <QFile>
+#include <QSaveFile>
+#include <QDir>
+#include <QFileInfo>
+#include <QDebug>
+#include <QDateTime>
+#include <QStringList>
+#include <ff7tk/utils/GZIP.h>
+#include <algorithm>
+#include <cmath>
+#include <cstring>
+#include <numeric>
+
+ArmorRandomizer::ArmorRandomizer(Randomizer* parent)
+    : m_parent(parent)
+{
+}
+
+bool ArmorRandomizer::randomize()
+{
+    const Config& config = m_parent->m_config;
+    const int statsMode = config.getRandomizeArmorStats();
+    const int slotsMode = config.getRandomizeArmorSlots();
+    m_kernelRoundtrip.clear();
+    if (statsMode == Config::ArmorVanilla && slotsMode == Config::ArmorVanilla)
+        return true;
+    m_rng.seed(m_parent->streamSeed("armor"));
+
+    // --- find kernel.bin -----------------------------------------------------
+    // Same relative path as the starting equipment pass, so a kernel.bin it
+    // already wrote is built on rather than replaced
+    const QString ff7Path = m_parent->getFF7Path();
+    const QString outputPath = m_parent->getOutputPath();
+    const QString kernelPath = GameFileLocator::findKernelBin(ff7Path, outputPath);
+    if (kernelPath.isEmpty()) {
+        qDebug() << "Armor: kernel.bin not found";
+        return false;
+    }
+    const QString outKernel = outputPath + kernelPath.mid(ff7Path.length());
+    const QString sourcePath = QFile::exists(outKernel) ? outKernel : kernelPath;
+
+    QFile in(sourcePath);
+    if (!in.open(QIODevice::ReadOnly)) {
+        m_parent->reportInputError(Randomizer::InputError::KernelParse, "kernel.bin",
+                                   "cannot be opened");
+        return false;
+    }
+    const QByteArray raw = in.readAll();
+    in.close();
+
+    // --- walk the section headers (see StartingEquipmentRandomizer) ----------
+    const int headerSize = KernelBinValidator::KERNEL_SECTION_HEADER_SIZE;
+    struct KSection { int offset; quint16 compSize; quint16 decSize; };
+    QVector<KSection> sections;
+    int pos = 0;
+    while (pos + headerSize <= raw.size() && sections.size() < 9) {
+        KSection ks;
+        ks.offset = pos;
+        memcpy(&ks.compSize, raw.constData() + pos, 2);
+        memcpy(&ks.decSize,  raw.constData() + pos + 2, 2);
+        if (pos + headerSize + ks.compSize > raw.size()) break;
+        sections.append(ks);
+        pos += headerSize + ks.compSize;
+    }
+    if (sections.size() <= ARMOR_SECTION) {
+        m_parent->reportInputError(Randomizer::InputError::KernelParse, "kernel.bin",
+                                   QString("only %1 sections, need at least %2")
+                                       .arg(sections.size()).arg(ARMOR_SECTION + 1));
+        return false;
+    }
+
+    const KSection& sec = sections[ARMOR_SECTION];
+    const QByteArray original = GZIP::decompress(raw.mid(sec.offset + headerSize, sec.compSize), sec.decSize);
+    if (original.isEmpty() || original.size() % RECORD_SIZE != 0) {
+        m_parent->reportInputError(Randomizer::InputError::KernelParse, "kernel.bin",
+                                   KernelBinValidator::kernelSectionName(ARMOR_SECTION)
+                                       + " does not decompress to whole armor records");
+        return false;
+    }
+
+    // --- randomize -----------------------------------------------------------
+    QVector<int> armors;
+    for (int i = 0; i < original.size() / RECORD_SIZE; ++i) {
+        quint16 mask;
+        memcpy(&mask, original.constData() + i * RECORD_SIZE + EQUIP_MASK_OFFSET, 2);
+        if (mask != 0)
+            armors.append(i);
+    }
+    const QVector<QVector<int>> tiers = defenseTiers(original, armors);
+
+    QByteArray table = original;
+    if (statsMode == Config::ArmorShuffle)
+        shuffleBlock(table, tiers, STATS_OFFSET, STATS_SIZE);
+    else if (statsMode == Config::ArmorReroll)
+        rerollStats(table, armors);
+    if (slotsMode == Config::ArmorShuffle)
+        shuffleBlock(table, tiers, SLOTS_OFFSET, SLOTS_SIZE);
+    else if (slotsMode == Config::ArmorReroll)
+        rerollSlots(table, armors);
+
+    // --- recompress and rebuild ----------------------------------------------
+    const QByteArray recompressed = GZIP::compress(table, config.getGzipLevel());
+    if (recompressed.isEmpty()) {
+        qDebug() << "Armor: failed to recompress the armor section";
+        return false;
+    }
+    if (recompressed.size() > KernelBinValidator::MAX_KERNEL_SECTION_SIZE) {
+        m_parent->reportPlanError(QString("kernel.bin: %1 recompresses to %2 bytes, the limit is %3")
+                                      .arg(KernelBinValidator::kernelSectionName(ARMOR_SECTION))
+                                      .arg(recompressed.size())
+                                      .arg(KernelBinValidator::MAX_KERNEL_SECTION_SIZE));
+        return false;
+    }
+
+    const quint16 compSize = static_cast<quint16>(recompressed.size());
+    const quint16 decSize  = static_cast<quint16>(table.size());
+    QByteArray rebuilt = raw.left(sec.offset);
+    rebuilt.append(reinterpret_cast<const char*>(&compSize), 2);
+    rebuilt.append(reinterpret_cast<const char*>(&decSize), 2);
+    rebuilt.append(raw.mid(sec.offset + 4, 2));   // file type
+    rebuilt.append(recompressed);
+    rebuilt.append(raw.mid(sec.offset + headerSize + sec.compSize));
+
+    const QStringList layoutErrors = KernelBinValidator::checkRebuiltKernel(raw, rebuilt, sections.size());
+    if (!layoutErrors.isEmpty()) {
+        m_parent->reportPlanError("kernel.bin: " + layoutErrors.join("; "));
+        return false;
+    }
+    const bool exact = GZIP::decompress(rebuilt.mid(sec.offset + headerSize, compSize), decSize) == table;
+    m_kernelRoundtrip[ARMOR_SECTION] = exact;
+    if (!exact) {
+        qDebug() << "Armor: rebuilt armor section does not round-trip";
+        return false;
+    }
+
+    // --- write back ----------------------------------------------------------
+    QDir().mkpath(QFileInfo(outKernel).path());
+    QSaveFile out(outKernel);
+    if (!out.open(QIODevice::WriteOnly) || out.write(rebuilt) != rebuilt.size() || !out.commit()) {
+        qDebug() << "Armor: cannot write" << outKernel;
+        return false;
+    }
+
+    QFile spoilerFile(QDir(outputPath).filePath("armor_spoiler.txt"));
+    if (spoilerFile.open(QIODevice::WriteOnly | QIODevice::Text)) {
+        static const char* const MODE_NAMES[] = { "vanilla", "shuffle", "reroll" };
+        QTextStream spoiler(&spoilerFile);
+        spoiler << "=== Armor (stats: " << MODE_NAMES[statsMode] << ", slots: "
+                << MODE_NAMES[slotsMode] << ") ===\n"
+                << QDateTime::currentDateTime().toString() << "\n\n";
+        writeSpoiler(original, table, armors, spoiler);
+    }
+    return true;
+}
+
+// Armors split into NUM_TIERS by their vanilla defense rank
+QVector<QVector<int>> ArmorRandomizer::defenseTiers(const QByteArray& table, const QVector<int>& armors) const
+{
+    QVector<int> ranked = armors;
+    std::stable_sort(ranked.begin(), ranked.end(), [&](int a, int b) {
+        return readStats(table, a).defense < readStats(table, b).defense;
+    });
+    QVector<QVector<int>> tiers(NUM_TIERS);
+    for (int rank = 0; rank < ranked.size(); ++rank)
+        tiers[rank * NUM_TIERS / ranked.size()].append(ranked[rank]);
+    return tiers;
+}
+
+void ArmorRandomizer::shuffleBlock(QByteArray& table, const QVector<QVector<int>>& tiers, int offset, int length)
+{
+    for (const QVector<int>& tier : tiers) {
+        QVector<QByteArray> blocks;
+        for (int armor : tier)
+            blocks.append(table.mid(armor * RECORD_SIZE + offset, length));
+        for (int i = blocks.size() - 1; i > 0; --i) {
+            std::uniform_int_distribution<int> dist(0, i);
+            std::swap(blocks[i], blocks[dist(m_rng)]);
+        }
+        for (int k = 0; k < tier.size(); ++k)
+            table.replace(tier[k] * RECORD_SIZE + offset, length, blocks[k]);
+    }
+}
+
+void ArmorRandomizer::rerollStats(QByteArray& table, const QVector<int>& armors)
+{
+    std::uniform_real_distribution<double> roll(STAT_ROLL_MIN, STAT_ROLL_MAX);
+    for (int armor : armors) {
+        uchar* stats = reinterpret_cast<uchar*>(table.data()) + armor * RECORD_SIZE + STATS_OFFSET;
+        for (int k = 0; k < STATS_SIZE; ++k) {
+            // Defense and magic defense are plain values, the other two evade chances
+            const int cap = k < 2 ? 255 : MAX_EVADE_PCT;
+            stats[k] = static_cast<quint8>(qBound(0, static_cast<int>(std::lround(stats[k] * roll(m_rng))), cap));
+        }
+    }
+}
+
+// One slot more or fewer and one link more or fewer than vanilla, linked
+// pairs placed at random; slots keep whether they grow materia
+void ArmorRandomizer::rerollSlots(QByteArray& table, const QVector<int>& armors)
+{
+    std::uniform_int_distribution<int> step(-1, 1);
+    for (int armor : armors) {
+        uchar* record = reinterpret_cast<uchar*>(table.data()) + armor * RECORD_SIZE;
+        uchar* slots = record + SLOTS_OFFSET;
+
+        int count = 0;
+        int links = 0;
+        bool growth = false;
+        for (int s = 0; s < SLOT_COUNT; ++s) {
+            if (slots[s] == SLOT_NONE) continue;
+            ++count;
+            if ((slots[s] & 0x03) == SLOT_LINK_LEFT) ++links;
+            if (slots[s] & SLOT_GROWTH) growth = true;
+        }
+        if (count == 0)
+            growth = true;   // a new slot grows like every vanilla one
+        const quint8 growthBit = growth ? SLOT_GROWTH : 0;
+
+        const int newCount = qBound(0, count + step(m_rng), SLOT_COUNT);
+        const int pairs = newCount / 2;
+        const int newLinks = qBound(0, links + step(m_rng), pairs);
+        QVector<int> pairOrder(pairs);
+        std::iota(pairOrder.begin(), pairOrder.end(), 0);
+        for (int i = pairs - 1; i > 0; --i) {
+            std::uniform_int_distribution<int> dist(0, i);
+            std::swap(pairOrder[i], pairOrder[dist(m_rng)]);
+        }
+        QVector<bool> linked(pairs, false);
+        for (int i = 0; i < newLinks; ++i)
+            linked[pairOrder[i]] = true;
+
+        for (int s = 0; s < SLOT_COUNT; ++s) {
+            quint8 value = SLOT_NONE;
+            if (s < newCount) {
+                const int pair = s / 2;
+                if (pair < pairs && linked[pair])
+                    value = static_cast<quint8>((s % 2 == 0 ? SLOT_LINK_LEFT : SLOT_LINK_RIGHT) | growthBit);
+                else
+                    value = static_cast<quint8>(SLOT_SINGLE | growthBit);
+            }
+            slots[s] = value;
+        }
+        if (newCount > 0 && record[GROWTH_RATE_OFFSET] == 0)
+            record[GROWTH_RATE_OFFSET] = GROWTH_RATE_NORMAL;
+    }
+}
+
+void ArmorRandomizer::writeSpoiler(const QByteArray& vanilla, const QByteArray& table,
+                                   const QVector<int>& armors, QTextStream& out) const
+{
+    for (int armor : armors) {
+        const ArmorStats before = readStats(vanilla, armor);
+        const ArmorStats after = readStats(table, armor);
+        out << QString("%1 DEF %2 -> %3, MDEF %4 -> %5, DEF% %6 -> %7, MDEF% %8 -> %9, slots %10 -> %11\n")
+                   .arg(armorName(armor), -20)
+                   .arg(before.defense).arg(after.defense)
+                   .arg(before.magicDefense).arg(after.magicDefense)
+                   .arg(before.defensePct).arg(after.defensePct)
+                   .arg(before.magicDefensePct).arg(after.magicDefensePct)
+                   .arg(slotString(vanilla, armor), slotString(table, armor));
+    }
+}
+
+ArmorRandomizer::ArmorStats ArmorRandomizer::readStats(const QByteArray& table, int armor)
+{
+    const uchar* p = reinterpret_cast<const uchar*>(table.constData()) + armor * RECORD_SIZE + STATS_OFFSET;
+    return ArmorStats{ p[0], p[1], p[2], p[3] };
+}
+
+// "O=O O" for a linked pair and a single slot, "none" without slots
+QString ArmorRandomizer::slotString(const QByteArray& table, int armor)
+{
+    const uchar* slots = reinterpret_cast<const uchar*>(table.constData()) + armor * RECORD_SIZE + SLOTS_OFFSET;
+    QStringList groups;
+    for (int s = 0; s < SLOT_COUNT; ++s) {
+        if (slots[s] == SLOT_NONE) continue;
+        if ((slots[s] & 0x03) == SLOT_LINK_LEFT && s + 1 < SLOT_COUNT
+            && (slots[s + 1] & 0x03) == SLOT_LINK_RIGHT) {
+            groups << QStringLiteral("O=O");
+            ++s;
+        } else {
+            groups << QStringLiteral("O");
+        }
+    }
+    return groups.isEmpty() ? QStringLiteral("none") : groups.join(' ');
+}
+
+QString ArmorRandomizer::armorName(int armor) const
+{
+    const quint16 itemId = static_cast<quint16>(ARMOR_ITEM_START + armor);
+    const QString name = m_parent->installNames().itemName(itemId);
+    return name.isEmpty() ? FieldPickupRandomizer_ff7tk::getCanonicalItemName(itemId) : name;
+}
@@ -0,0 +1,86 @@
+#pragma once
+
+#include <QString>
+#include <QByteArray>
+#include <QVector>
+#include <QMap>
+#include <QTextStream>
+#include <random>
+
+class Randomizer;
+
+/**
+ * ArmorRandomizer
+ *
+ * Rewrites the armor records of kernel.bin section 6 (36 bytes each, 32
+ * armors). Two independent settings, each a Config::ArmorMode:
+ *   - stats: defense, magic defense, defense% and magic defense%
+ *   - slots: the eight materia slot bytes and the materia growth rate
+ * Shuffle deals the values out again among the armors of the same defense
+ * tier (vanilla defense rank, in thirds); Reroll rolls each armor's values
+ * around its own.
+ *
+ * Armor has no model, so there is no appearance to keep; what stays with
+ * the record is everything its name promises or the menus rely on: equip
+ * and restriction masks, elemental and status protection and stat boosts.
+ * Records nobody can equip are left alone.
+ *
+ * Runs after the starting equipment pass and starts from its kernel.bin
+ * when one was written, else from the install's. The rebuilt file is
+ * checked with KernelBinValidator::checkRebuiltKernel before it is written.
+ */
+class ArmorRandomizer
+{
+public:
+    explicit ArmorRandomizer(Randomizer* parent);
+
+    bool randomize();
+
+    // Result of the last randomize()'s verification, as
+    // StartingEquipmentRandomizer::kernelRoundtrip (only the armor section)
+    QMap<int, bool> kernelRoundtrip() const { return m_kernelRoundtrip; }
+
+private:
+    struct ArmorStats {
+        int defense, magicDefense, defensePct, magicDefensePct;
+    };
+
+    Randomizer*  m_parent;
+    std::mt19937 m_rng;
+    QMap<int, bool> m_kernelRoundtrip;
+
+    QVector<QVector<int>> defenseTiers(const QByteArray& table, const QVector<int>& armors) const;
+    void shuffleBlock(QByteArray& table, const QVector<QVector<int>>& tiers, int offset, int length);
+    void rerollStats(QByteArray& table, const QVector<int>& armors);
+    void rerollSlots(QByteArray& table, const QVector<int>& armors);
+    void writeSpoiler(const QByteArray& vanilla, const QByteArray& table,
+                      const QVector<int>& armors, QTextStream& out) const;
+
+    static ArmorStats readStats(const QByteArray& table, int armor);
+    static QString slotString(const QByteArray& table, int armor);
+    QString armorName(int armor) const;
+
+    static constexpr int ARMOR_SECTION       = 6;
+    static constexpr int RECORD_SIZE         = 36;
+    static constexpr int STATS_OFFSET        = 0x02;   // def, mdef, def%, mdef%
+    static constexpr int STATS_SIZE          = 4;
+    static constexpr int SLOTS_OFFSET        = 0x08;   // 8 slot bytes + growth rate
+    static constexpr int SLOTS_SIZE          = 9;
+    static constexpr int SLOT_COUNT          = 8;
+    static constexpr int GROWTH_RATE_OFFSET  = 0x10;   // 0 = none, 1 = normal
+    static constexpr quint8 GROWTH_RATE_NORMAL = 1;
+    static constexpr int EQUIP_MASK_OFFSET   = 0x12;
+    static constexpr int NUM_TIERS           = 3;
+    static constexpr int MAX_EVADE_PCT       = 100;
+    static constexpr int ARMOR_ITEM_START    = 256;    // unified item ID of armor 0
+    static constexpr double STAT_ROLL_MIN = 0.75;
+    static constexpr double STAT_ROLL_MAX = 1.25;
+
+    // Slot bytes: 0 = none, 1 = unlinked, 2/3 = left/right of a linked
+    // pair; +4 = the slot grows its materia (every vanilla armor)
+    static constexpr quint8 SLOT_NONE        = 0;
+    static constexpr quint8 SLOT_SINGLE      = 1;
+    static constexpr quint8 SLOT_LINK_LEFT   = 2;
+    static constexpr quint8 SLOT_LINK_RIGHT  = 3;
+    static constexpr quint8 SLOT_GROWTH      = 4;
+};
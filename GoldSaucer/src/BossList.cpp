@@ -0,0 +1,123 @@
+#include "BossList.h"
+
+#include <QCoreApplication>
+#include <QFile>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QJsonObject>
+#include <QDebug>
+
+BossList& BossList::instance()
+{
+    static BossList list;
+    return list;
+}
+
+BossList::BossList()
+{
+    reload();
+}
+
+QString BossList::overridePath()
+{
+    return QCoreApplication::applicationDirPath() + "/boss_list.json";
+}
+
+void BossList::reload()
+{
+    m_names.clear();
+    m_scenes.clear();
+    m_warnings.clear();
+
+    QJsonObject builtIn;
+    if (readFile(":/data/boss_list.json", builtIn))
+        merge(builtIn);
+
+    const QString userPath = overridePath();
+    if (!QFile::exists(userPath))
+        return;
+
+    QJsonObject user;
+    if (!readFile(userPath, user))
+        return;
+    if (user["replace"].toBool(false)) {
+        m_names.clear();
+        m_scenes.clear();
+    }
+    merge(user);
+    qDebug() << "BossList: applied override" << userPath << "-" << m_names.size() << "names,"
+             << m_scenes.size() << "scenes";
+}
+
+bool BossList::readFile(const QString& path, QJsonObject& root)
+{
+    QFile file(path);
+    if (!file.open(QIODevice::ReadOnly)) {
+        m_warnings << QString("Cannot open %1").arg(path);
+        return false;
+    }
+    QJsonParseError parseError;
+    QJsonDocument doc = QJsonDocument::fromJson(file.readAll(), &parseError);
+    if (parseError.error != QJsonParseError::NoError || !doc.isObject()) {
+        m_warnings << QString("%1: %2").arg(path, parseError.errorString());
+        return false;
+    }
+    root = doc.object();
+    return true;
+}
+
+void BossList::merge(const QJsonObject& root)
+{
+    for (const QJsonValue& v : root["names"].toArray()) {
+        const QString name = normalizeName(v.toString());
+        if (!name.isEmpty()) m_names.insert(name);
+    }
+    for (const QJsonValue& v : root["scenes"].toArray()) {
+        if (v.isDouble()) m_scenes.insert(v.toInt());
+    }
+    // Formations live inside scenes; any listed formation marks its scene
+    for (const QJsonValue& v : root["formations"].toArray()) {
+        if (v.isDouble()) m_scenes.insert(v.toInt() / FORMATIONS_PER_SCENE);
+    }
+    for (const QJsonValue& v : root["exclude"].toArray())
+        m_names.remove(normalizeName(v.toString()));
+}
+
+// Letters and digits only, lowercased: "Jenova∙BIRTH" matches "JENOVA-BIRTH"
+QString BossList::normalizeName(const QString& name)
+{
+    QString out;
+    for (QChar c : name) {
+        if (c.isLetterOrNumber()) out.append(c.toLower());
+    }
+    return out;
+}
+
+bool BossList::isBossName(const QString& name) const
+{
+    return m_names.contains(normalizeName(name));
+}
+
+bool BossList::isBossScene(int sceneIndex) const
+{
+    return m_scenes.contains(sceneIndex) || m_learnedScenes.contains(sceneIndex);
+}
+
+void BossList::learnScenes(const QVector<QStringList>& sceneEnemies)
+{
+    m_learnedScenes.clear();
+    for (int s = 0; s < sceneEnemies.size(); ++s) {
+        for (const QString& name : sceneEnemies[s]) {
+            if (isBossName(name)) {
+                m_learnedScenes.insert(s);
+                break;
+            }
+        }
+    }
+    qDebug() << "BossList:" << m_learnedScenes.size() << "boss scenes found in scene.bin";
+}
+
+bool BossList::isBoss(const QString& name, int sceneIndex) const
+{
+    return isBossScene(sceneIndex) || isBossName(name);
+}
@@ -0,0 +1,65 @@
+#pragma once
+
+#include <QString>
+#include <QStringList>
+#include <QSet>
+#include <QVector>
+
+class QJsonObject;
+
+/**
+ * BossList
+ *
+ * Which scene.bin enemies count as bosses, for boss protection and the
+ * "Keep boss names" rename option. HP alone misses early bosses (Guard
+ * Scorpion, Air Buster...) that sit under the boss HP threshold, and a name
+ * list alone misses localized or renamed installs - so an enemy is a boss if
+ * its name OR its scene/formation is listed.
+ *
+ * The list ships as :/data/boss_list.json (src/data/boss_list.json). A
+ * boss_list.json next to the executable is merged on top:
+ *   "names" / "scenes" / "formations"  added to the built-in entries
+ *   "exclude"                          names removed from the result
+ *   "replace": true                    drop the built-in list first
+ */
+class BossList
+{
+public:
+    static BossList& instance();
+
+    bool isBossName(const QString& name) const;
+    bool isBossScene(int sceneIndex) const;
+    bool isBoss(const QString& name, int sceneIndex) const;
+
+    // Path of the user override file (may not exist)
+    static QString overridePath();
+    // Problems found while reading the override (shown in debug logs)
+    QStringList warnings() const { return m_warnings; }
+
+    // Re-read the built-in list and override (e.g. after the user edits it)
+    void reload();
+
+    // Marks every scene of the install's vanilla scene.bin that holds a
+    // listed boss name (sceneEnemies: names by scene index) as a boss scene,
+    // replacing the scenes learned before. Kept across reload(): they come
+    // from the game files, not the JSON.
+    void learnScenes(const QVector<QStringList>& sceneEnemies);
+
+private:
+    BossList();
+    ~BossList() = default;
+
+    BossList(const BossList&) = delete;
+    BossList& operator=(const BossList&) = delete;
+
+    bool readFile(const QString& path, QJsonObject& root);
+    void merge(const QJsonObject& root);
+    static QString normalizeName(const QString& name);
+
+    QSet<QString> m_names;      // normalized
+    QSet<int>     m_scenes;
+    QSet<int>     m_learnedScenes;
+    QStringList   m_warnings;
+
+    static const int FORMATIONS_PER_SCENE = 4;
+};
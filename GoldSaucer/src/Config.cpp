@@ -3,7 +3,45 @@
 #include <QJsonDocument>
 #include <QFile>
 #include <QDebug>
+#include <QJsonParseError>
+#include <QRandomGenerator>
 #include <random>
+#include <functional>
+#include <algorithm>
+#include <iterator>
+#include <QPair>
+
+namespace {
+// Prefix of the compact shareable settings string (version 1)
+const char* const SHARE_STRING_PREFIX = "GS1:";
+
+// JSON keys for the weighted pickup pool, indexed by Config::PickupCategory
+const char* const PICKUP_CATEGORY_KEYS[Config::PickupCategoryCount] = {
+    "consumables", "battleItems", "weapons", "armor", "accessories"
+};
+
+// JSON keys for the location groups, indexed by Config::LocationGroup
+const char* const LOCATION_GROUP_KEYS[Config::LocationGroupCount] = {
+    "wutai", "minigames", "missables"
+};
+
+// JSON keys for the starting equipment characters, in savemap order; the
+// flashback slots have no toggle
+const char* const STARTING_CHARACTER_KEYS[Config::STARTING_CHARACTER_COUNT] = {
+    "cloud", "barret", "tifa", "aerith", "red", "yuffie", nullptr, nullptr, "cid"
+};
+
+// JSON / `--debug` keys for the debug artifacts, indexed by Config::DebugOutput
+const char* const DEBUG_OUTPUT_KEYS[Config::DebugOutputCount] = {
+    "pickupsLog", "stitmIndex", "keyBitScan", "sceneDump", "scriptDumps", "patchLog"
+};
+}
+
+// 16 hex digits from the system RNG, so the seed card hash can't be searched
+static QString newSeedSalt()
+{
+    return QString::number(QRandomGenerator::system()->generate64(), 16).rightJustified(16, '0');
+}
 
 Config::Config()
 {
@@ -16,10 +54,14 @@ void Config::setDefaults()
     for (int i = 0; i < FeatureCount; ++i) {
         m_featuresEnabled[i] = true;
     }
+    // except the scene.bin stat and encounter passes, which change every battle
+    m_featuresEnabled[EnemyStatsRandomization] = false;
+    m_featuresEnabled[EnemyEncounterRandomization] = false;
     
     // Random seed
     std::random_device rd;
     m_seed = rd();
+    m_seedSalt = newSeedSalt();
     
     // Enemy settings
     m_enemyLevelVariance = 10; // ±10 levels
@@ -27,28 +69,97 @@ void Config::setDefaults()
     m_bossProtectionEnabled = true; // Enable boss protection by default
     m_bossRandomizationIntensity = 10; // 10% intensity for boss randomization
     m_encounterBossesIncluded = false; // Don't shuffle bosses by default
+    m_enemyNameMode = EnemyNamesVanilla; // Cosmetic renaming off
+    m_preserveBossNames = true; // Keep boss names when renaming
+    m_randomizeEnemySteals = false; // Vanilla steal tables
+    m_stealDropConversion = false; // Steals stay steals, drops stay drops
+    m_randomizeChocoboRatings = false; // Vanilla chocobo tracks
+    m_randomizeEnemyMorphs = false; // Vanilla morph results
+    m_morphItemPool.clear(); // Built-in curated morph pool
+    m_guaranteeSourceMorphs = true; // Every Source stays morphable somewhere
+    m_shuffleEnemyAttacks = false; // Vanilla attack power and MP costs
+    m_bossDifficulty = BossDifficultyNormal; // Vanilla boss stats
+    m_expMultiplier = 1.0; // Vanilla battle rewards
+    m_apMultiplier = 1.0;
+    m_gilMultiplier = 1.0;
+    m_apJitter = 0; // AP exactly as multiplied
+    m_aiScriptRescale = true; // Keep AI HP thresholds in step with scaled HP
+    m_encounterCrossTier = false; // Shuffle within HP tiers
+    m_encounterFormationShuffle = false; // Swap whole scenes
+    m_encounterEnemySwap = false; // Swap whole scenes
+    m_scaleShuffledEncounters = false; // Moved scenes keep their own stats
     
     // Shop settings
     m_shopItemPoolSize = 50; // Use 50 random items for shops
     m_shopPriceVariance = 0.5; // ±50% price variance
+    m_randomizeShopPrices = false; // Keep vanilla prices
     m_foreignItemChance = 30; // 30% chance for foreign items
     m_oneTimePurchaseEnabled = true; // Enable one-time purchases
+    m_balanceEconomy = false; // Keep vanilla gil pickups and prices
+    m_shopLocationSlot = false; // Shops only sell their category's stock
+    m_shopTierStrictness = 2; // Shops only sell their own tier's stock
     
     // Field pickup settings
     m_pickupRarityMode = 0; // Balanced mode
     m_keyItemRandomization = false; // Disabled by default (experimental)
+    m_earlyPhs = false; // PHS may land in any sphere
+    m_midgarPartsRequired = MIDGAR_PARTS_COUNT; // Vanilla turn-in
+    m_hugeMateriaRequired = HUGE_MATERIA_COUNT; // Vanilla observatory
+    m_progressiveKeycards = false; // Each door wants its own keycard
+    m_tieredEquipment = false; // Equipment only from random draws
+    m_archipelagoExport = false; // No multiworld export
+    m_validateLogic = true; // Unbeatable plans fail the run
+    m_keyItemSoundCue = false; // Key item pickups keep their vanilla sound
+    m_modelScaleGag = false; // Field models keep their vanilla size
+    m_fieldEncounterMode = FieldEncountersVanilla; // Field battles untouched
+    m_randomizeFieldEncounterRates = false; // Vanilla encounter rates
+    m_randomizeScriptedBattles = false; // Scripted fights keep their formation
+    m_optionalKeyItems = true; // Optional key items join the shuffle
+    m_chocoboChecks = true; // Randomize Chocobo Farm materia grants
+    m_materiaCaveChecks = false; // Caves keep their vanilla materia, so the Lure can move
+    m_finalDungeonLootDensity = 100; // Crater pickups draw from the full pool
+    m_messageSearchRadius = MESSAGE_SEARCH_RADIUS_DEFAULT;
+    m_messageSearchSameScript = true; // Only a MESSAGE in the pickup's own script
+    m_silentPickups = false; // Pickups announce what they now hold
+    m_useInstallNames = true; // Name items as the installed kernel2.bin does
+
+    // Weighted pickup pool - consumables and battle items at equal weight,
+    // equipment excluded (matches the built-in pool)
+    for (int i = 0; i < PickupCategoryCount; ++i) {
+        m_pickupCategoryEnabled[i] = (i == PickupConsumables || i == PickupBattleItems);
+        m_pickupCategoryWeight[i] = 5;
+    }
+
+    // Every location group is part of the shuffle
+    for (int i = 0; i < LocationGroupCount; ++i)
+        m_locationGroups[i] = true;
     
     // Starting equipment settings
     m_startingEquipmentTier = 1; // Balanced tier
+    for (int i = 0; i < STARTING_CHARACTER_COUNT; ++i)
+        m_startingCharacters[i] = true;
+    
+    // Armor records untouched
+    m_randomizeArmorStats = ArmorVanilla;
+    m_randomizeArmorSlots = ArmorVanilla;
     
     // Output folder - default to "Randomized" next to FF7 installation
     m_outputFolder = "Randomized";
+    m_outputCollision = OutputCollisionError; // Never overwrite a previous seed unasked
+
+    // Compression - zlib's default level and the LZS effort used so far
+    m_gzipLevel = -1;
+    m_lzsEffort = LZS_EFFORT_DEFAULT;
 
     // Archipelago JSON path - empty by default
     m_apJsonPath = "";
 
     // Free Roam mode - disabled by default
     m_freeRoam = false;
+
+    // Debug artifacts - only the field pickup log, as before
+    for (int i = 0; i < DebugOutputCount; ++i)
+        m_debugOutputs[i] = (i == DebugPickupsLog);
 }
 
 bool Config::loadFromFile(const QString& filename)
@@ -67,8 +178,13 @@ bool Config::loadFromFile(const QString& filename)
         return false;
     }
     
-    QJsonObject root = doc.object();
-    
+    loadFromJson(doc.object());
+    qDebug() << "Config loaded from:" << filename;
+    return true;
+}
+
+void Config::loadFromJson(const QJsonObject& root)
+{
     // Load feature flags
     QJsonArray features = root["features"].toArray();
     for (int i = 0; i < features.size() && i < FeatureCount; ++i) {
@@ -77,7 +193,10 @@ bool Config::loadFromFile(const QString& filename)
     
     // Load seed
     if (root.contains("seed")) {
-        m_seed = static_cast<unsigned int>(root["seed"].toInt(m_seed));
+        setSeed(static_cast<unsigned int>(root["seed"].toInt(m_seed)));
+    }
+    if (!root["seedSalt"].toString().isEmpty()) {
+        m_seedSalt = root["seedSalt"].toString();
     }
     
     // Load enemy settings
@@ -97,7 +216,72 @@ bool Config::loadFromFile(const QString& filename)
     if (enemySettings.contains("encounterBossesIncluded")) {
         m_encounterBossesIncluded = enemySettings["encounterBossesIncluded"].toBool(m_encounterBossesIncluded);
     }
+    if (enemySettings.contains("nameMode")) {
+        setEnemyNameMode(enemySettings["nameMode"].toInt(m_enemyNameMode));
+    }
+    if (enemySettings.contains("preserveBossNames")) {
+        m_preserveBossNames = enemySettings["preserveBossNames"].toBool(m_preserveBossNames);
+    }
+    if (enemySettings.contains("randomizeEnemySteals")) {
+        m_randomizeEnemySteals = enemySettings["randomizeEnemySteals"].toBool(m_randomizeEnemySteals);
+    }
+    if (enemySettings.contains("stealDropConversion")) {
+        m_stealDropConversion = enemySettings["stealDropConversion"].toBool(m_stealDropConversion);
+    }
+    if (enemySettings.contains("randomizeChocoboRatings")) {
+        m_randomizeChocoboRatings = enemySettings["randomizeChocoboRatings"].toBool(m_randomizeChocoboRatings);
+    }
+    if (enemySettings.contains("randomizeEnemyMorphs")) {
+        m_randomizeEnemyMorphs = enemySettings["randomizeEnemyMorphs"].toBool(m_randomizeEnemyMorphs);
+    }
+    if (enemySettings.contains("morphItemPool")) {
+        QVector<quint16> pool;
+        for (const QJsonValue& v : enemySettings["morphItemPool"].toArray()) {
+            int id = v.toInt(-1);
+            if (id >= 0 && id < 0xFFFF) pool.append(static_cast<quint16>(id));
+        }
+        m_morphItemPool = pool;
+    }
+    if (enemySettings.contains("guaranteeSourceMorphs")) {
+        m_guaranteeSourceMorphs = enemySettings["guaranteeSourceMorphs"].toBool(m_guaranteeSourceMorphs);
+    }
+    if (enemySettings.contains("shuffleEnemyAttacks")) {
+        m_shuffleEnemyAttacks = enemySettings["shuffleEnemyAttacks"].toBool(m_shuffleEnemyAttacks);
+    }
+    if (enemySettings.contains("bossDifficulty")) {
+        setBossDifficulty(enemySettings["bossDifficulty"].toInt(m_bossDifficulty));
+    }
+    if (enemySettings.contains("aiScriptRescale")) {
+        m_aiScriptRescale = enemySettings["aiScriptRescale"].toBool(m_aiScriptRescale);
+    }
+    if (enemySettings.contains("encounterCrossTier")) {
+        m_encounterCrossTier = enemySettings["encounterCrossTier"].toBool(m_encounterCrossTier);
+    }
+    if (enemySettings.contains("encounterFormationShuffle")) {
+        m_encounterFormationShuffle = enemySettings["encounterFormationShuffle"].toBool(m_encounterFormationShuffle);
+    }
+    if (enemySettings.contains("encounterEnemySwap")) {
+        m_encounterEnemySwap = enemySettings["encounterEnemySwap"].toBool(m_encounterEnemySwap);
+    }
+    if (enemySettings.contains("scaleShuffledEncounters")) {
+        m_scaleShuffledEncounters = enemySettings["scaleShuffledEncounters"].toBool(m_scaleShuffledEncounters);
+    }
     
+    // Load battle reward multipliers
+    QJsonObject rewardSettings = root["rewards"].toObject();
+    if (rewardSettings.contains("expMultiplier")) {
+        setExpMultiplier(rewardSettings["expMultiplier"].toDouble(m_expMultiplier));
+    }
+    if (rewardSettings.contains("apMultiplier")) {
+        setApMultiplier(rewardSettings["apMultiplier"].toDouble(m_apMultiplier));
+    }
+    if (rewardSettings.contains("gilMultiplier")) {
+        setGilMultiplier(rewardSettings["gilMultiplier"].toDouble(m_gilMultiplier));
+    }
+    if (rewardSettings.contains("apJitter")) {
+        setApJitter(rewardSettings["apJitter"].toInt(m_apJitter));
+    }
+
     // Load shop settings
     QJsonObject shopSettings = root["shopRandomization"].toObject();
     if (shopSettings.contains("itemPoolSize")) {
@@ -106,12 +290,24 @@ bool Config::loadFromFile(const QString& filename)
     if (shopSettings.contains("priceVariance")) {
         m_shopPriceVariance = shopSettings["priceVariance"].toDouble(m_shopPriceVariance);
     }
+    if (shopSettings.contains("randomizePrices")) {
+        m_randomizeShopPrices = shopSettings["randomizePrices"].toBool(m_randomizeShopPrices);
+    }
     if (shopSettings.contains("foreignItemChance")) {
         m_foreignItemChance = shopSettings["foreignItemChance"].toInt(m_foreignItemChance);
     }
     if (shopSettings.contains("oneTimePurchaseEnabled")) {
         m_oneTimePurchaseEnabled = shopSettings["oneTimePurchaseEnabled"].toBool(m_oneTimePurchaseEnabled);
     }
+    if (shopSettings.contains("balanceEconomy")) {
+        m_balanceEconomy = shopSettings["balanceEconomy"].toBool(m_balanceEconomy);
+    }
+    if (shopSettings.contains("locationSlot")) {
+        m_shopLocationSlot = shopSettings["locationSlot"].toBool(m_shopLocationSlot);
+    }
+    if (shopSettings.contains("tierStrictness")) {
+        setShopTierStrictness(shopSettings["tierStrictness"].toInt(m_shopTierStrictness));
+    }
     
     // Load field pickup settings
     QJsonObject pickupSettings = root["fieldPickupRandomization"].toObject();
@@ -121,17 +317,119 @@ bool Config::loadFromFile(const QString& filename)
     if (pickupSettings.contains("keyItemRandomization")) {
         m_keyItemRandomization = pickupSettings["keyItemRandomization"].toBool(m_keyItemRandomization);
     }
+    if (pickupSettings.contains("earlyPhs")) {
+        m_earlyPhs = pickupSettings["earlyPhs"].toBool(m_earlyPhs);
+    }
+    if (pickupSettings.contains("midgarPartsRequired")) {
+        setMidgarPartsRequired(pickupSettings["midgarPartsRequired"].toInt(m_midgarPartsRequired));
+    }
+    if (pickupSettings.contains("hugeMateriaRequired")) {
+        setHugeMateriaRequired(pickupSettings["hugeMateriaRequired"].toInt(m_hugeMateriaRequired));
+    }
+    if (pickupSettings.contains("progressiveKeycards")) {
+        m_progressiveKeycards = pickupSettings["progressiveKeycards"].toBool(m_progressiveKeycards);
+    }
+    // "progressiveEquipment" is the old name, kept so earlier configs and
+    // seed reports still load
+    if (pickupSettings.contains("tieredEquipment")) {
+        m_tieredEquipment = pickupSettings["tieredEquipment"].toBool(m_tieredEquipment);
+    } else if (pickupSettings.contains("progressiveEquipment")) {
+        m_tieredEquipment = pickupSettings["progressiveEquipment"].toBool(m_tieredEquipment);
+    }
+    if (pickupSettings.contains("archipelagoExport")) {
+        m_archipelagoExport = pickupSettings["archipelagoExport"].toBool(m_archipelagoExport);
+    }
+    if (pickupSettings.contains("validateLogic")) {
+        m_validateLogic = pickupSettings["validateLogic"].toBool(m_validateLogic);
+    }
+    if (pickupSettings.contains("keyItemSoundCue")) {
+        m_keyItemSoundCue = pickupSettings["keyItemSoundCue"].toBool(m_keyItemSoundCue);
+    }
+    if (pickupSettings.contains("modelScaleGag")) {
+        m_modelScaleGag = pickupSettings["modelScaleGag"].toBool(m_modelScaleGag);
+    }
+    if (pickupSettings.contains("fieldEncounterMode")) {
+        setFieldEncounterMode(pickupSettings["fieldEncounterMode"].toInt(m_fieldEncounterMode));
+    }
+    if (pickupSettings.contains("randomizeFieldEncounterRates")) {
+        m_randomizeFieldEncounterRates = pickupSettings["randomizeFieldEncounterRates"].toBool(m_randomizeFieldEncounterRates);
+    }
+    if (pickupSettings.contains("randomizeScriptedBattles")) {
+        m_randomizeScriptedBattles = pickupSettings["randomizeScriptedBattles"].toBool(m_randomizeScriptedBattles);
+    }
+    if (pickupSettings.contains("optionalKeyItems")) {
+        m_optionalKeyItems = pickupSettings["optionalKeyItems"].toBool(m_optionalKeyItems);
+    }
+    if (pickupSettings.contains("chocoboChecks")) {
+        m_chocoboChecks = pickupSettings["chocoboChecks"].toBool(m_chocoboChecks);
+    }
+    if (pickupSettings.contains("materiaCaveChecks")) {
+        m_materiaCaveChecks = pickupSettings["materiaCaveChecks"].toBool(m_materiaCaveChecks);
+    }
+    if (pickupSettings.contains("finalDungeonLootDensity")) {
+        m_finalDungeonLootDensity = qBound(0, pickupSettings["finalDungeonLootDensity"].toInt(m_finalDungeonLootDensity), 100);
+    }
+    if (pickupSettings.contains("messageSearchRadius")) {
+        setMessageSearchRadius(pickupSettings["messageSearchRadius"].toInt(m_messageSearchRadius));
+    }
+    if (pickupSettings.contains("messageSearchSameScript")) {
+        m_messageSearchSameScript = pickupSettings["messageSearchSameScript"].toBool(m_messageSearchSameScript);
+    }
+    if (pickupSettings.contains("silentPickups")) {
+        m_silentPickups = pickupSettings["silentPickups"].toBool(m_silentPickups);
+    }
+    if (pickupSettings.contains("useInstallNames")) {
+        m_useInstallNames = pickupSettings["useInstallNames"].toBool(m_useInstallNames);
+    }
+    QJsonObject itemPool = pickupSettings["itemPool"].toObject();
+    for (int i = 0; i < PickupCategoryCount; ++i) {
+        if (!itemPool.contains(PICKUP_CATEGORY_KEYS[i])) continue;
+        QJsonObject category = itemPool[PICKUP_CATEGORY_KEYS[i]].toObject();
+        m_pickupCategoryEnabled[i] = category["enabled"].toBool(m_pickupCategoryEnabled[i]);
+        m_pickupCategoryWeight[i] = qBound(1, category["weight"].toInt(m_pickupCategoryWeight[i]), 10);
+    }
+    QJsonObject locationGroups = pickupSettings["locationGroups"].toObject();
+    for (int i = 0; i < LocationGroupCount; ++i) {
+        if (locationGroups.contains(LOCATION_GROUP_KEYS[i]))
+            m_locationGroups[i] = locationGroups[LOCATION_GROUP_KEYS[i]].toBool(m_locationGroups[i]);
+    }
     
     // Load starting equipment settings
     QJsonObject equipmentSettings = root["startingEquipmentRandomization"].toObject();
     if (equipmentSettings.contains("tier")) {
         m_startingEquipmentTier = equipmentSettings["tier"].toInt(m_startingEquipmentTier);
     }
+    QJsonObject startingCharacters = equipmentSettings["characters"].toObject();
+    for (int i = 0; i < STARTING_CHARACTER_COUNT; ++i) {
+        if (hasStartingCharacterToggle(i) && startingCharacters.contains(STARTING_CHARACTER_KEYS[i]))
+            m_startingCharacters[i] = startingCharacters[STARTING_CHARACTER_KEYS[i]].toBool(m_startingCharacters[i]);
+    }
+    
+    // Load armor settings
+    QJsonObject armorSettings = root["armorRandomization"].toObject();
+    if (armorSettings.contains("randomizeArmorStats")) {
+        setRandomizeArmorStats(armorSettings["randomizeArmorStats"].toInt(m_randomizeArmorStats));
+    }
+    if (armorSettings.contains("randomizeArmorSlots")) {
+        setRandomizeArmorSlots(armorSettings["randomizeArmorSlots"].toInt(m_randomizeArmorSlots));
+    }
     
     // Load output folder settings
     if (root.contains("outputFolder")) {
         m_outputFolder = root["outputFolder"].toString(m_outputFolder);
     }
+    if (root.contains("outputCollision")) {
+        setOutputCollision(root["outputCollision"].toInt(m_outputCollision));
+    }
+
+    // Load compression settings
+    QJsonObject compression = root["compression"].toObject();
+    if (compression.contains("gzipLevel")) {
+        setGzipLevel(compression["gzipLevel"].toInt(m_gzipLevel));
+    }
+    if (compression.contains("lzsEffort")) {
+        setLzsEffort(compression["lzsEffort"].toInt(m_lzsEffort));
+    }
     
     // Load FF7 path settings
     if (root.contains("ff7Path")) {
@@ -153,11 +451,30 @@ bool Config::loadFromFile(const QString& filename)
         m_exportIro = root["exportIro"].toBool(false);
     }
 
-    qDebug() << "Config loaded from:" << filename;
-    return true;
+    // Load debug artifact selection
+    QJsonObject debugOutputs = root["debugOutputs"].toObject();
+    for (int i = 0; i < DebugOutputCount; ++i) {
+        if (debugOutputs.contains(DEBUG_OUTPUT_KEYS[i]))
+            m_debugOutputs[i] = debugOutputs[DEBUG_OUTPUT_KEYS[i]].toBool(m_debugOutputs[i]);
+    }
 }
 
 bool Config::saveToFile(const QString& filename) const
+{
+    QJsonDocument doc(toJson());
+    
+    QFile file(filename);
+    if (!file.open(QIODevice::WriteOnly)) {
+        qDebug() << "Could not write config file:" << filename << "Error:" << file.errorString();
+        return false;
+    }
+    
+    file.write(doc.toJson());
+    qDebug() << "Config saved to:" << filename;
+    return true;
+}
+
+QJsonObject Config::toJson() const
 {
     QJsonObject root;
     
@@ -170,6 +487,7 @@ bool Config::saveToFile(const QString& filename) const
     
     // Save seed
     root["seed"] = static_cast<int>(m_seed);
+    root["seedSalt"] = m_seedSalt;
     
     // Save enemy settings
     QJsonObject enemySettings;
@@ -178,29 +496,110 @@ bool Config::saveToFile(const QString& filename) const
     enemySettings["bossProtectionEnabled"] = m_bossProtectionEnabled;
     enemySettings["bossRandomizationIntensity"] = m_bossRandomizationIntensity;
     enemySettings["encounterBossesIncluded"] = m_encounterBossesIncluded;
+    enemySettings["nameMode"] = m_enemyNameMode;
+    enemySettings["preserveBossNames"] = m_preserveBossNames;
+    enemySettings["randomizeEnemySteals"] = m_randomizeEnemySteals;
+    enemySettings["stealDropConversion"] = m_stealDropConversion;
+    enemySettings["randomizeChocoboRatings"] = m_randomizeChocoboRatings;
+    enemySettings["randomizeEnemyMorphs"] = m_randomizeEnemyMorphs;
+    QJsonArray morphPool;
+    for (quint16 id : m_morphItemPool)
+        morphPool.append(id);
+    enemySettings["morphItemPool"] = morphPool;
+    enemySettings["guaranteeSourceMorphs"] = m_guaranteeSourceMorphs;
+    enemySettings["shuffleEnemyAttacks"] = m_shuffleEnemyAttacks;
+    enemySettings["bossDifficulty"] = m_bossDifficulty;
+    enemySettings["aiScriptRescale"] = m_aiScriptRescale;
+    enemySettings["encounterCrossTier"] = m_encounterCrossTier;
+    enemySettings["encounterFormationShuffle"] = m_encounterFormationShuffle;
+    enemySettings["encounterEnemySwap"] = m_encounterEnemySwap;
+    enemySettings["scaleShuffledEncounters"] = m_scaleShuffledEncounters;
     root["enemyRandomization"] = enemySettings;
+
+    // Save battle reward multipliers
+    QJsonObject rewardSettings;
+    rewardSettings["expMultiplier"] = m_expMultiplier;
+    rewardSettings["apMultiplier"] = m_apMultiplier;
+    rewardSettings["gilMultiplier"] = m_gilMultiplier;
+    rewardSettings["apJitter"] = m_apJitter;
+    root["rewards"] = rewardSettings;
     
     // Save shop settings
     QJsonObject shopSettings;
     shopSettings["itemPoolSize"] = m_shopItemPoolSize;
     shopSettings["priceVariance"] = m_shopPriceVariance;
+    shopSettings["randomizePrices"] = m_randomizeShopPrices;
     shopSettings["foreignItemChance"] = m_foreignItemChance;
     shopSettings["oneTimePurchaseEnabled"] = m_oneTimePurchaseEnabled;
+    shopSettings["balanceEconomy"] = m_balanceEconomy;
+    shopSettings["locationSlot"] = m_shopLocationSlot;
+    shopSettings["tierStrictness"] = m_shopTierStrictness;
     root["shopRandomization"] = shopSettings;
     
     // Save field pickup settings
     QJsonObject pickupSettings;
     pickupSettings["rarityMode"] = m_pickupRarityMode;
     pickupSettings["keyItemRandomization"] = m_keyItemRandomization;
+    pickupSettings["earlyPhs"] = m_earlyPhs;
+    pickupSettings["midgarPartsRequired"] = m_midgarPartsRequired;
+    pickupSettings["hugeMateriaRequired"] = m_hugeMateriaRequired;
+    pickupSettings["progressiveKeycards"] = m_progressiveKeycards;
+    pickupSettings["tieredEquipment"] = m_tieredEquipment;
+    pickupSettings["archipelagoExport"] = m_archipelagoExport;
+    pickupSettings["validateLogic"] = m_validateLogic;
+    pickupSettings["keyItemSoundCue"] = m_keyItemSoundCue;
+    pickupSettings["modelScaleGag"] = m_modelScaleGag;
+    pickupSettings["fieldEncounterMode"] = m_fieldEncounterMode;
+    pickupSettings["randomizeFieldEncounterRates"] = m_randomizeFieldEncounterRates;
+    pickupSettings["randomizeScriptedBattles"] = m_randomizeScriptedBattles;
+    pickupSettings["optionalKeyItems"] = m_optionalKeyItems;
+    pickupSettings["chocoboChecks"] = m_chocoboChecks;
+    pickupSettings["materiaCaveChecks"] = m_materiaCaveChecks;
+    pickupSettings["finalDungeonLootDensity"] = m_finalDungeonLootDensity;
+    pickupSettings["messageSearchRadius"] = m_messageSearchRadius;
+    pickupSettings["messageSearchSameScript"] = m_messageSearchSameScript;
+    pickupSettings["silentPickups"] = m_silentPickups;
+    pickupSettings["useInstallNames"] = m_useInstallNames;
+    QJsonObject itemPool;
+    for (int i = 0; i < PickupCategoryCount; ++i) {
+        QJsonObject category;
+        category["enabled"] = m_pickupCategoryEnabled[i];
+        category["weight"] = m_pickupCategoryWeight[i];
+        itemPool[PICKUP_CATEGORY_KEYS[i]] = category;
+    }
+    pickupSettings["itemPool"] = itemPool;
+    QJsonObject locationGroups;
+    for (int i = 0; i < LocationGroupCount; ++i)
+        locationGroups[LOCATION_GROUP_KEYS[i]] = m_locationGroups[i];
+    pickupSettings["locationGroups"] = locationGroups;
     root["fieldPickupRandomization"] = pickupSettings;
     
     // Save starting equipment settings
     QJsonObject equipmentSettings;
     equipmentSettings["tier"] = m_startingEquipmentTier;
+    QJsonObject startingCharacters;
+    for (int i = 0; i < STARTING_CHARACTER_COUNT; ++i) {
+        if (hasStartingCharacterToggle(i))
+            startingCharacters[STARTING_CHARACTER_KEYS[i]] = m_startingCharacters[i];
+    }
+    equipmentSettings["characters"] = startingCharacters;
     root["startingEquipmentRandomization"] = equipmentSettings;
     
+    // Save armor settings
+    QJsonObject armorSettings;
+    armorSettings["randomizeArmorStats"] = m_randomizeArmorStats;
+    armorSettings["randomizeArmorSlots"] = m_randomizeArmorSlots;
+    root["armorRandomization"] = armorSettings;
+    
     // Save output folder settings
     root["outputFolder"] = m_outputFolder;
+    root["outputCollision"] = m_outputCollision;
+
+    // Save compression settings
+    QJsonObject compression;
+    compression["gzipLevel"] = m_gzipLevel;
+    compression["lzsEffort"] = m_lzsEffort;
+    root["compression"] = compression;
     
     // Save FF7 path settings
     root["ff7Path"] = m_ff7Path;
@@ -214,16 +613,168 @@ bool Config::saveToFile(const QString& filename) const
     // Save .iro export setting
     root["exportIro"] = m_exportIro;
 
-    QJsonDocument doc(root);
-    
-    QFile file(filename);
-    if (!file.open(QIODevice::WriteOnly)) {
-        qDebug() << "Could not write config file:" << filename << "Error:" << file.errorString();
+    // Save debug artifact selection
+    QJsonObject debugOutputs;
+    for (int i = 0; i < DebugOutputCount; ++i)
+        debugOutputs[DEBUG_OUTPUT_KEYS[i]] = m_debugOutputs[i];
+    root["debugOutputs"] = debugOutputs;
+
+    return root;
+}
+
+QStringList Config::settingPaths() const
+{
+    QStringList paths;
+    const QJsonObject root = toJson();
+    for (auto it = root.begin(); it != root.end(); ++it) {
+        if (it.value().isObject()) {
+            const QJsonObject group = it.value().toObject();
+            for (auto g = group.begin(); g != group.end(); ++g) {
+                if (g.value().isObject()) {
+                    // e.g. fieldPickupRandomization.itemPool.weapons.weight,
+                    // or fieldPickupRandomization.locationGroups.wutai
+                    const QJsonObject sub = g.value().toObject();
+                    for (auto s = sub.begin(); s != sub.end(); ++s) {
+                        if (!s.value().isObject()) {
+                            paths << it.key() + "." + g.key() + "." + s.key();
+                            continue;
+                        }
+                        const QJsonObject leaf = s.value().toObject();
+                        for (auto l = leaf.begin(); l != leaf.end(); ++l)
+                            paths << it.key() + "." + g.key() + "." + s.key() + "." + l.key();
+                    }
+                } else {
+                    paths << it.key() + "." + g.key();
+                }
+            }
+        } else if (it.value().isArray()) {
+            const int n = it.value().toArray().size();
+            for (int i = 0; i < n; ++i)
+                paths << QString("%1[%2]").arg(it.key()).arg(i);
+        } else {
+            paths << it.key();
+        }
+    }
+    return paths;
+}
+
+// Splits "features[3]" into ("features", 3); plain keys return index -1.
+static QPair<QString, int> splitIndexedKey(const QString& key)
+{
+    const int open = key.indexOf('[');
+    if (open < 0 || !key.endsWith(']'))
+        return { key, -1 };
+    bool ok = false;
+    const int index = key.mid(open + 1, key.size() - open - 2).toInt(&ok);
+    return { key.left(open), ok ? index : -1 };
+}
+
+QJsonValue Config::settingValue(const QString& path) const
+{
+    const QStringList parts = path.split('.');
+    QJsonValue current = toJson();
+    for (const QString& part : parts) {
+        const QPair<QString, int> key = splitIndexedKey(part);
+        current = current.toObject().value(key.first);
+        if (key.second >= 0)
+            current = current.toArray().at(key.second);
+        if (current.isUndefined())
+            return QJsonValue(QJsonValue::Undefined);
+    }
+    return current;
+}
+
+bool Config::setSettingValue(const QString& path, const QJsonValue& value)
+{
+    if (settingValue(path).isUndefined())
+        return false;
+
+    // Rebuild the nested objects bottom-up with the new leaf value
+    std::function<QJsonValue(const QJsonValue&, QStringList)> assign =
+        [&](const QJsonValue& node, QStringList parts) -> QJsonValue {
+        if (parts.isEmpty())
+            return value;
+        const QPair<QString, int> key = splitIndexedKey(parts.takeFirst());
+        QJsonObject obj = node.toObject();
+        if (key.second >= 0) {
+            QJsonArray array = obj.value(key.first).toArray();
+            array[key.second] = assign(array.at(key.second), parts);
+            obj[key.first] = array;
+        } else {
+            obj[key.first] = assign(obj.value(key.first), parts);
+        }
+        return obj;
+    };
+
+    loadFromJson(assign(toJson(), path.split('.')).toObject());
+    return true;
+}
+
+QString Config::toShareString() const
+{
+    // Paths are machine-specific, so they are left out of the shared settings
+    QJsonObject root = toJson();
+    root.remove("outputFolder");
+    root.remove("outputCollision");
+    root.remove("ff7Path");
+    root.remove("apJsonPath");
+    // Debug artifacts and compression don't change the game, so they stay
+    // out of the hash too
+    root.remove("debugOutputs");
+    root.remove("compression");
+    QByteArray payload = QJsonDocument(root).toJson(QJsonDocument::Compact);
+    return QString(SHARE_STRING_PREFIX) + QString::fromLatin1(
+        payload.toBase64(QByteArray::Base64UrlEncoding | QByteArray::OmitTrailingEquals));
+}
+
+bool Config::loadFromShareString(const QString& text, QString* error)
+{
+    auto fail = [error](const QString& message) {
+        if (error) *error = message;
         return false;
+    };
+
+    const QString trimmed = text.trimmed();
+    if (trimmed.isEmpty())
+        return fail("Nothing to import - paste a settings string, preset JSON or seed number");
+
+    // Bare number: seed only
+    bool isNumber = false;
+    const unsigned int seed = trimmed.toUInt(&isNumber);
+    if (isNumber) {
+        setSeed(seed);
+        return true;
     }
-    
-    file.write(doc.toJson());
-    qDebug() << "Config saved to:" << filename;
+
+    QByteArray json;
+    if (trimmed.startsWith(SHARE_STRING_PREFIX)) {
+        QByteArray encoded = trimmed.mid(QString(SHARE_STRING_PREFIX).size()).toLatin1();
+        auto decoded = QByteArray::fromBase64Encoding(
+            encoded, QByteArray::Base64UrlEncoding | QByteArray::AbortOnBase64DecodingErrors);
+        if (!decoded)
+            return fail("Settings string is corrupt (invalid characters after the GS1: prefix)");
+        json = *decoded;
+    } else if (trimmed.startsWith('{')) {
+        json = trimmed.toUtf8();
+    } else {
+        return fail("Unrecognised input - expected a GS1: settings string, preset JSON or seed number");
+    }
+
+    QJsonParseError parseError;
+    QJsonDocument doc = QJsonDocument::fromJson(json, &parseError);
+    if (parseError.error != QJsonParseError::NoError)
+        return fail(QString("Invalid JSON at offset %1: %2")
+                        .arg(parseError.offset).arg(parseError.errorString()));
+    if (!doc.isObject())
+        return fail("Settings JSON must be an object");
+
+    QJsonObject root = doc.object();
+    if (root.contains("features") && !root["features"].isArray())
+        return fail("\"features\" must be an array of booleans");
+    if (root.contains("seed") && !root["seed"].isDouble())
+        return fail("\"seed\" must be a number");
+
+    loadFromJson(root);
     return true;
 }
 
@@ -244,6 +795,8 @@ bool Config::isFeatureEnabled(Feature feature) const
 
 void Config::setSeed(unsigned int seed)
 {
+    if (seed != m_seed)
+        m_seedSalt = newSeedSalt();
     m_seed = seed;
 }
 
@@ -252,6 +805,11 @@ unsigned int Config::getSeed() const
     return m_seed;
 }
 
+QString Config::getSeedSalt() const
+{
+    return m_seedSalt;
+}
+
 void Config::setEnemyLevelVariance(int variance)
 {
     m_enemyLevelVariance = variance;
@@ -282,6 +840,46 @@ bool Config::getEncounterBossesIncluded() const
     return m_encounterBossesIncluded;
 }
 
+void Config::setEncounterCrossTier(bool enabled)
+{
+    m_encounterCrossTier = enabled;
+}
+
+bool Config::getEncounterCrossTier() const
+{
+    return m_encounterCrossTier;
+}
+
+void Config::setEncounterFormationShuffle(bool enabled)
+{
+    m_encounterFormationShuffle = enabled;
+}
+
+bool Config::getEncounterFormationShuffle() const
+{
+    return m_encounterFormationShuffle;
+}
+
+void Config::setEncounterEnemySwap(bool enabled)
+{
+    m_encounterEnemySwap = enabled;
+}
+
+bool Config::getEncounterEnemySwap() const
+{
+    return m_encounterEnemySwap;
+}
+
+void Config::setScaleShuffledEncounters(bool enabled)
+{
+    m_scaleShuffledEncounters = enabled;
+}
+
+bool Config::getScaleShuffledEncounters() const
+{
+    return m_scaleShuffledEncounters;
+}
+
 void Config::setBossProtectionEnabled(bool enabled)
 {
     m_bossProtectionEnabled = enabled;
@@ -302,6 +900,163 @@ int Config::getBossRandomizationIntensity() const
     return m_bossRandomizationIntensity;
 }
 
+void Config::setEnemyNameMode(int mode)
+{
+    m_enemyNameMode = qBound(static_cast<int>(EnemyNamesVanilla), mode, static_cast<int>(EnemyNamesReplace));
+}
+
+int Config::getEnemyNameMode() const
+{
+    return m_enemyNameMode;
+}
+
+void Config::setPreserveBossNames(bool enabled)
+{
+    m_preserveBossNames = enabled;
+}
+
+bool Config::getPreserveBossNames() const
+{
+    return m_preserveBossNames;
+}
+
+void Config::setRandomizeEnemySteals(bool enabled)
+{
+    m_randomizeEnemySteals = enabled;
+}
+
+bool Config::getRandomizeEnemySteals() const
+{
+    return m_randomizeEnemySteals;
+}
+
+void Config::setStealDropConversion(bool enabled)
+{
+    m_stealDropConversion = enabled;
+}
+
+bool Config::getStealDropConversion() const
+{
+    return m_stealDropConversion;
+}
+
+void Config::setRandomizeChocoboRatings(bool enabled)
+{
+    m_randomizeChocoboRatings = enabled;
+}
+
+bool Config::getRandomizeChocoboRatings() const
+{
+    return m_randomizeChocoboRatings;
+}
+
+void Config::setRandomizeEnemyMorphs(bool enabled)
+{
+    m_randomizeEnemyMorphs = enabled;
+}
+
+bool Config::getRandomizeEnemyMorphs() const
+{
+    return m_randomizeEnemyMorphs;
+}
+
+void Config::setMorphItemPool(const QVector<quint16>& itemIds)
+{
+    m_morphItemPool = itemIds;
+}
+
+QVector<quint16> Config::getMorphItemPool() const
+{
+    return m_morphItemPool;
+}
+
+void Config::setGuaranteeSourceMorphs(bool enabled)
+{
+    m_guaranteeSourceMorphs = enabled;
+}
+
+bool Config::getGuaranteeSourceMorphs() const
+{
+    return m_guaranteeSourceMorphs;
+}
+
+void Config::setShuffleEnemyAttacks(bool enabled)
+{
+    m_shuffleEnemyAttacks = enabled;
+}
+
+bool Config::getShuffleEnemyAttacks() const
+{
+    return m_shuffleEnemyAttacks;
+}
+
+void Config::setBossDifficulty(int difficulty)
+{
+    m_bossDifficulty = qBound(static_cast<int>(BossDifficultyNormal), difficulty,
+                              static_cast<int>(BossDifficultyScaled));
+}
+
+int Config::getBossDifficulty() const
+{
+    return m_bossDifficulty;
+}
+
+void Config::setExpMultiplier(double factor)
+{
+    m_expMultiplier = qBound(0.0, factor, REWARD_MULTIPLIER_MAX);
+}
+
+double Config::getExpMultiplier() const
+{
+    return m_expMultiplier;
+}
+
+void Config::setApMultiplier(double factor)
+{
+    m_apMultiplier = qBound(0.0, factor, REWARD_MULTIPLIER_MAX);
+}
+
+double Config::getApMultiplier() const
+{
+    return m_apMultiplier;
+}
+
+void Config::setGilMultiplier(double factor)
+{
+    m_gilMultiplier = qBound(0.0, factor, REWARD_MULTIPLIER_MAX);
+}
+
+double Config::getGilMultiplier() const
+{
+    return m_gilMultiplier;
+}
+
+void Config::setApJitter(int percent)
+{
+    m_apJitter = qBound(0, percent, AP_JITTER_MAX);
+}
+
+int Config::getApJitter() const
+{
+    return m_apJitter;
+}
+
+bool Config::hasRewardMultipliers() const
+{
+    return m_expMultiplier != 1.0 || m_apMultiplier != 1.0 || m_gilMultiplier != 1.0
+        || m_apJitter > 0;
+}
+
+void Config::setAiScriptRescale(bool enabled)
+{
+    m_aiScriptRescale = enabled;
+}
+
+bool Config::getAiScriptRescale() const
+{
+    return m_aiScriptRescale;
+}
+
 void Config::setShopItemPoolSize(int size)
 {
     m_shopItemPoolSize = size;
@@ -322,6 +1077,16 @@ double Config::getShopPriceVariance() const
     return m_shopPriceVariance;
 }
 
+void Config::setRandomizeShopPrices(bool enabled)
+{
+    m_randomizeShopPrices = enabled;
+}
+
+bool Config::getRandomizeShopPrices() const
+{
+    return m_randomizeShopPrices;
+}
+
 void Config::setForeignItemChance(int percentage)
 {
     m_foreignItemChance = qBound(0, percentage, 100);
@@ -342,6 +1107,36 @@ bool Config::getOneTimePurchaseEnabled() const
     return m_oneTimePurchaseEnabled;
 }
 
+void Config::setBalanceEconomy(bool enabled)
+{
+    m_balanceEconomy = enabled;
+}
+
+bool Config::getBalanceEconomy() const
+{
+    return m_balanceEconomy;
+}
+
+void Config::setShopLocationSlot(bool enabled)
+{
+    m_shopLocationSlot = enabled;
+}
+
+bool Config::getShopLocationSlot() const
+{
+    return m_shopLocationSlot;
+}
+
+void Config::setShopTierStrictness(int strictness)
+{
+    m_shopTierStrictness = qBound(0, strictness, 2);
+}
+
+int Config::getShopTierStrictness() const
+{
+    return m_shopTierStrictness;
+}
+
 void Config::setPickupRarityMode(int mode)
 {
     m_pickupRarityMode = mode;
@@ -362,6 +1157,251 @@ bool Config::getKeyItemRandomization() const
     return m_keyItemRandomization;
 }
 
+void Config::setEarlyPhs(bool enabled)
+{
+    m_earlyPhs = enabled;
+}
+
+bool Config::getEarlyPhs() const
+{
+    return m_earlyPhs;
+}
+
+void Config::setMidgarPartsRequired(int count)
+{
+    m_midgarPartsRequired = qBound(1, count, static_cast<int>(MIDGAR_PARTS_COUNT));
+}
+
+int Config::getMidgarPartsRequired() const
+{
+    return m_midgarPartsRequired;
+}
+
+void Config::setHugeMateriaRequired(int count)
+{
+    m_hugeMateriaRequired = qBound(1, count, static_cast<int>(HUGE_MATERIA_COUNT));
+}
+
+int Config::getHugeMateriaRequired() const
+{
+    return m_hugeMateriaRequired;
+}
+
+void Config::setProgressiveKeycards(bool enabled)
+{
+    m_progressiveKeycards = enabled;
+}
+
+bool Config::getProgressiveKeycards() const
+{
+    return m_progressiveKeycards;
+}
+
+void Config::setTieredEquipment(bool enabled)
+{
+    m_tieredEquipment = enabled;
+}
+
+bool Config::getTieredEquipment() const
+{
+    return m_tieredEquipment;
+}
+
+void Config::setArchipelagoExport(bool enabled)
+{
+    m_archipelagoExport = enabled;
+}
+
+bool Config::getArchipelagoExport() const
+{
+    return m_archipelagoExport;
+}
+
+void Config::setValidateLogic(bool enabled)
+{
+    m_validateLogic = enabled;
+}
+
+bool Config::getValidateLogic() const
+{
+    return m_validateLogic;
+}
+
+void Config::setKeyItemSoundCue(bool enabled)
+{
+    m_keyItemSoundCue = enabled;
+}
+
+bool Config::getKeyItemSoundCue() const
+{
+    return m_keyItemSoundCue;
+}
+
+void Config::setModelScaleGag(bool enabled)
+{
+    m_modelScaleGag = enabled;
+}
+
+bool Config::getModelScaleGag() const
+{
+    return m_modelScaleGag;
+}
+
+void Config::setFieldEncounterMode(int mode)
+{
+    m_fieldEncounterMode = qBound(static_cast<int>(FieldEncountersVanilla), mode, static_cast<int>(FieldEncountersReroll));
+}
+
+int Config::getFieldEncounterMode() const
+{
+    return m_fieldEncounterMode;
+}
+
+void Config::setRandomizeFieldEncounterRates(bool enabled)
+{
+    m_randomizeFieldEncounterRates = enabled;
+}
+
+bool Config::getRandomizeFieldEncounterRates() const
+{
+    return m_randomizeFieldEncounterRates;
+}
+
+void Config::setRandomizeScriptedBattles(bool enabled)
+{
+    m_randomizeScriptedBattles = enabled;
+}
+
+bool Config::getRandomizeScriptedBattles() const
+{
+    return m_randomizeScriptedBattles;
+}
+
+void Config::setOptionalKeyItems(bool enabled)
+{
+    m_optionalKeyItems = enabled;
+}
+
+bool Config::getOptionalKeyItems() const
+{
+    return m_optionalKeyItems;
+}
+
+void Config::setPickupCategoryEnabled(PickupCategory category, bool enabled)
+{
+    if (category >= 0 && category < PickupCategoryCount) {
+        m_pickupCategoryEnabled[category] = enabled;
+    }
+}
+
+bool Config::isPickupCategoryEnabled(PickupCategory category) const
+{
+    if (category >= 0 && category < PickupCategoryCount) {
+        return m_pickupCategoryEnabled[category];
+    }
+    return false;
+}
+
+void Config::setPickupCategoryWeight(PickupCategory category, int weight)
+{
+    if (category >= 0 && category < PickupCategoryCount) {
+        m_pickupCategoryWeight[category] = qBound(1, weight, 10);
+    }
+}
+
+int Config::getPickupCategoryWeight(PickupCategory category) const
+{
+    if (category >= 0 && category < PickupCategoryCount) {
+        return m_pickupCategoryWeight[category];
+    }
+    return 0;
+}
+
+void Config::setChocoboChecks(bool enabled)
+{
+    m_chocoboChecks = enabled;
+}
+
+bool Config::getChocoboChecks() const
+{
+    return m_chocoboChecks;
+}
+
+void Config::setMateriaCaveChecks(bool enabled)
+{
+    m_materiaCaveChecks = enabled;
+}
+
+bool Config::getMateriaCaveChecks() const
+{
+    return m_materiaCaveChecks;
+}
+
+void Config::setLocationGroupEnabled(LocationGroup group, bool enabled)
+{
+    if (group >= 0 && group < LocationGroupCount) {
+        m_locationGroups[group] = enabled;
+    }
+}
+
+bool Config::isLocationGroupEnabled(LocationGroup group) const
+{
+    if (group >= 0 && group < LocationGroupCount) {
+        return m_locationGroups[group];
+    }
+    return true;
+}
+
+void Config::setFinalDungeonLootDensity(int percent)
+{
+    m_finalDungeonLootDensity = qBound(0, percent, 100);
+}
+
+int Config::getFinalDungeonLootDensity() const
+{
+    return m_finalDungeonLootDensity;
+}
+
+void Config::setMessageSearchRadius(int bytes)
+{
+    m_messageSearchRadius = qBound(1, bytes, MESSAGE_SEARCH_RADIUS_MAX);
+}
+
+int Config::getMessageSearchRadius() const
+{
+    return m_messageSearchRadius;
+}
+
+void Config::setMessageSearchSameScript(bool enabled)
+{
+    m_messageSearchSameScript = enabled;
+}
+
+bool Config::getMessageSearchSameScript() const
+{
+    return m_messageSearchSameScript;
+}
+
+void Config::setSilentPickups(bool enabled)
+{
+    m_silentPickups = enabled;
+}
+
+bool Config::getSilentPickups() const
+{
+    return m_silentPickups;
+}
+
+void Config::setUseInstallNames(bool enabled)
+{
+    m_useInstallNames = enabled;
+}
+
+bool Config::getUseInstallNames() const
+{
+    return m_useInstallNames;
+}
+
 void Config::setStartingEquipmentTier(int tier)
 {
     m_startingEquipmentTier = tier;
@@ -372,6 +1412,47 @@ int Config::getStartingEquipmentTier() const
     return m_startingEquipmentTier;
 }
 
+bool Config::hasStartingCharacterToggle(int character)
+{
+    return character >= 0 && character < STARTING_CHARACTER_COUNT
+        && STARTING_CHARACTER_KEYS[character] != nullptr;
+}
+
+void Config::setStartingCharacterRandomized(int character, bool enabled)
+{
+    if (hasStartingCharacterToggle(character)) {
+        m_startingCharacters[character] = enabled;
+    }
+}
+
+bool Config::isStartingCharacterRandomized(int character) const
+{
+    if (character >= 0 && character < STARTING_CHARACTER_COUNT) {
+        return m_startingCharacters[character];
+    }
+    return false;
+}
+
+void Config::setRandomizeArmorStats(int mode)
+{
+    m_randomizeArmorStats = qBound(static_cast<int>(ArmorVanilla), mode, static_cast<int>(ArmorReroll));
+}
+
+int Config::getRandomizeArmorStats() const
+{
+    return m_randomizeArmorStats;
+}
+
+void Config::setRandomizeArmorSlots(int mode)
+{
+    m_randomizeArmorSlots = qBound(static_cast<int>(ArmorVanilla), mode, static_cast<int>(ArmorReroll));
+}
+
+int Config::getRandomizeArmorSlots() const
+{
+    return m_randomizeArmorSlots;
+}
+
 void Config::setOutputFolder(const QString& folder)
 {
     m_outputFolder = folder;
@@ -382,6 +1463,37 @@ QString Config::getOutputFolder() const
     return m_outputFolder;
 }
 
+void Config::setOutputCollision(int policy)
+{
+    m_outputCollision = qBound(static_cast<int>(OutputCollisionError), policy,
+                               static_cast<int>(OutputCollisionSuffix));
+}
+
+int Config::getOutputCollision() const
+{
+    return m_outputCollision;
+}
+
+void Config::setGzipLevel(int level)
+{
+    m_gzipLevel = qBound(-1, level, 9);
+}
+
+int Config::getGzipLevel() const
+{
+    return m_gzipLevel;
+}
+
+void Config::setLzsEffort(int effort)
+{
+    m_lzsEffort = qBound(0, effort, LZS_EFFORT_MAX);
+}
+
+int Config::getLzsEffort() const
+{
+    return m_lzsEffort;
+}
+
 void Config::setFF7Path(const QString& path)
 {
     qDebug() << "Config::setFF7Path called with:" << path;
@@ -424,3 +1536,49 @@ bool Config::getExportIro() const
 {
     return m_exportIro;
 }
+
+void Config::setDebugOutputEnabled(DebugOutput output, bool enabled)
+{
+    if (output >= 0 && output < DebugOutputCount) {
+        m_debugOutputs[output] = enabled;
+    }
+}
+
+bool Config::isDebugOutputEnabled(DebugOutput output) const
+{
+    if (output >= 0 && output < DebugOutputCount) {
+        return m_debugOutputs[output];
+    }
+    return false;
+}
+
+bool Config::setDebugOutputsFromList(const QString& list, QString* error)
+{
+    bool selected[DebugOutputCount] = {};
+    for (const QString& entry : list.split(',', Qt::SkipEmptyParts)) {
+        const QString key = entry.trimmed();
+        if (key.compare("all", Qt::CaseInsensitive) == 0) {
+            std::fill(std::begin(selected), std::end(selected), true);
+            continue;
+        }
+        if (key.compare("none", Qt::CaseInsensitive) == 0)
+            continue;
+        int found = -1;
+        for (int i = 0; i < DebugOutputCount; ++i) {
+            if (key.compare(DEBUG_OUTPUT_KEYS[i], Qt::CaseInsensitive) == 0)
+                found = i;
+        }
+        if (found < 0) {
+            if (error) {
+                QStringList keys;
+                for (const char* k : DEBUG_OUTPUT_KEYS) keys << k;
+                *error = QString("Unknown debug output '%1' (expected %2, all or none)")
+                             .arg(key, keys.join(", "));
+            }
+            return false;
+        }
+        selected[found] = true;
+    }
+    std::copy(std::begin(selected), std::end(selected), m_debugOutputs);
+    return true;
+}
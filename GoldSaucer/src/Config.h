@@ -5,6 +5,8 @@
 #include <QJsonDocument>
 #include <QFile>
 #include <QJsonArray>
+#include <QStringList>
+#include <QVector>
 
 class Config
 {
@@ -20,17 +22,104 @@ public:
         EnemyEncounterRandomization,
         FeatureCount
     };
+
+    // Field pickup item categories for the weighted pool (Item Pool tab)
+    enum PickupCategory {
+        PickupConsumables = 0,  // items 0-31
+        PickupBattleItems,      // items 32-63
+        PickupWeapons,          // items 128-255
+        PickupArmor,            // items 256-287
+        PickupAccessories,      // items 288-319
+        PickupCategoryCount
+    };
+
+    // Groups of pickup checks that can be switched off as a whole; a
+    // disabled group's pickups (and key items) stay vanilla
+    enum LocationGroup {
+        LocationGroupWutai = 0,     // Wutai town, pagoda, Da-chao
+        LocationGroupMinigames,     // Gold Saucer Wonder Square, races, Battle Square
+        LocationGroupMissables,     // Shinra HQ, Temple of the Ancients, Whirlwind Maze, Rocket
+        LocationGroupCount
+    };
+
+    // Optional debug artifacts written to the output folder (Debug tab,
+    // `--debug` on the command line)
+    enum DebugOutput {
+        DebugPickupsLog = 0,    // field_randomization_debug.txt
+        DebugStitmIndex,        // field_stitm_index.txt: every STITM/SMTRA found
+        DebugKeyBitScan,        // key_bit_scan.txt: key item BITON collection pass
+        DebugSceneDump,         // scene_dump.csv: final scene.bin enemy table
+        DebugScriptDumps,       // field_script_dump.txt: scripts of changed fields
+        DebugPatchLog,          // field_patch_log.json: every changed byte range per field
+        DebugOutputCount
+    };
+
+    // Cosmetic enemy renaming (scene.bin names only, stats untouched)
+    enum EnemyNameMode {
+        EnemyNamesVanilla = 0,  // leave names alone
+        EnemyNamesShuffle,      // swap names between enemies
+        EnemyNamesReplace       // generated joke names
+    };
+
+    // Boss HP / STR / MAG curve, applied wherever a boss ends up
+    enum BossDifficulty {
+        BossDifficultyNormal = 0,   // vanilla boss stats
+        BossDifficultyEasy,         // flat nerf
+        BossDifficultyHard,         // flat buff
+        BossDifficultyScaled        // gentle early, tough late (by slot)
+    };
+
+    // What a run does when the output folder already holds files
+    enum OutputCollision {
+        OutputCollisionError = 0,   // stop (the GUI asks instead)
+        OutputCollisionOverwrite,   // write over the existing files
+        OutputCollisionSuffix       // use "<folder>_2", "<folder>_3", ...
+    };
+
+    // Random battles in each field's encounter table (section 7)
+    enum FieldEncounterMode {
+        FieldEncountersVanilla = 0, // tables untouched
+        FieldEncountersShuffle,     // battles swap slots within each table
+        FieldEncountersReroll       // battles drawn from nearby spheres
+    };
+
+    // Armor records in kernel.bin (stats and materia slots separately)
+    enum ArmorMode {
+        ArmorVanilla = 0,   // records untouched
+        ArmorShuffle,       // armors swap values within their defense tier
+        ArmorReroll         // values rolled around each armor's own
+    };
     
     Config();
     
     bool loadFromFile(const QString& filename);
     bool saveToFile(const QString& filename) const;
+
+    // The config file's JSON object; loadFromJson only overwrites keys present
+    QJsonObject toJson() const;
+    void loadFromJson(const QJsonObject& root);
+
+    // Settings schema shared by the GUI and headless tooling: every persisted
+    // setting as a flat path ("group.key", "features[i]"), derived from toJson()
+    QStringList settingPaths() const;
+    QJsonValue settingValue(const QString& path) const;
+    bool setSettingValue(const QString& path, const QJsonValue& value);
+
+    // Shareable settings string ("GS1:" + base64url JSON, paths omitted).
+    // loadFromShareString also accepts preset JSON or a bare seed number.
+    QString toShareString() const;
+    bool loadFromShareString(const QString& text, QString* error = nullptr);
     
     void setFeatureEnabled(Feature feature, bool enabled);
     bool isFeatureEnabled(Feature feature) const;
     
+    // Changing the seed also draws a new seed salt
     void setSeed(unsigned int seed);
     unsigned int getSeed() const;
+    // Random hex ID that travels with the seed in the settings string. It
+    // doesn't affect the game; it salts the seed card hash so the hash can't
+    // be brute-forced back to the seed from the settings shown on the card.
+    QString getSeedSalt() const;
     
     // Enemy randomization settings
     void setEnemyLevelVariance(int variance);
@@ -42,6 +131,26 @@ public:
     // Enemy encounter settings
     void setEncounterBossesIncluded(bool enabled);
     bool getEncounterBossesIncluded() const;
+
+    // Shuffle all tiers together instead of within each HP tier
+    void setEncounterCrossTier(bool enabled);
+    bool getEncounterCrossTier() const;
+
+    // Shuffle individual formations between scenes that hold their enemies
+    // instead of swapping whole scenes
+    void setEncounterFormationShuffle(bool enabled);
+    bool getEncounterFormationShuffle() const;
+
+    // Keep every scene's formations and camera and swap the enemies in them
+    // (record, AI, model) for similarly sized ones; overrides the formation
+    // shuffle
+    void setEncounterEnemySwap(bool enabled);
+    bool getEncounterEnemySwap() const;
+
+    // Rescale shuffled scenes to their new slot (damage-formula model).
+    // Off by default: the tiers already keep swaps close in strength
+    void setScaleShuffledEncounters(bool enabled);
+    bool getScaleShuffledEncounters() const;
     
     // Boss protection settings
     void setBossProtectionEnabled(bool enabled);
@@ -49,6 +158,71 @@ public:
     
     void setBossRandomizationIntensity(int intensity);
     int getBossRandomizationIntensity() const;
+
+    // Enemy name cosmetics
+    void setEnemyNameMode(int mode);
+    int getEnemyNameMode() const;
+    void setPreserveBossNames(bool enabled);
+    bool getPreserveBossNames() const;
+
+    // Shuffle steal slots (rate bit 7 set) between enemies; drops stay put
+    void setRandomizeEnemySteals(bool enabled);
+    bool getRandomizeEnemySteals() const;
+    // Let the steal shuffle take drops too and carry the steal bit (0x80)
+    // with each item, so a slot can turn from steal into drop and back
+    void setStealDropConversion(bool enabled);
+    bool getStealDropConversion() const;
+
+    // Shuffle the ratings of world map chocobo battles, keeping at least
+    // one great or wonderful chocobo per region
+    void setRandomizeChocoboRatings(bool enabled);
+    bool getRandomizeChocoboRatings() const;
+
+    // Reroll what morphable enemies morph into. The pool is a list of item
+    // IDs (empty = the built-in curated pool); with the guarantee on, every
+    // Source item is still some enemy's morph
+    void setRandomizeEnemyMorphs(bool enabled);
+    bool getRandomizeEnemyMorphs() const;
+    void setMorphItemPool(const QVector<quint16>& itemIds);
+    QVector<quint16> getMorphItemPool() const;
+    void setGuaranteeSourceMorphs(bool enabled);
+    bool getGuaranteeSourceMorphs() const;
+
+    // Shuffle enemy attack power and MP costs among attacks of the same
+    // tier; attack IDs (and so AI scripts) are untouched
+    void setShuffleEnemyAttacks(bool enabled);
+    bool getShuffleEnemyAttacks() const;
+
+    // Boss stat curve, separate from the trash stat variance and from
+    // encounter scaling; bosses moved by the encounter shuffle get the
+    // curve of the slot they land in
+    void setBossDifficulty(int difficulty);
+    int getBossDifficulty() const;
+
+    // Battle reward multipliers for EXP, AP and Gil (1.0 = vanilla, up to
+    // REWARD_MULTIPLIER_MAX). A pass of its own, so they work without any
+    // enemy randomization; gil economy balancing does not offset them
+    static constexpr double REWARD_MULTIPLIER_MAX = 10.0;
+    void setExpMultiplier(double factor);
+    double getExpMultiplier() const;
+    void setApMultiplier(double factor);
+    double getApMultiplier() const;
+    void setGilMultiplier(double factor);
+    double getGilMultiplier() const;
+
+    // Random spread of each enemy's AP on top of its multiplier (0 = none,
+    // up to AP_JITTER_MAX percent either way), so materia growth is paced
+    // differently from seed to seed
+    static const int AP_JITTER_MAX = 50;
+    void setApJitter(int percent);
+    int getApJitter() const;
+
+    // True when the battle reward pass has anything to do
+    bool hasRewardMultipliers() const;
+
+    // Rescale HP-threshold constants in enemy AI when HP is scaled
+    void setAiScriptRescale(bool enabled);
+    bool getAiScriptRescale() const;
     
     // Shop randomization settings
     void setShopItemPoolSize(int size);
@@ -56,6 +230,10 @@ public:
     
     void setShopPriceVariance(double variance);
     double getShopPriceVariance() const;
+
+    // Reroll each stocked item's price within the variance above
+    void setRandomizeShopPrices(bool enabled);
+    bool getRandomizeShopPrices() const;
     
     // Archipelago shop settings
     void setForeignItemChance(int percentage);
@@ -63,6 +241,18 @@ public:
     
     void setOneTimePurchaseEnabled(bool enabled);
     bool getOneTimePurchaseEnabled() const;
+
+    // Scale gil pickups and shop prices per tier to match randomized income
+    void setBalanceEconomy(bool enabled);
+    bool getBalanceEconomy() const;
+
+    // Stock one slot per shop with an item from the field pickup pool
+    void setShopLocationSlot(bool enabled);
+    bool getShopLocationSlot() const;
+
+    // How closely shop stock follows the shop's zone tier
+    void setShopTierStrictness(int strictness); // 0: off, 1: loose, 2: strict
+    int getShopTierStrictness() const;
     
     // Field pickup settings
     void setPickupRarityMode(int mode); // 0: balanced, 1: random, 2: high-tier only
@@ -70,13 +260,154 @@ public:
     
     void setKeyItemRandomization(bool enabled);
     bool getKeyItemRandomization() const;
+
+    // With key item randomization, place the PHS in the first sphere
+    // (Midgar start) instead of anywhere up to the end of the game
+    void setEarlyPhs(bool enabled);
+    bool getEarlyPhs() const;
+
+    // Midgar Parts the 65th floor model needs before it hands over
+    // Keycard 66 (1-MIDGAR_PARTS_COUNT). With key item randomization the
+    // parts beyond this are placed like filler, anywhere in the game
+    static const int MIDGAR_PARTS_COUNT = 5;
+    void setMidgarPartsRequired(int count);
+    int getMidgarPartsRequired() const;
+
+    // Huge Materia Bugenhagen's observatory needs before its rewards
+    // (1-HUGE_MATERIA_COUNT), for Huge Materia hunt goals
+    static const int HUGE_MATERIA_COUNT = 4;
+    void setHugeMateriaRequired(int count);
+    int getHugeMateriaRequired() const;
+
+    // Any Keycard found opens the next tier (60, 62, 65, 66, 68): the door
+    // checks count keycards instead of testing each one
+    void setProgressiveKeycards(bool enabled);
+    bool getProgressiveKeycards() const;
+
+    // Some pickups each hand out a fixed tier of Cloud's swords or the
+    // armlets, weakest in the earliest sphere, instead of random drops
+    void setTieredEquipment(bool enabled);
+    bool getTieredEquipment() const;
+
+    // Also write archipelago_export.json: the seed's placements under
+    // stable location names, classified for a multiworld generator
+    void setArchipelagoExport(bool enabled);
+    bool getArchipelagoExport() const;
+
+    // Fail the run when the key item plan's playthrough check can't collect
+    // every shuffled key item, instead of only logging it (--validate-logic)
+    void setValidateLogic(bool enabled);
+    bool getValidateLogic() const;
+
+    // Play a distinct sound when a pickup holds a randomized key item
+    void setKeyItemSoundCue(bool enabled);
+    bool getKeyItemSoundCue() const;
+
+    // Cosmetic: rescale field character models by a random factor per map
+    void setModelScaleGag(bool enabled);
+    bool getModelScaleGag() const;
+
+    // Field random encounters: a FieldEncounterMode, plus an optional
+    // reroll of each table's encounter rate
+    void setFieldEncounterMode(int mode);
+    int getFieldEncounterMode() const;
+    void setRandomizeFieldEncounterRates(bool enabled);
+    bool getRandomizeFieldEncounterRates() const;
+
+    // Scripted fights (BATTLE opcode) whose formation is in a
+    // ScriptedBattleTable group get another one from that group
+    void setRandomizeScriptedBattles(bool enabled);
+    bool getRandomizeScriptedBattles() const;
+
+    // Shuffle the optional key items (Gold Ticket, Leviathan Scales,
+    // coupons, letters, Glacier Map, Mythril) too; off = they stay vanilla
+    void setOptionalKeyItems(bool enabled);
+    bool getOptionalKeyItems() const;
+
+    // Weighted field pickup pool: per-category include flag and weight (1-10)
+    void setPickupCategoryEnabled(PickupCategory category, bool enabled);
+    bool isPickupCategoryEnabled(PickupCategory category) const;
+    void setPickupCategoryWeight(PickupCategory category, int weight);
+    int getPickupCategoryWeight(PickupCategory category) const;
+
+    // Chocobo Farm materia grants (Chocobo Lure, Choco/Mog) as randomized locations
+    void setChocoboChecks(bool enabled);
+    bool getChocoboChecks() const;
+
+    // Materia cave rewards (KOTR, Mime, HP<->MP, Quadra Magic) as randomized locations
+    void setMateriaCaveChecks(bool enabled);
+    bool getMateriaCaveChecks() const;
+
+    // Location groups whose checks are randomized (all on by default)
+    void setLocationGroupEnabled(LocationGroup group, bool enabled);
+    bool isLocationGroupEnabled(LocationGroup group) const;
+
+    // Chance (0-100%) that a Northern Crater pickup may roll rare/very rare
+    // loot; rerolled rewards come from the lower tiers instead
+    void setFinalDungeonLootDensity(int percent);
+    int getFinalDungeonLootDensity() const;
+
+    // "Received" text patching: how far (bytes) from a randomized pickup a
+    // MESSAGE may be, and whether it must sit in the pickup's own script
+    // (off = nearest MESSAGE anywhere in the radius, the old behaviour).
+    // A pickup with no MESSAGE that qualifies keeps its text unchanged.
+    static const int MESSAGE_SEARCH_RADIUS_DEFAULT = 500;
+    static const int MESSAGE_SEARCH_RADIUS_MAX = 4000;
+    void setMessageSearchRadius(int bytes);
+    int getMessageSearchRadius() const;
+    void setMessageSearchSameScript(bool enabled);
+    bool getMessageSearchSameScript() const;
+
+    // Silent pickups: the "Received" MESSAGE of a randomized pickup is
+    // removed instead of rewritten; the text section is never rebuilt
+    void setSilentPickups(bool enabled);
+    bool getSilentPickups() const;
+
+    // Use item/materia names from the install's kernel2.bin (localized games)
+    void setUseInstallNames(bool enabled);
+    bool getUseInstallNames() const;
     
     // Starting equipment settings
     void setStartingEquipmentTier(int tier); // 0: weak, 1: balanced, 2: strong
     int getStartingEquipmentTier() const;
+
+    // Which character records the starting equipment pass rerolls, in savemap
+    // order (Cloud, Barret, Tifa, Aerith, Red XIII, Yuffie, Cait Sith, Vincent,
+    // Cid); the others keep their vanilla loadout. All on by default.
+    // Slots 6 and 7 hold the Young Cloud and Sephiroth flashback records at new
+    // game, which the pass always keeps vanilla, so they have no toggle
+    static const int STARTING_CHARACTER_COUNT = 9;
+    static bool hasStartingCharacterToggle(int character);
+    void setStartingCharacterRandomized(int character, bool enabled);
+    bool isStartingCharacterRandomized(int character) const;
+
+    // Armor records: defense stats and materia slot layouts, each an
+    // ArmorMode. Equip and restriction masks never change
+    void setRandomizeArmorStats(int mode);
+    int getRandomizeArmorStats() const;
+    void setRandomizeArmorSlots(int mode);
+    int getRandomizeArmorSlots() const;
     
     void setOutputFolder(const QString& folder);
     QString getOutputFolder() const;
+
+    // Machine-local like the output folder, so not part of the share string
+    void setOutputCollision(int policy);
+    int getOutputCollision() const;
+
+    // Advanced compression: the zlib level (-1 = zlib's default, 0-9) for the
+    // gzip sections rebuilt in scene.bin and kernel.bin (scene.bin never
+    // goes below zlib's default, its blocks have no room), and the LZS effort
+    // (0-8) for field recompression. Effort sets how far the in-tree encoder
+    // searches for matches; above the default it also runs on every field and
+    // the smaller verified output wins. Only the output size and generation
+    // time change, so neither is part of the share string.
+    static const int LZS_EFFORT_DEFAULT = 3;
+    static const int LZS_EFFORT_MAX = 8;
+    void setGzipLevel(int level);
+    int getGzipLevel() const;
+    void setLzsEffort(int effort);
+    int getLzsEffort() const;
     
     void setFF7Path(const QString& path);
     QString getFF7Path() const;
@@ -91,11 +422,18 @@ public:
     void setExportIro(bool enabled);
     bool getExportIro() const;
 
+    void setDebugOutputEnabled(DebugOutput output, bool enabled);
+    bool isDebugOutputEnabled(DebugOutput output) const;
+    // Comma-separated debug output keys ("stitmIndex,sceneDump"), as taken by
+    // `--debug`; "all" and "none" are accepted too
+    bool setDebugOutputsFromList(const QString& list, QString* error = nullptr);
+
     void setDefaults();
     
 private:
     bool m_featuresEnabled[FeatureCount];
     unsigned int m_seed;
+    QString m_seedSalt;
     
     // Enemy settings
     int m_enemyLevelVariance;
@@ -103,22 +441,78 @@ private:
     bool m_bossProtectionEnabled;
     int m_bossRandomizationIntensity;
     bool m_encounterBossesIncluded;
+    int m_enemyNameMode;
+    bool m_preserveBossNames;
+    bool m_randomizeEnemySteals;
+    bool m_stealDropConversion;
+    bool m_randomizeChocoboRatings;
+    bool m_randomizeEnemyMorphs;
+    QVector<quint16> m_morphItemPool;
+    bool m_guaranteeSourceMorphs;
+    bool m_shuffleEnemyAttacks;
+    int m_bossDifficulty;
+
+    // Battle reward multipliers
+    double m_expMultiplier;
+    double m_apMultiplier;
+    double m_gilMultiplier;
+    int m_apJitter;
+    bool m_aiScriptRescale;
+    bool m_encounterCrossTier;
+    bool m_encounterFormationShuffle;
+    bool m_encounterEnemySwap;
+    bool m_scaleShuffledEncounters;
     
     // Shop settings
     int m_shopItemPoolSize;
     double m_shopPriceVariance;
+    bool m_randomizeShopPrices;
     int m_foreignItemChance;
     bool m_oneTimePurchaseEnabled;
+    bool m_balanceEconomy;
+    bool m_shopLocationSlot;
+    int m_shopTierStrictness;
     
     // Field pickup settings
     int m_pickupRarityMode;
     bool m_keyItemRandomization;
+    bool m_earlyPhs;
+    int m_midgarPartsRequired;
+    int m_hugeMateriaRequired;
+    bool m_progressiveKeycards;
+    bool m_tieredEquipment;
+    bool m_archipelagoExport;
+    bool m_validateLogic;
+    bool m_keyItemSoundCue;
+    bool m_modelScaleGag;
+    int m_fieldEncounterMode;
+    bool m_randomizeFieldEncounterRates;
+    bool m_randomizeScriptedBattles;
+    bool m_optionalKeyItems;
+    bool m_chocoboChecks;
+    bool m_materiaCaveChecks;
+    int m_finalDungeonLootDensity;
+    int m_messageSearchRadius;
+    bool m_messageSearchSameScript;
+    bool m_silentPickups;
+    bool m_useInstallNames;
+    bool m_pickupCategoryEnabled[PickupCategoryCount];
+    int m_pickupCategoryWeight[PickupCategoryCount];
+    bool m_locationGroups[LocationGroupCount];
     
     // Starting equipment settings
     int m_startingEquipmentTier;
+    bool m_startingCharacters[STARTING_CHARACTER_COUNT];
+    int m_randomizeArmorStats;
+    int m_randomizeArmorSlots;
     
     // Output folder settings
     QString m_outputFolder;
+    int m_outputCollision;
+
+    // Compression tuning (config-file only)
+    int m_gzipLevel;
+    int m_lzsEffort;
     
     // FF7 installation path
     QString m_ff7Path;
@@ -131,4 +525,6 @@ private:
 
     // Export randomized files as a 7th Heaven .iro archive (in addition to loose)
     bool m_exportIro;
+
+    bool m_debugOutputs[DebugOutputCount];
 };
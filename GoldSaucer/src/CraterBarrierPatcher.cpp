@@ -1,6 +1,8 @@
 #include "CraterBarrierPatcher.h"
+#include "GameFileLocator.h"
 
 #include <QFile>
+#include <QSaveFile>
 #include <QDir>
 #include <QFileInfo>
 #include <QDebug>
@@ -331,8 +333,13 @@ int CraterBarrierPatcher::patchCraterLanding(QByteArray& lgp) const
 
 bool CraterBarrierPatcher::patch()
 {
-    const QString src = QDir(m_ff7Path).filePath("data/wm/world_us.lgp");
     const QString dst = QDir(m_outputPath).filePath("data/wm/world_us.lgp");
+    // Build on the chocobo rating shuffle's output when that pass wrote it
+    // this run; anything else in the output folder may be a stale seed's
+    QString src = m_buildOnOutput ? dst
+                                  : GameFileLocator::resolveCaseInsensitive(m_ff7Path, "data/wm/world_us.lgp");
+    if (src.isEmpty())
+        src = QDir(m_ff7Path).filePath("data/wm/world_us.lgp");
 
     QFile in(src);
     if (!in.open(QIODevice::ReadOnly)) {
@@ -378,13 +385,16 @@ bool CraterBarrierPatcher::patch()
         return false;
     }
 
-    QFile out(dst);
-    if (!out.open(QIODevice::WriteOnly | QIODevice::Truncate)) {
+    QSaveFile out(dst);
+    if (!out.open(QIODevice::WriteOnly)) {
         qDebug() << "CraterBarrierPatcher: cannot write" << dst;
         return false;
     }
     out.write(lgp);
-    out.close();
+    if (!out.commit()) {
+        qDebug() << "CraterBarrierPatcher: cannot finish writing" << dst;
+        return false;
+    }
 
     qDebug() << "CraterBarrierPatcher: wrote" << dst
              << "(" << m_sitesPatched << "barrier site(s),"
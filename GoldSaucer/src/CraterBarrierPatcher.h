@@ -37,6 +37,11 @@ public:
     // idempotent case where the file was already patched).
     bool patch();
 
+    // Build on <outputPath>/data/wm/world_us.lgp instead of the install's
+    // copy. Only set when this run wrote that file (the chocobo rating
+    // shuffle); an output left over from an earlier seed is never reused.
+    void setBuildOnOutput(bool enabled) { m_buildOnOutput = enabled; }
+
     int sitesPatched() const { return m_sitesPatched; }
 
 private:
@@ -95,6 +100,7 @@ private:
 
     QString m_ff7Path;
     QString m_outputPath;
+    bool    m_buildOnOutput = false;
     int     m_sitesPatched = 0;
     int     m_diamondSitesPatched = 0;
     int     m_diamondAmbientPatched = 0;
@@ -0,0 +1,93 @@
+#include "DiagnosticBundle.h"
+#include "Config.h"
+#include "SeedSummary.h"
+#include <QCoreApplication>
+#include <QDateTime>
+#include <QDir>
+#include <QFile>
+#include <QJsonDocument>
+#include <QSaveFile>
+#include <QTextStream>
+
+QMap<QString, QString> DiagnosticBundle::collectLogs(const QString& outputFolder)
+{
+    QMap<QString, QString> logs;
+    if (outputFolder.isEmpty()) return logs;
+    const QDir dir(outputFolder);
+    const QStringList names = dir.entryList({ "*.txt", "*.json", "*.csv" }, QDir::Files, QDir::Name);
+    for (const QString& name : names) {
+        QFile file(dir.filePath(name));
+        if (!file.open(QIODevice::ReadOnly)) continue;
+        const qint64 size = file.size();
+        QString text;
+        if (size > LOG_TAIL_BYTES) {
+            file.seek(size - LOG_TAIL_BYTES);
+            text = QString("[... first %1 bytes cut ...]\n").arg(size - LOG_TAIL_BYTES);
+        }
+        text += QString::fromUtf8(file.readAll());
+        logs.insert(name, text);
+    }
+    return logs;
+}
+
+QString DiagnosticBundle::defaultFileName(const Config& config)
+{
+    return QString("goldsaucer_diagnostics_%1_%2.txt")
+        .arg(config.getSeed())
+        .arg(QDateTime::currentDateTime().toString("yyyyMMdd-hhmmss"));
+}
+
+bool DiagnosticBundle::write(const QString& path, const SeedGenerator::Failure& failure,
+                             const Config& config, const QString& ff7Path,
+                             const QStringList& consoleLines, QString* error)
+{
+    QSaveFile file(path);
+    if (!file.open(QIODevice::WriteOnly | QIODevice::Text)) {
+        if (error) *error = QString("Cannot write %1").arg(path);
+        return false;
+    }
+    QTextStream out(&file);
+    auto section = [&out](const QString& title) {
+        out << "\n===== " << title << " =====\n";
+    };
+
+    out << "Gold Saucer diagnostic bundle\n"
+        << "Created:  " << QDateTime::currentDateTime().toString(Qt::ISODate) << "\n"
+        << "Version:  " << QCoreApplication::applicationVersion()
+        << " (" << SeedSummary::gitHash() << ")\n"
+        << "Platform: " << SeedSummary::platform() << "\n";
+
+    section("Failure");
+    out << "Step:    " << failure.step << "\n"
+        << "Message: " << failure.message << "\n";
+    if (!failure.outputPath.isEmpty())
+        out << "Output:  " << failure.outputPath << "\n";
+    for (const QString& inputError : failure.inputErrors)
+        out << "Input:   " << inputError << "\n";
+
+    // Same record a finished run writes, so `--reproduce` can replay it
+    section(SeedSummary::REPORT_FILE_NAME);
+    out << QJsonDocument(SeedSummary::buildReport(config, ff7Path, QDateTime::currentDateTime()))
+               .toJson(QJsonDocument::Indented);
+
+    section("randomizer_config.json");
+    out << QJsonDocument(config.toJson()).toJson(QJsonDocument::Indented);
+
+    section("Console");
+    for (const QString& line : consoleLines)
+        out << line << "\n";
+
+    for (auto it = failure.logs.begin(); it != failure.logs.end(); ++it) {
+        section(it.key());
+        out << it.value();
+        if (!it.value().endsWith('\n'))
+            out << "\n";
+    }
+
+    out.flush();
+    if (!file.commit()) {
+        if (error) *error = QString("Cannot write %1").arg(path);
+        return false;
+    }
+    return true;
+}
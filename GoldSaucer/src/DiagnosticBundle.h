@@ -0,0 +1,32 @@
+#pragma once
+
+#include "SeedGenerator.h"
+#include <QMap>
+#include <QString>
+#include <QStringList>
+
+class Config;
+
+// DiagnosticBundle — one text file to attach to a bug report after a failed
+// run: the failure (step, message, unreadable game files), the settings, a
+// seed report for `--reproduce` (version, platform, input hashes), the
+// console and the debug logs the run had written.
+//
+// A failed run removes its partial output folder, so the logs are read with
+// collectLogs() while the folder still exists and carried in the Failure.
+class DiagnosticBundle
+{
+public:
+    // Debug logs (txt / json / csv) at the top of an output folder, keyed by
+    // file name; each is cut to its last LOG_TAIL_BYTES
+    static QMap<QString, QString> collectLogs(const QString& outputFolder);
+
+    static QString defaultFileName(const Config& config);
+
+    // ff7Path is hashed for the seed report; consoleLines are written as is
+    static bool write(const QString& path, const SeedGenerator::Failure& failure,
+                      const Config& config, const QString& ff7Path,
+                      const QStringList& consoleLines, QString* error = nullptr);
+
+    static const int LOG_TAIL_BYTES = 256 * 1024;
+};
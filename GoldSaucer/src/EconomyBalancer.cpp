@@ -0,0 +1,259 @@
+#include "EconomyBalancer.h"
+#include "Randomizer.h"
+#include "EnemyRandomizer.h"
+#include "FieldPickupRandomizer_ff7tk.h"
+#include "GameFileLocator.h"
+#include "InputCache.h"
+#include "MakouLgpManager.h"
+#include <LZS>
+#include <QFile>
+#include <QDir>
+#include <QDebug>
+#include <QDateTime>
+#include <algorithm>
+#include <cmath>
+#include <cstring>
+
+EconomyBalancer::EconomyBalancer(Randomizer* parent)
+    : m_parent(parent)
+{
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tiers
+// ─────────────────────────────────────────────────────────────────────────────
+
+int EconomyBalancer::levelTier(int enemyLevel)
+{
+    if (enemyLevel < MID_TIER_LEVEL)  return 0;
+    if (enemyLevel < LATE_TIER_LEVEL) return 1;
+    return 2;
+}
+
+int EconomyBalancer::fieldTier(const QString& fieldName)
+{
+    // Spheres 0-7 are Midgar, 8-12 the first world map loop up to the
+    // Sleeping Forest, 13+ the Forgotten Capital onwards. Unlisted fields
+    // (towns revisited all game) count as mid.
+    const int sphere = FieldPickupRandomizer_ff7tk::getFieldSphere(fieldName);
+    if (sphere <= 7)  return 0;
+    if (sphere <= 12) return 1;
+    if (sphere <= 15) return 2;
+    return 1;
+}
+
+double EconomyBalancer::pickupFactor(int tier) const
+{
+    if (!m_valid || tier < 0 || tier >= NUM_TIERS) return 1.0;
+    const TierIncome& t = m_tiers[tier];
+    if (t.vanillaPickups == 0) return 1.0;
+
+    // Pickups cover whatever drops lost, never less than vanilla
+    const double vanilla = double(t.vanillaDrops + t.vanillaPickups);
+    const double needed  = vanilla - double(t.currentDrops);
+    return std::clamp(needed / double(t.vanillaPickups), 1.0, MAX_PICKUP_FACTOR);
+}
+
+double EconomyBalancer::priceFactor(int tier) const
+{
+    if (!m_valid || tier < 0 || tier >= NUM_TIERS) return 1.0;
+    const TierIncome& t = m_tiers[tier];
+    const double vanilla = double(t.vanillaDrops + t.vanillaPickups);
+    if (vanilla <= 0.0) return 1.0;
+
+    const double income = double(t.currentDrops) + double(t.vanillaPickups) * pickupFactor(tier);
+    return std::clamp(income / vanilla, MIN_PRICE_FACTOR, MAX_PRICE_FACTOR);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// GOLDU scanning
+// ─────────────────────────────────────────────────────────────────────────────
+
+QVector<EconomyBalancer::GilPickup> EconomyBalancer::scanGilPickups(const QByteArray& field)
+{
+    QVector<GilPickup> results;
+    const int fileSize = field.size();
+    if (fileSize < 6 + 9 * 4) return results;
+
+    quint32 sections[9];
+    memcpy(sections, field.constData() + 6, sizeof(sections));
+    const int sec0DataStart = static_cast<int>(sections[0]) + 4;
+    if (sec0DataStart + 8 > fileSize) return results;
+
+    // Same script bounds as the STITM scan: 32-byte header + 72 bytes per
+    // entity, up to the text section
+    const quint8 nbEntities = static_cast<quint8>(field.at(sec0DataStart + 2));
+    quint16 posTexts;
+    memcpy(&posTexts, field.constData() + sec0DataStart + 4, 2);
+    const int scriptStart = sec0DataStart + 32 + 72 * nbEntities;
+    const int scriptEnd   = sec0DataStart + posTexts;
+    if (scriptStart >= scriptEnd || scriptEnd > fileSize) return results;
+
+    for (int i = scriptStart; i <= scriptEnd - GOLDU_SIZE; ++i) {
+        if (static_cast<quint8>(field.at(i)) != GOLDU_OPCODE) continue;
+        if (field.at(i + 1) != 0) continue;   // amount read from a variable
+
+        quint32 amount;
+        memcpy(&amount, field.constData() + i + 2, 4);
+        // Pickups are round amounts; this also rejects most stray 0x39 bytes
+        if (amount == 0 || amount > MAX_PICKUP_GIL || amount % 10 != 0) continue;
+        results.append({ i, amount });
+    }
+    return results;
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Analysis
+// ─────────────────────────────────────────────────────────────────────────────
+
+bool EconomyBalancer::analyze()
+{
+    if (m_analyzed) return m_valid;
+    m_analyzed = true;
+
+    const QString outputPath = m_parent->getOutputPath();
+    const QString ff7Path    = m_parent->getFF7Path();
+    QDir().mkpath(outputPath);
+
+    QFile logFile(outputPath + "/economy_debug.txt");
+    bool logOk = logFile.open(QIODevice::WriteOnly | QIODevice::Text);
+    Q_UNUSED(logOk);
+    QTextStream log(&logFile);
+    log << "=== Economy Balancing ===\n"
+        << QDateTime::currentDateTime().toString() << "\n\n";
+
+    const QString vanillaScene = GameFileLocator::findSceneBin(ff7Path, outputPath);
+    if (vanillaScene.isEmpty() || !tallyDrops(vanillaScene, true, log)) {
+        log << "ERROR: original scene.bin unavailable - economy left vanilla\n";
+        return false;
+    }
+    // The enemy passes ran before the shop and pickup passes that call this;
+    // without them the output holds the vanilla copy and drops are unchanged
+    const QString outputScene = QDir(outputPath).filePath("data/lang-en/battle/scene.bin");
+    if (!QFile::exists(outputScene) || !tallyDrops(outputScene, false, log)) {
+        log << "No randomized scene.bin - using vanilla drops\n";
+        for (TierIncome& t : m_tiers)
+            t.currentDrops = t.vanillaDrops;
+    }
+    bool dropsChanged = false;
+    for (const TierIncome& t : m_tiers)
+        dropsChanged = dropsChanged || t.currentDrops != t.vanillaDrops;
+    if (!dropsChanged)
+        log << "Drops match vanilla (enemy stat randomization off) - every factor stays 1.0\n";
+    if (!tallyPickups(log))
+        log << "No field gil pickups found - prices alone absorb the difference\n";
+
+    m_valid = true;
+    for (int t = 0; t < NUM_TIERS; ++t) {
+        log << "Tier " << t << ": drops " << m_tiers[t].vanillaDrops << " -> "
+            << m_tiers[t].currentDrops << ", pickups " << m_tiers[t].vanillaPickups
+            << " x" << QString::number(pickupFactor(t), 'f', 2)
+            << ", prices x" << QString::number(priceFactor(t), 'f', 2) << "\n";
+    }
+    writeReport(outputPath + "/economy_report.txt");
+    return true;
+}
+
+bool EconomyBalancer::tallyDrops(const QString& sceneBinPath, bool vanilla, QTextStream& log)
+{
+    QFile file(sceneBinPath);
+    if (!file.open(QIODevice::ReadOnly)) return false;
+    QVector<EnemyRandomizer::SceneEntry> scenes;
+    if (!EnemyRandomizer::extractScenes(file.readAll(), scenes, log)) return false;
+
+    for (TierIncome& t : m_tiers)
+        (vanilla ? t.vanillaDrops : t.currentDrops) = 0;
+
+    // Each enemy record counts once: a rough stand-in for how often it is
+    // fought, good enough to compare vanilla against randomized
+    for (const auto& scene : scenes) {
+        const QByteArray& d = scene.decompressed;
+        if (d.size() != EnemyRandomizer::SCENE_SIZE) continue;
+        for (int e = 0; e < EnemyRandomizer::ENEMIES_PER_SCENE; ++e) {
+            const int off = EnemyRandomizer::ENEMY_DATA_BASE + e * EnemyRandomizer::ENEMY_RECORD_SIZE;
+            const int level = static_cast<quint8>(d.at(off + EnemyRandomizer::ENM_LEVEL));
+            if (level == 0) continue;   // empty slot
+            quint32 gil;
+            memcpy(&gil, d.constData() + off + EnemyRandomizer::ENM_GIL, 4);
+            TierIncome& t = m_tiers[levelTier(level)];
+            if (vanilla) {
+                t.vanillaDrops += gil;
+                t.enemies++;
+            } else {
+                t.currentDrops += gil;
+            }
+        }
+    }
+    log << (vanilla ? "Vanilla" : "Randomized") << " drops read from " << sceneBinPath << "\n";
+    return true;
+}
+
+bool EconomyBalancer::tallyPickups(QTextStream& log)
+{
+    // Always the install's flevel: pickups are only ever scaled from vanilla
+    if (const InputCache* cache = m_parent->inputCache())
+        return tallyPickups(cache->fileNames(),
+                            [cache](const QString& f) { return cache->fieldData(f); }, log);
+
+    const QString flevelPath = GameFileLocator::findFlevelLgp(m_parent->getFF7Path(),
+                                                              m_parent->getOutputPath());
+    MakouLgpManager lgp;
+    if (flevelPath.isEmpty() || !lgp.open(flevelPath)) {
+        log << "flevel.lgp not available: " << lgp.lastError() << "\n";
+        return false;
+    }
+    return tallyPickups(lgp.fileList(), [&lgp](const QString& f) { return lgp.fileData(f); }, log);
+}
+
+bool EconomyBalancer::tallyPickups(const QStringList& fileNames,
+                                   const std::function<QByteArray(const QString&)>& fieldData,
+                                   QTextStream& log)
+{
+    int found = 0;
+    for (const QString& fileName : fileNames) {
+        if (fileName.contains('.')) continue;   // textures / tables, not fields
+        const QByteArray field = LZS::decompressAllWithHeader(fieldData(fileName));
+        const QVector<GilPickup> pickups = scanGilPickups(field);
+        if (pickups.isEmpty()) continue;
+        TierIncome& t = m_tiers[fieldTier(fileName)];
+        for (const GilPickup& p : pickups) {
+            t.vanillaPickups += p.amount;
+            t.pickups++;
+            found++;
+            log << "  " << fileName << " @" << p.offset << ": " << p.amount
+                << " gil (tier " << fieldTier(fileName) << ")\n";
+        }
+    }
+    log << "Gil pickups found: " << found << "\n\n";
+    return found > 0;
+}
+
+bool EconomyBalancer::writeReport(const QString& path) const
+{
+    QFile file(path);
+    if (!file.open(QIODevice::WriteOnly | QIODevice::Text)) return false;
+    QTextStream out(&file);
+    out << "=== Gil Economy Report ===\n"
+        << QDateTime::currentDateTime().toString() << "\n\n"
+        << "Income per progression tier. Drops = Gil of every enemy once;\n"
+        << "pickups = gil found in field scripts. Prices scale by the income ratio.\n\n"
+        << QString("%1 %2 %3 %4 %5 %6 %7  %8\n")
+               .arg(QStringLiteral("Tier"), -6).arg(QStringLiteral("Drops"), 10)
+               .arg(QStringLiteral("Now"), 10).arg(QStringLiteral("Pickups"), 10)
+               .arg(QStringLiteral("Now"), 10).arg(QStringLiteral("Pickup x"), 9)
+               .arg(QStringLiteral("Price x"), 8).arg(QStringLiteral("Sources"));
+
+    static const char* const TIER_NAMES[NUM_TIERS] = { "early", "mid", "late" };
+    for (int t = 0; t < NUM_TIERS; ++t) {
+        const TierIncome& ti = m_tiers[t];
+        const double pf = pickupFactor(t);
+        out << QString("%1 %2 %3 %4 %5 %6 %7  %8 enemies, %9 pickups\n")
+                   .arg(QString::fromLatin1(TIER_NAMES[t]), -6)
+                   .arg(ti.vanillaDrops, 10).arg(ti.currentDrops, 10)
+                   .arg(ti.vanillaPickups, 10)
+                   .arg(qulonglong(std::llround(double(ti.vanillaPickups) * pf)), 10)
+                   .arg(pf, 9, 'f', 2).arg(priceFactor(t), 8, 'f', 2)
+                   .arg(ti.enemies).arg(ti.pickups);
+    }
+    return true;
+}
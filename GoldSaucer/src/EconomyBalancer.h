@@ -0,0 +1,87 @@
+#pragma once
+
+#include <QString>
+#include <QByteArray>
+#include <QVector>
+#include <QTextStream>
+#include <QStringList>
+#include <functional>
+
+class Randomizer;
+
+/**
+ * EconomyBalancer
+ *
+ * Keeps gil income and shop prices in step once enemies and pickups are
+ * randomized. Income is tallied per progression tier (0 = early, 1 = mid,
+ * 2 = late - the same split shops use):
+ *   - drops:   Gil of every enemy record in scene.bin, tiered by enemy level
+ *   - pickups: literal GOLDU opcodes in flevel field scripts, tiered by the
+ *              field's progression sphere
+ * Vanilla income comes from the install; current drops from the output
+ * scene.bin. The enemy stat and encounter passes run before the shop and
+ * pickup passes that ask for the factors, so that is the randomized file.
+ *
+ * A tier whose drops fall short of vanilla gets its gil pickups raised to
+ * cover the gap (up to MAX_PICKUP_FACTOR). Whatever difference is left moves
+ * that tier's shop prices by the same ratio, clamped to
+ * [MIN_PRICE_FACTOR, MAX_PRICE_FACTOR]. Shop prices are tiered the way the
+ * shop pools are: by price rank within their category.
+ */
+class EconomyBalancer
+{
+public:
+    static const int NUM_TIERS = 3;
+
+    struct GilPickup {
+        int     offset;   // GOLDU opcode offset in the decompressed field
+        quint32 amount;
+    };
+
+    explicit EconomyBalancer(Randomizer* parent);
+
+    // Tally vanilla and current income and write economy_report.txt.
+    // Runs once; later calls return the first result.
+    bool analyze();
+    bool isAnalyzed() const { return m_analyzed; }
+
+    // Multipliers for a tier (1.0 until analyze() succeeds)
+    double pickupFactor(int tier) const;
+    double priceFactor(int tier) const;
+
+    static int levelTier(int enemyLevel);
+    static int fieldTier(const QString& fieldName);
+
+    // Literal (bank 0) GOLDU opcodes in a decompressed field's script section
+    static QVector<GilPickup> scanGilPickups(const QByteArray& field);
+
+private:
+    struct TierIncome {
+        quint64 vanillaDrops   = 0;
+        quint64 currentDrops   = 0;
+        quint64 vanillaPickups = 0;
+        int     enemies        = 0;
+        int     pickups        = 0;
+    };
+
+    Randomizer* m_parent;
+    bool        m_analyzed = false;
+    bool        m_valid    = false;
+    TierIncome  m_tiers[NUM_TIERS];
+
+    bool tallyDrops(const QString& sceneBinPath, bool vanilla, QTextStream& log);
+    bool tallyPickups(QTextStream& log);
+    bool tallyPickups(const QStringList& fileNames,
+                      const std::function<QByteArray(const QString&)>& fieldData,
+                      QTextStream& log);
+    bool writeReport(const QString& path) const;
+
+    static const int     GOLDU_OPCODE     = 0x39;
+    static const int     GOLDU_SIZE       = 6;       // opcode, banks, u32 amount
+    static const quint32 MAX_PICKUP_GIL   = 100000;  // larger literals are not pickups
+    static const int     MID_TIER_LEVEL   = 20;      // enemy level where tier 1 starts
+    static const int     LATE_TIER_LEVEL  = 40;      // ... and tier 2
+    static constexpr double MAX_PICKUP_FACTOR = 3.0;
+    static constexpr double MIN_PRICE_FACTOR  = 0.5;
+    static constexpr double MAX_PRICE_FACTOR  = 1.5;
+};
@@ -4,29 +4,65 @@
 
 #include "Config.h"
 
+#include "GameFileLocator.h"
+
+#include "InputCache.h"
+
+#include "BossList.h"
+
+#include "EnemyScaling.h"
+
+#include "MakouLgpManager.h"
+
+#include "SceneCatalog.h"
+
 #include <ff7tk/data/FF7Text.h>
 
+#include <ff7tk/data/FF7Item.h>
+
+#include <LZS>
+
 #include <QFile>
 
+#include <QSaveFile>
+
 #include <QDir>
 
 #include <QDebug>
 
 #include <QDateTime>
 
+#include <QSet>
+
+#include <QHash>
+
+#include <QJsonArray>
+
+#include <QJsonObject>
+
+#include <QJsonDocument>
+
+#include <QMap>
+
+#include <QRegularExpression>
+
 #include <cstring>
 
 #include <algorithm>
 
+#include <cmath>
+
 #include <zlib.h>
 
 
 
 
 
-// Compress data as gzip using raw zlib (GZIP::compress may silently fail)
+// Compress data as gzip using raw zlib (GZIP::compress may silently fail).
+
+// level is a zlib level, -1 (Z_DEFAULT_COMPRESSION) to 9 (Config::getGzipLevel)
 
-static QByteArray gzipCompress(const QByteArray& data)
+static QByteArray gzipCompress(const QByteArray& data, int level = Z_DEFAULT_COMPRESSION)
 
 {
 
@@ -40,7 +76,7 @@ static QByteArray gzipCompress(const QByteArray& data)
 
     // 15 + 16 = gzip wrapper
 
-    if (deflateInit2(&strm, Z_DEFAULT_COMPRESSION, Z_DEFLATED,
+    if (deflateInit2(&strm, level, Z_DEFLATED,
 
                      15 + 16, 8, Z_DEFAULT_STRATEGY) != Z_OK)
 
@@ -188,7 +224,11 @@ bool EnemyRandomizer::randomize()
 
 
 
-    QString srcScene   = QDir(ff7Path).filePath("data/lang-en/battle/scene.bin");
+    QString srcScene   = GameFileLocator::findSceneBin(ff7Path, outputPath);
+
+    if (srcScene.isEmpty())
+
+        srcScene = QDir(ff7Path).filePath("data/lang-en/battle/scene.bin");
 
     QString dstScene   = QDir(outputPath).filePath("data/lang-en/battle/scene.bin");
 
@@ -442,11 +482,7 @@ bool EnemyRandomizer::randomize()
 
     QDir().mkpath(QFileInfo(dstScene).path());
 
-    if (QFile::exists(dstScene))
-
-        QFile::remove(dstScene);
-
-    QFile outFile(dstScene);
+    QSaveFile outFile(dstScene);
 
     if (!outFile.open(QIODevice::WriteOnly)) {
 
@@ -458,9 +494,13 @@ bool EnemyRandomizer::randomize()
 
     qint64 written = outFile.write(newSceneBin);
 
-    outFile.flush();
+    if (!outFile.commit()) {
+
+        dbg << "ERROR: Cannot finish writing output scene.bin\n";
+
+        return false;
 
-    outFile.close();
+    }
 
 
 
@@ -539,7 +579,7 @@ bool EnemyRandomizer::randomizeEncounters()
 
         // Fall back to original
 
-        scenePath = QDir(m_parent->getFF7Path()).filePath("data/lang-en/battle/scene.bin");
+        scenePath = GameFileLocator::findSceneBin(m_parent->getFF7Path(), outputPath);
 
         sceneFile.setFileName(scenePath);
 
@@ -569,6 +609,10 @@ bool EnemyRandomizer::randomizeEncounters()
 
         dbg << "ERROR: extractScenes failed\n";
 
+        m_parent->reportInputError(Randomizer::InputError::SceneParse, scenePath,
+
+                                   "no scene decompresses (see the pass's debug log)");
+
         return false;
 
     }
@@ -583,7 +627,23 @@ bool EnemyRandomizer::randomizeEncounters()
 
     bool includeBosses = config.getEncounterBossesIncluded();
 
-    dbg << "Include bosses in shuffle: " << (includeBosses ? "YES" : "NO") << "\n\n";
+    bool crossTier     = config.getEncounterCrossTier();
+
+    bool scaleScenes   = config.getScaleShuffledEncounters();
+
+    bool enemyMode     = config.getEncounterEnemySwap();
+
+    bool formationMode = config.getEncounterFormationShuffle() && !enemyMode;
+
+    dbg << "Include bosses in shuffle: " << (includeBosses ? "YES" : "NO") << "\n"
+
+        << "Shuffle across tiers: " << (crossTier ? "YES" : "NO") << "\n"
+
+        << "Scale to new slot: " << (scaleScenes ? "YES" : "NO") << "\n"
+
+        << "Formation-level shuffle: " << (formationMode ? "YES" : "NO") << "\n"
+
+        << "Enemy-level swap: " << (enemyMode ? "YES" : "NO") << "\n\n";
 
 
 
@@ -603,6 +663,8 @@ bool EnemyRandomizer::randomizeEncounters()
 
 
 
+    const QSet<int> arena = arenaFormations(scenes);
+
     QVector<int> tier(scenes.size(), -1);
 
     for (int i = 0; i < scenes.size(); ++i) {
@@ -653,6 +715,22 @@ bool EnemyRandomizer::randomizeEncounters()
 
 
 
+        // The Battle Square picks its next fight from the arena scenes' own
+
+        // formation lists; shuffling them in makes the arena trivial or
+
+        // impossible and sends field scenes there
+
+        if (isArenaScene(i, arena)) {
+
+            dbg << "Scene " << i << ": Battle Square, kept vanilla\n";
+
+            continue;
+
+        }
+
+
+
         quint32 avgHP = static_cast<quint32>(totalHP / enemyCount);
 
         if      (avgHP >= 10000) tier[i] = 4;
@@ -669,6 +747,66 @@ bool EnemyRandomizer::randomizeEncounters()
 
 
 
+    // Random encounters come from the field and world map tables; a scene
+
+    // neither uses is a scripted fight or dead data, so it only joins the
+
+    // pool when bosses (scripted fights) are opted in
+
+    QMap<QString, QSet<int>> zones = loadZoneFormations(dbg);
+
+    zones.insert(loadWorldFormations(dbg));
+
+    QSet<int> usedIn;
+
+    for (const QSet<int>& formations : zones) {
+
+        for (int formation : formations)
+
+            usedIn.insert(formation / FORMATIONS_PER_SCENE);
+
+    }
+
+    QVector<QByteArray> vanillaScenes;
+
+    for (const SceneEntry& scene : scenes)
+
+        vanillaScenes.append(scene.decompressed);
+
+    const SceneCatalog catalog(vanillaScenes, zones);
+
+    if (usedIn.isEmpty()) {
+
+        dbg << "No encounter tables found, pooling every scene\n";
+
+    } else if (!includeBosses) {
+
+        int dropped = 0;
+
+        for (int i = 0; i < scenes.size(); ++i) {
+
+            if (tier[i] < 0 || usedIn.contains(i)) continue;
+
+            tier[i] = -1;
+
+            ++dropped;
+
+        }
+
+        dbg << "Scenes outside every encounter table, kept vanilla: " << dropped << "\n";
+
+    }
+
+    for (int i = 0; i < scenes.size(); ++i) {
+
+        if (usedIn.contains(i)) dbg << catalog.describeScene(i) << "\n";
+
+    }
+
+    dbg << "\n";
+
+
+
     // Log tier counts
 
     int tierCounts[5] = {};
@@ -693,7 +831,13 @@ bool EnemyRandomizer::randomizeEncounters()
 
 
 
-    for (int t = 0; t <= maxTier; ++t) {
+    // Formation and enemy modes move formations or enemies instead (below)
+
+    for (int t = 0; t <= maxTier && !formationMode && !enemyMode; ++t) {
+
+        // Cross-tier: one pool holding every tier up to maxTier
+
+        if (crossTier && t > 0) break;
 
         // Collect indices for this tier
 
@@ -701,7 +845,9 @@ bool EnemyRandomizer::randomizeEncounters()
 
         for (int i = 0; i < scenes.size(); ++i) {
 
-            if (tier[i] == t) indices.append(i);
+            bool inPool = crossTier ? (tier[i] >= 0 && tier[i] <= maxTier) : (tier[i] == t);
+
+            if (inPool) indices.append(i);
 
         }
 
@@ -747,7 +893,21 @@ bool EnemyRandomizer::randomizeEncounters()
 
             scenes[indices[i]].decompressed = origData[shuffled[i]];
 
-            if (shuffled[i] != i) ++swaps;
+            if (shuffled[i] != i) {
+
+                ++swaps;
+
+                if (scaleScenes) {
+
+                    dbg << "  " << catalog.describeScene(indices[i]) << " <- "
+
+                        << catalog.describeScene(indices[shuffled[i]]) << "\n";
+
+                    scaleSceneForSlot(scenes[indices[i]].decompressed, origData[i], dbg);
+
+                }
+
+            }
 
         }
 
@@ -761,33 +921,41 @@ bool EnemyRandomizer::randomizeEncounters()
 
 
 
-        // Log first few swaps
+        // Log every swap: what the slot held and what replaced it (already
 
-        for (int i = 0; i < qMin(5, indices.size()); ++i) {
+        // logged above the scaling of each when scaling)
+
+        for (int i = 0; i < indices.size() && !scaleScenes; ++i) {
 
             if (shuffled[i] != i) {
 
-                // Read enemy name from newly assigned data
+                dbg << "  " << catalog.describeScene(indices[i]) << " <- "
+
+                    << catalog.describeScene(indices[shuffled[i]]) << "\n";
+
+            }
+
+        }
 
-                QByteArray nameRaw = scenes[indices[i]].decompressed.mid(
+    }
 
-                    ENEMY_DATA_BASE + ENM_NAME, 32);
 
-                QString name = FF7Text::toPC(nameRaw);
 
-                dbg << "  Scene " << indices[i] << " <- was scene "
+    if (formationMode)
 
-                    << indices[shuffled[i]] << " (\"" << name << "\")\n";
+        totalSwaps = shuffleFormations(scenes, tier, maxTier, crossTier, catalog, dbg);
 
-            }
+    if (enemyMode)
 
-        }
+        totalSwaps = swapEnemies(scenes, tier, maxTier, crossTier, scaleScenes, catalog, dbg);
 
-    }
 
 
+    dbg << (formationMode ? "\nTotal formations moved: "
 
-    dbg << "\nTotal scenes swapped: " << totalSwaps << "\n";
+            : enemyMode   ? "\nTotal enemies swapped: " : "\nTotal scenes swapped: ")
+
+        << totalSwaps << "\n";
 
 
 
@@ -819,7 +987,7 @@ bool EnemyRandomizer::randomizeEncounters()
 
     QDir().mkpath(QFileInfo(dstScene).path());
 
-    QFile outFile(dstScene);
+    QSaveFile outFile(dstScene);
 
     if (!outFile.open(QIODevice::WriteOnly)) {
 
@@ -831,7 +999,13 @@ bool EnemyRandomizer::randomizeEncounters()
 
     outFile.write(newSceneBin);
 
-    outFile.close();
+    if (!outFile.commit()) {
+
+        dbg << "ERROR: Cannot finish writing " << dstScene << "\n";
+
+        return false;
+
+    }
 
 
 
@@ -887,6 +1061,22 @@ bool EnemyRandomizer::extractScenes(const QByteArray& sceneBin,
 
 
 
+            // Pointers count dwords within the block; anything past the block
+
+            // is corrupt and would overflow the multiply below
+
+            const quint32 maxPtr = BLOCK_SIZE / 4;
+
+            if (ptr > maxPtr) {
+
+                log << "  WARNING: bad pointer in block " << b << " slot " << p << "\n";
+
+                ++sceneIndex;
+
+                continue;
+
+            }
+
             int sceneOff = blockStart + static_cast<int>(ptr) * 4;
 
 
@@ -901,7 +1091,7 @@ bool EnemyRandomizer::extractScenes(const QByteArray& sceneBin,
 
                 memcpy(&nextPtr, sceneBin.constData() + blockStart + (p + 1) * 4, 4);
 
-                sceneEnd = (nextPtr == 0xFFFFFFFF)
+                sceneEnd = (nextPtr == 0xFFFFFFFF || nextPtr > maxPtr)
 
                          ? blockStart + BLOCK_SIZE
 
@@ -989,6 +1179,36 @@ QByteArray EnemyRandomizer::rebuildSceneBin(const QVector<SceneEntry>& scenes,
 
 {
 
+    // Scenes are packed into their vanilla blocks, which vanilla fills
+
+    // closely; below zlib's default level a randomized scene may no longer
+
+    // fit, so the setting can only make the output smaller here
+
+    int gzipLevel = m_parent ? m_parent->m_config.getGzipLevel() : Z_DEFAULT_COMPRESSION;
+
+    if (gzipLevel != Z_DEFAULT_COMPRESSION && gzipLevel < SCENE_MIN_GZIP_LEVEL)
+
+        gzipLevel = Z_DEFAULT_COMPRESSION;
+
+    // A scene that still doesn't fit fails the pass: writing its vanilla
+
+    // data instead would silently undo it (and break swaps with other scenes)
+
+    auto overflow = [&](const QString& message) {
+
+        log << "  ERROR: " << message << "\n";
+
+        if (m_parent)
+
+            m_parent->reportPlanError("scene.bin: " + message);
+
+        return QByteArray();
+
+    };
+
+
+
     // Group scene indices by their original block
 
     QMap<int, QVector<int>> blockMap;   // blockIndex -> [scene list indices]
@@ -1017,7 +1237,7 @@ QByteArray EnemyRandomizer::rebuildSceneBin(const QVector<SceneEntry>& scenes,
 
 
 
-    int compressOk = 0, compressFail = 0;
+    int compressOk = 0;
 
 
 
@@ -1057,21 +1277,21 @@ QByteArray EnemyRandomizer::rebuildSceneBin(const QVector<SceneEntry>& scenes,
 
             if (scenes[si].decompressed.size() == SCENE_SIZE) {
 
-                comp = gzipCompress(scenes[si].decompressed);
+                comp = gzipCompress(scenes[si].decompressed, gzipLevel);
 
-                if (comp.isEmpty()) {
+                // Short of room: the smallest zlib can do
 
-                    log << "  WARNING: gzip compress failed for scene " << si << "\n";
+                if (!comp.isEmpty() && dataOffset + ((comp.size() + 3) & ~3) > BLOCK_SIZE
 
-                    comp = scenes[si].compressed;
+                    && gzipLevel != Z_BEST_COMPRESSION)
 
-                    ++compressFail;
+                    comp = gzipCompress(scenes[si].decompressed, Z_BEST_COMPRESSION);
 
-                } else {
+                if (comp.isEmpty())
 
-                    ++compressOk;
+                    return overflow(QString("gzip compression failed for scene %1").arg(si));
 
-                }
+                ++compressOk;
 
             } else {
 
@@ -1079,43 +1299,17 @@ QByteArray EnemyRandomizer::rebuildSceneBin(const QVector<SceneEntry>& scenes,
 
             }
 
-
-
             // Pad to 4-byte alignment
 
             while (comp.size() % 4 != 0)
 
                 comp.append(static_cast<char>(0xFF));
 
+            if (dataOffset + comp.size() > BLOCK_SIZE)
 
+                return overflow(QString("scene %1 does not fit block %2 (%3 bytes, %4 free)")
 
-            // Overflow check
-
-            if (dataOffset + comp.size() > BLOCK_SIZE) {
-
-                log << "  WARNING: block " << b << " overflow for scene " << si << "\n";
-
-                comp = scenes[si].compressed;
-
-                while (comp.size() % 4 != 0)
-
-                    comp.append(static_cast<char>(0xFF));
-
-                // Double-check: if STILL doesn't fit, skip
-
-                if (dataOffset + comp.size() > BLOCK_SIZE) {
-
-                    log << "  ERROR: block " << b << " scene " << si
-
-                        << " cannot fit even with original data\n";
-
-                    ++slot;
-
-                    continue;
-
-                }
-
-            }
+                                    .arg(si).arg(b).arg(comp.size()).arg(BLOCK_SIZE - dataOffset));
 
 
 
@@ -1159,7 +1353,7 @@ QByteArray EnemyRandomizer::rebuildSceneBin(const QVector<SceneEntry>& scenes,
 
         << result.size() << " bytes\n";
 
-    log << "Compress stats: " << compressOk << " ok, " << compressFail << " failed\n";
+    log << "Compress stats: " << compressOk << " scenes recompressed\n";
 
     return result;
 
@@ -1189,6 +1383,8 @@ void EnemyRandomizer::randomizeScene(SceneEntry& scene, int sceneIndex,
 
     int    bossIntensity = config.getBossRandomizationIntensity();
 
+    bool   aiRescale     = config.getAiScriptRescale();
+
 
 
     for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
@@ -1225,13 +1421,19 @@ void EnemyRandomizer::randomizeScene(SceneEntry& scene, int sceneIndex,
 
 
 
-        // Determine variance (boss protection based on HP)
+        // Determine variance (boss protection based on HP or the boss list)
+
+        QByteArray nameRaw = scene.decompressed.mid(off + ENM_NAME, 32);
+
+        QString name = FF7Text::toPC(nameRaw);
+
+        bool listedBoss = BossList::instance().isBoss(name, sceneIndex);
 
         double variance = baseVariance;
 
         QString typeStr = "Normal";
 
-        if (bossProtect && hp >= BOSS_HP_THRESHOLD) {
+        if (bossProtect && (hp >= BOSS_HP_THRESHOLD || listedBoss)) {
 
             variance = baseVariance * 0.15 * (bossIntensity / 100.0);
 
@@ -1263,6 +1465,10 @@ void EnemyRandomizer::randomizeScene(SceneEntry& scene, int sceneIndex,
 
         memcpy(&origMP, scene.decompressed.constData() + off + ENM_MP, 2);
 
+        quint16 origAP;
+
+        memcpy(&origAP, scene.decompressed.constData() + off + ENM_AP, 2);
+
         quint32 origEXP, origGil;
 
         memcpy(&origEXP, scene.decompressed.constData() + off + ENM_EXP, 4);
@@ -1295,7 +1501,9 @@ void EnemyRandomizer::randomizeScene(SceneEntry& scene, int sceneIndex,
 
 
 
-        quint16 newMP  = randU16(origMP, variance);
+        quint16 newMP  = scaledEnemyMp(origMP, randU16(origMP, variance),
+
+                                       maxAttackMpCost(scene.decompressed, e));
 
         memcpy(d + ENM_MP, &newMP, 2);
 
@@ -1307,17 +1515,29 @@ void EnemyRandomizer::randomizeScene(SceneEntry& scene, int sceneIndex,
 
         memcpy(d + ENM_EXP, &newEXP, 4);
 
+        quint16 newAP  = randU16(origAP,  variance);
+
+        memcpy(d + ENM_AP, &newAP, 2);
+
         quint32 newGil = randU32(origGil, variance);
 
         memcpy(d + ENM_GIL, &newGil, 4);
 
+        int aiPatched = 0;
 
+        QString aiLog;
 
-        // Decode FF7-encoded enemy name for log
+        if (aiRescale && hp > 0 && newHP != hp) {
+
+            QTextStream aiStream(&aiLog);
+
+            aiPatched = rescaleAiHpConstants(scene.decompressed, e,
+
+                                             static_cast<double>(newHP) / hp, aiStream);
+
+        }
 
-        QByteArray nameRaw = scene.decompressed.mid(off + ENM_NAME, 32);
 
-        QString name = FF7Text::toPC(nameRaw);
 
 
 
@@ -1339,10 +1559,4634 @@ void EnemyRandomizer::randomizeScene(SceneEntry& scene, int sceneIndex,
 
             << " MP:" << origMP << "->" << newMP
 
-            << "\n";
+            << " AP:" << origAP << "->" << newAP;
+
+        if (aiPatched > 0)
+
+            log << " AI-HP-consts:" << aiPatched;
+
+        log << "\n" << aiLog;
+
+    }
+
+}
+
+
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// scaleSceneForSlot — rescale a shuffled-in scene to the slot it now occupies
+
+//
+
+// The slot's original enemies tell us the party level the game expects there
+
+// (FF7 enemy levels track the party closely); EnemyScaling moves each enemy
+
+// from its own scene's level to that one via the damage formulas.
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+
+
+int EnemyRandomizer::averageEnemyLevel(const QByteArray& scene)
+
+{
+
+    if (scene.size() != SCENE_SIZE) return 0;
+
+    int total = 0, count = 0;
+
+    for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+        int off = ENEMY_DATA_BASE + e * ENEMY_RECORD_SIZE;
+
+        if (scene.mid(off + ENM_NAME, ENM_NAME_SIZE).count(char(0xFF)) == ENM_NAME_SIZE) continue;
+
+        total += static_cast<quint8>(scene.at(off + ENM_LEVEL));
+
+        ++count;
 
     }
 
+    return count > 0 ? (total + count / 2) / count : 0;
+
+}
+
+
+
+void EnemyRandomizer::scaleSceneForSlot(QByteArray& scene, const QByteArray& slotOriginal,
+
+                                        QTextStream& log)
+
+{
+
+    const int fromLevel = averageEnemyLevel(scene);
+
+    const int toLevel   = averageEnemyLevel(slotOriginal);
+
+    if (fromLevel <= 0 || toLevel <= 0 || fromLevel == toLevel) return;
+
+
+
+    const bool aiRescale = m_parent->m_config.getAiScriptRescale();
+
+    log << "    scale party Lv " << fromLevel << " -> " << toLevel << "\n";
+
+
+
+    for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+        int off = ENEMY_DATA_BASE + e * ENEMY_RECORD_SIZE;
+
+        QByteArray nameRaw = scene.mid(off + ENM_NAME, ENM_NAME_SIZE);
+
+        if (nameRaw.count(char(0xFF)) == ENM_NAME_SIZE) continue;
+
+
+
+        char* d = scene.data() + off;
+
+        EnemyScaling::Stats before;
+
+        before.level = static_cast<quint8>(d[ENM_LEVEL]);
+
+        before.str   = static_cast<quint8>(d[ENM_STR]);
+
+        before.def   = static_cast<quint8>(d[ENM_DEF]);
+
+        before.mag   = static_cast<quint8>(d[ENM_MAG]);
+
+        before.mdef  = static_cast<quint8>(d[ENM_MDEF]);
+
+        memcpy(&before.hp, d + ENM_HP, 4);
+
+
+
+        EnemyScaling::Stats after = EnemyScaling::rescale(before, fromLevel, toLevel);
+
+        d[ENM_LEVEL] = static_cast<char>(after.level);
+
+        d[ENM_STR]   = static_cast<char>(after.str);
+
+        d[ENM_MAG]   = static_cast<char>(after.mag);
+
+        memcpy(d + ENM_HP, &after.hp, 4);
+
+
+
+        // MP follows fight length (HP), floored so the moveset still works
+
+        quint16 mpBefore;
+
+        memcpy(&mpBefore, d + ENM_MP, 2);
+
+        double hpFactor = before.hp > 0 ? static_cast<double>(after.hp) / before.hp : 1.0;
+
+        quint16 mpAfter = scaledEnemyMp(mpBefore,
+
+                                        static_cast<quint16>(qMin(65535.0, std::round(mpBefore * hpFactor))),
+
+                                        maxAttackMpCost(scene, e));
+
+        memcpy(d + ENM_MP, &mpAfter, 2);
+
+
+
+        log << "    E" << e << " \"" << FF7Text::toPC(nameRaw) << "\""
+
+            << " Lv:" << before.level << "->" << after.level
+
+            << " STR:" << before.str << "->" << after.str
+
+            << " MAG:" << before.mag << "->" << after.mag
+
+            << " HP:" << before.hp << "->" << after.hp
+
+            << " MP:" << mpBefore << "->" << mpAfter
+
+            << " (DPT " << qRound(EnemyScaling::damagePerTurn(before, fromLevel))
+
+            << "@" << fromLevel << " -> " << qRound(EnemyScaling::damagePerTurn(after, toLevel))
+
+            << "@" << toLevel << ")\n";
+
+
+
+        if (aiRescale && before.hp > 0 && after.hp != before.hp)
+
+            rescaleAiHpConstants(scene, e, static_cast<double>(after.hp) / before.hp, log);
+
+    }
+
+}
+
+
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// MP scaling — keep signature moves castable
+
+//
+
+// An enemy's moves are listed in its record (ENM_ATTACKS, scene attack IDs);
+
+// each scene attack record carries its MP cost. Scaled or randomized MP is
+
+// floored so the most expensive move can still be cast MP_FLOOR_CASTS times
+
+// (never above the vanilla MP though: some enemies are meant to run dry).
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+
+
+quint16 EnemyRandomizer::maxAttackMpCost(const QByteArray& scene, int enemySlot)
+
+{
+
+    if (scene.size() != SCENE_SIZE) return 0;
+
+    const uchar* raw = reinterpret_cast<const uchar*>(scene.constData());
+
+    int off = ENEMY_DATA_BASE + enemySlot * ENEMY_RECORD_SIZE;
+
+
+
+    quint16 maxCost = 0;
+
+    for (int a = 0; a < ENEMY_ATTACK_SLOTS; ++a) {
+
+        quint16 attackId;
+
+        memcpy(&attackId, raw + off + ENM_ATTACKS + a * 2, 2);
+
+        if (attackId == 0xFFFF) continue;
+
+        for (int i = 0; i < SCENE_ATTACK_COUNT; ++i) {
+
+            quint16 sceneId;
+
+            memcpy(&sceneId, raw + SCENE_ATTACK_IDS + i * 2, 2);
+
+            if (sceneId != attackId) continue;
+
+            quint16 cost;
+
+            memcpy(&cost, raw + SCENE_ATTACK_DATA + i * ATTACK_RECORD_SIZE + ATK_MP_COST, 2);
+
+            maxCost = qMax(maxCost, cost);
+
+            break;
+
+        }
+
+    }
+
+    return maxCost;
+
+}
+
+
+
+quint16 EnemyRandomizer::scaledEnemyMp(quint16 origMp, quint16 newMp, quint16 maxCost)
+
+{
+
+    const int floor = qMin<int>(origMp, maxCost * MP_FLOOR_CASTS);
+
+    return static_cast<quint16>(qBound(floor, static_cast<int>(newMp), 65535));
+
+}
+
+
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// rescaleAiHpConstants — keep AI HP checks in step with scaled HP
+
+//
+
+// Scripts like "if (self.HP < 5000) use Big Move" compare the enemy's HP
+
+// against a constant pushed inline. Once HP is scaled the constant no longer
+
+// lines up (a 20000 -> 6000 HP boss would open with its desperation move, a
+
+// scaled-up one would never reach it). Rather than disassembling the whole
+
+// script we match the compare sequence:
+
+//     0x10-0x12 2060       push the self mask
+
+//     0x00-0x03 <addr16>   push HP (0x4160) or max HP (0x4180) of the mask
+
+//     0x60-0x62 <1-3 B>    push constant
+
+//     0x40-0x45            compare
+
+// (also with the constant pushed first) and scale the constant by
+
+// newHP/oldHP. The HP address alone says nothing about whose HP is read;
+
+// without the self mask in front the check is about the party or another
+
+// enemy ("if target.HP < 1000") and is left alone. Relative checks
+
+// (HP < MaxHP / 4) need no change and are not matched.
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+
+
+int EnemyRandomizer::rescaleAiHpConstants(QByteArray& scene, int enemySlot,
+
+                                          double factor, QTextStream& log)
+
+{
+
+    if (scene.size() != SCENE_SIZE || factor <= 0.0) return 0;
+
+    const uchar* raw = reinterpret_cast<const uchar*>(scene.constData());
+
+
+
+    // This enemy's AI block runs from its offset to the next enemy's block
+
+    auto aiOffset = [&](int slot) -> int {
+
+        quint16 rel;
+
+        memcpy(&rel, raw + AI_ENEMY_BASE + slot * 2, 2);
+
+        return rel == 0xFFFF ? -1 : AI_ENEMY_BASE + rel;
+
+    };
+
+    int start = aiOffset(enemySlot);
+
+    if (start < 0 || start >= SCENE_SIZE) return 0;
+
+    int end = SCENE_SIZE;
+
+    for (int s = 0; s < ENEMIES_PER_SCENE; ++s) {
+
+        int other = aiOffset(s);
+
+        if (other > start && other < end) end = other;
+
+    }
+
+
+
+    auto addrAt = [&](int pos) {
+
+        return static_cast<quint16>(raw[pos] | (raw[pos + 1] << 8));
+
+    };
+
+    // Self mask push followed by the HP load (SELF_HP_LOAD_SIZE bytes)
+
+    auto isSelfHpLoad = [&](int pos) {
+
+        if (pos + SELF_HP_LOAD_SIZE > end) return false;
+
+        if (raw[pos] < 0x10 || raw[pos] > 0x12 || addrAt(pos + 1) != AI_ADDR_SELF) return false;
+
+        if (raw[pos + 3] > 0x03) return false;
+
+        quint16 addr = addrAt(pos + 4);
+
+        return addr == AI_ADDR_HP || addr == AI_ADDR_MAX_HP;
+
+    };
+
+    auto constWidth = [&](int pos) {
+
+        return (pos < end && raw[pos] >= 0x60 && raw[pos] <= 0x62) ? raw[pos] - 0x5F : 0;
+
+    };
+
+    auto isCompare = [&](int pos) {
+
+        return pos < end && raw[pos] >= 0x40 && raw[pos] <= 0x45;
+
+    };
+
+
+
+    int patched = 0;
+
+    for (int pos = start; pos < end; ++pos) {
+
+        int constPos = -1;
+
+        int width = 0;
+
+        if (isSelfHpLoad(pos) && (width = constWidth(pos + SELF_HP_LOAD_SIZE)) > 0
+
+            && isCompare(pos + SELF_HP_LOAD_SIZE + 1 + width))
+
+            constPos = pos + SELF_HP_LOAD_SIZE;
+
+        else if ((width = constWidth(pos)) > 0 && isSelfHpLoad(pos + 1 + width)
+
+                 && isCompare(pos + SELF_HP_LOAD_SIZE + 1 + width))
+
+            constPos = pos;
+
+        if (constPos < 0) continue;
+
+
+
+        quint32 value = 0;
+
+        for (int b = 0; b < width; ++b)
+
+            value |= quint32(raw[constPos + 1 + b]) << (8 * b);
+
+        if (value != 0) {   // "HP == 0" checks stay as they are
+
+            const long long maxValue = (1LL << (8 * width)) - 1;
+
+            quint32 scaled = static_cast<quint32>(
+
+                std::clamp(std::llround(value * factor), 1LL, maxValue));
+
+            char* out = scene.data();
+
+            for (int b = 0; b < width; ++b)
+
+                out[constPos + 1 + b] = static_cast<char>((scaled >> (8 * b)) & 0xFF);
+
+            raw = reinterpret_cast<const uchar*>(scene.constData());
+
+            log << "    AI @0x" << QString::number(constPos, 16)
+
+                << " HP const " << value << " -> " << scaled << "\n";
+
+            ++patched;
+
+        }
+
+        pos += SELF_HP_LOAD_SIZE + width;   // continue after this sequence
+
+    }
+
+    return patched;
+
+}
+
+
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// arenaFormations — the Battle Square's formation IDs
+
+//
+
+// Arena battles chain: each one's battle setup lists the formations the
+
+// arena may pick next. Every formation that lists a next battle, and every
+
+// formation so listed, is an arena battle. Read once from the scenes as
+
+// loaded, so the set is by formation ID and not by whatever setup bytes a
+
+// scene holds later; a scene with any of them is an arena scene.
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+QSet<int> EnemyRandomizer::arenaFormations(const QVector<SceneEntry>& scenes)
+
+{
+
+    QSet<int> arena;
+
+    for (int i = 0; i < scenes.size(); ++i) {
+
+        const QByteArray& scene = scenes[i].decompressed;
+
+        if (scene.size() != SCENE_SIZE) continue;
+
+        for (int f = 0; f < FORMATIONS_PER_SCENE; ++f) {
+
+            const int setup = BATTLE_SETUP_BASE + f * BATTLE_SETUP_SIZE;
+
+            for (int n = 0; n < 4; ++n) {
+
+                quint16 next;
+
+                memcpy(&next, scene.constData() + setup + SETUP_ARENA_NEXT + n * 2, 2);
+
+                if (next == ARENA_NEXT_NONE || next == 0xFFFF) continue;
+
+                arena.insert(i * FORMATIONS_PER_SCENE + f);
+
+                arena.insert(next);
+
+            }
+
+        }
+
+    }
+
+    return arena;
+
+}
+
+
+
+bool EnemyRandomizer::isArenaScene(int sceneIndex, const QSet<int>& arena)
+
+{
+
+    for (int f = 0; f < FORMATIONS_PER_SCENE; ++f) {
+
+        if (arena.contains(sceneIndex * FORMATIONS_PER_SCENE + f)) return true;
+
+    }
+
+    return false;
+
+}
+
+
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// Formation-level shuffle
+
+//
+
+// A formation only names its enemies by ID; the stats, attacks and AI come
+
+// from the records of whichever scene it sits in. So a formation can move to
+
+// any scene whose three enemy IDs cover its own, taking its battle setup,
+
+// camera and formation AI script along, and everything it references
+
+// resolves to the same enemies there. Moves are random pairwise swaps
+
+// between such scenes, which always leaves every scene.bin slot valid.
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+QSet<quint16> EnemyRandomizer::sceneEnemyIds(const QByteArray& scene)
+
+{
+
+    QSet<quint16> ids;
+
+    if (scene.size() != SCENE_SIZE) return ids;
+
+    for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+        quint16 id;
+
+        memcpy(&id, scene.constData() + e * 2, 2);
+
+        if (id != 0xFFFF) ids.insert(id);
+
+    }
+
+    return ids;
+
+}
+
+
+QSet<quint16> EnemyRandomizer::formationEnemyIds(const Formation& formation)
+
+{
+
+    QSet<quint16> ids;
+
+    for (int pos = 0; pos + FORMATION_ENTRY_SIZE <= formation.entries.size();
+
+         pos += FORMATION_ENTRY_SIZE) {
+
+        quint16 id;
+
+        memcpy(&id, formation.entries.constData() + pos, 2);
+
+        if (id != 0xFFFF) ids.insert(id);
+
+    }
+
+    return ids;
+
+}
+
+
+QVector<EnemyRandomizer::Formation> EnemyRandomizer::readFormations(const QByteArray& scene)
+
+{
+
+    QVector<Formation> formations;
+
+    if (scene.size() != SCENE_SIZE) return formations;
+
+    const uchar* raw = reinterpret_cast<const uchar*>(scene.constData());
+
+    auto aiOffset = [&](int f) -> int {
+
+        quint16 rel;
+
+        memcpy(&rel, raw + AI_FORMATION_BASE + f * 2, 2);
+
+        return rel == 0xFFFF ? -1 : AI_FORMATION_BASE + rel;
+
+    };
+
+    const int aiEnd = AI_FORMATION_BASE + AI_FORMATION_SIZE;
+
+    for (int f = 0; f < FORMATIONS_PER_SCENE; ++f) {
+
+        Formation formation;
+
+        formation.setup   = scene.mid(BATTLE_SETUP_BASE + f * BATTLE_SETUP_SIZE, BATTLE_SETUP_SIZE);
+
+        formation.camera  = scene.mid(CAMERA_BASE + f * CAMERA_SIZE, CAMERA_SIZE);
+
+        formation.entries = scene.mid(FORMATION_BASE + f * FORMATION_SIZE, FORMATION_SIZE);
+
+        // The script runs to the next script or the end of the block,
+
+        // minus the 0xFF padding
+
+        int start = aiOffset(f);
+
+        if (start >= AI_FORMATION_BASE && start < aiEnd) {
+
+            int end = aiEnd;
+
+            for (int other = 0; other < FORMATIONS_PER_SCENE; ++other) {
+
+                int o = aiOffset(other);
+
+                if (o > start && o < end) end = o;
+
+            }
+
+            while (end > start && raw[end - 1] == 0xFF) --end;
+
+            formation.ai = scene.mid(start, end - start);
+
+        }
+
+        formations.append(formation);
+
+    }
+
+    return formations;
+
+}
+
+
+bool EnemyRandomizer::writeFormations(QByteArray& scene, const QVector<Formation>& formations)
+
+{
+
+    if (scene.size() != SCENE_SIZE || formations.size() != FORMATIONS_PER_SCENE)
+
+        return false;
+
+    // Repack the formation AI block: offset table, then the scripts back to back
+
+    QByteArray aiBlock(AI_FORMATION_SIZE, char(0xFF));
+
+    int pos = FORMATIONS_PER_SCENE * 2;
+
+    for (int f = 0; f < FORMATIONS_PER_SCENE; ++f) {
+
+        quint16 rel = 0xFFFF;
+
+        const QByteArray& script = formations[f].ai;
+
+        if (!script.isEmpty()) {
+
+            if (pos + script.size() > AI_FORMATION_SIZE) return false;
+
+            rel = static_cast<quint16>(pos);
+
+            memcpy(aiBlock.data() + pos, script.constData(), script.size());
+
+            pos += script.size();
+
+        }
+
+        memcpy(aiBlock.data() + f * 2, &rel, 2);
+
+    }
+
+    for (int f = 0; f < FORMATIONS_PER_SCENE; ++f) {
+
+        const Formation& formation = formations[f];
+
+        scene.replace(BATTLE_SETUP_BASE + f * BATTLE_SETUP_SIZE, BATTLE_SETUP_SIZE, formation.setup);
+
+        scene.replace(CAMERA_BASE + f * CAMERA_SIZE, CAMERA_SIZE, formation.camera);
+
+        scene.replace(FORMATION_BASE + f * FORMATION_SIZE, FORMATION_SIZE, formation.entries);
+
+    }
+
+    scene.replace(AI_FORMATION_BASE, AI_FORMATION_SIZE, aiBlock);
+
+    return true;
+
+}
+
+
+int EnemyRandomizer::shuffleFormations(QVector<SceneEntry>& scenes, const QVector<int>& tier,
+
+                                       int maxTier, bool crossTier, const SceneCatalog& catalog,
+
+                                       QTextStream& log)
+
+{
+
+    // Every non-empty formation of every pooled scene is a slot
+
+    QMap<int, QVector<Formation>> formations;
+
+    QMap<int, QSet<quint16>> enemies;
+
+    QMap<int, QVector<int>> origin;    // scene * 4 + formation it came from
+
+    QVector<QPair<int, int>> slots;
+
+    for (int i = 0; i < scenes.size(); ++i) {
+
+        if (tier[i] < 0 || tier[i] > maxTier) continue;
+
+        formations[i] = readFormations(scenes[i].decompressed);
+
+        enemies[i]    = sceneEnemyIds(scenes[i].decompressed);
+
+        for (int f = 0; f < formations[i].size(); ++f) {
+
+            origin[i].append(i * FORMATIONS_PER_SCENE + f);
+
+            if (!formationEnemyIds(formations[i][f]).isEmpty())
+
+                slots.append({i, f});
+
+        }
+
+    }
+
+    log << "Formation pool: " << slots.size() << " formations in "
+
+        << formations.size() << " scenes\n";
+
+    if (slots.size() < 2) return 0;
+
+    auto aiBytes = [&](int scene) {
+
+        int total = FORMATIONS_PER_SCENE * 2;
+
+        for (const Formation& f : formations[scene]) total += f.ai.size();
+
+        return total;
+
+    };
+
+    // b's formation may replace a's if a's scene holds its enemies and the
+
+    // swapped script still fits a's formation AI block
+
+    auto fitsInto = [&](const QPair<int, int>& a, const QPair<int, int>& b) {
+
+        if (a.first == b.first) return true;
+
+        if (!crossTier && tier[a.first] != tier[b.first]) return false;
+
+        const Formation& incoming = formations[b.first][b.second];
+
+        if (!enemies[a.first].contains(formationEnemyIds(incoming))) return false;
+
+        int ai = aiBytes(a.first) - formations[a.first][a.second].ai.size() + incoming.ai.size();
+
+        return ai <= AI_FORMATION_SIZE;
+
+    };
+
+    auto swapSlots = [&](int a, int b) {
+
+        const QPair<int, int>& sa = slots[a];
+
+        const QPair<int, int>& sb = slots[b];
+
+        std::swap(formations[sa.first][sa.second], formations[sb.first][sb.second]);
+
+        std::swap(origin[sa.first][sa.second], origin[sb.first][sb.second]);
+
+    };
+
+    const QMap<int, QVector<Formation>> vanillaFormations = formations;
+
+    const QMap<int, QVector<int>> vanillaOrigin = origin;
+
+    QVector<QPair<int, int>> swaps;
+
+    QVector<int> order(slots.size());
+
+    for (int i = 0; i < order.size(); ++i) order[i] = i;
+
+    for (int i = order.size() - 1; i > 0; --i) {
+
+        std::uniform_int_distribution<int> dist(0, i);
+
+        std::swap(order[i], order[dist(m_rng)]);
+
+    }
+
+    for (int a : order) {
+
+        QVector<int> candidates;
+
+        for (int b = 0; b < slots.size(); ++b) {
+
+            if (fitsInto(slots[a], slots[b]) && fitsInto(slots[b], slots[a]))
+
+                candidates.append(b);
+
+        }
+
+        std::uniform_int_distribution<int> dist(0, candidates.size() - 1);
+
+        int b = candidates[dist(m_rng)];
+
+        if (b == a) continue;
+
+        swapSlots(a, b);
+
+        swaps.append({a, b});
+
+    }
+
+
+
+    // Every swap changes two scenes, so a scene that can't be written takes
+
+    // its swaps back in both. Its formations go back to vanilla and the
+
+    // other swaps are replayed (where they still fit) until every scene
+
+    // writes; a scene left vanilla is not rewritten at all
+
+    QSet<int> pinned;
+
+    QMap<int, QByteArray> written;
+
+    for (;;) {
+
+        written.clear();
+
+        QSet<int> failed;
+
+        for (auto it = formations.cbegin(); it != formations.cend(); ++it) {
+
+            if (pinned.contains(it.key())) continue;
+
+            QByteArray updated = scenes[it.key()].decompressed;
+
+            if (writeFormations(updated, it.value()))
+
+                written.insert(it.key(), updated);
+
+            else
+
+                failed.insert(it.key());
+
+        }
+
+        if (failed.isEmpty()) break;
+
+        for (int sceneIndex : failed)
+
+            log << "  Scene " << sceneIndex << ": formation AI does not fit, its swaps undone\n";
+
+        pinned.unite(failed);
+
+        formations = vanillaFormations;
+
+        origin = vanillaOrigin;
+
+        for (const QPair<int, int>& swap : swaps) {
+
+            const QPair<int, int>& sa = slots[swap.first];
+
+            const QPair<int, int>& sb = slots[swap.second];
+
+            if (pinned.contains(sa.first) || pinned.contains(sb.first)) continue;
+
+            if (fitsInto(sa, sb) && fitsInto(sb, sa))
+
+                swapSlots(swap.first, swap.second);
+
+        }
+
+    }
+
+
+
+    int moved = 0;
+
+    for (auto it = written.cbegin(); it != written.cend(); ++it) {
+
+        int sceneIndex = it.key();
+
+        scenes[sceneIndex].decompressed = it.value();
+
+        for (int f = 0; f < FORMATIONS_PER_SCENE; ++f) {
+
+            int from = origin[sceneIndex][f];
+
+            if (from == sceneIndex * FORMATIONS_PER_SCENE + f) continue;
+
+            ++moved;
+
+            log << "  " << catalog.describeFormation(sceneIndex * FORMATIONS_PER_SCENE + f)
+
+                << " <- " << catalog.describeFormation(from) << "\n";
+
+        }
+
+    }
+
+    log << "Formations moved: " << moved << "\n";
+
+    return moved;
+
+}
+
+
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// Enemy-level swap
+
+//
+
+// Keeps every scene's battle setup, camera and formations (so the background
+
+// always matches the field) and swaps the enemies standing in them. An enemy
+
+// is its scene ID (which picks the battle model), its 184-byte record, its
+
+// enemy AI block and the scene attacks its attack list names; all of it moves
+
+// together, so its AI still finds every attack it uses. Enemies trade places
+
+// only with enemies of a similar size, together with their HP tier unless
+
+// tiers are crossed. scene.bin has no model dimensions, but the vanilla
+
+// formations place every enemy with the room its model needs: its footprint
+
+// is the closest another enemy ever stands to it (x/z distance), or for an
+
+// enemy that only fights alone, how close the battle camera gets to it.
+
+// Enemies are compared within one kind of footprint and grouped into bands
+
+// no wider than SIZE_BAND_RATIO. Each enemy ID gets one replacement
+
+// everywhere it appears.
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+
+
+EnemyRandomizer::EnemyUnit EnemyRandomizer::readEnemyUnit(const QByteArray& scene, int slot)
+
+{
+
+    EnemyUnit unit;
+
+    if (scene.size() != SCENE_SIZE) return unit;
+
+    const uchar* raw = reinterpret_cast<const uchar*>(scene.constData());
+
+    memcpy(&unit.id, raw + slot * 2, 2);
+
+    const int off = ENEMY_DATA_BASE + slot * ENEMY_RECORD_SIZE;
+
+    unit.record = scene.mid(off, ENEMY_RECORD_SIZE);
+
+
+
+    // AI block: to the next enemy's block or the end of the scene, minus padding
+
+    auto aiOffset = [&](int s) -> int {
+
+        quint16 rel;
+
+        memcpy(&rel, raw + AI_ENEMY_BASE + s * 2, 2);
+
+        return rel == 0xFFFF ? -1 : AI_ENEMY_BASE + rel;
+
+    };
+
+    const int start = aiOffset(slot);
+
+    if (start >= AI_ENEMY_BASE + ENEMIES_PER_SCENE * 2 && start < SCENE_SIZE) {
+
+        int end = SCENE_SIZE;
+
+        for (int s = 0; s < ENEMIES_PER_SCENE; ++s) {
+
+            const int other = aiOffset(s);
+
+            if (other > start && other < end) end = other;
+
+        }
+
+        while (end > start && raw[end - 1] == 0xFF) --end;
+
+        unit.ai = scene.mid(start, end - start);
+
+    }
+
+
+
+    for (int a = 0; a < ENEMY_ATTACK_SLOTS; ++a) {
+
+        quint16 attackId;
+
+        memcpy(&attackId, raw + off + ENM_ATTACKS + a * 2, 2);
+
+        if (attackId == 0xFFFF || unit.attackIds.contains(attackId)) continue;
+
+        for (int i = 0; i < SCENE_ATTACK_COUNT; ++i) {
+
+            quint16 sceneId;
+
+            memcpy(&sceneId, raw + SCENE_ATTACK_IDS + i * 2, 2);
+
+            if (sceneId != attackId) continue;
+
+            unit.attackIds.append(attackId);
+
+            unit.attacks.append(scene.mid(SCENE_ATTACK_DATA + i * ATTACK_RECORD_SIZE, ATTACK_RECORD_SIZE));
+
+            unit.attackNames.append(scene.mid(SCENE_ATTACK_NAMES + i * ATTACK_NAME_SIZE, ATTACK_NAME_SIZE));
+
+            break;
+
+        }
+
+    }
+
+    return unit;
+
+}
+
+
+
+bool EnemyRandomizer::writeEnemyUnit(QByteArray& scene, int slot, const EnemyUnit& unit)
+
+{
+
+    if (scene.size() != SCENE_SIZE || unit.record.size() != ENEMY_RECORD_SIZE) return false;
+
+    QByteArray updated = scene;
+
+    uchar* raw = reinterpret_cast<uchar*>(updated.data());
+
+    quint16 oldId;
+
+    memcpy(&oldId, raw + slot * 2, 2);
+
+
+
+    // Only the outgoing enemy's own attacks make room; anything the other two
+
+    // enemies list, or that nobody lists (formation AI may use it), stays
+
+    const QVector<quint16> outgoing = readEnemyUnit(scene, slot).attackIds;
+
+    QSet<quint16> keep;
+
+    for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+        if (e == slot) continue;
+
+        const int off = ENEMY_DATA_BASE + e * ENEMY_RECORD_SIZE;
+
+        for (int a = 0; a < ENEMY_ATTACK_SLOTS; ++a) {
+
+            quint16 attackId;
+
+            memcpy(&attackId, raw + off + ENM_ATTACKS + a * 2, 2);
+
+            if (attackId != 0xFFFF) keep.insert(attackId);
+
+        }
+
+    }
+
+    QVector<int> freeSlots;
+
+    QSet<quint16> present;
+
+    for (int i = 0; i < SCENE_ATTACK_COUNT; ++i) {
+
+        quint16 sceneId;
+
+        memcpy(&sceneId, raw + SCENE_ATTACK_IDS + i * 2, 2);
+
+        if (sceneId == 0xFFFF || (outgoing.contains(sceneId) && !keep.contains(sceneId)))
+
+            freeSlots.append(i);
+
+        else
+
+            present.insert(sceneId);
+
+    }
+
+    for (int i : freeSlots) {
+
+        const quint16 none = 0xFFFF;
+
+        memcpy(raw + SCENE_ATTACK_IDS + i * 2, &none, 2);
+
+        memset(raw + SCENE_ATTACK_DATA + i * ATTACK_RECORD_SIZE, 0xFF, ATTACK_RECORD_SIZE);
+
+        memset(raw + SCENE_ATTACK_NAMES + i * ATTACK_NAME_SIZE, 0xFF, ATTACK_NAME_SIZE);
+
+    }
+
+    int nextFree = 0;
+
+    for (int a = 0; a < unit.attackIds.size(); ++a) {
+
+        const quint16 attackId = unit.attackIds[a];
+
+        if (present.contains(attackId)) continue;   // same attack, same data
+
+        if (nextFree >= freeSlots.size()) return false;
+
+        const int i = freeSlots[nextFree++];
+
+        memcpy(raw + SCENE_ATTACK_IDS + i * 2, &attackId, 2);
+
+        memcpy(raw + SCENE_ATTACK_DATA + i * ATTACK_RECORD_SIZE, unit.attacks[a].constData(), ATTACK_RECORD_SIZE);
+
+        memcpy(raw + SCENE_ATTACK_NAMES + i * ATTACK_NAME_SIZE, unit.attackNames[a].constData(), ATTACK_NAME_SIZE);
+
+        present.insert(attackId);
+
+    }
+
+
+
+    // Repack the enemy AI blocks with this slot's replaced
+
+    QVector<QByteArray> ai(ENEMIES_PER_SCENE);
+
+    for (int e = 0; e < ENEMIES_PER_SCENE; ++e)
+
+        ai[e] = e == slot ? unit.ai : readEnemyUnit(scene, e).ai;
+
+    QByteArray aiBlock(SCENE_SIZE - AI_ENEMY_BASE, char(0xFF));
+
+    int pos = ENEMIES_PER_SCENE * 2;
+
+    for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+        quint16 rel = 0xFFFF;
+
+        if (!ai[e].isEmpty()) {
+
+            if (pos + ai[e].size() > aiBlock.size()) return false;
+
+            rel = static_cast<quint16>(pos);
+
+            memcpy(aiBlock.data() + pos, ai[e].constData(), ai[e].size());
+
+            pos += ai[e].size();
+
+        }
+
+        memcpy(aiBlock.data() + e * 2, &rel, 2);
+
+    }
+
+    updated.replace(AI_ENEMY_BASE, aiBlock.size(), aiBlock);
+
+    raw = reinterpret_cast<uchar*>(updated.data());
+
+
+
+    // ID in the scene header and every formation entry, then the record
+
+    memcpy(raw + slot * 2, &unit.id, 2);
+
+    for (int f = 0; f < FORMATIONS_PER_SCENE; ++f) {
+
+        for (int k = 0; k < FORMATION_SIZE / FORMATION_ENTRY_SIZE; ++k) {
+
+            uchar* entry = raw + FORMATION_BASE + f * FORMATION_SIZE + k * FORMATION_ENTRY_SIZE;
+
+            quint16 id;
+
+            memcpy(&id, entry, 2);
+
+            if (id == oldId) memcpy(entry, &unit.id, 2);
+
+        }
+
+    }
+
+    updated.replace(ENEMY_DATA_BASE + slot * ENEMY_RECORD_SIZE, ENEMY_RECORD_SIZE, unit.record);
+
+    scene = updated;
+
+    return true;
+
+}
+
+
+
+QVector<EnemyRandomizer::Placement> EnemyRandomizer::placements(const Formation& formation)
+
+{
+
+    QVector<Placement> placed;
+
+    const char* raw = formation.entries.constData();
+
+    for (int pos = 0; pos + FORMATION_ENTRY_SIZE <= formation.entries.size();
+
+         pos += FORMATION_ENTRY_SIZE) {
+
+        Placement p;
+
+        memcpy(&p.id, raw + pos, 2);
+
+        if (p.id == 0xFFFF) continue;
+
+        memcpy(&p.x, raw + pos + FORMATION_ENTRY_POS, 2);
+
+        memcpy(&p.y, raw + pos + FORMATION_ENTRY_POS + 2, 2);
+
+        memcpy(&p.z, raw + pos + FORMATION_ENTRY_POS + 4, 2);
+
+        placed.append(p);
+
+    }
+
+    return placed;
+
+}
+
+
+
+double EnemyRandomizer::cameraDistance(const Formation& formation, const Placement& placed)
+
+{
+
+    if (formation.camera.size() < CAMERA_POS + 6) return 0.0;
+
+    qint16 cam[3];
+
+    memcpy(cam, formation.camera.constData() + CAMERA_POS, sizeof(cam));
+
+    if (cam[0] == -1 && cam[1] == -1 && cam[2] == -1) return 0.0;   // 0xFF padding
+
+    return std::sqrt(std::pow(double(cam[0] - placed.x), 2)
+
+                     + std::pow(double(cam[1] - placed.y), 2)
+
+                     + std::pow(double(cam[2] - placed.z), 2));
+
+}
+
+
+
+int EnemyRandomizer::swapEnemies(QVector<SceneEntry>& scenes, const QVector<int>& tier,
+
+                                 int maxTier, bool crossTier, bool scaleScenes,
+
+                                 const SceneCatalog& catalog, QTextStream& log)
+
+{
+
+    // ── Every enemy of a pooled scene, first copy wins, with its footprint ──
+
+    QMap<quint16, EnemyUnit> units;
+
+    QHash<quint16, double> spacing;     // closest neighbour in any formation
+
+    QHash<quint16, double> cameraReach; // closest camera, formations it fights alone
+
+    for (int i = 0; i < scenes.size(); ++i) {
+
+        if (tier[i] < 0 || tier[i] > maxTier) continue;
+
+        const QByteArray& scene = scenes[i].decompressed;
+
+        for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+            EnemyUnit unit = readEnemyUnit(scene, e);
+
+            const QByteArray name = unit.record.left(ENM_NAME_SIZE);
+
+            if (unit.id == 0xFFFF || name.count(char(0xFF)) == ENM_NAME_SIZE) continue;
+
+            if (!units.contains(unit.id)) units.insert(unit.id, unit);
+
+        }
+
+        for (const Formation& formation : readFormations(scene)) {
+
+            const QVector<Placement> placed = placements(formation);
+
+            for (int a = 0; a < placed.size(); ++a) {
+
+                double nearest = -1.0;
+
+                for (int b = 0; b < placed.size(); ++b) {
+
+                    if (b == a) continue;
+
+                    const double d = std::hypot(double(placed[a].x - placed[b].x),
+
+                                                double(placed[a].z - placed[b].z));
+
+                    if (nearest < 0.0 || d < nearest) nearest = d;
+
+                }
+
+                const quint16 id = placed[a].id;
+
+                if (nearest > 0.0) {
+
+                    spacing[id] = spacing.contains(id) ? qMin(spacing[id], nearest) : nearest;
+
+                } else if (placed.size() == 1) {
+
+                    const double reach = cameraDistance(formation, placed[a]);
+
+                    if (reach > 0.0)
+
+                        cameraReach[id] = cameraReach.contains(id) ? qMin(cameraReach[id], reach) : reach;
+
+                }
+
+            }
+
+        }
+
+    }
+
+    // Footprint kinds: 0 = spacing, 1 = camera reach (never fights with
+
+    // others), 2 = unknown (never placed)
+
+    QMap<QPair<int, int>, QVector<QPair<double, quint16>>> kinds;
+
+    for (auto it = units.cbegin(); it != units.cend(); ++it) {
+
+        quint32 hp;
+
+        memcpy(&hp, it.value().record.constData() + ENM_HP, 4);
+
+        int hpTier;
+
+        if      (hp >= 10000) hpTier = 4;
+
+        else if (hp >= 4000)  hpTier = 3;
+
+        else if (hp >= 1000)  hpTier = 2;
+
+        else if (hp >= 200)   hpTier = 1;
+
+        else                  hpTier = 0;
+
+        const int kind = spacing.contains(it.key()) ? 0 : cameraReach.contains(it.key()) ? 1 : 2;
+
+        const double footprint = kind == 0 ? spacing[it.key()]
+
+                               : kind == 1 ? cameraReach[it.key()] : 0.0;
+
+        kinds[qMakePair(crossTier ? 0 : hpTier, kind)].append(qMakePair(footprint, it.key()));
+
+    }
+
+    // ── One replacement per enemy ID, shuffled within its size band ──
+
+    static const char* const KIND_NAMES[] = { "spacing", "camera", "unplaced" };
+
+    QHash<quint16, quint16> replacement;
+
+    for (auto it = kinds.begin(); it != kinds.end(); ++it) {
+
+        QVector<QPair<double, quint16>>& sorted = it.value();
+
+        std::sort(sorted.begin(), sorted.end());
+
+        for (int first = 0; first < sorted.size();) {
+
+            int last = first + 1;
+
+            while (last < sorted.size() && (it.key().second == 2
+
+                   || sorted[last].first <= sorted[first].first * SIZE_BAND_RATIO))
+
+                ++last;
+
+            QVector<quint16> ids;
+
+            for (int k = first; k < last; ++k)
+
+                ids.append(sorted[k].second);
+
+            QVector<quint16> donors = ids;
+
+            for (int i = donors.size() - 1; i > 0; --i) {
+
+                std::uniform_int_distribution<int> dist(0, i);
+
+                std::swap(donors[i], donors[dist(m_rng)]);
+
+            }
+
+            for (int i = 0; i < ids.size(); ++i)
+
+                replacement.insert(ids[i], donors[i]);
+
+            log << "Band tier " << it.key().first << ", " << KIND_NAMES[it.key().second]
+
+                << " " << qRound(sorted[first].first) << "-" << qRound(sorted[last - 1].first)
+
+                << ": " << ids.size() << " enemies\n";
+
+            first = last;
+
+        }
+
+    }
+
+    log << "\n";
+
+
+
+    auto unitName = [](const EnemyUnit& unit) {
+
+        return FF7Text::toPC(unit.record.left(ENM_NAME_SIZE)).trimmed();
+
+    };
+
+
+
+    // ── Apply per scene, slot by slot ──
+
+    int swapped = 0;
+
+    for (int i = 0; i < scenes.size(); ++i) {
+
+        if (tier[i] < 0 || tier[i] > maxTier) continue;
+
+        const QByteArray original = scenes[i].decompressed;
+
+        QByteArray scene = original;
+
+        bool changed = false;
+
+        for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+            quint16 id;
+
+            memcpy(&id, original.constData() + e * 2, 2);
+
+            const quint16 donorId = replacement.value(id, id);
+
+            if (donorId == id || !units.contains(id)) continue;
+
+            const EnemyUnit& donor = units[donorId];
+
+            if (sceneEnemyIds(scene).contains(donorId)) {
+
+                log << "  Scene " << i << ": " << unitName(units[id]) << " kept, "
+
+                    << unitName(donor) << " already in the scene\n";
+
+                continue;
+
+            }
+
+            if (!writeEnemyUnit(scene, e, donor)) {
+
+                log << "  Scene " << i << ": " << unitName(units[id]) << " kept, "
+
+                    << unitName(donor) << "'s attacks or AI don't fit\n";
+
+                continue;
+
+            }
+
+            log << "  Scene " << i << " slot " << e << ": " << unitName(units[id])
+
+                << " [" << id << "] <- " << unitName(donor) << " [" << donorId << "]\n";
+
+            changed = true;
+
+            ++swapped;
+
+        }
+
+        if (!changed) continue;
+
+        if (scaleScenes) {
+
+            log << "  " << catalog.describeScene(i) << "\n";
+
+            scaleSceneForSlot(scene, original, log);
+
+        }
+
+        scenes[i].decompressed = scene;
+
+    }
+
+    log << "Enemies swapped: " << swapped << "\n";
+
+    return swapped;
+
+}
+
+
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// writeDangerReport — per-zone threat summary of the randomized encounters
+
+//
+
+// Field encounter tables (flevel section 6) list the formations each map can
+
+// roll; formation N lives in scene N / 4. Maps are grouped into zones by name
+
+// prefix ("mds7st1" -> "mds7st", "nmkin_3" -> "nmkin"). Each fight's threat is
+
+// the share of party HP it takes before the party kills it,
+
+//     sum over enemies of damagePerTurn × turnsToKill / partyHp,
+
+// at the party level the VANILLA game expects in that zone (median vanilla
+
+// enemy level), so shuffled-in deathtraps stand out against their zone.
+
+//
+
+// The world map keeps its own tables in world_us.lgp/enc_w.bin: per region,
+
+// one encounter set per terrain type, each laid out like a field table with
+
+// four chocobo formations after the special ones. Each region is a zone.
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+
+
+namespace {
+
+
+
+const int FIELD_ENCOUNTER_SECTION = 6;
+
+const int ENCOUNTER_TABLES        = 2;
+
+const int ENCOUNTER_TABLE_SIZE    = 24;   // enabled, rate, 6 normal + 4 special u16, pad
+
+
+const int WORLD_SETS_PER_REGION   = 4;    // one per terrain type
+
+const int WORLD_SET_SIZE          = 32;   // enabled, rate, 6 normal + 4 special + 4 chocobo u16, pad
+
+const int WORLD_SET_ENTRIES       = 14;
+
+const int WORLD_CHOCOBO_FIRST     = 10;   // entries 10-13; high 6 bits = chocobo rating
+
+
+
+QString zoneForField(QString field)
+
+{
+
+    static const QRegularExpression suffix(QStringLiteral("(_\\d+)?\\d*$"));
+
+    field.remove(suffix);
+
+    return field.isEmpty() ? QStringLiteral("?") : field;
+
+}
+
+
+
+double median(QVector<double> values)
+
+{
+
+    if (values.isEmpty()) return 0.0;
+
+    std::sort(values.begin(), values.end());
+
+    int mid = values.size() / 2;
+
+    return values.size() % 2 ? values[mid] : (values[mid - 1] + values[mid]) / 2.0;
+
+}
+
+
+
+}
+
+
+
+QMap<QString, QSet<int>> EnemyRandomizer::loadZoneFormations(QTextStream& log) const
+
+{
+
+    QMap<QString, QSet<int>> zones;
+
+    // Encounter tables are never touched by the field pass, so the resident
+
+    // cache of vanilla fields serves as well as the archive
+
+    const InputCache* cache = m_parent->inputCache();
+
+    MakouLgpManager lgp;
+
+    if (!cache) {
+
+        QString flevelPath = GameFileLocator::findFlevelLgp(m_parent->getFF7Path(),
+
+                                                            m_parent->getOutputPath());
+
+        if (flevelPath.isEmpty() || !lgp.open(flevelPath)) {
+
+            log << "flevel.lgp not available: " << lgp.lastError() << "\n";
+
+            return zones;
+
+        }
+
+    }
+
+    const QStringList fileNames = cache ? cache->fileNames() : lgp.fileList();
+
+
+
+    for (const QString& fileName : fileNames) {
+
+        if (fileName.contains('.')) continue;   // textures / tables, not fields
+
+        QByteArray field = LZS::decompressAllWithHeader(cache ? cache->fieldData(fileName)
+
+                                                              : lgp.fileData(fileName));
+
+        if (field.size() < 6 + 9 * 4) continue;
+
+        quint32 sections[9];
+
+        memcpy(sections, field.constData() + 6, sizeof(sections));
+
+        int start = static_cast<int>(sections[FIELD_ENCOUNTER_SECTION]) + 4;
+
+        if (start + ENCOUNTER_TABLES * ENCOUNTER_TABLE_SIZE > field.size()) continue;
+
+
+
+        for (int t = 0; t < ENCOUNTER_TABLES; ++t) {
+
+            const uchar* table = reinterpret_cast<const uchar*>(field.constData())
+
+                               + start + t * ENCOUNTER_TABLE_SIZE;
+
+            if (!table[0]) continue;   // table disabled
+
+            for (int b = 0; b < 10; ++b) {
+
+                quint16 entry = static_cast<quint16>(table[2 + b * 2] | (table[3 + b * 2] << 8));
+
+                int formation = entry & 0x03FF;          // high 6 bits = chance / 64
+
+                if ((entry >> 10) == 0 || formation == 0) continue;
+
+                zones[zoneForField(fileName)].insert(formation);
+
+            }
+
+        }
+
+    }
+
+    return zones;
+
+}
+
+
+
+QMap<QString, QSet<int>> EnemyRandomizer::loadWorldFormations(QTextStream& log) const
+
+{
+
+    QMap<QString, QSet<int>> zones;
+
+    // Read from the install: the chocobo rating shuffle is the only pass that
+
+    // rewrites enc_w.bin, and it never moves a formation
+
+    QString worldPath = GameFileLocator::findFile(m_parent->getFF7Path(),
+
+                                                  {QStringLiteral("data/wm/world_us.lgp")},
+
+                                                  QStringLiteral("world_us.lgp"),
+
+                                                  m_parent->getOutputPath());
+
+    MakouLgpManager lgp;
+
+    if (worldPath.isEmpty() || !lgp.open(worldPath)) {
+
+        log << "world_us.lgp not available: " << lgp.lastError() << "\n";
+
+        return zones;
+
+    }
+
+    QByteArray table = lgp.fileData(QStringLiteral("enc_w.bin"));
+
+    const int regionSize = WORLD_SETS_PER_REGION * WORLD_SET_SIZE;
+
+    if (table.isEmpty() || table.size() % regionSize != 0) {
+
+        log << "enc_w.bin missing or not " << regionSize << "-byte regions ("
+
+            << table.size() << " bytes)\n";
+
+        return zones;
+
+    }
+
+    const uchar* raw = reinterpret_cast<const uchar*>(table.constData());
+
+    for (int region = 0; region < table.size() / regionSize; ++region) {
+
+        QString zone = QStringLiteral("world region %1").arg(region);
+
+        for (int set = 0; set < WORLD_SETS_PER_REGION; ++set) {
+
+            const uchar* entries = raw + region * regionSize + set * WORLD_SET_SIZE;
+
+            if (!entries[0]) continue;   // set disabled
+
+            for (int b = 0; b < WORLD_SET_ENTRIES; ++b) {
+
+                quint16 entry = static_cast<quint16>(entries[2 + b * 2] | (entries[3 + b * 2] << 8));
+
+                int formation = entry & 0x03FF;
+
+                if ((entry >> 10) == 0 || formation == 0) continue;
+
+                zones[zone].insert(formation);
+
+            }
+
+        }
+
+    }
+
+    log << "World map: " << zones.size() << " regions with encounters\n";
+
+    return zones;
+
+}
+
+
+
+double EnemyRandomizer::sceneThreat(const QByteArray& scene, int partyLevel)
+
+{
+
+    if (scene.size() != SCENE_SIZE) return 0.0;
+
+    double threat = 0.0;
+
+    for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+        int off = ENEMY_DATA_BASE + e * ENEMY_RECORD_SIZE;
+
+        if (scene.mid(off + ENM_NAME, ENM_NAME_SIZE).count(char(0xFF)) == ENM_NAME_SIZE) continue;
+
+        EnemyScaling::Stats stats;
+
+        stats.level = static_cast<quint8>(scene.at(off + ENM_LEVEL));
+
+        stats.str   = static_cast<quint8>(scene.at(off + ENM_STR));
+
+        stats.def   = static_cast<quint8>(scene.at(off + ENM_DEF));
+
+        stats.mag   = static_cast<quint8>(scene.at(off + ENM_MAG));
+
+        stats.mdef  = static_cast<quint8>(scene.at(off + ENM_MDEF));
+
+        memcpy(&stats.hp, scene.constData() + off + ENM_HP, 4);
+
+        threat += EnemyScaling::damagePerTurn(stats, partyLevel)
+
+                * EnemyScaling::turnsToKill(stats, partyLevel);
+
+    }
+
+    return threat / EnemyScaling::partyHp(partyLevel);
+
+}
+
+
+
+bool EnemyRandomizer::writeDangerReport(const QVector<SceneEntry>& scenes, QTextStream& log)
+
+{
+
+    QString outputPath = m_parent->getOutputPath();
+
+    QString ff7Path    = m_parent->getFF7Path();
+
+
+
+    // Vanilla scenes give each zone's expected party level
+
+    QVector<SceneEntry> vanilla;
+
+    QFile vanillaFile(GameFileLocator::findSceneBin(ff7Path, outputPath));
+
+    if (!vanillaFile.open(QIODevice::ReadOnly)
+
+        || !extractScenes(vanillaFile.readAll(), vanilla, log)) {
+
+        log << "Danger report skipped: original scene.bin unavailable\n";
+
+        return false;
+
+    }
+
+    QMap<QString, QSet<int>> zones = loadZoneFormations(log);
+
+    zones.insert(loadWorldFormations(log));
+
+    if (zones.isEmpty()) {
+
+        log << "Danger report skipped: no encounter tables found\n";
+
+        return false;
+
+    }
+
+
+
+    QVector<QByteArray> current;
+
+    for (const SceneEntry& scene : scenes)
+
+        current.append(scene.decompressed);
+
+    const SceneCatalog catalog(current);
+
+    QFile reportFile(outputPath + "/danger_report.txt");
+
+    if (!reportFile.open(QIODevice::WriteOnly | QIODevice::Text)) {
+
+        log << "Danger report skipped: cannot write " << reportFile.fileName() << "\n";
+
+        return false;
+
+    }
+
+    QTextStream out(&reportFile);
+
+    out << "=== Encounter Danger Report ===\n"
+
+        << QDateTime::currentDateTime().toString() << "\n\n"
+
+        << "Threat = share of party HP a fight takes before the party wins\n"
+
+        << "(sum of enemy damage/turn x turns to kill / party HP) at the party level\n"
+
+        << "the vanilla game expects in that zone. 1.0 = could wipe a fresh party.\n\n"
+
+        << QString("%1 %2 %3 %4 %5 %6  %7\n")
+
+               .arg(QStringLiteral("Zone"), -10).arg(QStringLiteral("Lv"), 3)
+
+               .arg(QStringLiteral("Fights"), 6).arg(QStringLiteral("Min"), 6)
+
+               .arg(QStringLiteral("Median"), 6).arg(QStringLiteral("Max"), 6)
+
+               .arg(QStringLiteral("Worst fight"));
+
+
+
+    QStringList outliers;
+
+    for (auto it = zones.constBegin(); it != zones.constEnd(); ++it) {
+
+        QVector<double> levels;
+
+        for (int formation : it.value()) {
+
+            int scene = formation / 4;
+
+            if (scene < vanilla.size() && averageEnemyLevel(vanilla[scene].decompressed) > 0)
+
+                levels.append(averageEnemyLevel(vanilla[scene].decompressed));
+
+        }
+
+        if (levels.isEmpty()) continue;
+
+        int partyLevel = qRound(median(levels));
+
+
+
+        QVector<double> threats;
+
+        double worst = -1.0;
+
+        int worstScene = -1;
+
+        for (int formation : it.value()) {
+
+            int scene = formation / 4;
+
+            if (scene >= scenes.size() || scenes[scene].decompressed.size() != SCENE_SIZE) continue;
+
+            double threat = sceneThreat(scenes[scene].decompressed, partyLevel);
+
+            threats.append(threat);
+
+            if (threat > worst) { worst = threat; worstScene = scene; }
+
+        }
+
+        if (threats.isEmpty()) continue;
+
+
+
+        double med = median(threats);
+
+        double minThreat = *std::min_element(threats.begin(), threats.end());
+
+        QString worstName = catalog.sceneEnemies(worstScene).join(QStringLiteral(", "));
+
+        out << QString("%1 %2 %3 %4 %5 %6  scene %7 (%8)\n")
+
+                   .arg(it.key(), -10).arg(partyLevel, 3).arg(threats.size(), 6)
+
+                   .arg(minThreat, 6, 'f', 2).arg(med, 6, 'f', 2).arg(worst, 6, 'f', 2)
+
+                   .arg(worstScene).arg(worstName);
+
+        if (med > 0.0 && worst > 3.0 * med && worst >= 0.5)
+
+            outliers << QString("  %1: scene %2 (%3) threat %4 vs zone median %5")
+
+                            .arg(it.key()).arg(worstScene).arg(worstName)
+
+                            .arg(worst, 0, 'f', 2).arg(med, 0, 'f', 2);
+
+    }
+
+
+
+    out << "\nOutliers (worst fight > 3x zone median):\n";
+
+    if (outliers.isEmpty())
+
+        out << "  none\n";
+
+    for (const QString& line : outliers)
+
+        out << line << "\n";
+
+
+
+    log << "Danger report written: " << reportFile.fileName() << " (" << zones.size() << " zones)\n";
+
+    return true;
+
+}
+
+
+
+// Once per run, after every pass that moves or scales enemies, from the
+
+// scene.bin they left in the output
+
+bool EnemyRandomizer::writeDangerReport()
+
+{
+
+    QString logText;
+
+    QTextStream log(&logText);
+
+    QFile sceneFile(QDir(m_parent->getOutputPath()).filePath("data/lang-en/battle/scene.bin"));
+
+    QVector<SceneEntry> scenes;
+
+    if (!sceneFile.open(QIODevice::ReadOnly) || !extractScenes(sceneFile.readAll(), scenes, log)) {
+
+        qDebug() << "Danger report skipped: no scene.bin in the output";
+
+        return false;
+
+    }
+
+    const bool ok = writeDangerReport(scenes, log);
+
+    if (!ok)
+
+        qDebug().noquote() << logText;
+
+    return ok;
+
+}
+
+
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// renameEnemies — cosmetic shuffle / replacement of scene.bin enemy names
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+
+
+namespace {
+
+
+
+// Word banks for EnemyNamesReplace ("Sleepy Cactuar")
+
+const char* const NAME_ADJECTIVES[] = {
+
+    "Sleepy", "Grumpy", "Fancy", "Tiny", "Mega", "Angry", "Shy", "Dapper",
+
+    "Soggy", "Spicy", "Cursed", "Lucky", "Fluffy", "Rusty", "Sneaky",
+
+    "Wobbly", "Loud", "Turbo", "Ancient", "Confused",
+
+};
+
+const char* const NAME_NOUNS[] = {
+
+    "Moogle", "Tonberry", "Cactuar", "Chocobo", "Bomb", "Mu", "Sahagin",
+
+    "Toad", "Grunt", "Pudding", "Mandragora", "Mimic", "Goblin",
+
+    "Hedgehog Pie", "Whole Eater", "Malboro", "Ghost", "Turtle", "Sweeper",
+
+    "Dorky Face",
+
+};
+
+
+
+}
+
+
+
+bool EnemyRandomizer::renameEnemies()
+
+{
+
+    const Config& config = m_parent->m_config;
+
+    int  mode           = config.getEnemyNameMode();
+
+    bool preserveBosses = config.getPreserveBossNames();
+
+    if (mode == Config::EnemyNamesVanilla) return true;
+
+
+
+    // Read from OUTPUT so earlier stat/encounter changes are kept
+
+    QString outputPath = m_parent->getOutputPath();
+
+    QString logPath = outputPath + "/enemy_names_debug.txt";
+
+    QFile logFile(logPath);
+
+    bool logOk = logFile.open(QIODevice::WriteOnly | QIODevice::Text);
+
+    Q_UNUSED(logOk);
+
+    QTextStream dbg(&logFile);
+
+    dbg << "=== Enemy Names ("
+
+        << (mode == Config::EnemyNamesShuffle ? "shuffle" : "replace") << ") ===\n"
+
+        << QDateTime::currentDateTime().toString() << "\n"
+
+        << "Preserve boss names: " << (preserveBosses ? "YES" : "NO") << "\n\n";
+
+
+
+    QString scenePath = QDir(outputPath).filePath("data/lang-en/battle/scene.bin");
+
+    QFile sceneFile(scenePath);
+
+    if (!sceneFile.open(QIODevice::ReadOnly)) {
+
+        // Fall back to original
+
+        scenePath = GameFileLocator::findSceneBin(m_parent->getFF7Path(), outputPath);
+
+        sceneFile.setFileName(scenePath);
+
+        if (!sceneFile.open(QIODevice::ReadOnly)) {
+
+            dbg << "ERROR: Cannot open scene.bin\n";
+
+            return false;
+
+        }
+
+    }
+
+    QByteArray sceneBin = sceneFile.readAll();
+
+    sceneFile.close();
+
+
+
+    QVector<SceneEntry> scenes;
+
+    if (!extractScenes(sceneBin, scenes, dbg)) {
+
+        dbg << "ERROR: extractScenes failed\n";
+
+        m_parent->reportInputError(Randomizer::InputError::SceneParse, scenePath,
+
+                                   "no scene decompresses (see the pass's debug log)");
+
+        return false;
+
+    }
+
+
+
+    // ── Collect distinct names. The same enemy appears in many scenes and
+
+    // must carry the same new name everywhere. A name counts as a boss if
+
+    // any of its records clears the HP threshold or is on the boss list. ──
+
+    QStringList names;
+
+    QSet<QString> seen;
+
+    QSet<QString> bossNames;
+
+    const BossList& bossList = BossList::instance();
+
+    for (const QString& warning : bossList.warnings())
+
+        dbg << "Boss list: " << warning << "\n";
+
+    for (int s = 0; s < scenes.size(); ++s) {
+
+        const SceneEntry& scene = scenes[s];
+
+        if (scene.decompressed.size() != SCENE_SIZE) continue;
+
+        for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+            int off = ENEMY_DATA_BASE + e * ENEMY_RECORD_SIZE;
+
+            QByteArray nameRaw = scene.decompressed.mid(off + ENM_NAME, ENM_NAME_SIZE);
+
+            if (nameRaw.count(char(0xFF)) == ENM_NAME_SIZE) continue;  // empty slot
+
+            QString name = FF7Text::toPC(nameRaw).trimmed();
+
+            if (name.isEmpty()) continue;
+
+
+
+            quint32 hp;
+
+            memcpy(&hp, scene.decompressed.constData() + off + ENM_HP, 4);
+
+            if (hp >= BOSS_HP_THRESHOLD || bossList.isBoss(name, s))
+
+                bossNames.insert(name);
+
+            if (!seen.contains(name)) {
+
+                seen.insert(name);
+
+                names.append(name);
+
+            }
+
+        }
+
+    }
+
+    if (preserveBosses) {
+
+        names.erase(std::remove_if(names.begin(), names.end(),
+
+                                   [&](const QString& n) { return bossNames.contains(n); }),
+
+                    names.end());
+
+    }
+
+    dbg << "Distinct names: " << seen.size() << " (" << names.size() << " renameable)\n\n";
+
+
+
+    // ── Build old -> new mapping ──
+
+    QHash<QString, QString> newNames;
+
+    if (mode == Config::EnemyNamesShuffle) {
+
+        QStringList shuffled = names;
+
+        for (int i = shuffled.size() - 1; i > 0; --i) {
+
+            std::uniform_int_distribution<int> dist(0, i);
+
+            shuffled.swapItemsAt(i, dist(m_rng));
+
+        }
+
+        for (int i = 0; i < names.size(); ++i)
+
+            newNames.insert(names[i], shuffled[i]);
+
+    } else {
+
+        for (const QString& name : names)
+
+            newNames.insert(name, generateEnemyName());
+
+    }
+
+
+
+    // ── Apply to every record ──
+
+    int renamed = 0;
+
+    for (SceneEntry& scene : scenes) {
+
+        if (scene.decompressed.size() != SCENE_SIZE) continue;
+
+        for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+            int off = ENEMY_DATA_BASE + e * ENEMY_RECORD_SIZE;
+
+            QByteArray nameRaw = scene.decompressed.mid(off + ENM_NAME, ENM_NAME_SIZE);
+
+            if (nameRaw.count(char(0xFF)) == ENM_NAME_SIZE) continue;
+
+            auto it = newNames.constFind(FF7Text::toPC(nameRaw).trimmed());
+
+            if (it == newNames.constEnd()) continue;
+
+            scene.decompressed.replace(off + ENM_NAME, ENM_NAME_SIZE, encodeEnemyName(it.value()));
+
+            ++renamed;
+
+        }
+
+    }
+
+
+
+    for (const QString& name : names)
+
+        dbg << "  \"" << name << "\" -> \"" << newNames.value(name) << "\"\n";
+
+    if (preserveBosses) {
+
+        QStringList kept(bossNames.begin(), bossNames.end());
+
+        kept.sort();
+
+        for (const QString& name : kept)
+
+            dbg << "  \"" << name << "\" (boss, kept)\n";
+
+    }
+
+    dbg << "\nEnemy records renamed: " << renamed << "\n";
+
+
+
+    // ── Rebuild and write ──
+
+    QByteArray newSceneBin = rebuildSceneBin(scenes, dbg);
+
+    if (newSceneBin.isEmpty()) {
+
+        dbg << "ERROR: rebuildSceneBin failed\n";
+
+        return false;
+
+    }
+
+    QString dstScene = QDir(outputPath).filePath("data/lang-en/battle/scene.bin");
+
+    QDir().mkpath(QFileInfo(dstScene).path());
+
+    QSaveFile outFile(dstScene);
+
+    if (!outFile.open(QIODevice::WriteOnly)) {
+
+        dbg << "ERROR: Cannot write " << dstScene << "\n";
+
+        return false;
+
+    }
+
+    outFile.write(newSceneBin);
+
+    if (!outFile.commit()) {
+
+        dbg << "ERROR: Cannot finish writing " << dstScene << "\n";
+
+        return false;
+
+    }
+
+
+
+    dbg << "SUCCESS: Written " << newSceneBin.size() << " bytes to " << dstScene << "\n";
+
+    qDebug() << "Enemy renaming complete." << renamed << "records renamed.";
+
+    return true;
+
+}
+
+
+
+QString EnemyRandomizer::generateEnemyName()
+
+{
+
+    const int adjCount  = int(sizeof(NAME_ADJECTIVES) / sizeof(NAME_ADJECTIVES[0]));
+
+    const int nounCount = int(sizeof(NAME_NOUNS) / sizeof(NAME_NOUNS[0]));
+
+    std::uniform_int_distribution<int> adjDist(0, adjCount - 1);
+
+    std::uniform_int_distribution<int> nounDist(0, nounCount - 1);
+
+    QString adjective = QString::fromUtf8(NAME_ADJECTIVES[adjDist(m_rng)]);
+
+    return adjective + ' ' + QString::fromUtf8(NAME_NOUNS[nounDist(m_rng)]);
+
+}
+
+
+
+QByteArray EnemyRandomizer::encodeEnemyName(const QString& name)
+
+{
+
+    // Shorten until the encoded name leaves room for its 0xFF terminator,
+
+    // then pad the rest of the 32-byte field with 0xFF like the game does
+
+    QString text = name;
+
+    QByteArray encoded;
+
+    for (;;) {
+
+        encoded = FF7Text::toFF7(text);
+
+        while (encoded.endsWith(char(0xFF))) encoded.chop(1);
+
+        if (encoded.size() < ENM_NAME_SIZE || text.isEmpty()) break;
+
+        text.chop(1);
+
+    }
+
+    encoded = encoded.left(ENM_NAME_SIZE - 1);
+
+    encoded.append(QByteArray(ENM_NAME_SIZE - encoded.size(), char(0xFF)));
+
+    return encoded;
+
+}
+
+
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// exportEnemyTable — read-only enemy dump for `--inspect-enemies`
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+
+
+namespace {
+
+
+
+QString csvField(const QString& value)
+
+{
+
+    if (!value.contains(',') && !value.contains('"') && !value.contains('\n'))
+
+        return value;
+
+    QString quoted = value;
+
+    quoted.replace("\"", "\"\"");
+
+    return "\"" + quoted + "\"";
+
+}
+
+
+
+QString itemLabel(quint16 itemId)
+
+{
+
+    QString name = FF7Item::name(itemId);
+
+    return name.isEmpty() ? QString("Item_%1").arg(itemId) : name;
+
+}
+
+
+
+}
+
+
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// sceneEnemyNames — distinct enemy names of every scene, by scene index
+
+//
+
+// For BossList::learnScenes(): empty when the file can't be read or no
+
+// scene decompresses.
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+QVector<QStringList> EnemyRandomizer::sceneEnemyNames(const QString& sceneBinPath)
+
+{
+
+    QFile sceneFile(sceneBinPath);
+
+    if (!sceneFile.open(QIODevice::ReadOnly)) return {};
+
+    QVector<SceneEntry> scenes;
+
+    QString discarded;
+
+    QTextStream log(&discarded);
+
+    if (!extractScenes(sceneFile.readAll(), scenes, log)) return {};
+
+
+
+    QVector<QByteArray> decompressed;
+
+    for (const SceneEntry& scene : scenes)
+
+        decompressed.append(scene.decompressed);
+
+    const SceneCatalog catalog(decompressed);
+
+    QVector<QStringList> names;
+
+    for (int s = 0; s < decompressed.size(); ++s)
+
+        names.append(catalog.sceneEnemies(s));
+
+    return names;
+
+}
+
+
+
+bool EnemyRandomizer::exportEnemyTable(const QString& sceneBinPath, const QString& format,
+
+                                       QTextStream& out, QString* error)
+
+{
+
+    const bool json = format.compare("json", Qt::CaseInsensitive) == 0;
+
+    if (!json && format.compare("csv", Qt::CaseInsensitive) != 0) {
+
+        if (error) *error = QString("Unknown format '%1' (expected csv or json)").arg(format);
+
+        return false;
+
+    }
+
+
+
+    QFile sceneFile(sceneBinPath);
+
+    if (!sceneFile.open(QIODevice::ReadOnly)) {
+
+        if (error) *error = QString("Cannot open %1").arg(sceneBinPath);
+
+        return false;
+
+    }
+
+    QByteArray sceneBin = sceneFile.readAll();
+
+    sceneFile.close();
+
+
+
+    QString extractLog;
+
+    QTextStream logStream(&extractLog);
+
+    QVector<SceneEntry> scenes;
+
+    if (!extractScenes(sceneBin, scenes, logStream)) {
+
+        if (error) *error = QString("%1 does not look like scene.bin").arg(sceneBinPath);
+
+        return false;
+
+    }
+
+
+
+    QJsonArray enemies;
+
+    if (!json) {
+
+        out << "scene,slot,enemy_id,name,level,hp,mp,exp,ap,gil,str,def,mag,mdef,"
+
+               "speed,luck,evade,drops,steals,morph\n";
+
+    }
+
+
+
+    for (int s = 0; s < scenes.size(); ++s) {
+
+        const QByteArray& d = scenes[s].decompressed;
+
+        if (d.size() != SCENE_SIZE) continue;
+
+        const uchar* raw = reinterpret_cast<const uchar*>(d.constData());
+
+
+
+        for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+            int off = ENEMY_DATA_BASE + e * ENEMY_RECORD_SIZE;
+
+            QByteArray nameRaw = d.mid(off + ENM_NAME, ENM_NAME_SIZE);
+
+            if (nameRaw.count(char(0xFF)) == ENM_NAME_SIZE) continue;  // empty slot
+
+
+
+            quint16 enemyId, mp, ap, morph;
+
+            quint32 hp, exp, gil;
+
+            memcpy(&enemyId, raw + e * 2, 2);   // scene header: 3 × u16 enemy IDs
+
+            memcpy(&mp,    raw + off + ENM_MP, 2);
+
+            memcpy(&ap,    raw + off + ENM_AP, 2);
+
+            memcpy(&morph, raw + off + ENM_MORPH, 2);
+
+            memcpy(&hp,    raw + off + ENM_HP, 4);
+
+            memcpy(&exp,   raw + off + ENM_EXP, 4);
+
+            memcpy(&gil,   raw + off + ENM_GIL, 4);
+
+
+
+            // Four item slots; the rate byte's high bit marks a steal, the
+
+            // low 7 bits are the chance out of 64. 0xFF/0xFFFF = unused slot.
+
+            QJsonArray drops, steals;
+
+            QStringList dropText, stealText;
+
+            for (int i = 0; i < 4; ++i) {
+
+                quint8 rate = raw[off + ENM_ITEM_RATES + i];
+
+                quint16 itemId;
+
+                memcpy(&itemId, raw + off + ENM_ITEM_IDS + i * 2, 2);
+
+                if (rate == 0xFF || itemId == 0xFFFF) continue;
+
+                QJsonObject item;
+
+                item["itemId"] = itemId;
+
+                item["name"]   = itemLabel(itemId);
+
+                item["rate"]   = rate & 0x7F;
+
+                QString text = QString("%1 (%2/64)").arg(itemLabel(itemId)).arg(rate & 0x7F);
+
+                if (rate & 0x80) { steals.append(item); stealText << text; }
+
+                else             { drops.append(item);  dropText << text; }
+
+            }
+
+
+
+            QString name = FF7Text::toPC(nameRaw).trimmed();
+
+            QString morphName = morph == 0xFFFF ? QString() : itemLabel(morph);
+
+
+
+            if (json) {
+
+                QJsonObject enemy;
+
+                enemy["scene"]   = s;
+
+                enemy["slot"]    = e;
+
+                enemy["enemyId"] = enemyId;
+
+                enemy["name"]    = name;
+
+                enemy["level"]   = raw[off + ENM_LEVEL];
+
+                enemy["hp"]      = static_cast<qint64>(hp);
+
+                enemy["mp"]      = mp;
+
+                enemy["exp"]     = static_cast<qint64>(exp);
+
+                enemy["ap"]      = ap;
+
+                enemy["gil"]     = static_cast<qint64>(gil);
+
+                enemy["str"]     = raw[off + ENM_STR];
+
+                enemy["def"]     = raw[off + ENM_DEF];
+
+                enemy["mag"]     = raw[off + ENM_MAG];
+
+                enemy["mdef"]    = raw[off + ENM_MDEF];
+
+                enemy["speed"]   = raw[off + ENM_SPEED];
+
+                enemy["luck"]    = raw[off + ENM_LUCK];
+
+                enemy["evade"]   = raw[off + ENM_EVADE];
+
+                enemy["drops"]   = drops;
+
+                enemy["steals"]  = steals;
+
+                if (morph == 0xFFFF) {
+
+                    enemy["morph"] = QJsonValue::Null;
+
+                } else {
+
+                    QJsonObject morphItem;
+
+                    morphItem["itemId"] = morph;
+
+                    morphItem["name"]   = morphName;
+
+                    enemy["morph"] = morphItem;
+
+                }
+
+                enemies.append(enemy);
+
+            } else {
+
+                QStringList row;
+
+                row << QString::number(s) << QString::number(e) << QString::number(enemyId)
+
+                    << csvField(name)
+
+                    << QString::number(raw[off + ENM_LEVEL]) << QString::number(hp)
+
+                    << QString::number(mp) << QString::number(exp)
+
+                    << QString::number(ap) << QString::number(gil)
+
+                    << QString::number(raw[off + ENM_STR]) << QString::number(raw[off + ENM_DEF])
+
+                    << QString::number(raw[off + ENM_MAG]) << QString::number(raw[off + ENM_MDEF])
+
+                    << QString::number(raw[off + ENM_SPEED]) << QString::number(raw[off + ENM_LUCK])
+
+                    << QString::number(raw[off + ENM_EVADE])
+
+                    << csvField(dropText.join("; ")) << csvField(stealText.join("; "))
+
+                    << csvField(morphName);
+
+                out << row.join(',') << "\n";
+
+            }
+
+        }
+
+    }
+
+
+
+    if (json)
+
+        out << QJsonDocument(enemies).toJson(QJsonDocument::Indented);
+
+    out.flush();
+
+    return true;
+
+}
+
+
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// randomizeSteals — shuffle steal slots between enemies
+
+//
+
+// Each record has four item slots; a rate with bit 7 set is a steal, the rest
+
+// are drops. Every distinct enemy (by scene.bin enemy ID, so its copies across
+
+// scenes stay identical) puts its steal items into one pool, the pool is
+
+// shuffled, and each enemy takes back as many as it gave, into the same
+
+// slots. The item moves with its rate, so rare steals stay rare. Drops are
+
+// left alone unless conversion is on (below). The slots come from the
+
+// enemy's first record; a copy where one of them is a different kind (or
+
+// empty) keeps it. The shuffle draws from its own stream (seed mixed with
+
+// STEAL_RNG_STREAM) so turning it on doesn't reroll anything else.
+
+//
+
+// With steal/drop conversion on, drop slots join the pool too. Bit 7 moves
+
+// with the rate like the rest of it, so a drop dealt into a steal slot turns
+
+// that slot into a drop and the other way round; each enemy keeps its number
+
+// of item slots, and the game keeps its number of steals and drops.
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+bool EnemyRandomizer::randomizeSteals()
+
+{
+
+    const Config& config = m_parent->m_config;
+
+    if (!config.getRandomizeEnemySteals()) return true;
+
+    const bool convert = config.getStealDropConversion();
+
+    // Read from OUTPUT so earlier stat/encounter/name changes are kept
+
+    QString outputPath = m_parent->getOutputPath();
+
+    QString logPath = outputPath + "/enemy_steals_debug.txt";
+
+    QFile logFile(logPath);
+
+    bool logOk = logFile.open(QIODevice::WriteOnly | QIODevice::Text);
+
+    Q_UNUSED(logOk);
+
+    QTextStream dbg(&logFile);
+
+    dbg << "=== Enemy Steals (shuffle) ===\n"
+
+        << QDateTime::currentDateTime().toString() << "\n\n"
+
+        << "Each enemy has four item slots. Bit 7 (0x80) of a slot's rate byte picks\n"
+
+        << "its kind: set = steal, clear = drop; the low 7 bits are the chance out of 64.\n"
+
+        << "Rate 0xFF marks an empty slot. A slot is only written in a record where it\n"
+
+        << "is the same kind as in the enemy's first record.\n"
+
+        << (convert ? "Steal/drop conversion: ON - drops join the pool and each slot takes the\n"
+
+                      "kind of the item dealt into it (converted slots are marked)\n\n"
+
+                    : "Steal/drop conversion: OFF - only steal slots are shuffled\n\n");
+
+    QString scenePath = QDir(outputPath).filePath("data/lang-en/battle/scene.bin");
+
+    QFile sceneFile(scenePath);
+
+    if (!sceneFile.open(QIODevice::ReadOnly)) {
+
+        // Fall back to original
+
+        scenePath = GameFileLocator::findSceneBin(m_parent->getFF7Path(), outputPath);
+
+        sceneFile.setFileName(scenePath);
+
+        if (!sceneFile.open(QIODevice::ReadOnly)) {
+
+            dbg << "ERROR: Cannot open scene.bin\n";
+
+            return false;
+
+        }
+
+    }
+
+    QByteArray sceneBin = sceneFile.readAll();
+
+    sceneFile.close();
+
+    QVector<SceneEntry> scenes;
+
+    if (!extractScenes(sceneBin, scenes, dbg)) {
+
+        dbg << "ERROR: extractScenes failed\n";
+
+        m_parent->reportInputError(Randomizer::InputError::SceneParse, scenePath,
+
+                                   "no scene decompresses (see the pass's debug log)");
+
+        return false;
+
+    }
+
+    // ── Collect each distinct enemy's steal slots (first record wins) ──
+
+    struct Steal { quint8 rate; quint16 item; };
+
+    QMap<quint16, QVector<int>> stealSlots;   // enemy ID -> slot indices
+
+    QMap<quint16, QVector<bool>> wasSteal;    // enemy ID -> slot kind in the first record
+
+    QMap<quint16, QString> enemyNames;
+
+    QVector<Steal> pool;
+
+    for (const SceneEntry& scene : scenes) {
+
+        if (scene.decompressed.size() != SCENE_SIZE) continue;
+
+        const uchar* raw = reinterpret_cast<const uchar*>(scene.decompressed.constData());
+
+        for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+            quint16 id;
+
+            memcpy(&id, raw + e * 2, 2);
+
+            int off = ENEMY_DATA_BASE + e * ENEMY_RECORD_SIZE;
+
+            QByteArray nameRaw = scene.decompressed.mid(off + ENM_NAME, ENM_NAME_SIZE);
+
+            if (id == 0xFFFF || nameRaw.count(char(0xFF)) == ENM_NAME_SIZE) continue;
+
+            if (enemyNames.contains(id)) continue;
+
+            enemyNames.insert(id, FF7Text::toPC(nameRaw).trimmed());
+
+            for (int i = 0; i < 4; ++i) {
+
+                quint8 rate = raw[off + ENM_ITEM_RATES + i];
+
+                quint16 item;
+
+                memcpy(&item, raw + off + ENM_ITEM_IDS + i * 2, 2);
+
+                if (rate == 0xFF || item == 0xFFFF) continue;
+
+                if (!convert && !(rate & 0x80)) continue;
+
+                stealSlots[id].append(i);
+
+                wasSteal[id].append(rate & 0x80);
+
+                pool.append({rate, item});
+
+            }
+
+        }
+
+    }
+
+    dbg << "Enemies: " << enemyNames.size() << ", in the pool: " << stealSlots.size()
+
+        << ", slots: " << pool.size() << "\n\n";
+
+    if (pool.size() < 2) {
+
+        dbg << "Nothing to shuffle.\n";
+
+        return true;
+
+    }
+
+    // ── Shuffle on a separate stream so drops and other passes don't correlate ──
+
+    std::seed_seq streamSeed{config.getSeed(), STEAL_RNG_STREAM};
+
+    std::mt19937 stealRng(streamSeed);
+
+    for (int i = pool.size() - 1; i > 0; --i) {
+
+        std::uniform_int_distribution<int> dist(0, i);
+
+        std::swap(pool[i], pool[dist(stealRng)]);
+
+    }
+
+    QHash<quint16, QVector<Steal>> newSteals;
+
+    int next = 0;
+
+    int converted = 0;
+
+    for (auto it = stealSlots.constBegin(); it != stealSlots.constEnd(); ++it) {
+
+        QStringList text;
+
+        for (int n = 0; n < it.value().size(); ++n) {
+
+            const Steal& steal = pool[next++];
+
+            newSteals[it.key()].append(steal);
+
+            const bool isSteal = steal.rate & 0x80;
+
+            QString entry = QString("%1 (%2/64").arg(itemLabel(steal.item)).arg(steal.rate & 0x7F);
+
+            if (convert) entry += isSteal ? ", steal" : ", drop";
+
+            if (isSteal != wasSteal[it.key()][n]) {
+
+                entry += isSteal ? ", was a drop" : ", was a steal";
+
+                ++converted;
+
+            }
+
+            text << entry + ")";
+
+        }
+
+        dbg << "  " << enemyNames.value(it.key()) << " [" << it.key() << "]: "
+
+            << text.join(", ") << "\n";
+
+    }
+
+    if (convert)
+
+        dbg << "\nSlots converted between steal and drop: " << converted << "\n";
+
+    // ── Apply to every record of each enemy ──
+
+    int records = 0;
+
+    int skipped = 0;
+
+    for (SceneEntry& scene : scenes) {
+
+        if (scene.decompressed.size() != SCENE_SIZE) continue;
+
+        for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+            quint16 id;
+
+            memcpy(&id, scene.decompressed.constData() + e * 2, 2);
+
+            auto steals = newSteals.constFind(id);
+
+            if (steals == newSteals.constEnd()) continue;
+
+            int off = ENEMY_DATA_BASE + e * ENEMY_RECORD_SIZE;
+
+            const QVector<int>& slots = stealSlots[id];
+
+            for (int n = 0; n < slots.size(); ++n) {
+
+                // Slots come from the enemy's first record; a copy that holds
+
+                // the other kind (or nothing) there keeps it
+
+                const quint8 rate = static_cast<quint8>(scene.decompressed[off + ENM_ITEM_RATES + slots[n]]);
+
+                if (rate == 0xFF || bool(rate & 0x80) != wasSteal[id][n]) {
+
+                    dbg << "  SKIP: " << enemyNames.value(id) << " [" << id << "] slot "
+
+                        << slots[n] << " is not a " << (wasSteal[id][n] ? "steal" : "drop")
+
+                        << " in this copy (rate 0x"
+
+                        << QString::number(rate, 16).toUpper() << ")\n";
+
+                    ++skipped;
+
+                    continue;
+
+                }
+
+                const Steal& steal = steals.value()[n];
+
+                scene.decompressed[off + ENM_ITEM_RATES + slots[n]] = static_cast<char>(steal.rate);
+
+                memcpy(scene.decompressed.data() + off + ENM_ITEM_IDS + slots[n] * 2, &steal.item, 2);
+
+            }
+
+            ++records;
+
+        }
+
+    }
+
+    dbg << "\nEnemy records updated: " << records << ", slots skipped: " << skipped << "\n";
+
+    // ── Rebuild and write ──
+
+    QByteArray newSceneBin = rebuildSceneBin(scenes, dbg);
+
+    if (newSceneBin.isEmpty()) {
+
+        dbg << "ERROR: rebuildSceneBin failed\n";
+
+        return false;
+
+    }
+
+    QString dstScene = QDir(outputPath).filePath("data/lang-en/battle/scene.bin");
+
+    QDir().mkpath(QFileInfo(dstScene).path());
+
+    QSaveFile outFile(dstScene);
+
+    if (!outFile.open(QIODevice::WriteOnly)) {
+
+        dbg << "ERROR: Cannot write " << dstScene << "\n";
+
+        return false;
+
+    }
+
+    outFile.write(newSceneBin);
+
+    if (!outFile.commit()) {
+
+        dbg << "ERROR: Cannot finish writing " << dstScene << "\n";
+
+        return false;
+
+    }
+
+    dbg << "SUCCESS: Written " << newSceneBin.size() << " bytes to " << dstScene << "\n";
+
+    qDebug() << "Enemy steal shuffle complete." << records << "records updated.";
+
+    return true;
+
+}
+
+
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// randomizeMorphs — reroll what morphable enemies turn into
+
+//
+
+// Only enemies with a vanilla morph result (ENM_MORPH != 0xFFFF) are touched,
+
+// so Morph stays as rare as it was. Each distinct enemy ID gets one result
+
+// from the pool (the config's list, or curatedMorphPool() when it's empty) and
+
+// every copy of it across scenes is written the same. With the Source
+
+// guarantee on, each Source item is dealt to a different morphable enemy
+
+// first so none of them drops out of the game. Own RNG stream, like steals.
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+
+const QVector<quint16>& EnemyRandomizer::curatedMorphPool()
+
+{
+
+    // X-Potion, the Ether and Elixir lines, Phoenix Down, Remedy, the
+
+    // battle drinks, Tent and all six Sources
+
+    static const QVector<quint16> pool = {
+
+        2, 3, 4, 5, 6, 7, 15, 17, 18, 70,
+
+        71, 72, 73, 74, 75, 76
+
+    };
+
+    return pool;
+
+}
+
+
+bool EnemyRandomizer::randomizeMorphs()
+
+{
+
+    const Config& config = m_parent->m_config;
+
+    if (!config.getRandomizeEnemyMorphs()) return true;
+
+
+    // Read from OUTPUT so earlier stat/encounter/name/steal changes are kept
+
+    QString outputPath = m_parent->getOutputPath();
+
+    QString logPath = outputPath + "/enemy_morphs_debug.txt";
+
+    QFile logFile(logPath);
+
+    bool logOk = logFile.open(QIODevice::WriteOnly | QIODevice::Text);
+
+    Q_UNUSED(logOk);
+
+    QTextStream dbg(&logFile);
+
+    dbg << "=== Enemy Morphs (curated pool) ===\n"
+
+        << QDateTime::currentDateTime().toString() << "\n\n";
+
+
+    QString scenePath = QDir(outputPath).filePath("data/lang-en/battle/scene.bin");
+
+    QFile sceneFile(scenePath);
+
+    if (!sceneFile.open(QIODevice::ReadOnly)) {
+
+        // Fall back to original
+
+        scenePath = GameFileLocator::findSceneBin(m_parent->getFF7Path(), outputPath);
+
+        sceneFile.setFileName(scenePath);
+
+        if (!sceneFile.open(QIODevice::ReadOnly)) {
+
+            dbg << "ERROR: Cannot open scene.bin\n";
+
+            return false;
+
+        }
+
+    }
+
+    QByteArray sceneBin = sceneFile.readAll();
+
+    sceneFile.close();
+
+
+    QVector<SceneEntry> scenes;
+
+    if (!extractScenes(sceneBin, scenes, dbg)) {
+
+        dbg << "ERROR: extractScenes failed\n";
+
+        m_parent->reportInputError(Randomizer::InputError::SceneParse, scenePath,
+
+                                   "no scene decompresses (see the pass's debug log)");
+
+        return false;
+
+    }
+
+
+    QVector<quint16> pool = config.getMorphItemPool();
+
+    if (pool.isEmpty()) pool = curatedMorphPool();
+
+    dbg << "Pool (" << (config.getMorphItemPool().isEmpty() ? "curated" : "custom") << "): ";
+
+    QStringList poolText;
+
+    for (quint16 item : pool) poolText << itemLabel(item);
+
+    dbg << poolText.join(", ") << "\n\n";
+
+
+    // ── Collect morphable enemies (first record wins for the name) ──
+
+    QMap<quint16, QString> morphable;   // enemy ID -> name
+
+    QHash<quint16, quint16> vanillaMorph;
+
+    for (const SceneEntry& scene : scenes) {
+
+        if (scene.decompressed.size() != SCENE_SIZE) continue;
+
+        const uchar* raw = reinterpret_cast<const uchar*>(scene.decompressed.constData());
+
+        for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+            quint16 id;
+
+            memcpy(&id, raw + e * 2, 2);
+
+            int off = ENEMY_DATA_BASE + e * ENEMY_RECORD_SIZE;
+
+            QByteArray nameRaw = scene.decompressed.mid(off + ENM_NAME, ENM_NAME_SIZE);
+
+            if (id == 0xFFFF || nameRaw.count(char(0xFF)) == ENM_NAME_SIZE) continue;
+
+            if (morphable.contains(id)) continue;
+
+            quint16 morph;
+
+            memcpy(&morph, raw + off + ENM_MORPH, 2);
+
+            if (morph == 0xFFFF) continue;
+
+            morphable.insert(id, FF7Text::toPC(nameRaw).trimmed());
+
+            vanillaMorph.insert(id, morph);
+
+        }
+
+    }
+
+    dbg << "Morphable enemies: " << morphable.size() << "\n\n";
+
+    if (morphable.isEmpty()) {
+
+        dbg << "Nothing to randomize.\n";
+
+        return true;
+
+    }
+
+
+    // ── Deal results on a separate stream ──
+
+    std::seed_seq streamSeed{config.getSeed(), MORPH_RNG_STREAM};
+
+    std::mt19937 morphRng(streamSeed);
+
+
+    QVector<quint16> ids = morphable.keys();
+
+    for (int i = ids.size() - 1; i > 0; --i) {
+
+        std::uniform_int_distribution<int> dist(0, i);
+
+        std::swap(ids[i], ids[dist(morphRng)]);
+
+    }
+
+
+    QHash<quint16, quint16> newMorph;
+
+    int next = 0;
+
+    if (config.getGuaranteeSourceMorphs()) {
+
+        for (quint16 source = SOURCE_ITEM_FIRST; source <= SOURCE_ITEM_LAST && next < ids.size(); ++source)
+
+            newMorph.insert(ids[next++], source);
+
+        if (next < SOURCE_ITEM_LAST - SOURCE_ITEM_FIRST + 1)
+
+            dbg << "WARNING: only " << next << " morphable enemies, not every Source is guaranteed\n";
+
+    }
+
+    std::uniform_int_distribution<int> pick(0, pool.size() - 1);
+
+    for (; next < ids.size(); ++next)
+
+        newMorph.insert(ids[next], pool[pick(morphRng)]);
+
+
+    for (auto it = morphable.constBegin(); it != morphable.constEnd(); ++it) {
+
+        dbg << "  " << it.value() << " [" << it.key() << "]: "
+
+            << itemLabel(vanillaMorph.value(it.key())) << " -> "
+
+            << itemLabel(newMorph.value(it.key())) << "\n";
+
+    }
+
+
+    // ── Apply to every record of each enemy ──
+
+    int records = 0;
+
+    for (SceneEntry& scene : scenes) {
+
+        if (scene.decompressed.size() != SCENE_SIZE) continue;
+
+        for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+            quint16 id;
+
+            memcpy(&id, scene.decompressed.constData() + e * 2, 2);
+
+            auto morph = newMorph.constFind(id);
+
+            if (morph == newMorph.constEnd()) continue;
+
+            int off = ENEMY_DATA_BASE + e * ENEMY_RECORD_SIZE;
+
+            quint16 item = morph.value();
+
+            memcpy(scene.decompressed.data() + off + ENM_MORPH, &item, 2);
+
+            ++records;
+
+        }
+
+    }
+
+    dbg << "\nEnemy records updated: " << records << "\n";
+
+
+    // ── Rebuild and write ──
+
+    QByteArray newSceneBin = rebuildSceneBin(scenes, dbg);
+
+    if (newSceneBin.isEmpty()) {
+
+        dbg << "ERROR: rebuildSceneBin failed\n";
+
+        return false;
+
+    }
+
+
+    QString dstScene = QDir(outputPath).filePath("data/lang-en/battle/scene.bin");
+
+    QDir().mkpath(QFileInfo(dstScene).path());
+
+    QSaveFile outFile(dstScene);
+
+    if (!outFile.open(QIODevice::WriteOnly)) {
+
+        dbg << "ERROR: Cannot write " << dstScene << "\n";
+
+        return false;
+
+    }
+
+    outFile.write(newSceneBin);
+
+    if (!outFile.commit()) {
+
+        dbg << "ERROR: Cannot finish writing " << dstScene << "\n";
+
+        return false;
+
+    }
+
+
+    dbg << "SUCCESS: Written " << newSceneBin.size() << " bytes to " << dstScene << "\n";
+
+    qDebug() << "Enemy morph randomization complete." << records << "records updated.";
+
+    return true;
+
+}
+
+
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// randomizeAttacks — shuffle attack power and MP cost among same-tier attacks
+
+//
+
+// Attacks are kept by ID, so AI scripts that pick an attack by index still
+
+// get the same animation, targeting and effect; only the power byte and the
+
+// MP cost move. Each attack ID gets the tier (encounter HP bands) of the
+
+// toughest enemy that has it in its attack list. Power is dealt only among
+
+// attacks with the same damage calculation byte (physical or magical, and
+
+// the same formula) in the same tier, MP costs among costed attacks in the
+
+// same tier, and every copy of an attack across scenes is written the same.
+
+// An enemy that could cast its priciest move before gets the MP for
+
+// MP_FLOOR_CASTS casts of its new priciest move. Own RNG stream
+
+// (Randomizer::streamSeed), so a reroll of the pass deals it again.
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+
+
+bool EnemyRandomizer::randomizeAttacks()
+
+{
+
+    const Config& config = m_parent->m_config;
+
+    if (!config.getShuffleEnemyAttacks()) return true;
+
+
+
+    // Read from OUTPUT so earlier stat/encounter/name/steal/morph changes are kept
+
+    QString outputPath = m_parent->getOutputPath();
+
+    QString logPath = outputPath + "/enemy_attacks_debug.txt";
+
+    QFile logFile(logPath);
+
+    bool logOk = logFile.open(QIODevice::WriteOnly | QIODevice::Text);
+
+    Q_UNUSED(logOk);
+
+    QTextStream dbg(&logFile);
+
+    dbg << "=== Enemy Attacks (same-tier power/MP shuffle) ===\n"
+
+        << QDateTime::currentDateTime().toString() << "\n\n";
+
+
+
+    QString scenePath = QDir(outputPath).filePath("data/lang-en/battle/scene.bin");
+
+    QFile sceneFile(scenePath);
+
+    if (!sceneFile.open(QIODevice::ReadOnly)) {
+
+        // Fall back to original
+
+        scenePath = GameFileLocator::findSceneBin(m_parent->getFF7Path(), outputPath);
+
+        sceneFile.setFileName(scenePath);
+
+        if (!sceneFile.open(QIODevice::ReadOnly)) {
+
+            dbg << "ERROR: Cannot open scene.bin\n";
+
+            return false;
+
+        }
+
+    }
+
+    QByteArray sceneBin = sceneFile.readAll();
+
+    sceneFile.close();
+
+
+
+    QVector<SceneEntry> scenes;
+
+    if (!extractScenes(sceneBin, scenes, dbg)) {
+
+        dbg << "ERROR: extractScenes failed\n";
+
+        m_parent->reportInputError(Randomizer::InputError::SceneParse, scenePath,
+
+                                   "no scene decompresses (see the pass's debug log)");
+
+        return false;
+
+    }
+
+
+
+    // ── Collect attacks (first record wins) and their tiers ──
+
+    struct AttackInfo { QString name; quint8 formula; quint8 power; quint16 mpCost; int tier; };
+
+    QMap<quint16, AttackInfo> attacks;
+
+    for (const SceneEntry& scene : scenes) {
+
+        if (scene.decompressed.size() != SCENE_SIZE) continue;
+
+        const uchar* raw = reinterpret_cast<const uchar*>(scene.decompressed.constData());
+
+        for (int i = 0; i < SCENE_ATTACK_COUNT; ++i) {
+
+            quint16 id;
+
+            memcpy(&id, raw + SCENE_ATTACK_IDS + i * 2, 2);
+
+            if (id == 0xFFFF || attacks.contains(id)) continue;
+
+            const uchar* rec = raw + SCENE_ATTACK_DATA + i * ATTACK_RECORD_SIZE;
+
+            AttackInfo info;
+
+            info.name    = FF7Text::toPC(scene.decompressed.mid(SCENE_ATTACK_NAMES + i * ATTACK_NAME_SIZE,
+
+                                                                ATTACK_NAME_SIZE)).trimmed();
+
+            info.formula = rec[ATK_DAMAGE_CALC];
+
+            info.power   = rec[ATK_POWER];
+
+            memcpy(&info.mpCost, rec + ATK_MP_COST, 2);
+
+            info.tier    = -1;
+
+            attacks.insert(id, info);
+
+        }
+
+        for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+            int off = ENEMY_DATA_BASE + e * ENEMY_RECORD_SIZE;
+
+            quint32 hp;
+
+            memcpy(&hp, raw + off + ENM_HP, 4);
+
+            int tier;
+
+            if      (hp >= 10000) tier = 4;
+
+            else if (hp >= 4000)  tier = 3;
+
+            else if (hp >= 1000)  tier = 2;
+
+            else if (hp >= 200)   tier = 1;
+
+            else                  tier = 0;
+
+            for (int a = 0; a < ENEMY_ATTACK_SLOTS; ++a) {
+
+                quint16 attackId;
+
+                memcpy(&attackId, raw + off + ENM_ATTACKS + a * 2, 2);
+
+                auto it = attacks.find(attackId);
+
+                if (attackId != 0xFFFF && it != attacks.end())
+
+                    it->tier = qMax(it->tier, tier);
+
+            }
+
+        }
+
+    }
+
+
+
+    // ── Pools: power by (tier, damage calc byte), MP cost by tier ──
+
+    // The low nibble picks the formula the power feeds (standard, percent of
+
+    // HP, fixed...), so power only moves between attacks with the same byte
+
+    QMap<QPair<int, int>, QVector<quint16>> powerPools;
+
+    QMap<int, QVector<quint16>> mpPools;
+
+    for (auto it = attacks.constBegin(); it != attacks.constEnd(); ++it) {
+
+        const AttackInfo& info = it.value();
+
+        if (info.tier < 0) continue;   // no enemy uses it (player-side or unused)
+
+        if (((info.formula >> 4) == 1 || (info.formula >> 4) == 2) && info.power > 0)
+
+            powerPools[qMakePair(info.tier, int(info.formula))].append(it.key());
+
+        if (info.mpCost > 0 && info.mpCost != 0xFFFF)
+
+            mpPools[info.tier].append(it.key());
+
+    }
+
+
+
+    // ── Deal on a separate stream ──
+
+    std::mt19937 attackRng(m_parent->streamSeed("enemy attacks"));
+
+    auto shuffled = [&](QVector<quint16> ids) {
+
+        for (int i = ids.size() - 1; i > 0; --i) {
+
+            std::uniform_int_distribution<int> dist(0, i);
+
+            std::swap(ids[i], ids[dist(attackRng)]);
+
+        }
+
+        return ids;
+
+    };
+
+
+
+    QHash<quint16, quint8> newPower;
+
+    for (auto pool = powerPools.constBegin(); pool != powerPools.constEnd(); ++pool) {
+
+        const QVector<quint16> donors = shuffled(pool.value());
+
+        for (int i = 0; i < donors.size(); ++i)
+
+            newPower.insert(pool.value()[i], attacks[donors[i]].power);
+
+    }
+
+    QHash<quint16, quint16> newMpCost;
+
+    for (auto pool = mpPools.constBegin(); pool != mpPools.constEnd(); ++pool) {
+
+        const QVector<quint16> donors = shuffled(pool.value());
+
+        for (int i = 0; i < donors.size(); ++i)
+
+            newMpCost.insert(pool.value()[i], attacks[donors[i]].mpCost);
+
+    }
+
+
+
+    for (auto it = attacks.constBegin(); it != attacks.constEnd(); ++it) {
+
+        const AttackInfo& info = it.value();
+
+        const bool power = newPower.contains(it.key());
+
+        const bool mp    = newMpCost.contains(it.key());
+
+        if (!power && !mp) continue;
+
+        dbg << "  T" << info.tier << " " << (info.name.isEmpty() ? QString("Attack_%1").arg(it.key()) : info.name)
+
+            << " [" << it.key() << "]:";
+
+        if (power) dbg << " power " << int(info.power) << " -> " << int(newPower.value(it.key()));
+
+        if (mp)    dbg << " MP " << info.mpCost << " -> " << newMpCost.value(it.key());
+
+        dbg << "\n";
+
+    }
+
+
+
+    // ── Apply to every copy of each attack, then top up enemy MP ──
+
+    int records = 0;
+
+    int mpRaised = 0;
+
+    for (SceneEntry& scene : scenes) {
+
+        if (scene.decompressed.size() != SCENE_SIZE) continue;
+
+        quint16 oldMaxCost[ENEMIES_PER_SCENE];
+
+        for (int e = 0; e < ENEMIES_PER_SCENE; ++e)
+
+            oldMaxCost[e] = maxAttackMpCost(scene.decompressed, e);
+
+
+
+        uchar* raw = reinterpret_cast<uchar*>(scene.decompressed.data());
+
+        for (int i = 0; i < SCENE_ATTACK_COUNT; ++i) {
+
+            quint16 id;
+
+            memcpy(&id, raw + SCENE_ATTACK_IDS + i * 2, 2);
+
+            uchar* rec = raw + SCENE_ATTACK_DATA + i * ATTACK_RECORD_SIZE;
+
+            bool changed = false;
+
+            auto power = newPower.constFind(id);
+
+            if (power != newPower.constEnd()) {
+
+                rec[ATK_POWER] = power.value();
+
+                changed = true;
+
+            }
+
+            auto cost = newMpCost.constFind(id);
+
+            if (cost != newMpCost.constEnd()) {
+
+                quint16 mpCost = cost.value();
+
+                memcpy(rec + ATK_MP_COST, &mpCost, 2);
+
+                changed = true;
+
+            }
+
+            if (changed) ++records;
+
+        }
+
+
+
+        for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+            int off = ENEMY_DATA_BASE + e * ENEMY_RECORD_SIZE;
+
+            quint16 mp;
+
+            memcpy(&mp, raw + off + ENM_MP, 2);
+
+            const quint16 newMaxCost = maxAttackMpCost(scene.decompressed, e);
+
+            if (mp < oldMaxCost[e] || mp >= newMaxCost) continue;
+
+            mp = static_cast<quint16>(qMin(65535, newMaxCost * MP_FLOOR_CASTS));
+
+            memcpy(raw + off + ENM_MP, &mp, 2);
+
+            ++mpRaised;
+
+        }
+
+    }
+
+    dbg << "\nAttack records updated: " << records
+
+        << "\nEnemy MP raised to afford new costs: " << mpRaised << "\n";
+
+
+
+    // ── Rebuild and write ──
+
+    QByteArray newSceneBin = rebuildSceneBin(scenes, dbg);
+
+    if (newSceneBin.isEmpty()) {
+
+        dbg << "ERROR: rebuildSceneBin failed\n";
+
+        return false;
+
+    }
+
+
+
+    QString dstScene = QDir(outputPath).filePath("data/lang-en/battle/scene.bin");
+
+    QDir().mkpath(QFileInfo(dstScene).path());
+
+    QSaveFile outFile(dstScene);
+
+    if (!outFile.open(QIODevice::WriteOnly)) {
+
+        dbg << "ERROR: Cannot write " << dstScene << "\n";
+
+        return false;
+
+    }
+
+    outFile.write(newSceneBin);
+
+    if (!outFile.commit()) {
+
+        dbg << "ERROR: Cannot finish writing " << dstScene << "\n";
+
+        return false;
+
+    }
+
+
+
+    dbg << "SUCCESS: Written " << newSceneBin.size() << " bytes to " << dstScene << "\n";
+
+    qDebug() << "Enemy attack shuffle complete." << records << "attack records updated.";
+
+    return true;
+
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// applyBossDifficulty — boss-only HP / STR / MAG curve
+
+//
+
+// Separate from the stat variance (which only protects bosses) and from
+
+// encounter scaling. A boss is an enemy on the boss list or over the boss HP
+
+// threshold; the list's scene indices only count for scenes still holding
+
+// their vanilla enemies, since the encounter shuffle moves scenes around.
+
+// Scaled takes the vanilla enemy level of the slot the boss is fought in
+
+// now, so a late boss moved into an early slot gets the early curve. Runs
+
+// before renaming so boss names still match the list.
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+void EnemyRandomizer::bossDifficultyFactors(int difficulty, int slotLevel,
+
+                                            double& hpFactor, double& attackFactor)
+
+{
+
+    switch (difficulty) {
+
+    case Config::BossDifficultyEasy:
+
+        hpFactor = BOSS_EASY_HP;
+
+        attackFactor = BOSS_EASY_ATTACK;
+
+        return;
+
+    case Config::BossDifficultyHard:
+
+        hpFactor = BOSS_HARD_HP;
+
+        attackFactor = BOSS_HARD_ATTACK;
+
+        return;
+
+    case Config::BossDifficultyScaled: {
+
+        const double t = qBound(0.0, static_cast<double>(slotLevel - BOSS_SCALED_LOW_LEVEL)
+
+                                         / (BOSS_SCALED_HIGH_LEVEL - BOSS_SCALED_LOW_LEVEL), 1.0);
+
+        hpFactor = BOSS_SCALED_LOW_HP + t * (BOSS_SCALED_HIGH_HP - BOSS_SCALED_LOW_HP);
+
+        attackFactor = BOSS_SCALED_LOW_ATK + t * (BOSS_SCALED_HIGH_ATK - BOSS_SCALED_LOW_ATK);
+
+        return;
+
+    }
+
+    default:
+
+        hpFactor = 1.0;
+
+        attackFactor = 1.0;
+
+        return;
+
+    }
+
+}
+
+
+
+bool EnemyRandomizer::applyBossDifficulty()
+
+{
+
+    const Config& config = m_parent->m_config;
+
+    const int difficulty = config.getBossDifficulty();
+
+    if (difficulty == Config::BossDifficultyNormal) return true;
+
+
+
+    // Read from OUTPUT so a shuffled scene.bin is scaled where bosses landed
+
+    QString outputPath = m_parent->getOutputPath();
+
+    QString logPath = outputPath + "/boss_difficulty_debug.txt";
+
+    QFile logFile(logPath);
+
+    bool logOk = logFile.open(QIODevice::WriteOnly | QIODevice::Text);
+
+    Q_UNUSED(logOk);
+
+    QTextStream dbg(&logFile);
+
+
+
+    static const char* const DIFFICULTY_NAMES[] = { "Normal", "Easy", "Hard", "Scaled" };
+
+    dbg << "=== Boss Difficulty (" << DIFFICULTY_NAMES[difficulty] << ") ===\n"
+
+        << QDateTime::currentDateTime().toString() << "\n\n";
+
+
+
+    QString scenePath = QDir(outputPath).filePath("data/lang-en/battle/scene.bin");
+
+    QFile sceneFile(scenePath);
+
+    if (!sceneFile.open(QIODevice::ReadOnly)) {
+
+        // Fall back to original
+
+        scenePath = GameFileLocator::findSceneBin(m_parent->getFF7Path(), outputPath);
+
+        sceneFile.setFileName(scenePath);
+
+        if (!sceneFile.open(QIODevice::ReadOnly)) {
+
+            dbg << "ERROR: Cannot open scene.bin\n";
+
+            return false;
+
+        }
+
+    }
+
+    QByteArray sceneBin = sceneFile.readAll();
+
+    sceneFile.close();
+
+
+
+    QVector<SceneEntry> scenes;
+
+    if (!extractScenes(sceneBin, scenes, dbg)) {
+
+        dbg << "ERROR: extractScenes failed\n";
+
+        m_parent->reportInputError(Randomizer::InputError::SceneParse, scenePath,
+
+                                   "no scene decompresses (see the pass's debug log)");
+
+        return false;
+
+    }
+
+
+
+    // Vanilla scenes give each slot's level and tell which scenes moved
+
+    QVector<SceneEntry> vanilla;
+
+    QFile vanillaFile(GameFileLocator::findSceneBin(m_parent->getFF7Path(), outputPath));
+
+    if (vanillaFile.open(QIODevice::ReadOnly)) {
+
+        QString ignored;
+
+        QTextStream quiet(&ignored);
+
+        extractScenes(vanillaFile.readAll(), vanilla, quiet);
+
+    }
+
+    if (vanilla.isEmpty())
+
+        dbg << "WARNING: vanilla scene.bin unreadable, bosses are curved by their own level\n";
+
+
+
+    const BossList& bossList = BossList::instance();
+
+    for (const QString& warning : bossList.warnings())
+
+        dbg << "Boss list: " << warning << "\n";
+
+    const bool aiRescale = config.getAiScriptRescale();
+
+
+
+    int bossesScaled = 0;
+
+    for (int s = 0; s < scenes.size(); ++s) {
+
+        QByteArray& scene = scenes[s].decompressed;
+
+        if (scene.size() != SCENE_SIZE) continue;
+
+        const bool hasVanilla = s < vanilla.size() && vanilla[s].decompressed.size() == SCENE_SIZE;
+
+        const bool inVanillaSlot = hasVanilla && sceneEnemyIds(scene) == sceneEnemyIds(vanilla[s].decompressed);
+
+        const int slotLevel = averageEnemyLevel(hasVanilla ? vanilla[s].decompressed : scene);
+
+
+
+        for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+            int off = ENEMY_DATA_BASE + e * ENEMY_RECORD_SIZE;
+
+            QByteArray nameRaw = scene.mid(off + ENM_NAME, ENM_NAME_SIZE);
+
+            if (nameRaw.count(char(0xFF)) == ENM_NAME_SIZE) continue;  // empty slot
+
+            const QString name = FF7Text::toPC(nameRaw).trimmed();
+
+
+
+            char* d = scene.data() + off;
+
+            quint32 hp;
+
+            memcpy(&hp, d + ENM_HP, 4);
+
+            const bool boss = hp >= BOSS_HP_THRESHOLD || bossList.isBossName(name)
+
+                              || (inVanillaSlot && bossList.isBossScene(s));
+
+            if (!boss) continue;
+
+
+
+            double hpFactor, attackFactor;
+
+            bossDifficultyFactors(difficulty, slotLevel, hpFactor, attackFactor);
+
+            const quint8 str = static_cast<quint8>(d[ENM_STR]);
+
+            const quint8 mag = static_cast<quint8>(d[ENM_MAG]);
+
+            const quint8 newStr = static_cast<quint8>(qBound(1.0, std::round(str * attackFactor), 255.0));
+
+            const quint8 newMag = static_cast<quint8>(qBound(1.0, std::round(mag * attackFactor), 255.0));
+
+            const quint32 newHp = static_cast<quint32>(qBound(1.0, std::round(hp * hpFactor), 4294967295.0));
+
+            d[ENM_STR] = static_cast<char>(newStr);
+
+            d[ENM_MAG] = static_cast<char>(newMag);
+
+            memcpy(d + ENM_HP, &newHp, 4);
+
+
+
+            // MP follows fight length like in encounter scaling
+
+            quint16 mp;
+
+            memcpy(&mp, d + ENM_MP, 2);
+
+            const quint16 newMp = scaledEnemyMp(mp, static_cast<quint16>(qMin(65535.0, std::round(mp * hpFactor))),
+
+                                                maxAttackMpCost(scene, e));
+
+            memcpy(d + ENM_MP, &newMp, 2);
+
+
+
+            dbg << "S" << s << " E" << e << " \"" << name << "\""
+
+                << (inVanillaSlot ? "" : " (moved)")
+
+                << " slot Lv " << slotLevel
+
+                << " HP:" << hp << "->" << newHp
+
+                << " STR:" << str << "->" << newStr
+
+                << " MAG:" << mag << "->" << newMag
+
+                << " MP:" << mp << "->" << newMp << "\n";
+
+            if (aiRescale && hp > 0 && newHp != hp)
+
+                rescaleAiHpConstants(scene, e, static_cast<double>(newHp) / hp, dbg);
+
+            ++bossesScaled;
+
+        }
+
+    }
+
+    dbg << "\nBoss records scaled: " << bossesScaled << "\n";
+
+
+
+    // ── Rebuild and write ──
+
+    QByteArray newSceneBin = rebuildSceneBin(scenes, dbg);
+
+    if (newSceneBin.isEmpty()) {
+
+        dbg << "ERROR: rebuildSceneBin failed\n";
+
+        return false;
+
+    }
+
+    QString dstScene = QDir(outputPath).filePath("data/lang-en/battle/scene.bin");
+
+    QDir().mkpath(QFileInfo(dstScene).path());
+
+    QSaveFile outFile(dstScene);
+
+    if (!outFile.open(QIODevice::WriteOnly)) {
+
+        dbg << "ERROR: Cannot write " << dstScene << "\n";
+
+        return false;
+
+    }
+
+    outFile.write(newSceneBin);
+
+    if (!outFile.commit()) {
+
+        dbg << "ERROR: Cannot finish writing " << dstScene << "\n";
+
+        return false;
+
+    }
+
+    dbg << "SUCCESS: Written " << newSceneBin.size() << " bytes to " << dstScene << "\n";
+
+    qDebug() << "Boss difficulty applied." << bossesScaled << "boss records scaled.";
+
+    return true;
+
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// applyRewardMultipliers — battle EXP / AP / Gil multipliers
+
+//
+
+// Independent of every other enemy pass: only the three reward fields of each
+
+// enemy record change, rounded and clamped to their field width. Runs on the
+
+// output scene.bin after the enemy passes, so shuffled enemies pay the
+
+// multiplied rewards too. AP jitter draws a factor per enemy record from the
+
+// seed's RNG, within Config::getApJitter percent of the AP multiplier.
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+bool EnemyRandomizer::applyRewardMultipliers()
+
+{
+
+    const Config& config = m_parent->m_config;
+
+    if (!config.hasRewardMultipliers()) return true;
+
+    const double expFactor = config.getExpMultiplier();
+
+    const double apFactor  = config.getApMultiplier();
+
+    const double gilFactor = config.getGilMultiplier();
+
+    const double apJitter  = config.getApJitter() / 100.0;
+
+    std::uniform_real_distribution<double> apJitterDist(-apJitter, apJitter);
+
+
+
+    // Read from OUTPUT so earlier enemy passes are kept
+
+    QString outputPath = m_parent->getOutputPath();
+
+    QString logPath = outputPath + "/reward_multipliers_debug.txt";
+
+    QFile logFile(logPath);
+
+    bool logOk = logFile.open(QIODevice::WriteOnly | QIODevice::Text);
+
+    Q_UNUSED(logOk);
+
+    QTextStream dbg(&logFile);
+
+    dbg << "=== Battle Rewards (EXP x" << expFactor << ", AP x" << apFactor
+
+        << ", Gil x" << gilFactor << ", AP jitter +/-" << config.getApJitter() << "%) ===\n"
+
+        << QDateTime::currentDateTime().toString() << "\n\n";
+
+
+
+    QString scenePath = QDir(outputPath).filePath("data/lang-en/battle/scene.bin");
+
+    QFile sceneFile(scenePath);
+
+    if (!sceneFile.open(QIODevice::ReadOnly)) {
+
+        // Fall back to original
+
+        scenePath = GameFileLocator::findSceneBin(m_parent->getFF7Path(), outputPath);
+
+        sceneFile.setFileName(scenePath);
+
+        if (!sceneFile.open(QIODevice::ReadOnly)) {
+
+            dbg << "ERROR: Cannot open scene.bin\n";
+
+            return false;
+
+        }
+
+    }
+
+    QByteArray sceneBin = sceneFile.readAll();
+
+    sceneFile.close();
+
+
+
+    QVector<SceneEntry> scenes;
+
+    if (!extractScenes(sceneBin, scenes, dbg)) {
+
+        dbg << "ERROR: extractScenes failed\n";
+
+        m_parent->reportInputError(Randomizer::InputError::SceneParse, scenePath,
+
+                                   "no scene decompresses (see the pass's debug log)");
+
+        return false;
+
+    }
+
+
+
+    auto scaled = [](quint32 value, double factor, double max) {
+
+        return static_cast<quint32>(qMin(max, std::round(value * factor)));
+
+    };
+
+
+
+    int records = 0;
+
+    quint64 expBefore = 0, expAfter = 0, apBefore = 0, apAfter = 0, gilBefore = 0, gilAfter = 0;
+
+    for (int s = 0; s < scenes.size(); ++s) {
+
+        QByteArray& scene = scenes[s].decompressed;
+
+        if (scene.size() != SCENE_SIZE) continue;
+
+        for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+
+            int off = ENEMY_DATA_BASE + e * ENEMY_RECORD_SIZE;
+
+            QByteArray nameRaw = scene.mid(off + ENM_NAME, ENM_NAME_SIZE);
+
+            if (nameRaw.count(char(0xFF)) == ENM_NAME_SIZE) continue;  // empty slot
+
+
+
+            char* d = scene.data() + off;
+
+            quint32 exp, gil;
+
+            quint16 ap;
+
+            memcpy(&exp, d + ENM_EXP, 4);
+
+            memcpy(&ap,  d + ENM_AP,  2);
+
+            memcpy(&gil, d + ENM_GIL, 4);
+
+            const quint32 newExp = scaled(exp, expFactor, 4294967295.0);
+
+            const double apRecordFactor = apJitter > 0.0 ? apFactor * (1.0 + apJitterDist(m_rng))
+
+                                                         : apFactor;
+
+            const quint16 newAp  = static_cast<quint16>(scaled(ap, apRecordFactor, 65535.0));
+
+            const quint32 newGil = scaled(gil, gilFactor, 4294967295.0);
+
+            memcpy(d + ENM_EXP, &newExp, 4);
+
+            memcpy(d + ENM_AP,  &newAp,  2);
+
+            memcpy(d + ENM_GIL, &newGil, 4);
+
+
+
+            expBefore += exp;  expAfter += newExp;
+
+            apBefore  += ap;   apAfter  += newAp;
+
+            gilBefore += gil;  gilAfter += newGil;
+
+            ++records;
+
+            dbg << "S" << s << " E" << e << " \"" << FF7Text::toPC(nameRaw).trimmed() << "\""
+
+                << " EXP:" << exp << "->" << newExp
+
+                << " AP:" << ap << "->" << newAp
+
+                << " Gil:" << gil << "->" << newGil << "\n";
+
+        }
+
+    }
+
+    dbg << "\nEnemy records: " << records
+
+        << "\nTotal EXP: " << expBefore << " -> " << expAfter
+
+        << "\nTotal AP: " << apBefore << " -> " << apAfter
+
+        << "\nTotal Gil: " << gilBefore << " -> " << gilAfter << "\n";
+
+
+
+    // ── Rebuild and write ──
+
+    QByteArray newSceneBin = rebuildSceneBin(scenes, dbg);
+
+    if (newSceneBin.isEmpty()) {
+
+        dbg << "ERROR: rebuildSceneBin failed\n";
+
+        return false;
+
+    }
+
+    QString dstScene = QDir(outputPath).filePath("data/lang-en/battle/scene.bin");
+
+    QDir().mkpath(QFileInfo(dstScene).path());
+
+    QSaveFile outFile(dstScene);
+
+    if (!outFile.open(QIODevice::WriteOnly)) {
+
+        dbg << "ERROR: Cannot write " << dstScene << "\n";
+
+        return false;
+
+    }
+
+    outFile.write(newSceneBin);
+
+    if (!outFile.commit()) {
+
+        dbg << "ERROR: Cannot finish writing " << dstScene << "\n";
+
+        return false;
+
+    }
+
+    dbg << "SUCCESS: Written " << newSceneBin.size() << " bytes to " << dstScene << "\n";
+
+    qDebug() << "Battle reward multipliers applied to" << records << "enemy records.";
+
+    return true;
+
+}
+
+
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// randomizeChocoboRatings — which world map chocobo battles give good chocobos
+
+//
+
+// The rating of a caught chocobo is not in scene.bin: enc_w.bin's encounter
+
+// sets end with four chocobo formations, and the high 6 bits of those entries
+
+// are the rating of the chocobo that battle yields (CHOCOBO_RATING_WONDERFUL
+
+// best) instead of a chance. The ratings of every used chocobo slot are
+
+// pooled and dealt back at random, so the mix of good and poor chocobos is
+
+// vanilla's but where they are found is not; formations are kept. Every
+
+// region with chocobo battles then gets at least one great or wonderful one.
+
+// Reads enc_w.bin from the install and writes world_us.lgp to the output,
+
+// where the crater barrier patch picks it up. Own RNG stream, like steals.
+
+// ═══════════════════════════════════════════════════════════════════════════════
+
+bool EnemyRandomizer::randomizeChocoboRatings()
+
+{
+
+    const Config& config = m_parent->m_config;
+
+    m_wroteWorldMap = false;
+
+    if (!config.getRandomizeChocoboRatings()) return true;
+
+
+
+    QString outputPath = m_parent->getOutputPath();
+
+    QString logPath = outputPath + "/chocobo_ratings_debug.txt";
+
+    QFile logFile(logPath);
+
+    bool logOk = logFile.open(QIODevice::WriteOnly | QIODevice::Text);
+
+    Q_UNUSED(logOk);
+
+    QTextStream dbg(&logFile);
+
+    dbg << "=== Chocobo Ratings ===\n"
+
+        << QDateTime::currentDateTime().toString() << "\n\n";
+
+
+
+    QString worldPath = GameFileLocator::findFile(m_parent->getFF7Path(),
+
+                                                  {QStringLiteral("data/wm/world_us.lgp")},
+
+                                                  QStringLiteral("world_us.lgp"),
+
+                                                  outputPath);
+
+    MakouLgpManager lgp;
+
+    if (worldPath.isEmpty() || !lgp.open(worldPath)) {
+
+        dbg << "ERROR: world_us.lgp not available: " << lgp.lastError() << "\n";
+
+        return false;
+
+    }
+
+    QByteArray table = lgp.fileData(QStringLiteral("enc_w.bin"));
+
+    const int regionSize = WORLD_SETS_PER_REGION * WORLD_SET_SIZE;
+
+    if (table.isEmpty() || table.size() % regionSize != 0) {
+
+        dbg << "ERROR: enc_w.bin missing or not " << regionSize << "-byte regions ("
+
+            << table.size() << " bytes)\n";
+
+        return false;
+
+    }
+
+
+
+    // Byte offsets of the used chocobo slots, by region
+
+    uchar* raw = reinterpret_cast<uchar*>(table.data());
+
+    auto entryAt = [raw](int at) { return static_cast<quint16>(raw[at] | (raw[at + 1] << 8)); };
+
+    QMap<int, QVector<int>> slotsByRegion;
+
+    QVector<int> ratings;
+
+    for (int region = 0; region < table.size() / regionSize; ++region) {
+
+        for (int set = 0; set < WORLD_SETS_PER_REGION; ++set) {
+
+            const int setAt = region * regionSize + set * WORLD_SET_SIZE;
+
+            if (!raw[setAt]) continue;   // set disabled
+
+            for (int b = WORLD_CHOCOBO_FIRST; b < WORLD_SET_ENTRIES; ++b) {
+
+                const int at = setAt + 2 + b * 2;
+
+                const quint16 entry = entryAt(at);
+
+                if ((entry & 0x03FF) == 0 || (entry >> 10) == 0) continue;
+
+                slotsByRegion[region].append(at);
+
+                ratings.append(entry >> 10);
+
+            }
+
+        }
+
+    }
+
+    if (ratings.isEmpty()) {
+
+        dbg << "No chocobo battles found - nothing to do\n";
+
+        return true;
+
+    }
+
+
+
+    std::seed_seq streamSeed{config.getSeed(), CHOCOBO_RNG_STREAM};
+
+    std::mt19937 chocoboRng(streamSeed);
+
+    for (int i = ratings.size() - 1; i > 0; --i) {
+
+        std::uniform_int_distribution<int> dist(0, i);
+
+        std::swap(ratings[i], ratings[dist(chocoboRng)]);
+
+    }
+
+
+
+    auto setRating = [raw, &entryAt](int at, int rating) {
+
+        const quint16 entry = static_cast<quint16>((entryAt(at) & 0x03FF) | (rating << 10));
+
+        raw[at]     = static_cast<uchar>(entry & 0xFF);
+
+        raw[at + 1] = static_cast<uchar>(entry >> 8);
+
+    };
+
+    int dealt = 0;
+
+    int guaranteed = 0;
+
+    for (auto it = slotsByRegion.cbegin(); it != slotsByRegion.cend(); ++it) {
+
+        const QVector<int>& slots = it.value();
+
+        bool hasGood = false;
+
+        for (int at : slots) {
+
+            const int before = entryAt(at) >> 10;
+
+            const int rating = ratings[dealt++];
+
+            setRating(at, rating);
+
+            hasGood = hasGood || rating <= CHOCOBO_RATING_GREAT;
+
+            dbg << "Region " << it.key() << " @0x" << QString::number(at, 16)
+
+                << " formation " << (entryAt(at) & 0x03FF)
+
+                << " rating " << before << " -> " << rating << "\n";
+
+        }
+
+        if (hasGood) continue;
+
+        std::uniform_int_distribution<int> slotDist(0, slots.size() - 1);
+
+        std::uniform_int_distribution<int> ratingDist(CHOCOBO_RATING_WONDERFUL, CHOCOBO_RATING_GREAT);
+
+        const int at = slots[slotDist(chocoboRng)];
+
+        const int rating = ratingDist(chocoboRng);
+
+        setRating(at, rating);
+
+        ++guaranteed;
+
+        dbg << "Region " << it.key() << " @0x" << QString::number(at, 16)
+
+            << " raised to rating " << rating << " (no great/wonderful chocobo)\n";
+
+    }
+
+
+
+    if (!lgp.setFileData(QStringLiteral("enc_w.bin"), table)) {
+
+        dbg << "ERROR: Cannot update enc_w.bin: " << lgp.lastError() << "\n";
+
+        return false;
+
+    }
+
+    QString dstWorld = QDir(outputPath).filePath("data/wm/world_us.lgp");
+
+    QDir().mkpath(QFileInfo(dstWorld).path());
+
+    if (!lgp.save(dstWorld)) {
+
+        dbg << "ERROR: Cannot write " << dstWorld << ": " << lgp.lastError() << "\n";
+
+        return false;
+
+    }
+
+    m_wroteWorldMap = true;
+
+
+
+    dbg << "\nChocobo battles: " << ratings.size() << " in " << slotsByRegion.size()
+
+        << " regions, " << guaranteed << " raised to great/wonderful\n"
+
+        << "SUCCESS: Written " << dstWorld << "\n";
+
+    qDebug() << "Chocobo ratings shuffled across" << ratings.size() << "chocobo battles.";
+
+    return true;
+
 }
 
 
@@ -3,10 +3,13 @@
 #include <QString>
 #include <QByteArray>
 #include <QVector>
+#include <QMap>
+#include <QSet>
 #include <QTextStream>
 #include <random>
 
 class Randomizer;
+class SceneCatalog;
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // EnemyRandomizer — properly parses scene.bin's compressed block/scene format
@@ -31,18 +34,38 @@ class Randomizer;
 
 class EnemyRandomizer
 {
+    friend class EconomyBalancer;   // reads scene.bin gil per tier
 public:
     explicit EnemyRandomizer(Randomizer* parent);
     bool randomize();           // stats randomization
     bool randomizeEncounters(); // encounter shuffling
+    bool renameEnemies();       // cosmetic name shuffle/replace
+    bool randomizeSteals();     // steal slot shuffle between enemies
+    bool randomizeMorphs();     // morph results from a curated pool
+    bool randomizeAttacks();    // attack power/MP cost shuffle within tiers
+    bool applyBossDifficulty(); // boss-only HP/STR/MAG curve
+    bool applyRewardMultipliers(); // EXP/AP/Gil multipliers
+    bool randomizeChocoboRatings(); // world map chocobo battle ratings
+    bool writeDangerReport();   // danger_report.txt from the output scene.bin
+    // Whether randomizeChocoboRatings() wrote world_us.lgp to the output this run
+    bool wroteWorldMap() const { return m_wroteWorldMap; }
+
+    // Read-only dump of every enemy record (name, stats, drops, steals,
+    // morph) for `--inspect-enemies`. format is "csv" or "json".
+    static bool exportEnemyTable(const QString& sceneBinPath, const QString& format,
+                                 QTextStream& out, QString* error = nullptr);
+    // Distinct enemy names of every scene in sceneBinPath, by scene index
+    static QVector<QStringList> sceneEnemyNames(const QString& sceneBinPath);
 
 private:
     Randomizer*    m_parent;
     std::mt19937&  m_rng;
+    bool           m_wroteWorldMap = false;
 
     // ── scene.bin constants ──────────────────────────────────────────────
     static const int BLOCK_SIZE        = 0x2000;  // 8192 bytes per block
     static const int BLOCK_HEADER_SIZE = 64;      // 16 × 4-byte pointers
+    static const int SCENE_MIN_GZIP_LEVEL = 6;    // zlib's default; lower levels may not fit a block
     static const int SCENE_COUNT       = 256;
     static const int SCENE_SIZE        = 7808;    // 0x1E80 decompressed
     static const int ENEMIES_PER_SCENE = 3;
@@ -54,6 +77,7 @@ private:
 
     // ── offsets within a 184-byte enemy record ───────────────────────────
     static const int ENM_NAME     = 0x00;  // 32 bytes (FF7 text)
+    static const int ENM_NAME_SIZE = 32;   // 0xFF-terminated, 0xFF-padded
     static const int ENM_LEVEL    = 0x20;  // u8
     static const int ENM_SPEED    = 0x21;  // u8
     static const int ENM_LUCK     = 0x22;  // u8
@@ -62,15 +86,95 @@ private:
     static const int ENM_DEF      = 0x25;  // u8
     static const int ENM_MAG      = 0x26;  // u8
     static const int ENM_MDEF     = 0x27;  // u8
+    static const int ENM_ATTACKS  = 0x48;  // 16 × u16 scene attack IDs
+    static const int ENM_ITEM_RATES = 0x88; // 4 × u8 (bit 7 set = steal)
+    static const int ENM_ITEM_IDS = 0x8C;  // 4 × u16 (0xFFFF = none)
     static const int ENM_MP       = 0x9C;  // u16
+    static const int ENM_AP       = 0x9E;  // u16
+    static const int ENM_MORPH    = 0xA0;  // u16 (0xFFFF = none)
     static const int ENM_HP       = 0xA4;  // u32
     static const int ENM_EXP      = 0xA8;  // u32
     static const int ENM_GIL      = 0xAC;  // u32
 
+    // Battle setup: 4 × 20-byte records after the enemy IDs, one per formation
+    static const int BATTLE_SETUP_BASE   = 0x0008;
+    static const int BATTLE_SETUP_SIZE   = 20;
+    static const int SETUP_LOCATION      = 0x00;  // u16 battle background
+    static const int SETUP_ARENA_NEXT    = 0x08;  // 4 × u16 next Battle Square battles
+    static const quint16 ARENA_NEXT_NONE = 0x03E7;
+    static const int FORMATIONS_PER_SCENE = 4;
+
+    // Camera (4 × 48 bytes) and formations (4 × 6 × 16-byte enemy entries)
+    static const int CAMERA_BASE          = 0x0058;
+    static const int CAMERA_SIZE          = 48;
+    static const int FORMATION_BASE       = 0x0118;
+    static const int FORMATION_SIZE       = 96;
+    static const int FORMATION_ENTRY_SIZE = 16;    // u16 enemy ID first, 0xFFFF = empty
+    static const int FORMATION_ENTRY_POS  = 0x02;  // s16 x, y, z
+    static const int CAMERA_POS           = 0x00;  // s16 x, y, z of the main camera
+
+    // Formation AI: 4 × u16 offsets relative to this, 0xFFFF = none,
+    // scripts padded with 0xFF up to the enemy AI
+    static const int AI_FORMATION_BASE = 0x0C80;
+    static const int AI_FORMATION_SIZE = 0x0200;
+
+    // Scene attack table: 32 × 28-byte records, then their 32 × u16 IDs
+    static const int SCENE_ATTACK_DATA  = 0x04C0;
+    static const int SCENE_ATTACK_IDS   = 0x0840;
+    static const int SCENE_ATTACK_COUNT = 32;
+    static const int ATTACK_RECORD_SIZE = 28;
+    static const int ATK_MP_COST        = 0x04;  // u16
+    static const int ATK_DAMAGE_CALC    = 0x0E;  // u8, high nibble = formula (1 physical, 2 magical)
+    static const int ATK_POWER          = 0x0F;  // u8
+    static const int SCENE_ATTACK_NAMES = 0x0880;
+    static const int ATTACK_NAME_SIZE   = 32;
+    static const int ENEMY_ATTACK_SLOTS = 16;
+    static const int MP_FLOOR_CASTS     = 2;     // casts of the priciest move
+
+    // Enemy AI (after the formation AI): 3 × u16 block offsets relative to
+    // this, 0xFFFF = none
+    static const int AI_ENEMY_BASE     = 0x0E80;
+    static const quint16 AI_ADDR_HP     = 0x4160;  // current HP of the masked actors
+    static const quint16 AI_ADDR_MAX_HP = 0x4180;  // max HP of the masked actors
+    static const quint16 AI_ADDR_SELF   = 0x2060;  // actor mask: this enemy
+    static const int SELF_HP_LOAD_SIZE  = 6;       // self mask push + HP load
+
+    // Steal shuffle RNG stream, mixed with the seed ("STEL")
+    static const quint32 STEAL_RNG_STREAM = 0x5354454C;
+
+    // Chocobo rating shuffle RNG stream ("CHOC"). Ratings run from 1
+    // (wonderful) and 2 (great) down to 8 (so-so)
+    static const quint32 CHOCOBO_RNG_STREAM = 0x43484F43;
+    static const int CHOCOBO_RATING_WONDERFUL = 1;
+    static const int CHOCOBO_RATING_GREAT     = 2;
+
+    // Morph pool: item IDs (0-127 are consumables). Sources are Power,
+    // Guard, Magic, Mind, Speed and Luck Source
+    static const quint32 MORPH_RNG_STREAM  = 0x4D525048;  // "MRPH"
+    static const quint16 SOURCE_ITEM_FIRST = 71;
+    static const quint16 SOURCE_ITEM_LAST  = 76;
+    static const QVector<quint16>& curatedMorphPool();
+
     // Boss detection by HP (no reliable global ID in scene.bin)
     static const quint32 BOSS_HP_THRESHOLD     = 10000;
     static const quint32 MINIBOSS_HP_THRESHOLD = 4000;
 
+    // Boss difficulty curves as HP and STR/MAG factors. Scaled runs from
+    // the low pair at BOSS_SCALED_LOW_LEVEL to the high pair at
+    // BOSS_SCALED_HIGH_LEVEL (vanilla enemy level of the boss's slot)
+    static constexpr double BOSS_EASY_HP          = 0.70;
+    static constexpr double BOSS_EASY_ATTACK      = 0.85;
+    static constexpr double BOSS_HARD_HP          = 1.50;
+    static constexpr double BOSS_HARD_ATTACK      = 1.15;
+    static constexpr double BOSS_SCALED_LOW_HP    = 0.80;
+    static constexpr double BOSS_SCALED_LOW_ATK   = 0.90;
+    static constexpr double BOSS_SCALED_HIGH_HP   = 1.60;
+    static constexpr double BOSS_SCALED_HIGH_ATK  = 1.20;
+    static const int BOSS_SCALED_LOW_LEVEL  = 10;
+    static const int BOSS_SCALED_HIGH_LEVEL = 60;
+    static void bossDifficultyFactors(int difficulty, int slotLevel,
+                                      double& hpFactor, double& attackFactor);
+
     // ── internal types ───────────────────────────────────────────────────
     struct SceneEntry {
         int  blockIndex;      // which 0x2000 block this came from
@@ -80,15 +184,86 @@ private:
     };
 
     // ── scene extraction / rebuild ───────────────────────────────────────
-    bool       extractScenes(const QByteArray& sceneBin,
-                             QVector<SceneEntry>& scenes,
-                             QTextStream& log);
+    static bool extractScenes(const QByteArray& sceneBin,
+                              QVector<SceneEntry>& scenes,
+                              QTextStream& log);
     QByteArray rebuildSceneBin(const QVector<SceneEntry>& scenes,
                                QTextStream& log);
 
     // ── per-scene randomization ──────────────────────────────────────────
     void randomizeScene(SceneEntry& scene, int sceneIndex, QTextStream& log);
 
+    // ── name helpers ─────────────────────────────────────────────────────
+    QString    generateEnemyName();
+    static QByteArray encodeEnemyName(const QString& name);
+
+    // ── encounter scaling (see EnemyScaling) ─────────────────────────────
+    static int averageEnemyLevel(const QByteArray& scene);
+    void       scaleSceneForSlot(QByteArray& scene, const QByteArray& slotOriginal,
+                                 QTextStream& log);
+
+    // Battle Square formation IDs (the arena's battle chain) and whether a
+    // scene holds one. Arena scenes are kept out of the encounter shuffle
+    static QSet<int> arenaFormations(const QVector<SceneEntry>& scenes);
+    static bool isArenaScene(int sceneIndex, const QSet<int>& arena);
+
+    // ── formation-level shuffle ──────────────────────────────────────────
+    // One formation with everything the game reads for it: battle setup,
+    // camera, enemy placement and its formation AI script
+    struct Formation {
+        QByteArray setup;
+        QByteArray camera;
+        QByteArray entries;
+        QByteArray ai;      // empty = no formation AI
+    };
+    static QSet<quint16> sceneEnemyIds(const QByteArray& scene);
+    static QSet<quint16> formationEnemyIds(const Formation& formation);
+    static QVector<Formation> readFormations(const QByteArray& scene);
+    static bool writeFormations(QByteArray& scene, const QVector<Formation>& formations);
+    int shuffleFormations(QVector<SceneEntry>& scenes, const QVector<int>& tier,
+                          int maxTier, bool crossTier, const SceneCatalog& catalog,
+                          QTextStream& log);
+
+    // ── enemy-level swap ─────────────────────────────────────────────────
+    // One enemy with everything it brings along: scene ID (model), record,
+    // enemy AI block and the scene attacks its attack list names
+    struct EnemyUnit {
+        quint16 id = 0xFFFF;
+        QByteArray record;
+        QByteArray ai;      // empty = no AI
+        QVector<quint16> attackIds;
+        QVector<QByteArray> attacks;
+        QVector<QByteArray> attackNames;
+    };
+    static EnemyUnit readEnemyUnit(const QByteArray& scene, int slot);
+    // Where a formation stands an enemy
+    struct Placement {
+        quint16 id;
+        qint16 x, y, z;
+    };
+    static QVector<Placement> placements(const Formation& formation);
+    // From the formation's main camera to a placed enemy, 0 = no camera
+    static double cameraDistance(const Formation& formation, const Placement& placed);
+    static constexpr double SIZE_BAND_RATIO = 1.5;  // widest footprint / narrowest in a band
+    static bool writeEnemyUnit(QByteArray& scene, int slot, const EnemyUnit& unit);
+    int swapEnemies(QVector<SceneEntry>& scenes, const QVector<int>& tier,
+                    int maxTier, bool crossTier, bool scaleScenes,
+                    const SceneCatalog& catalog, QTextStream& log);
+
+    // ── danger report ────────────────────────────────────────────────────
+    QMap<QString, QSet<int>> loadZoneFormations(QTextStream& log) const;
+    QMap<QString, QSet<int>> loadWorldFormations(QTextStream& log) const;
+    static double sceneThreat(const QByteArray& scene, int partyLevel);
+    bool       writeDangerReport(const QVector<SceneEntry>& scenes, QTextStream& log);
+
+    // ── MP scaling ───────────────────────────────────────────────────────
+    static quint16 maxAttackMpCost(const QByteArray& scene, int enemySlot);
+    static quint16 scaledEnemyMp(quint16 origMp, quint16 newMp, quint16 maxCost);
+
+    // ── AI script coherence ──────────────────────────────────────────────
+    static int rescaleAiHpConstants(QByteArray& scene, int enemySlot,
+                                    double factor, QTextStream& log);
+
     // ── stat helpers ─────────────────────────────────────────────────────
     quint8  randU8 (quint8  base, double variance);
     quint16 randU16(quint16 base, double variance);
@@ -0,0 +1,97 @@
+#include "EnemyScaling.h"
+
+#include <algorithm>
+#include <cmath>
+
+double EnemyScaling::physicalDamage(int attack, int level, int targetDef, int power)
+{
+    // Base = Att + [(Att + Lvl) / 32] * [(Att * Lvl) / 32]
+    // Damage = [Power * (512 - Def) * Base] / (16 * 512)
+    const int base = attack + ((attack + level) / 32) * ((attack * level) / 32);
+    const int def = std::clamp(targetDef, 0, 511);
+    return double(power) * (512 - def) * base / (16.0 * 512.0);
+}
+
+double EnemyScaling::magicDamage(int magAttack, int level, int targetMdef, int power)
+{
+    // Base = 6 * (MAt + Lvl)
+    // Damage = [Power * (512 - MDef) * Base] / (16 * 512)
+    const int base = 6 * (magAttack + level);
+    const int mdef = std::clamp(targetMdef, 0, 511);
+    return double(power) * (512 - mdef) * base / (16.0 * 512.0);
+}
+
+double EnemyScaling::partyHp(int level)
+{
+    return std::min(9999.0, 100.0 + 90.0 * std::clamp(level, 1, 99));
+}
+
+int EnemyScaling::partyAttack(int level)
+{
+    return std::min(255, 15 + 2 * std::clamp(level, 1, 99));
+}
+
+int EnemyScaling::partyDefense(int level)
+{
+    return std::min(250, 10 + 2 * std::clamp(level, 1, 99));
+}
+
+int EnemyScaling::partyMagicDefense(int level)
+{
+    return std::min(250, 8 + 2 * std::clamp(level, 1, 99));
+}
+
+double EnemyScaling::damagePerTurn(const Stats& enemy, int partyLevel)
+{
+    // Enemies mix attacks and spells; the stronger of the two sets the pace
+    const double phys = physicalDamage(enemy.str, enemy.level, partyDefense(partyLevel));
+    const double mag  = magicDamage(enemy.mag, enemy.level, partyMagicDefense(partyLevel));
+    return std::max(phys, mag);
+}
+
+double EnemyScaling::turnsToKill(const Stats& enemy, int partyLevel)
+{
+    const double partyDamage = std::max(1.0, physicalDamage(partyAttack(partyLevel),
+                                                             partyLevel, enemy.def));
+    return double(enemy.hp) / partyDamage;
+}
+
+int EnemyScaling::solveAttack(double target, int level, int partyLevel, bool magic)
+{
+    for (int stat = 1; stat <= 255; ++stat) {
+        const double dmg = magic
+            ? magicDamage(stat, level, partyMagicDefense(partyLevel))
+            : physicalDamage(stat, level, partyDefense(partyLevel));
+        if (dmg >= target)
+            return stat;
+    }
+    return 255;
+}
+
+EnemyScaling::Stats EnemyScaling::rescale(const Stats& enemy, int fromLevel, int toLevel)
+{
+    fromLevel = std::clamp(fromLevel, 1, 99);
+    toLevel   = std::clamp(toLevel, 1, 99);
+    if (fromLevel == toLevel)
+        return enemy;
+
+    Stats out = enemy;
+    out.level = std::clamp(int(std::lround(double(enemy.level) * toLevel / fromLevel)), 1, 99);
+
+    // Keep each damage type's share of party HP
+    const double hpRatio = partyHp(toLevel) / partyHp(fromLevel);
+    if (enemy.str > 0) {
+        const double phys = physicalDamage(enemy.str, enemy.level, partyDefense(fromLevel));
+        out.str = solveAttack(phys * hpRatio, out.level, toLevel, false);
+    }
+    if (enemy.mag > 0) {
+        const double mag = magicDamage(enemy.mag, enemy.level, partyMagicDefense(fromLevel));
+        out.mag = solveAttack(mag * hpRatio, out.level, toLevel, true);
+    }
+
+    // Keep the number of party turns needed to kill it
+    const double turns = turnsToKill(enemy, fromLevel);
+    const double partyDamage = std::max(1.0, physicalDamage(partyAttack(toLevel), toLevel, out.def));
+    out.hp = quint32(std::clamp(std::llround(turns * partyDamage), 1LL, 999999LL));
+    return out;
+}
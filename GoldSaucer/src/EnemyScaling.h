@@ -0,0 +1,57 @@
+#pragma once
+
+#include <QtGlobal>
+
+/**
+ * EnemyScaling
+ *
+ * Rescales an enemy for a different point in the game using FF7's own damage
+ * formulas instead of flat multipliers. Halving STR does not halve damage:
+ * physical base damage grows with Att * Lvl, so a late-game enemy moved early
+ * with "scaled-down" stats still one-shots a level 10 party.
+ *
+ * The model compares an enemy against a reference party at a given level and
+ * keeps two things constant when the enemy moves from one party level to
+ * another:
+ *   - damage per turn as a share of the party's HP (threat), and
+ *   - turns the party needs to kill it (durability).
+ * STR / MAG are solved against the damage formulas at the new level, HP from
+ * the party's damage output at that level. DEF / MDEF are left alone: they are
+ * already fractions of 512 in both formulas.
+ *
+ * Party curves are rough fits of a typical playthrough (Cloud, average gear);
+ * they only need to be monotonic and in the right ballpark.
+ */
+class EnemyScaling
+{
+public:
+    struct Stats {
+        int     level = 1;
+        int     str   = 0;
+        int     def   = 0;
+        int     mag   = 0;
+        int     mdef  = 0;
+        quint32 hp    = 1;
+    };
+
+    // FF7 damage formulas. Power 16 = a plain "Attack"; spells are similar.
+    static double physicalDamage(int attack, int level, int targetDef, int power = 16);
+    static double magicDamage(int magAttack, int level, int targetMdef, int power = 16);
+
+    // Reference party at a given level
+    static double partyHp(int level);
+    static int    partyAttack(int level);
+    static int    partyDefense(int level);
+    static int    partyMagicDefense(int level);
+
+    // Threat and durability of an enemy against the party at partyLevel
+    static double damagePerTurn(const Stats& enemy, int partyLevel);
+    static double turnsToKill(const Stats& enemy, int partyLevel);
+
+    // Move an enemy fought at fromLevel to an encounter fought at toLevel
+    static Stats rescale(const Stats& enemy, int fromLevel, int toLevel);
+
+private:
+    // Smallest stat in [1, 255] whose damage reaches target (255 if none)
+    static int solveAttack(double target, int level, int partyLevel, bool magic);
+};
@@ -1,7 +1,16 @@
 #include "FieldPickupRandomizer_ff7tk.h"
 #include "Randomizer.h"
 #include "Config.h"
+#include "GameFileLocator.h"
+#include "InputCache.h"
+#include "SaveBootstrap.h"
+#include "FieldScript.h"
+#include "LogicOverrides.h"
+#include "BossList.h"
+#include "ScriptedBattleTable.h"
+#include "SceneCatalog.h"
 #include <QFile>
+#include <QSaveFile>
 #include <QDir>
 #include <QDebug>
 #include <QTextStream>
@@ -10,11 +19,17 @@
 #include <QJsonDocument>
 #include <QJsonObject>
 #include <QJsonArray>
+#include <QMutex>
+#include <QMutexLocker>
+#include <QtConcurrent/QtConcurrentMap>
 #include <LZS>
 #include <ff7tk/data/FF7Text.h>
 #include <ff7tk/data/FF7Item.h>
 #include <algorithm>
 #include <array>
+#include <functional>
+#include <iterator>
+#include <cmath>
 #include <limits>
 #include <vector>
 #include <cstring>
@@ -22,7 +37,7 @@
 #include <QSet>
 
 // Forward decl: NOP all real PMVIE/MOVIE opcodes in a field's section-0 scripts.
-// Defined below (after fieldOpcodeLength); used by the md1stin Free Roam handler.
+// Defined below; used by the md1stin Free Roam handler.
 static int nopFieldScriptMovies(QByteArray& d, const QString& fieldName, QTextStream& dbg);
 
 // Forward decl: NOP all SPLIT (0x09) opcodes in a field's scripts (reduced-party
@@ -34,12 +49,78 @@ static int neuterInnGoScript(QByteArray& d, const QString& fieldName,
                              quint8 keepAddr, quint8 keepBit,
                              QTextStream& dbg);
 
+// Forward decl: " (entity 16, script 19)" owner suffix for log lines; defined
+// with the script ownership helpers below.
+namespace {
+QString ownerTag(int entity, int script);
+}
+
 namespace {
     constexpr int MOMENT_GAME_START    = 0;
     constexpr int MOMENT_MIDGAR_ESCAPE = 1008; // MainProgress threshold when Shinra HQ changes
     constexpr int MOMENT_FOREVER       = std::numeric_limits<int>::max();
 }
 
+// ============================================================================
+// Helper scripts
+//
+// Byte sequences the hand-written field patches search for or write, as hex.
+// Each is decoded once on first use; validateHelperScripts() checks them all
+// (and the md1stin new-game sequence) at startup, so a malformed entry stops
+// the program up front instead of silently never matching. An anchor ends
+// inside its last opcode - an IF test without its jump byte.
+// ============================================================================
+
+namespace {
+enum HelperScript {
+    HelperGoldChocoboGrant,     // elmin4_2 'choko' grant start
+    HelperGoldChocoboCleanup,   // ... and its trigger-bit cleanup
+    HelperKalmMateriaLeadIn,    // elmin4_2 lead-in before each trade SMTRA
+    HelperCondorMovie,          // convil_2 post-minigame PMVIE/WAIT/MOVIE
+    HelperCondorMovieNopped,    // ... after the patch
+    HelperSnowShinraGate,       // snow man1 blockade gate
+    HelperSnowShinraGateNopped, // ... after the patch
+    HelperKalmDisc1Gate,        // elm* game_moment > 999 test
+    HelperSector5Gate,          // mds5_5 entry walkmesh test
+    HelperBasementGate,         // sininb2 basement test
+    HelperSnowboardCheck,       // snow "have the Snowboard" test
+    HelperGlacierMapCheck,      // snow "have the Glacier Map" test
+    HelperScriptCount
+};
+
+struct HelperScriptDef {
+    const char* name;
+    const char* hex;
+    bool        anchor;
+};
+
+const HelperScriptDef kHelperScripts[HelperScriptCount] = {
+    { "gold chocobo grant",   "1533585902d902", false },   // IFUBL 33 58 59 02 D9 02
+    { "gold chocobo cleanup", "83f05704",       false },   // BITOFF Var[15][0x57] bit 4
+    { "Kalm materia lead-in", "c500500033014a01", false },
+    { "Condor movie",         "f821240100f9",   false },   // PMVIE 33 ; WAIT 1 ; MOVIE
+    { "Condor movie NOP'd",   "5f5f2401005f",   false },
+    { "snow Shinra gate",     "15501001000901", false },   // IFUBL Var[5][16] == 1
+    { "snow Shinra gate NOP'd", "155010ff000901", false },
+    { "Kalm disc-1 gate",     "16200000e70302", true },    // IFSW game_moment > 999
+    { "Sector 5 gate",        "14f026030a",     true },    // IFUB Var[15][38] bitOFF 3
+    { "basement gate",        "1410e8010a",     true },    // IFUB Var[1][232] bitOFF 1
+    { "Snowboard check",      "1410820109",     true },    // IFUB Var[1][130] bitON 1
+    { "Glacier Map check",    "1410820609",     true },    // IFUB Var[1][130] bitON 6
+};
+
+const QByteArray& helperScript(HelperScript id)
+{
+    static const QVector<QByteArray> decoded = [] {
+        QVector<QByteArray> bytes;
+        for (const HelperScriptDef& def : kHelperScripts)
+            bytes.append(QByteArray::fromHex(def.hex));
+        return bytes;
+    }();
+    return decoded[id];
+}
+} // namespace
+
 // ============================================================================
 // Constants
 // ============================================================================
@@ -53,7 +134,6 @@ const QString FieldPickupRandomizer_ff7tk::DEBUG_FILE_NAME =
 FieldPickupRandomizer_ff7tk::FieldPickupRandomizer_ff7tk(Randomizer* parent)
     : QObject(nullptr)
     , m_parent(parent)
-    , m_debugMode(true)
 {
     m_rng.seed(QDateTime::currentMSecsSinceEpoch());
     qDebug() << "FieldPickupRandomizer_ff7tk: Initialised (Makou-compatible rewrite)";
@@ -70,6 +150,18 @@ FieldPickupRandomizer_ff7tk::~FieldPickupRandomizer_ff7tk()
 bool FieldPickupRandomizer_ff7tk::randomize()
 {
     qDebug() << "FieldPickupRandomizer_ff7tk::randomize() called";
+    if (m_parent)
+        m_rng.seed(m_parent->streamSeed("field pickups"));
+    // Decided by this call's key item plan, never carried over from an earlier one
+    m_chocoboLureRequired = false;
+
+    // Malformed patch bytes would corrupt every field they touch
+    const QStringList helperProblems = validateHelperScripts();
+    if (!helperProblems.isEmpty()) {
+        for (const QString& problem : helperProblems)
+            qDebug() << "ERROR:" << problem;
+        return false;
+    }
 
     // --- build item pools ---------------------------------------------------
     initializeItemPools();
@@ -100,17 +192,57 @@ bool FieldPickupRandomizer_ff7tk::randomize()
     MakouLgpManager lgp;
     if (!lgp.open(flevelPath)) {
         qDebug() << "ERROR: Failed to open LGP:" << lgp.lastError();
+        if (m_parent)
+            m_parent->reportInputError(Randomizer::InputError::FieldParse, flevelPath, lgp.lastError());
         return false;
     }
 
     QStringList allFiles = lgp.fileList();
     qDebug() << "LGP contains" << allFiles.size() << "files";
 
-    // --- open debug log -----------------------------------------------------
+    // Vanilla field bytes come from the resident cache when there is one
+    const InputCache* cache = m_parent ? m_parent->inputCache() : nullptr;
+    auto vanillaField = [&lgp, cache](const QString& fileName) {
+        return cache ? cache->fieldData(fileName) : lgp.fileData(fileName);
+    };
+
+    // --- open debug logs ----------------------------------------------------
+    // Each artifact is a Config::DebugOutput toggle. Streams for disabled
+    // ones write into a string sink that is cleared as it goes.
+    auto debugEnabled = [this](Config::DebugOutput output) {
+        return m_parent ? m_parent->m_config.isDebugOutputEnabled(output)
+                        : output == Config::DebugPickupsLog;
+    };
     QString debugPath = outputPath + "/field_randomization_debug.txt";
     QFile debugFile(debugPath);
-    bool debugOk = debugFile.open(QIODevice::WriteOnly | QIODevice::Truncate);
-    QTextStream debugStream(&debugFile);
+    QString discardedLog;
+    bool debugOk = debugEnabled(Config::DebugPickupsLog)
+                   && debugFile.open(QIODevice::WriteOnly | QIODevice::Truncate);
+    QTextStream debugStream;
+    if (debugOk) debugStream.setDevice(&debugFile);
+    else         debugStream.setString(&discardedLog);
+
+    QFile keyScanFile(outputPath + "/key_bit_scan.txt");
+    QString discardedKeyScan;
+    const bool keyScanOk = debugEnabled(Config::DebugKeyBitScan)
+                           && keyScanFile.open(QIODevice::WriteOnly | QIODevice::Truncate | QIODevice::Text);
+    QTextStream keyScanStream;
+    if (keyScanOk) keyScanStream.setDevice(&keyScanFile);
+    else           keyScanStream.setString(&discardedKeyScan);
+
+    QFile stitmIndexFile(outputPath + "/field_stitm_index.txt");
+    QTextStream stitmIndexStream(&stitmIndexFile);
+    QTextStream* stitmIndex = nullptr;
+    if (debugEnabled(Config::DebugStitmIndex)
+        && stitmIndexFile.open(QIODevice::WriteOnly | QIODevice::Truncate | QIODevice::Text)) {
+        stitmIndex = &stitmIndexStream;
+        stitmIndexStream << "field\topcode\toffset\tentity\tscript\tvanilla\tdirect\n";
+    }
+
+    QFile scriptDumpFile(outputPath + "/field_script_dump.txt");
+    QTextStream scriptDumpStream(&scriptDumpFile);
+    const bool scriptDumpOk = debugEnabled(Config::DebugScriptDumps)
+                              && scriptDumpFile.open(QIODevice::WriteOnly | QIODevice::Truncate | QIODevice::Text);
     if (debugOk) {
         debugStream << "=== Field Pickup Randomization ===\n";
         debugStream << "Date      : " << QDateTime::currentDateTime().toString() << "\n";
@@ -147,62 +279,268 @@ bool FieldPickupRandomizer_ff7tk::randomize()
 
     // --- key item placement plan (computed but NOT applied to LGP yet) ------
     QMap<QString, KeyItemFieldMod> keyItemMods;
+    m_lostKeyItems.clear();
+    m_uncollectableKeyItems.clear();
+    m_vanillaKeyItems.clear();
+    m_keyItemSources.clear();
 
     if (keyItemEnabled) {
-        debugStream << "=== KEY ITEM COLLECTION PASS ===\n";
+        keyScanStream << "=== KEY ITEM COLLECTION PASS ===\n";
         QMap<quint32, GlobalKeyItem> uniqueKeyItems;
         QVector<GlobalStitmLocation> globalStitmLocations;
+        QMap<quint32, QVector<GlobalKeyItem>> keyItemSources;
 
         for (int idx = 0; idx < allFiles.size(); ++idx) {
             const QString& fn = allFiles[idx];
             if (fn.startsWith("blackbg")) continue;
             if (fn == "onna_5") continue; // onna_5 has no key item BITONs but triggers false STITM detections
 
-            QByteArray fd = lgp.fileData(fn);
+            QByteArray fd = vanillaField(fn);
             if (fd.isEmpty()) continue;
             collectKeyItemsAndStitm(fd, idx, fn, uniqueKeyItems,
-                                     globalStitmLocations, debugStream);
+                                     globalStitmLocations, keyItemSources, keyScanStream);
+            discardedKeyScan.clear();
         }
 
         if (!uniqueKeyItems.isEmpty() && !globalStitmLocations.isEmpty()) {
+            // Slots are checked against the vanilla bytes before the plan, so
+            // its logic playthrough covers exactly what gets written
+            QMap<QString, QByteArray> slotFields;
+            for (const GlobalStitmLocation& loc : globalStitmLocations) {
+                const QString& fn = allFiles[loc.fileIndex];
+                if (!slotFields.contains(fn))
+                    slotFields.insert(fn, LZS::decompressAllWithHeader(vanillaField(fn)));
+            }
+            dropUnwritableKeyItemSlots(globalStitmLocations, slotFields, allFiles, debugStream);
             keyItemMods = performKeyItemSwaps(uniqueKeyItems, globalStitmLocations,
                                               allFiles, debugStream);
+            const QStringList unwritable = verifyKeyItemPlacements(keyItemMods, slotFields,
+                                                                   debugStream);
+            if (!unwritable.isEmpty()) {
+                const QString message = "slot can't be written after the logic playthrough: "
+                                        + unwritable.join(", ");
+                debugStream << "LOGIC: key item " << message << " – aborting\n";
+                m_parent->reportPlanError("Key item placement: " + message);
+                return false;
+            }
+            if (!m_lostKeyItems.isEmpty()) {
+                const QString message = QString("no slot for %1 after %2 placement attempts"
+                                                " (too few eligible locations left)")
+                                            .arg(m_lostKeyItems.join(", "))
+                                            .arg(KEY_ITEM_PLACEMENT_ATTEMPTS);
+                debugStream << "LOGIC: key item " << message << " – aborting\n";
+                m_parent->reportPlanError("Key item placement: " + message);
+                return false;
+            }
+            if (!m_uncollectableKeyItems.isEmpty()) {
+                const QString message = QString("%1 still uncollectable after %2 placement attempts")
+                                            .arg(m_uncollectableKeyItems.join(", "))
+                                            .arg(KEY_ITEM_PLACEMENT_ATTEMPTS);
+                debugStream << "LOGIC: key item " << message << " – aborting\n";
+                m_parent->reportPlanError("Key item placement: " + message);
+                return false;
+            }
+            if (!auditKeyItemSources(keyItemMods, keyItemSources, allFiles, debugStream))
+                return false;
+            if (!m_keyItemLogicOk && m_parent->m_config.getValidateLogic()) {
+                debugStream << "LOGIC: key item placement is not beatable – aborting (validateLogic)\n";
+                m_parent->reportPlanError("Key item placement: the logic playthrough can't collect"
+                                          " every shuffled key item (see the field pickup log)");
+                return false;
+            }
         } else {
             debugStream << "No key items or STITM targets found – skipping swap.\n";
         }
+
+        // A key item behind a river/ocean crossing needs a catchable chocobo,
+        // so the Chocobo Farm must keep handing out the Lure (Greens are safe:
+        // the shop randomizer never touches the vegetable shops).
+        for (auto it = keyItemMods.cbegin(); it != keyItemMods.cend(); ++it) {
+            if (!it.value().placements.isEmpty() && requiresChocoboCrossing(it.key())) {
+                m_chocoboLureRequired = true;
+                debugStream << "CHOCOBO: key item placed in " << it.key()
+                            << " – Chocobo Lure grant will be preserved\n";
+                break;
+            }
+        }
         debugStream << "\n";
     }
 
+    // Randomized materia caves are chocobo locations too: without the Lure
+    // no chocobo can be caught, bred, or ridden to them.
+    if (m_parent && m_parent->m_config.getMateriaCaveChecks()
+        && !m_parent->m_config.isFeatureEnabled(Config::ArchipelagoIntegration)) {
+        m_chocoboLureRequired = true;
+        debugStream << "CHOCOBO: materia cave checks enabled – Chocobo Lure grant will be preserved\n";
+    }
+
+    m_moddedFields.clear();
+    m_fieldsScanned = 0;
+    m_bytesRecompressed = 0;
+    m_batterySlots.clear();
+    m_tieredSlots.clear();
+    m_midgarPartsChecksPatched = 0;
+    m_hugeMateriaChecksPatched = 0;
+    m_uncuedKeyItems.clear();
+    m_trackerEntries.clear();
+    m_exportLocations.clear();
+    m_patchLog.clear();
+    m_patchLogEnabled = debugEnabled(Config::DebugPatchLog);
+    if (!apMode)
+        planBatterySlots(lgp, allFiles, keyItemMods, debugStream);
+    if (!apMode && m_parent && m_parent->m_config.getTieredEquipment())
+        planTieredSlots(lgp, allFiles, keyItemMods, debugStream);
+    m_fieldEncounterPool.clear();
+    if (m_parent && m_parent->m_config.getFieldEncounterMode() == Config::FieldEncountersReroll)
+        collectFieldEncounterPool(lgp, allFiles, debugStream);
+    if (m_parent && m_parent->m_config.getRandomizeScriptedBattles()) {
+        ScriptedBattleTable& table = ScriptedBattleTable::instance();
+        table.reload();
+        debugStream << "SCRIPTED BATTLES: " << table.groupCount() << " group(s) of interchangeable"
+                    << " formations" << (table.groupCount() == 0 ? ", scripted fights stay vanilla" : "")
+                    << "\n";
+        for (const QString& warning : table.warnings())
+            debugStream << "  WARNING: scripted battles: " << warning << "\n";
+    }
+
     // --- process every field file -------------------------------------------
     // Key item byte modifications AND STITM/SMTRA randomization are applied
     // in a single pass per field so nothing gets overwritten.
     int totalModified = 0;
     int filesWithChanges = 0;
+    QStringList protectedViolations;
+
+    // Each field runs on its own: decompress, scan, patch and recompress,
+    // drawing from a generator seeded from streamSeed("field <name>"), so its
+    // rolls don't depend on the other fields or on thread timing. Logs,
+    // tracker entries and patch records are merged in file order afterwards.
+    // Archipelago mode hands out the .apff7 slots in file order, so there
+    // the same pipeline runs serially.
+    struct FieldJob {
+        QString                name;
+        QByteArray             vanilla;      // compressed, as read
+        const KeyItemFieldMod* keyItemMod = nullptr;
+        bool                   changed = false;
+        QByteArray             patched;      // decompressed
+        QByteArray             recompressed; // empty when recompression failed
+        QString                note;
+        QString                log;
+        QString                stitmIndexRows;
+        FieldResult            result;
+    };
+    QVector<FieldJob> jobs;
+    const bool writeExport = !apMode && m_parent && m_parent->m_config.getArchipelagoExport();
+    auto setField = [&](const QString& fileName, const QByteArray& data) {
+        if (!lgp.setFileData(fileName, data)) {
+            qDebug() << "WARNING: setFileData failed for" << fileName;
+            if (debugOk) debugStream << "WARNING: setFileData failed for "
+                                     << fileName << "\n";
+        }
+    };
 
     for (const QString& fileName : allFiles) {
         if (fileName.startsWith("blackbg")) continue;
         if (fileName == "onna_5") continue; // Exclude onna_5 from randomization
 
-        QByteArray fieldData = lgp.fileData(fileName);
-        if (fieldData.isEmpty()) continue;
-
-        // Check if this field has key item modifications
-        const KeyItemFieldMod* kiMod = keyItemMods.contains(fileName)
-                                        ? &keyItemMods[fileName] : nullptr;
-
-        bool changed = processFieldFile(fileName, fieldData, debugStream, kiMod);
-        if (changed) filesWithChanges++;
-
-        if (!lgp.setFileData(fileName, fieldData)) {
-            qDebug() << "WARNING: setFileData failed for" << fileName;
-            if (debugOk) debugStream << "WARNING: setFileData failed for "
-                                     << fileName << "\n";
+        FieldJob job;
+        job.name = fileName;
+        job.vanilla = vanillaField(fileName);
+        if (job.vanilla.isEmpty()) continue;
+        const auto kiMod = keyItemMods.constFind(fileName);
+        if (kiMod != keyItemMods.cend())
+            job.keyItemMod = &kiMod.value();
+        if (m_parent)
+            job.result.rng.seed(m_parent->streamSeed((QStringLiteral("field ") + fileName).toUtf8().constData()));
+        else
+            job.result.rng.seed(m_rng.generate());
+        jobs.append(job);
+    }
+    m_fieldsScanned = jobs.size();
+
+    // Warm the economy analysis here: the field pass only reads it
+    if (m_parent && m_parent->m_config.getBalanceEconomy())
+        m_parent->economy();
+
+    const int lzsEffort = m_parent ? m_parent->m_config.getLzsEffort() : Config::LZS_EFFORT_DEFAULT;
+    const bool indexRows = stitmIndex != nullptr;
+    auto runField = [this, lzsEffort, indexRows](FieldJob& job) {
+        QTextStream log(&job.log);
+        QTextStream index(&job.stitmIndexRows);
+        QByteArray fieldData = job.vanilla;
+        job.changed = processFieldFile(job.name, fieldData, log, job.result, job.keyItemMod,
+                                       indexRows ? &index : nullptr);
+        if (job.changed) {
+            job.patched = fieldData;
+            job.recompressed = recompressField(job.patched, lzsEffort, job.note);
+        }
+    };
+    if (apMode)
+        std::for_each(jobs.begin(), jobs.end(), runField);
+    else
+        QtConcurrent::blockingMap(jobs, runField);
+
+    for (const FieldJob& job : jobs) {
+        const QString& fileName = job.name;
+        const FieldResult& result = job.result;
+        debugStream << job.log;
+        if (stitmIndex)
+            *stitmIndex << job.stitmIndexRows;
+        if (!result.inputError.isEmpty() && m_parent)
+            m_parent->reportInputError(Randomizer::InputError::FieldParse, fileName, result.inputError);
+        if (!result.exportLocations.isEmpty())
+            m_exportLocations.insert(fileName, result.exportLocations);
+        if (!result.layoutIssue.isEmpty())
+            m_moddedFields[fileName] = result.layoutIssue;
+        if (!job.changed) {
+            setField(fileName, job.vanilla);
+            discardedLog.clear();
+            continue;
+        }
+        if (!job.note.isEmpty())
+            debugStream << "  " << fileName << ": " << job.note << "\n";
+        if (job.recompressed.isEmpty()) {
+            // Ships vanilla: nothing it found goes into the tracker map or logs
+            setField(fileName, job.vanilla);
+            discardedLog.clear();
+            continue;
+        }
+        filesWithChanges++;
+        m_bytesRecompressed += job.recompressed.size();
+        m_trackerEntries += result.trackerEntries;
+        m_patchLog += result.patchRecords;
+        m_uncuedKeyItems += result.uncuedKeyItems;
+        m_midgarPartsChecksPatched += result.midgarPartsChecksPatched;
+        m_hugeMateriaChecksPatched += result.hugeMateriaChecksPatched;
+
+        const QMap<quint32, int> before = protectedFlagWrites(LZS::decompressAllWithHeader(job.vanilla));
+        const QMap<quint32, int> after  = protectedFlagWrites(job.patched);
+        for (auto it = after.constBegin(); it != after.constEnd(); ++it) {
+            if (it.value() <= before.value(it.key())) continue;
+            protectedViolations << QString("%1: Var[%2][0x%3] bit %4 written %5x (vanilla %6x)")
+                                       .arg(fileName).arg(it.key() >> 16)
+                                       .arg((it.key() >> 8) & 0xFF, 2, 16, QChar('0'))
+                                       .arg(it.key() & 0xFF).arg(it.value())
+                                       .arg(before.value(it.key()));
         }
+        if (scriptDumpOk)
+            dumpFieldScripts(job.patched, fileName, scriptDumpStream);
+        discardedLog.clear();
+
+        const QString dungeon = optionalDungeonName(fileName);
+        if (!dungeon.isEmpty()) {
+            debugStream << "OPTIONAL: " << fileName << " (" << dungeon << ", sphere "
+                        << getFieldSphere(fileName) << ")"
+                        << (requiresSubmarine(fileName) ? " – needs the submarine, no key items"
+                            : requiresChocoboCrossing(fileName) ? " – needs a chocobo crossing" : "")
+                        << "\n";
+        }
+
+        setField(fileName, job.recompressed);
     }
 
     // --- key item verification (before save) ---------------------------------
-    if (debugOk && keyItemEnabled) {
-        debugStream << "\n=== KEY ITEM VERIFICATION (pre-save) ===\n";
+    if (keyScanOk && keyItemEnabled) {
+        keyScanStream << "\n=== KEY ITEM VERIFICATION (pre-save) ===\n";
         // Pick first placed key item field for verification
         // Re-read from LGP to see final state after all processing
         QStringList verifyFields;
@@ -212,35 +550,35 @@ bool FieldPickupRandomizer_ff7tk::randomize()
             if (vData.isEmpty()) continue;
             QByteArray vDec = LZS::decompressAllWithHeader(vData);
             if (vDec.isEmpty()) {
-                debugStream << "  " << vf << ": decompress failed\n";
+                keyScanStream << "  " << vf << ": decompress failed\n";
                 continue;
             }
-            debugStream << "  " << vf << ": decompressed " << vDec.size() << " bytes\n";
+            keyScanStream << "  " << vf << ": decompressed " << vDec.size() << " bytes\n";
 
             // Parse section 0 to find text section
             if (vDec.size() < 42 + 4) continue;
             quint32 sec0off;
             memcpy(&sec0off, vDec.constData() + 6, 4);
+            if (sec0off > static_cast<quint32>(vDec.size() - 12)) continue;
             int sec0Data = static_cast<int>(sec0off) + 4;
-            if (sec0Data + 8 > vDec.size()) continue;
             quint16 posTexts;
             memcpy(&posTexts, vDec.constData() + sec0Data + 4, 2);
             int textAbsStart = sec0Data + posTexts;
 
             // Hex dump first 20 bytes of text section
-            debugStream << "  TextSection @" << textAbsStart << " first 20 bytes: ";
+            keyScanStream << "  TextSection @" << textAbsStart << " first 20 bytes: ";
             for (int i = 0; i < 20 && textAbsStart + i < vDec.size(); ++i) {
-                debugStream << QString("%1 ").arg(
+                keyScanStream << QString("%1 ").arg(
                     static_cast<quint8>(vDec.at(textAbsStart + i)), 2, 16, QChar('0'));
             }
-            debugStream << "\n";
+            keyScanStream << "\n";
 
             // Derive text count
             if (textAbsStart + 4 <= vDec.size()) {
                 quint16 firstOff;
                 memcpy(&firstOff, vDec.constData() + textAbsStart + 2, 2);
                 int tc = firstOff / 2 - 1;
-                debugStream << "  firstOff=" << firstOff << " textCount=" << tc << "\n";
+                keyScanStream << "  firstOff=" << firstOff << " textCount=" << tc << "\n";
 
                 // Read last text entry (should be the newest added)
                 if (tc > 0 && tc <= 255) {
@@ -253,7 +591,7 @@ bool FieldPickupRandomizer_ff7tk::randomize()
                             if (static_cast<quint8>(vDec.at(i)) == 0xFF) break;
                             lastRaw.append(vDec.at(i));
                         }
-                        debugStream << "  Last text entry [" << (tc - 1) << "] @" << lastOff
+                        keyScanStream << "  Last text entry [" << (tc - 1) << "] @" << lastOff
                                     << ": \"" << FF7Text::toPC(lastRaw) << "\"\n";
                     }
                 }
@@ -272,7 +610,7 @@ bool FieldPickupRandomizer_ff7tk::randomize()
                     // Key item BITONs use bank 1-2, address 0x40-0x46
                     quint8 destBank = (bank >> 4) & 0x0F;
                     if (destBank >= 1 && destBank <= 2 && addr >= 0x40 && addr <= 0x46) {
-                        debugStream << "  BITON @" << i << " bank=0x"
+                        keyScanStream << "  BITON @" << i << " bank=0x"
                                     << QString::number(bank, 16) << " addr=0x"
                                     << QString::number(addr, 16) << " bit=" << bit << "\n";
                         // Check if there's a MESSAGE within 10 bytes before
@@ -280,7 +618,7 @@ bool FieldPickupRandomizer_ff7tk::randomize()
                             if (m >= scriptStart && static_cast<quint8>(vDec.at(m)) == 0x40) {
                                 quint8 winId = static_cast<quint8>(vDec.at(m + 1));
                                 quint8 txtId = static_cast<quint8>(vDec.at(m + 2));
-                                debugStream << "    MESSAGE @" << m << " win=" << winId
+                                keyScanStream << "    MESSAGE @" << m << " win=" << winId
                                             << " textID=" << txtId << "\n";
                             }
                         }
@@ -288,8 +626,46 @@ bool FieldPickupRandomizer_ff7tk::randomize()
                     }
                 }
             }
-            debugStream << "  Key-item BITONs found: " << bitonCount << "\n\n";
+            keyScanStream << "  Key-item BITONs found: " << bitonCount << "\n\n";
+        }
+    }
+
+    // --- protected flags ------------------------------------------------------
+    // A rewrite that lands on date / minigame / quest state corrupts saves in
+    // ways nobody traces back to the randomizer, so don't ship the flevel
+    if (!protectedViolations.isEmpty()) {
+        qDebug() << "ERROR: rewritten scripts touch protected flags:" << protectedViolations;
+        if (debugOk) {
+            debugStream << "\nERROR: rewritten scripts touch protected flags, flevel.lgp not written\n";
+            for (const QString& line : protectedViolations)
+                debugStream << "  " << line << "\n";
         }
+        lgp.close();
+        return false;
+    }
+
+    // --- counted checks -------------------------------------------------------
+    // A lowered count that found no check to retarget would ship a seed that
+    // still needs the vanilla count while the seed card says otherwise
+    if (m_parent && m_parent->m_config.getMidgarPartsRequired() < Config::MIDGAR_PARTS_COUNT
+        && m_midgarPartsChecksPatched == 0) {
+        const QString message = QString("no turn-in check found in the blin65* fields,"
+                                        " the 65th floor model would still need all %1 Midgar Parts")
+                                    .arg(Config::MIDGAR_PARTS_COUNT);
+        debugStream << "\nERROR: " << message << ", flevel.lgp not written\n";
+        m_parent->reportPlanError("Midgar Parts count: " + message);
+        lgp.close();
+        return false;
+    }
+    if (m_parent && m_parent->m_config.getHugeMateriaRequired() < Config::HUGE_MATERIA_COUNT
+        && m_hugeMateriaChecksPatched == 0) {
+        const QString message = QString("no observatory count check found in the cos* fields,"
+                                        " Bugenhagen would still need all %1 Huge Materia")
+                                    .arg(Config::HUGE_MATERIA_COUNT);
+        debugStream << "\nERROR: " << message << ", flevel.lgp not written\n";
+        m_parent->reportPlanError("Huge Materia count: " + message);
+        lgp.close();
+        return false;
     }
 
     // --- Archipelago verification log -------------------------------------
@@ -297,12 +673,31 @@ bool FieldPickupRandomizer_ff7tk::randomize()
         writeArchipelagoSidecar(outputPath, debugStream);
     }
 
+    // --- auto-tracker map (Archipelago trackers use the sidecar instead) ----
+    if (!apMode)
+        writeTrackerMap(outputPath, debugStream);
+
+    // --- multiworld placement export ------------------------------------------
+    if (writeExport)
+        writeArchipelagoExport(outputPath, debugStream);
+
+    // --- field patch log --------------------------------------------------------
+    if (m_patchLogEnabled)
+        writePatchLog(outputPath, debugStream);
+
     // --- summary ------------------------------------------------------------
     if (debugOk) {
         debugStream << "\n=== Summary ===\n";
         debugStream << "Files with STITM changes: " << filesWithChanges << "\n";
         if (apMode)
             debugStream << "Archipelago BITONs assigned: " << m_apBitonEntries.size() << "\n";
+        if (m_parent && m_parent->m_config.getMidgarPartsRequired() < Config::MIDGAR_PARTS_COUNT)
+            debugStream << "Midgar Parts checks patched: " << m_midgarPartsChecksPatched << "\n";
+        if (m_parent && m_parent->m_config.getHugeMateriaRequired() < Config::HUGE_MATERIA_COUNT)
+            debugStream << "Huge Materia checks patched: " << m_hugeMateriaChecksPatched << "\n";
+        if (!m_uncuedKeyItems.isEmpty())
+            debugStream << "Key items without a sound cue (no SOUND next to the grant): "
+                        << m_uncuedKeyItems.join(", ") << "\n";
         debugStream << "Session completed: "
                     << QDateTime::currentDateTime().toString() << "\n";
         debugFile.close();
@@ -338,52 +733,10 @@ bool FieldPickupRandomizer_ff7tk::randomize()
 // (4078) and 0x00 init. We encode plain LZSS over the output and map (distance ->
 // ring position) with pos = (4078 + outPos - distance) & 4095, which never needs
 // the init fill. Output is prefixed with the 4-byte LE compressed-length header.
-// Verified by round-trip against LZS::decompressAllWithHeader before use.
-// ============================================================================
-// Game-compatible FF7 LZS decompressor (Okumura: ring N=4096, r starts at N-F=4078,
-// 0x00 init). Matches the GAME's / vanilla decoder. We need our OWN decoder to VERIFY
-// recompression: ff7tk's LZS::decompressAllWithHeader agrees with ff7tk's compressor
-// (its round-trip always "passes") but ff7tk's compressed output for large fields is
-// NOT what the game decodes — so verifying with ff7tk's decoder is useless. Verifying
-// with this one catches the game-incompatible output. Input includes the 4-byte LE
-// length header.
-static QByteArray ff7LzsDecompress(const QByteArray& blob)
-{
-    if (blob.size() < 4) return QByteArray();
-    const unsigned char* p = reinterpret_cast<const unsigned char*>(blob.constData());
-    quint32 fsize = quint32(p[0]) | (quint32(p[1]) << 8) | (quint32(p[2]) << 16) | (quint32(p[3]) << 24);
-    int n = blob.size() - 4;
-    if (static_cast<int>(fsize) < n) n = static_cast<int>(fsize);
-    const unsigned char* data = p + 4;
-    const int N = 4096;
-    unsigned char tb[4096];
-    memset(tb, 0, sizeof(tb));
-    int r = N - 18;
-    QByteArray out;
-    int i = 0;
-    while (i < n) {
-        unsigned char ctrl = data[i++];
-        for (int b = 0; b < 8 && i < n; ++b) {
-            if (ctrl & 1) {
-                unsigned char c = data[i++];
-                out.append(char(c)); tb[r] = c; r = (r + 1) & (N - 1);
-            } else {
-                if (i + 1 >= n) break;
-                unsigned char b1 = data[i], b2 = data[i + 1]; i += 2;
-                int pos = b1 | ((b2 & 0xF0) << 4);
-                int cnt = (b2 & 0x0F) + 3;
-                for (int k = 0; k < cnt; ++k) {
-                    unsigned char c = tb[(pos + k) & (N - 1)];
-                    out.append(char(c)); tb[r] = c; r = (r + 1) & (N - 1);
-                }
-            }
-            ctrl >>= 1;
-        }
-    }
-    return out;
-}
-
-static QByteArray ff7LzsCompressWithHeader(const QByteArray& in)
+// maxChain caps the hash-chain candidates tried per position (16 << effort,
+// Config::getLzsEffort). Verified by round-trip before use.
+// ============================================================================
+static QByteArray ff7LzsCompressWithHeader(const QByteArray& in, int maxChain = 128)
 {
     const int n = in.size();
     const unsigned char* d = reinterpret_cast<const unsigned char*>(in.constData());
@@ -417,7 +770,7 @@ static QByteArray ff7LzsCompressWithHeader(const QByteArray& in)
             const int maxLen = F < n - p ? F : n - p;
             int cand = head.value(quint32(hv), -1);
             int tries = 0;
-            while (cand >= minPos && tries < 128) {
+            while (cand >= minPos && tries < maxChain) {
                 int l = 0;
                 while (l < maxLen && d[cand + l] == d[p + l]) ++l;
                 if (l > bestLen) { bestLen = l; bestDist = p - cand; if (l == maxLen) break; }
@@ -456,6 +809,78 @@ static QByteArray ff7LzsCompressWithHeader(const QByteArray& in)
     return result;
 }
 
+// ============================================================================
+// decompressField / recompressField  –  LZS for the parallel field pass
+//
+// Both run on the worker threads of randomize()'s field pass. ff7tk's LZS
+// works in static buffers, so its calls are serialised by one mutex;
+// verification and the in-tree encoder run unlocked.
+// ============================================================================
+
+static QMutex& ff7tkLzsMutex()
+{
+    static QMutex mutex;
+    return mutex;
+}
+
+static QByteArray decompressField(const QByteArray& fieldData)
+{
+    QMutexLocker lock(&ff7tkLzsMutex());
+    QByteArray decompressed = LZS::decompressAllWithHeader(fieldData);
+    decompressed.detach();   // own buffer, not LZS's static cache
+    return decompressed;
+}
+
+// ============================================================================
+// recompressField  –  LZS-compress one patched field for flevel.lgp
+//
+// Returns the field with its 4-byte length header, or an empty array when no
+// encoder produced a stream the game decodes back to `decompressed` (the
+// field then ships vanilla). `note` is set to a line for the field log when
+// there is one.
+// ============================================================================
+
+static QByteArray recompressField(const QByteArray& decompressed, int lzsEffort, QString& note)
+{
+    const int lzsChain = 16 << lzsEffort;
+
+    QByteArray recompressed;
+    {
+        QMutexLocker lock(&ff7tkLzsMutex());
+        recompressed = LZS::compressWithHeader(decompressed);
+        recompressed.detach();   // own buffer, not LZS's static cache
+    }
+    if (recompressed.isEmpty()) {
+        note = QStringLiteral("LZS recompression failed!");
+        return QByteArray();
+    }
+    // ff7tk's LZS compressor corrupts some large/complex fields (e.g. convil_2,
+    // the Fort Condor minigame field): its output round-trips through ITS OWN
+    // decoder but the GAME decodes it to garbage and crashes when the post-minigame
+    // cutscene plays. So verify with a GAME-COMPATIBLE decoder (ff7LzsDecompress),
+    // NOT LZS::decompressAllWithHeader (which always agrees with ff7tk's compressor).
+    // If ff7tk's output fails, recompress with our own verified encoder; as a last
+    // resort leave the field VANILLA rather than ship a corrupt one.
+    if (FieldScript::lzsDecompress(recompressed) != decompressed) {
+        QByteArray alt = ff7LzsCompressWithHeader(decompressed, lzsChain);
+        if (!alt.isEmpty() && FieldScript::lzsDecompress(alt) == decompressed) {
+            note = QString("ff7tk LZS game-incompatible — used in-tree encoder (%1 bytes)")
+                       .arg(alt.size());
+            return alt;
+        }
+        note = QStringLiteral("LZS recompress corrupt (both encoders) — left VANILLA");
+        return QByteArray();
+    }
+    if (lzsEffort > Config::LZS_EFFORT_DEFAULT) {
+        // Extra effort: try the in-tree encoder as well, keep the smaller
+        QByteArray alt = ff7LzsCompressWithHeader(decompressed, lzsChain);
+        if (!alt.isEmpty() && alt.size() < recompressed.size()
+            && FieldScript::lzsDecompress(alt) == decompressed)
+            return alt;
+    }
+    return recompressed;
+}
+
 // ============================================================================
 // processFieldFile  –  scan, validate, randomise opcodes in one field
 // ============================================================================
@@ -464,26 +889,55 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
     const QString& fieldName,
     QByteArray& fieldData,
     QTextStream& debugStream,
-    const KeyItemFieldMod* keyItemMod)
+    FieldResult& result,
+    const KeyItemFieldMod* keyItemMod,
+    QTextStream* stitmIndex)
 {
     // Field files in flevel.lgp are LZS-compressed with a 4-byte header.
     if (fieldData.size() < 4) return false;
 
-    QByteArray decompressed = LZS::decompressAllWithHeader(fieldData);
+    QByteArray decompressed = decompressField(fieldData);
     if (decompressed.isEmpty()) {
         debugStream << fieldName << ": LZS decompression failed, skipping\n";
+        result.inputError = QStringLiteral("LZS decompression failed, field left unchanged");
         return false;
     }
 
+    // Archipelago Export names slots after the vanilla opcodes, so the table
+    // is read before anything is patched
+    bool apMode = m_parent && m_parent->m_config.isFeatureEnabled(Config::ArchipelagoIntegration);
+    if (!apMode && m_parent && m_parent->m_config.getArchipelagoExport())
+        result.exportLocations = exportLocations(decompressed, fieldName);
+    QRandomGenerator& rng = result.rng;
+
     int totalMods = 0;
     QVector<OpcodeModification> modifications;
 
+    // Every step below is diffed against the previous checkpoint so each
+    // changed byte range is logged with the step that made it
+    QVector<PatchRecord> fieldPatches;
+    QByteArray checkpoint = decompressed;
+    auto logStep = [&](const char* reason) {
+        if (!m_patchLogEnabled || decompressed == checkpoint)
+            return;
+        appendPatchRecords(fieldName, checkpoint, decompressed, QString::fromLatin1(reason), fieldPatches);
+        checkpoint = decompressed;
+    };
+
     // --- Key item modifications (applied BEFORE STITM scan) -----------------
     // This writes BITON opcodes over STITM locations claimed by key items,
     // so the subsequent STITM scan won't find them (0x82 != 0x58).
     if (keyItemMod) {
         // NOP original BITONs (rewrite as harmless BITON targeting unused var)
         for (int off : keyItemMod->bitonNopOffsets) {
+            // A listed grant becomes a chest unless a key item lands on it
+            bool hosted = false;
+            for (const KeyItemPlacement& p : keyItemMod->placements)
+                hosted = hosted || (p.targetOffset > off - STITM_SIZE && p.targetOffset < off + STITM_SIZE);
+            if (!hosted && convertBitonGrantToChest(decompressed, fieldName, off, rng, debugStream)) {
+                totalMods++;
+                continue;
+            }
             if (off + 3 < decompressed.size()) {
                 decompressed[off]     = static_cast<char>(BITON_OPCODE);
                 decompressed[off + 1] = static_cast<char>(0x30);  // bank 3 dest, bank 0 src
@@ -524,6 +978,12 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
                             << " -> " << p.keyName
                             << (p.targetIsBiton ? " (existing BITON host)\n" : "\n");
                 totalMods++;
+                if (m_parent && m_parent->m_config.getKeyItemSoundCue()) {
+                    if (applyKeyItemSoundCue(decompressed, p.targetOffset, debugStream))
+                        totalMods++;
+                    else
+                        result.uncuedKeyItems << QString("%1 @%2").arg(fieldName).arg(p.targetOffset);
+                }
             }
             modifications.append(
                 OpcodeModification(p.targetOffset,
@@ -532,6 +992,8 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
         }
     }
 
+    logStep("key item placement");
+
     // --- Free Roam MAPJUMP injection (must run before STITM scan) -----------
     bool freeRoam = m_parent && m_parent->m_config.getFreeRoam();
     // Free Roam: NOP SPLIT in the Forgotten Capital inn (losinn). SPLIT positions
@@ -561,6 +1023,8 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
             totalMods++;
     }
 
+    logStep("Free Roam: new-game jump, losinn SPLIT");
+
     // --- Free Roam: suppress the Kalm Traveler gold-chocobo grant ------------
     // In elmin4_2 the 'choko' entity (script 1) awards a Gold Chocobo into the
     // stable when the Desert Rose trade sets its trigger bit (savemap addr 0x57
@@ -577,24 +1041,15 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
     // is added to the stable.
     if (freeRoam && fieldName.toLower() == "elmin4_2") {
         // 'choko' script 1 @ grant start: IFUBL 33 58 59 02 D9 02
-        static const unsigned char kGrantStart[] = {0x15,0x33,0x58,0x59,0x02,0xD9,0x02};
         // cleanup: BITOFF (bank 0xF0) addr 0x57 bit 4  — clears the trigger
-        static const unsigned char kGrantTail[]  = {0x83,0xF0,0x57,0x04};
-        auto findUnique = [&](const unsigned char* pat, int n) -> int {
-            int first = -1;
-            for (int i = 0; i + n <= decompressed.size(); ++i) {
-                bool m = true;
-                for (int k = 0; k < n; ++k)
-                    if (static_cast<quint8>(decompressed[i + k]) != pat[k]) { m = false; break; }
-                if (m) {
-                    if (first >= 0) return -2;   // not unique
-                    first = i;
-                }
-            }
+        auto findUnique = [&](const QByteArray& pat) -> int {
+            const int first = decompressed.indexOf(pat);
+            if (first >= 0 && decompressed.indexOf(pat, first + 1) >= 0)
+                return -2;   // not unique
             return first;
         };
-        int s = findUnique(kGrantStart, sizeof(kGrantStart));
-        int t = findUnique(kGrantTail,  sizeof(kGrantTail));
+        int s = findUnique(helperScript(HelperGoldChocoboGrant));
+        int t = findUnique(helperScript(HelperGoldChocoboCleanup));
         if (s < 0 || t < 0) {
             debugStream << "  GOLD_CHOCO: anchors not found/unique (s=" << s
                         << " t=" << t << ") — skipping\n";
@@ -621,6 +1076,8 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
         }
     }
 
+    logStep("Free Roam: Kalm Traveler gold chocobo");
+
     // --- Free Roam: suppress the Kalm Traveler materia grants ----------------
     // elmin4_2 also hands out materia on trade-in: Guide Book -> Underwater
     // (0x11), Earth Harp -> Master Command (0x30) + Master Summon (0x5A) +
@@ -633,15 +1090,11 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
     // are untouched, so the AP checks still fire. Each grant is uniquely preceded
     // by the lead-in C5 00 50 00 33 01 4A 01 then the SMTRA 5B 00 00 <mat>.
     if (freeRoam && fieldName.toLower() == "elmin4_2") {
-        static const unsigned char kLeadIn[] = {0xC5,0x00,0x50,0x00,0x33,0x01,0x4A,0x01};
+        const QByteArray& kLeadIn = helperScript(HelperKalmMateriaLeadIn);
         static const QSet<quint8> kKalmMateria = {0x11, 0x30, 0x49, 0x5A};
-        const int L = static_cast<int>(sizeof(kLeadIn));
+        const int L = kLeadIn.size();
         int nopped = 0;
-        for (int i = 0; i + L + 7 <= decompressed.size(); ++i) {
-            bool lead = true;
-            for (int k = 0; k < L; ++k)
-                if (static_cast<quint8>(decompressed[i + k]) != kLeadIn[k]) { lead = false; break; }
-            if (!lead) continue;
+        for (int i = 0; (i = decompressed.indexOf(kLeadIn, i)) >= 0 && i + L + 7 <= decompressed.size(); ++i) {
             const int s = i + L;                                   // SMTRA opcode offset
             if (static_cast<quint8>(decompressed[s])     != 0x5B) continue;  // SMTRA
             if (static_cast<quint8>(decompressed[s + 1]) != 0x00) continue;  // bank0 (direct)
@@ -665,6 +1118,8 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
     // (Diamond Weapon is fully hidden in Free Roam — his ambient spawn is
     // neutralized in wm0.ev, so fr_e is never entered and needs no patch.)
 
+    logStep("Free Roam: Kalm Traveler materia");
+
     // --- Free Roam: skip the Fort Condor (convil_2) post-minigame movie --------
     // After the Condor minigame, the "event" cutscene runs PMVIE(set movie #33) ;
     // WAIT 1 ; MOVIE(play). On disc 3 (forced in Free Roam) movie #33 is a "No33"
@@ -673,11 +1128,10 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
     // is untouched. (Movie opcodes are 0xF8 PMVIE / 0xF9 MOVIE — verified by
     // disassembly; the in-tree getOpcodeName labels for 0xD8/0xD9 are inaccurate.)
     if (freeRoam && fieldName.toLower() == "convil_2") {
-        static const QByteArray kCondorMovie = QByteArray::fromHex("f8212401 00f9");
-        int at = decompressed.indexOf(kCondorMovie);
+        int at = decompressed.indexOf(helperScript(HelperCondorMovie));
         if (at < 0) {
             // already patched? (set-movie NOP'd)
-            if (decompressed.indexOf(QByteArray::fromHex("5f5f2401005f")) >= 0)
+            if (decompressed.indexOf(helperScript(HelperCondorMovieNopped)) >= 0)
                 debugStream << "  CONDOR_MOVIE: already patched — skipping\n";
             else
                 debugStream << "  CONDOR_MOVIE: PMVIE/MOVIE anchor not found — skipping\n";
@@ -691,6 +1145,8 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
         }
     }
 
+    logStep("Free Roam: Fort Condor movie");
+
     // --- Free Roam: skip the Icicle Inn (snow) Shinra-blockade cutscene --------
     // man1's contact script asks "It's dangerous, please don't go!" and, if the
     // answer (Var[5][16]) == 1, runs the Elena/Shinra confrontation that seals the
@@ -699,10 +1155,9 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
     // be true (the answer is only ever 1/2, bank-5 temp default 0), making it always
     // take the skip branch. Unique anchor; length-preserving 1-byte edit; idempotent.
     if (freeRoam && fieldName.toLower() == "snow") {
-        static const QByteArray kSnowGate = QByteArray::fromHex("15501001000901"); // IFUBL Var5[16]==1, jmp 0x0109
-        int at = decompressed.indexOf(kSnowGate);
+        int at = decompressed.indexOf(helperScript(HelperSnowShinraGate)); // IFUBL Var5[16]==1, jmp 0x0109
         if (at < 0) {
-            if (decompressed.indexOf(QByteArray::fromHex("155010ff000901")) >= 0)
+            if (decompressed.indexOf(helperScript(HelperSnowShinraGateNopped)) >= 0)
                 debugStream << "  SNOW_SHINRA: already patched — skipping\n";
             else
                 debugStream << "  SNOW_SHINRA: IFUBL Var[5][16]==1 gate not found "
@@ -715,6 +1170,8 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
         }
     }
 
+    logStep("Free Roam: Icicle Inn blockade");
+
     // --- Free Roam: force Kalm to its disc-1 behaviour (music + inn rest) ------
     // Every Kalm field (elm*) gates music AND inn-rest behaviour on
     //   IFSW Var[2][0] (game_moment) > 999   [bytes: 16 20 00 00 e7 03 02 <jmp>]
@@ -738,7 +1195,7 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
             memcpy(&posTexts, decompressed.constData() + sd + 4, 2);
             int hi = sd + static_cast<int>(posTexts);
             if (hi > decompressed.size() || hi <= sd) hi = decompressed.size();
-            static const QByteArray kGate = QByteArray::fromHex("16200000e70302"); // IFSW game_moment>999
+            const QByteArray& kGate = helperScript(HelperKalmDisc1Gate); // IFSW game_moment>999
             int patched = 0;
             int at = sd;
             while ((at = decompressed.indexOf(kGate, at)) >= 0 && at < hi) {
@@ -755,6 +1212,8 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
         }
     }
 
+    logStep("Free Roam: Kalm disc-1 behaviour");
+
     // --- Free Roam: re-gate the Midgar Sector-5 entry walkmesh on the
     // Key-to-Sector-5 POSSESSION bit -----------------------------------------
     // mds5_5 gates its entry triangle on  IFUB Var[15][38] bitOFF 3
@@ -776,7 +1235,7 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
             memcpy(&posTexts, decompressed.constData() + sd + 4, 2);
             int hi = sd + static_cast<int>(posTexts);
             if (hi > decompressed.size() || hi <= sd) hi = decompressed.size();
-            static const QByteArray kGate = QByteArray::fromHex("14f026030a"); // IFUB Var[15][38] bitOFF 3
+            const QByteArray& kGate = helperScript(HelperSector5Gate); // IFUB Var[15][38] bitOFF 3
             int patched = 0;
             int at = sd;
             while ((at = decompressed.indexOf(kGate, at)) >= 0 && at < hi) {
@@ -794,6 +1253,8 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
         }
     }
 
+    logStep("Free Roam: Sector 5 gate");
+
     // --- Free Roam: re-gate the Shinra Mansion basement on the Basement-Key
     // POSSESSION bit ---------------------------------------------------------
     // sininb2 gates basement access on  IFUB Var[1][232] bitOFF 1
@@ -812,7 +1273,7 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
             memcpy(&posTexts, decompressed.constData() + sd + 4, 2);
             int hi = sd + static_cast<int>(posTexts);
             if (hi > decompressed.size() || hi <= sd) hi = decompressed.size();
-            static const QByteArray kGate = QByteArray::fromHex("1410e8010a"); // IFUB Var[1][232] bitOFF 1
+            const QByteArray& kGate = helperScript(HelperBasementGate); // IFUB Var[1][232] bitOFF 1
             int patched = 0;
             int at = sd;
             while ((at = decompressed.indexOf(kGate, at)) >= 0 && at < hi) {
@@ -829,6 +1290,8 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
         }
     }
 
+    logStep("Free Roam: Shinra Mansion basement gate");
+
     // --- Free Roam: re-gate the Icicle slope (snow) snowboard / glacier-map
     // ACCESS checks on the key-item INVENTORY bits ----------------------------
     // snowboard/glacier "obtained" is tracked by the story flags Var[1][130]
@@ -856,8 +1319,8 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
             if (hi > decompressed.size() || hi <= sd) hi = decompressed.size();
             struct SnowRepoint { QByteArray find; char addr; char bit; const char* what; };
             const SnowRepoint reps[] = {
-                { QByteArray::fromHex("1410820109"), static_cast<char>(0x46), 0x02, "Snowboard" },
-                { QByteArray::fromHex("1410820609"), static_cast<char>(0x45), 0x04, "Glacier Map" },
+                { helperScript(HelperSnowboardCheck),  static_cast<char>(0x46), 0x02, "Snowboard" },
+                { helperScript(HelperGlacierMapCheck), static_cast<char>(0x45), 0x04, "Glacier Map" },
             };
             int patched = 0;
             for (const SnowRepoint& r : reps) {
@@ -877,15 +1340,15 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
         }
     }
 
+    logStep("Free Roam: Icicle slope checks");
+
     // --- Free Roam diagnostics (disabled): the Rocket Town soft-lock was traced
     //     to the rckt/rckt2 'cloud' init gating UC(disable control)+MENU2 on
     //     Var[3][130] bit 3 (the first-visit intro flag), now pre-set in the
-    //     md1stin injection above. dumpFieldScripts() is kept for future use.
-    //     To re-enable, dump fields whose lowercased name startsWith("rckt"/"rkt").
+    //     md1stin injection above. dumpFieldScripts() now backs the "scriptDumps"
+    //     debug output (field_script_dump.txt).
 
     // --- Archipelago mode vs. normal randomization -------------------------
-    bool apMode = m_parent && m_parent->m_config.isFeatureEnabled(Config::ArchipelagoIntegration);
-
     // --- STITM (items) ------------------------------------------------------
     // Key item BITONs are already written, so scan won't match those offsets.
     QVector<STITMInfo> stitmCandidates = scanForSTITM(decompressed, fieldName, debugStream);
@@ -895,6 +1358,22 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
     for (int idx = 0; idx < stitmCandidates.size(); ++idx) {
         if (validateSTITM(stitmCandidates[idx]))
             validIndices.append(idx);
+        if (stitmIndex) {
+            const STITMInfo& info = stitmCandidates[idx];
+            *stitmIndex << fieldName << "\tSTITM\t" << info.offset << "\t"
+                        << info.entityIndex << "\t" << info.scriptIndex << "\t"
+                        << getItemName(info.originalItemID) << " x" << info.originalQuantity
+                        << "\t" << (info.isDirectValue ? "yes" : "no") << "\n";
+        }
+    }
+
+    // Switched-off location groups keep their vanilla pickups (Archipelago
+    // locations come from the .apff7, so they're left to it)
+    const bool groupOff = !apMode && isLocationGroupOff(fieldName);
+    if (groupOff && !validIndices.isEmpty()) {
+        debugStream << "  GROUP_OFF " << fieldName << ": " << validIndices.size()
+                    << " pickup(s) kept vanilla (location group disabled)\n";
+        validIndices.clear();
     }
 
     if (apMode) {
@@ -930,6 +1409,7 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
                         decompressed[info.offset + 4] = static_cast<char>(0x5F);
                         totalMods++;
                         debugStream << "  AP_STITM @" << info.offset
+                                    << ownerTag(info.entityIndex, info.scriptIndex)
                                     << "  (md1stin copy parity=" << parity << ") "
                                     << getItemName(info.originalItemID)
                                     << " -> reusing BITON bank=" << ((pb.bankByte >> 4) & 0x0F)
@@ -952,7 +1432,7 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
         bool isMktW    = (fieldName.toLower() == "mkt_w");
 
         if (isMd1stin && validIndices.size() >= 2) {
-            quint16 sharedItems[2] = { getRandomItem(1), getRandomItem(1) };
+            quint16 sharedItems[2] = { getWeightedItem(rng), getWeightedItem(rng) };
             debugStream << "  md1stin special: syncing all entities to items "
                         << getItemName(sharedItems[0]) << " (" << sharedItems[0] << ") and "
                         << getItemName(sharedItems[1]) << " (" << sharedItems[1] << ")\n";
@@ -967,7 +1447,7 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
         } else if (isMktW && validIndices.size() >= 2) {
             QVector<quint16> sharedItems;
             for (int i = 0; i < validIndices.size(); ++i)
-                sharedItems.append(getRandomItem(1));
+                sharedItems.append(getWeightedItem(rng));
             debugStream << "  mkt_w special: syncing all entities to items\n";
             for (int v = 0; v < validIndices.size(); ++v) {
                 STITMInfo& info = stitmCandidates[validIndices[v]];
@@ -977,10 +1457,52 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
                     totalMods++;
                 }
             }
-        } else {
+        } else if (isCraterHandoutField(fieldName)) {
+            // Each split-path handout is scripted once per party member who
+            // may have taken the other path, as the same script slot in each
+            // member's entity. Copies sit at the same offset within that
+            // script, so one draw per (script, offset) keeps a handout a
+            // single location without merging different handouts of the
+            // same vanilla item. An unresolved owner is its own location.
+            QMap<QPair<int, int>, quint16> handoutItems;
             for (int idx : validIndices) {
                 STITMInfo& info = stitmCandidates[idx];
-                quint16 newItemID = getRandomItem(1);
+                const QPair<int, int> key = info.scriptOffset >= 0
+                                                ? qMakePair(info.scriptIndex, info.scriptOffset)
+                                                : qMakePair(-1, info.offset);
+                if (!handoutItems.contains(key)) {
+                    handoutItems[key] = drawFieldItem(fieldName, rng);
+                    debugStream << "  CRATER_HANDOUT " << fieldName << " @" << info.offset
+                                << ownerTag(info.entityIndex, info.scriptIndex) << ": "
+                                << getItemName(info.originalItemID) << " -> "
+                                << getItemName(handoutItems[key]) << "\n";
+                }
+                quint16 newItemID = handoutItems[key];
+                if (applySTITMRandomization(info, decompressed, newItemID, debugStream)) {
+                    modifications.append(OpcodeModification(info.offset, getItemName(newItemID), false));
+                    totalMods++;
+                }
+            }
+        } else {
+            const QSet<int> batterySlots = m_batterySlots.value(fieldName);
+            const QMap<int, quint16> tieredSlots = m_tieredSlots.value(fieldName);
+            for (int v = 0; v < validIndices.size(); ++v) {
+                STITMInfo& info = stitmCandidates[validIndices[v]];
+                quint16 newItemID = batterySlots.contains(v)     ? ITEM_BATTERY
+                                  : tieredSlots.contains(v) ? tieredSlots[v]
+                                                                 : drawFieldItem(fieldName, rng);
+                if (batterySlots.contains(v))
+                    debugStream << "  BATTERY @" << info.offset
+                                << ownerTag(info.entityIndex, info.scriptIndex) << " x"
+                                << info.originalQuantity << " (before the wire climb)\n";
+                else if (tieredSlots.contains(v))
+                    debugStream << "  TIERED @" << info.offset
+                                << ownerTag(info.entityIndex, info.scriptIndex) << "  "
+                                << getItemName(newItemID) << "\n";
+                else if (isCouponExchangeGrant(fieldName, info.originalItemID))
+                    debugStream << "  COUPON_EXCHANGE @" << info.offset
+                                << ownerTag(info.entityIndex, info.scriptIndex) << "  "
+                                << getItemName(info.originalItemID) << " grant\n";
                 if (applySTITMRandomization(info, decompressed, newItemID, debugStream)) {
                     modifications.append(OpcodeModification(info.offset, getItemName(newItemID), false));
                     totalMods++;
@@ -989,15 +1511,66 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
         }
     }
 
+    logStep("item pickups");
+
     // --- SMTRA (materia) ----------------------------------------------------
     QVector<SMTRAInfo> smtraCandidates = scanForSMTRA(decompressed, fieldName, debugStream);
+    if (stitmIndex) {
+        for (const SMTRAInfo& info : smtraCandidates) {
+            *stitmIndex << fieldName << "\tSMTRA\t" << info.offset << "\t"
+                        << info.entityIndex << "\t" << info.scriptIndex << "\t"
+                        << getMateriaName(info.originalMateriaID)
+                        << "\t" << (info.isDirectValue ? "yes" : "no") << "\n";
+        }
+    }
+    const bool chocoboField = isChocoboFarmField(fieldName);
+    const bool chocoboChecks = m_parent && m_parent->m_config.getChocoboChecks();
+    const bool caveChecks = m_parent && m_parent->m_config.getMateriaCaveChecks();
     for (SMTRAInfo& info : smtraCandidates) {
-        if (!validateSMTRA(info)) continue;
+        if (!validateSMTRA(info) || groupOff) continue;
+        const QString caveChocobo = materiaCaveChocobo(info.originalMateriaID);
         if (apMode) {
             if (applySMTRAAsArchipelago(info, decompressed, fieldName, debugStream))
                 totalMods++;
+        } else if (!caveChocobo.isEmpty()) {
+            if (!caveChecks) {
+                debugStream << "  MATERIA_CAVE @" << info.offset
+                            << ownerTag(info.entityIndex, info.scriptIndex) << "  "
+                            << getMateriaName(info.originalMateriaID)
+                            << " kept vanilla (materia cave checks disabled)\n";
+                continue;
+            }
+            // Only a chocobo reaches the cave, so it can't hold the Lure that
+            // catches one
+            quint8 newMateriaID = getRandomMateria(rng);
+            while (newMateriaID == MATERIA_CHOCOBO_LURE && m_materiaPool.size() > 1)
+                newMateriaID = getRandomMateria(rng);
+            debugStream << "  MATERIA_CAVE_CHECK " << fieldName << " @" << info.offset
+                        << ownerTag(info.entityIndex, info.scriptIndex)
+                        << " (needs " << caveChocobo << " chocobo)\n";
+            if (applySMTRARandomization(info, decompressed, newMateriaID, debugStream)) {
+                modifications.append(OpcodeModification(info.offset, getMateriaName(newMateriaID), true));
+                totalMods++;
+            }
+        } else if (chocoboField && !chocoboChecks) {
+            debugStream << "  CHOCOBO @" << info.offset
+                        << ownerTag(info.entityIndex, info.scriptIndex) << "  "
+                        << getMateriaName(info.originalMateriaID)
+                        << " kept vanilla (chocobo checks disabled)\n";
+        } else if (chocoboField && m_chocoboLureRequired
+                   && info.originalMateriaID == MATERIA_CHOCOBO_LURE) {
+            debugStream << "  CHOCOBO @" << info.offset
+                        << ownerTag(info.entityIndex, info.scriptIndex)
+                        << "  Chocobo Lure kept (logic needs a chocobo crossing)\n";
         } else {
-            quint8 newMateriaID = getRandomMateria();
+            if (chocoboField)
+                debugStream << "  CHOCOBO_CHECK " << fieldName << " @" << info.offset
+                            << ownerTag(info.entityIndex, info.scriptIndex) << "\n";
+            if (isCouponExchangeMateria(fieldName, info.originalMateriaID))
+                debugStream << "  COUPON_EXCHANGE @" << info.offset
+                            << ownerTag(info.entityIndex, info.scriptIndex) << "  "
+                            << getMateriaName(info.originalMateriaID) << " grant\n";
+            quint8 newMateriaID = getRandomMateria(rng);
             if (applySMTRARandomization(info, decompressed, newMateriaID, debugStream)) {
                 modifications.append(OpcodeModification(info.offset, getMateriaName(newMateriaID), true));
                 totalMods++;
@@ -1005,6 +1578,28 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
         }
     }
 
+    logStep("materia pickups");
+
+    // --- GOLDU (gil pickups) -----------------------------------------------
+    // Economy balancing raises gil found in the field when randomized enemy
+    // drops pay less than vanilla in this field's progression tier. Amounts
+    // stay round; the pickup message keeps its vanilla text.
+    if (m_parent && m_parent->m_config.getBalanceEconomy()) {
+        const int tier = EconomyBalancer::fieldTier(fieldName);
+        const double factor = m_parent->economy().pickupFactor(tier);
+        if (factor != 1.0) {
+            for (const EconomyBalancer::GilPickup& p : EconomyBalancer::scanGilPickups(decompressed)) {
+                quint32 amount = static_cast<quint32>(std::lround(p.amount * factor / 10.0)) * 10;
+                memcpy(decompressed.data() + p.offset + 2, &amount, 4);
+                debugStream << "  GIL @" << p.offset << "  " << p.amount << " -> " << amount
+                            << " (tier " << tier << ")\n";
+                totalMods++;
+            }
+        }
+    }
+
+    logStep("gil pickups");
+
     // --- Vanilla BITON replacement for Key Items in AP mode -----------------
     if (apMode) {
         int vanillaMods = replaceVanillaBitonsForAP(decompressed, fieldName, debugStream);
@@ -1013,6 +1608,8 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
         }
     }
 
+    logStep("AP key item BITONs");
+
     // --- mktpb old-man visibility patch (AP mode only) ----------------------
     // Vanilla mktpb init runs:
     //   Var[5][16] = 0
@@ -1061,47 +1658,95 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
         }
     }
 
+    logStep("AP mktpb old man");
+
+    // --- Midgar Parts turn-in count --------------------------------------------
+    if (m_parent && fieldName.startsWith("blin65")) {
+        const int required = m_parent->m_config.getMidgarPartsRequired();
+        if (required < Config::MIDGAR_PARTS_COUNT) {
+            const int patched = patchMidgarPartsCheck(decompressed, fieldName, required, debugStream);
+            result.midgarPartsChecksPatched += patched;
+            totalMods += patched;
+        }
+    }
+
+    logStep("Midgar Parts count");
+
+    // --- Huge Materia count for Bugenhagen's observatory ------------------------
+    // The observatory is in Cosmo Canyon (cos*); the patch only acts on
+    // scripts that write the Huge Materia bits
+    if (m_parent && fieldName.startsWith("cos")) {
+        const int required = m_parent->m_config.getHugeMateriaRequired();
+        if (required < Config::HUGE_MATERIA_COUNT) {
+            const int patched = patchHugeMateriaCheck(decompressed, fieldName, required, debugStream);
+            result.hugeMateriaChecksPatched += patched;
+            totalMods += patched;
+        }
+    }
+
+    logStep("Huge Materia count");
+
+    // --- progressive keycards ---------------------------------------------------
+    if (m_parent && m_parent->m_config.getProgressiveKeycards()
+        && !m_parent->m_config.isFeatureEnabled(Config::ArchipelagoIntegration))
+        totalMods += patchProgressiveKeycards(decompressed, fieldName, debugStream);
+
+    logStep("progressive keycards");
+
+    // --- model scale gag (cosmetic) -----------------------------------------
+    if (m_parent && m_parent->m_config.getModelScaleGag()
+        && scaleFieldModels(decompressed, fieldName, rng, debugStream))
+        totalMods++;
+
+    logStep("model scale");
+
+    // --- field encounter tables ---------------------------------------------
+    if (m_parent && (m_parent->m_config.getFieldEncounterMode() != Config::FieldEncountersVanilla
+                     || m_parent->m_config.getRandomizeFieldEncounterRates())
+        && randomizeFieldEncounters(decompressed, fieldName, rng, debugStream))
+        totalMods++;
+
+    logStep("field encounters");
+
+    // --- scripted battles -----------------------------------------------------
+    if (m_parent && m_parent->m_config.getRandomizeScriptedBattles())
+        totalMods += randomizeScriptedBattles(decompressed, fieldName, rng, debugStream);
+
+    logStep("scripted battles");
+
     // --- update dialog texts to reflect randomized pickups ------------------
+    // A modded text layout would be rebuilt wrongly, so those fields keep
+    // their vanilla "Received" texts. Silent pickups never rebuild it, so
+    // they apply there too.
+    const bool silentPickups = m_parent && m_parent->m_config.getSilentPickups();
+    const QString layoutIssue = fieldLayoutIssue(decompressed);
+    if (!layoutIssue.isEmpty()) {
+        result.layoutIssue = layoutIssue;
+        if (!modifications.isEmpty() && !silentPickups)
+            debugStream << "  MODDED: " << layoutIssue << " – pickup texts left unchanged\n";
+    }
     // Sort by offset so the closest-MESSAGE search assigns correctly
-    if (!modifications.isEmpty()) {
+    if (!modifications.isEmpty() && (layoutIssue.isEmpty() || silentPickups)) {
         std::sort(modifications.begin(), modifications.end(),
                   [](const OpcodeModification& a, const OpcodeModification& b) {
                       return a.opcodeOffset < b.opcodeOffset;
                   });
-        updateFieldTexts(decompressed, modifications, debugStream);
+        updateFieldTexts(decompressed, modifications, debugStream, silentPickups);
     }
 
-    // --- recompress if anything changed -------------------------------------
+    logStep("pickup texts");
+
+    // --- hand the patched field back for recompression ------------------------
+    // The caller runs recompressField() next, so fieldData now holds the
+    // decompressed field
     if (totalMods > 0) {
-        QByteArray recompressed = LZS::compressWithHeader(decompressed);
-        recompressed.detach();   // own buffer, not LZS's static cache
-        if (recompressed.isEmpty()) {
-            debugStream << fieldName << ": LZS recompression failed!\n";
-            return false;
-        }
-        // ff7tk's LZS compressor corrupts some large/complex fields (e.g. convil_2,
-        // the Fort Condor minigame field): its output round-trips through ITS OWN
-        // decoder but the GAME decodes it to garbage and crashes when the post-minigame
-        // cutscene plays. So verify with a GAME-COMPATIBLE decoder (ff7LzsDecompress),
-        // NOT LZS::decompressAllWithHeader (which always agrees with ff7tk's compressor).
-        // If ff7tk's output fails, recompress with our own verified encoder; as a last
-        // resort leave the field VANILLA rather than ship a corrupt one.
-        if (ff7LzsDecompress(recompressed) != decompressed) {
-            QByteArray alt = ff7LzsCompressWithHeader(decompressed);
-            if (!alt.isEmpty() && ff7LzsDecompress(alt) == decompressed) {
-                recompressed = alt;
-                debugStream << "  " << fieldName
-                            << ": ff7tk LZS game-incompatible — used in-tree encoder ("
-                            << recompressed.size() << " bytes)\n";
-            } else {
-                debugStream << "  " << fieldName
-                            << ": LZS recompress corrupt (both encoders) — left VANILLA\n";
-                return false;   // keep original fieldData (caller writes it unchanged)
-            }
-        }
-        fieldData = recompressed;
+        fieldData = decompressed;
         debugStream << "  >> " << fieldName << ": modified "
                     << totalMods << " opcode(s)\n\n";
+        if (!apMode)
+            recordTrackerEntries(decompressed, fieldName, modifications, keyItemMod,
+                                 result.trackerEntries);
+        result.patchRecords = fieldPatches;
     }
     return totalMods > 0;
 }
@@ -1130,76 +1775,6 @@ bool FieldPickupRandomizer_ff7tk::processFieldFile(
 // ignored for wm* dummy fields.
 // ============================================================================
 
-// Returns the total byte length (including the opcode byte) of the FF7 field
-// script opcode at `pos`, or -1 if the opcode is invalid/unknown or would run
-// past the end of the buffer. Operand counts are from the standard FF7 opcode
-// table (cf. PyFF7 / Makou Reactor). SPECIAL (0x0F) and KAWAI (0x28) are
-// variable length and handled explicitly.
-static int fieldOpcodeLength(const QByteArray& d, int pos, int fileSize)
-{
-    // Operand byte counts (excluding the 1-byte opcode). -1 = invalid opcode.
-    static const int kOperands[256] = {
-        /*00*/  0, 2, 2, 2, 2, 2, 2, 1,  1,14, 5, 5,-1,-1, 1, 0,
-        /*10*/  1, 2, 1, 2, 5, 6, 7, 8,  7, 8,-1,-1,-1,-1,-1,-1,
-        /*20*/ 10, 1, 4, 2, 2, 8, 1, 1,  0, 0, 1, 1, 4, 6, 1, 9,
-        /*30*/  3, 3, 3, 1, 1, 3, 4, 7,  5, 5, 5, 3, 0, 0, 0, 0,
-        /*40*/  2, 4, 5, 1,-1, 4,-1, 4,  6, 3, 1, 1,-1, 4,-1, 4,
-        /*50*/  9, 5, 3, 1, 1, 2, 6, 6,  4, 4, 4, 6, 7, 9, 7, 0,
-        /*60*/  9, 1, 4, 5, 5, 0, 8, 0,  8, 1, 6, 8, 0, 3, 2, 5,
-        /*70*/  3, 1, 2, 3, 3, 7, 3, 4,  3, 4, 2, 2, 2, 2, 1, 2,
-        /*80*/  3, 4, 3, 3, 3, 3, 4, 3,  4, 3, 4, 3, 4, 3, 4, 3,
-        /*90*/  4, 3, 4, 3, 4, 2, 2, 2,  2, 2, 3, 4, 5, 6, 6,10,
-        /*a0*/  1, 1, 2, 2, 1,10, 8, 8,  5, 5, 1, 3, 0, 5, 2, 2,
-        /*b0*/  4, 4, 3, 2, 5, 5, 1, 3,  4, 3, 2, 4, 4, 3,-1, 1,
-        /*c0*/ 10, 7,14,11, 0, 2, 2, 1,  1, 1, 3, 2, 2, 2, 1, 1,
-        /*d0*/ 12, 1, 1,15, 9, 9, 3, 3,  2, 0,14, 1, 3, 0, 0,10,
-        /*e0*/  3, 3, 2, 2, 2, 4, 4, 4,  6, 9, 9, 4, 4, 7, 7,10,
-        /*f0*/  1, 4,13, 1, 1, 1, 1, 3,  1, 0, 2, 1, 1, 5, 2, 0,
-    };
-
-    if (pos < 0 || pos >= fileSize)
-        return -1;
-    quint8 op = static_cast<quint8>(d.at(pos));
-
-    if (op == 0x0F) {  // SPECIAL: 2-byte header (0x0F + sub) + sub operands
-        if (pos + 1 >= fileSize)
-            return -1;
-        quint8 sub = static_cast<quint8>(d.at(pos + 1));
-        int subOps;
-        switch (sub) {
-            case 0xF5: subOps = 1; break;  // arrow
-            case 0xF6: subOps = 4; break;  // pname
-            case 0xF7: subOps = 2; break;  // gmspd
-            case 0xF8: subOps = 2; break;  // smspd
-            case 0xF9: subOps = 0; break;  // flmat
-            case 0xFA: subOps = 0; break;  // flitm
-            case 0xFB: subOps = 1; break;  // btlck
-            case 0xFC: subOps = 1; break;  // mvlck
-            case 0xFD: subOps = 2; break;  // spcnm
-            case 0xFE: subOps = 0; break;  // rsglb
-            case 0xFF: subOps = 0; break;  // clitm
-            default:   return -1;
-        }
-        int len = 2 + subOps;
-        return (pos + len <= fileSize) ? len : -1;
-    }
-
-    if (op == 0x28) {  // KAWAI: total length is encoded in the second byte
-        if (pos + 1 >= fileSize)
-            return -1;
-        int len = static_cast<quint8>(d.at(pos + 1));
-        if (len < 2)
-            return -1;
-        return (pos + len <= fileSize) ? len : -1;
-    }
-
-    int ops = kOperands[op];
-    if (ops < 0)
-        return -1;
-    int len = 1 + ops;
-    return (pos + len <= fileSize) ? len : -1;
-}
-
 // NOP every real PMVIE (0xF8, set movie) and MOVIE (0xF9, play movie) opcode in
 // a field's section-0 entity scripts. Walks each entity's 32 script entry points
 // with fieldOpcodeLength so operand bytes (and false 0xF8/0xF9 inside the offset
@@ -1213,37 +1788,16 @@ static int fieldOpcodeLength(const QByteArray& d, int pos, int fileSize)
 static int nopFieldScriptMovies(QByteArray& d, const QString& fieldName, QTextStream& dbg)
 {
     const int fileSize = d.size();
-    const int HEADER_SIZE = 6 + 9 * 4;
-    if (fileSize < HEADER_SIZE) return 0;
-
-    quint32 sectionPositions[9];
-    memcpy(sectionPositions, d.constData() + 6, 9 * 4);
-    int sec0DataStart = static_cast<int>(sectionPositions[0]) + 4;
-    if (sec0DataStart + 32 > fileSize) return 0;
-
-    quint8  nbEntities    = static_cast<quint8>(d.at(sec0DataStart + 2));
-    quint16 wStringOffset = 0, nAkaoOffsets = 0;
-    memcpy(&wStringOffset, d.constData() + sec0DataStart + 4, 2);
-    memcpy(&nAkaoOffsets,  d.constData() + sec0DataStart + 6, 2);
-    if (nbEntities == 0) return 0;
-
-    int namesStart       = sec0DataStart + 32;
-    int akaoTableStart   = namesStart + 8 * static_cast<int>(nbEntities);
-    int offsetTableStart = akaoTableStart + 4 * static_cast<int>(nAkaoOffsets);
-    if (offsetTableStart + 64 * static_cast<int>(nbEntities) > fileSize) return 0;
-
-    int walkEnd = sec0DataStart + static_cast<int>(wStringOffset);
-    if (nAkaoOffsets > 0 && akaoTableStart + 4 <= fileSize) {
-        quint32 firstAkao = 0;
-        memcpy(&firstAkao, d.constData() + akaoTableStart, 4);
-        int akaoAbs = sec0DataStart + static_cast<int>(firstAkao);
-        if (akaoAbs > offsetTableStart && akaoAbs < walkEnd) walkEnd = akaoAbs;
-    }
-    if (walkEnd > fileSize || walkEnd <= offsetTableStart) walkEnd = fileSize;
+    FieldScript::ScriptRange range;
+    if (!FieldScript::scriptRange(d, range)) return 0;
+    const int sec0DataStart    = range.dataStart;
+    const int nbEntities       = range.entityCount;
+    const int offsetTableStart = range.offsetTable;
+    const int walkEnd          = range.walkEnd;
 
     int nopped = 0;
     QSet<quint16> seen;
-    for (int e = 0; e < static_cast<int>(nbEntities); ++e) {
+    for (int e = 0; e < nbEntities; ++e) {
         int tbl = offsetTableStart + 64 * e;
         quint16 slot[32];
         memcpy(slot, d.constData() + tbl, 64);
@@ -1254,7 +1808,7 @@ static int nopFieldScriptMovies(QByteArray& d, const QString& fieldName, QTextSt
             int guard = 0;
             while (pos >= 0 && pos < walkEnd && guard++ < 4000) {
                 quint8 op = static_cast<quint8>(d.at(pos));
-                int len = fieldOpcodeLength(d, pos, fileSize);
+                int len = FieldScript::opcodeLength(d, pos, fileSize);
                 if (len <= 0) break;
                 if (op == 0xF8 && pos + 1 < fileSize) {        // PMVIE (set movie)
                     d[pos]     = static_cast<char>(0x5F);
@@ -1292,25 +1846,10 @@ static int nopFieldScriptMovies(QByteArray& d, const QString& fieldName, QTextSt
 static int nopFieldScriptSplits(QByteArray& d, const QString& fieldName, QTextStream& dbg)
 {
     const int fileSize = d.size();
-    const int HEADER_SIZE = 6 + 9 * 4;
-    if (fileSize < HEADER_SIZE) return 0;
-    quint32 sp[9]; memcpy(sp, d.constData() + 6, 36);
-    int sd = static_cast<int>(sp[0]) + 4;
-    if (sd + 32 > fileSize) return 0;
-    quint8 nb = static_cast<quint8>(d.at(sd + 2));
-    quint16 wstr = 0, nak = 0;
-    memcpy(&wstr, d.constData() + sd + 4, 2);
-    memcpy(&nak,  d.constData() + sd + 6, 2);
-    if (nb == 0) return 0;
-    int names = sd + 32, akao = names + 8 * nb, offt = akao + 4 * nak;
-    if (offt + 64 * nb > fileSize) return 0;
-    int walkEnd = sd + static_cast<int>(wstr);
-    if (nak > 0 && akao + 4 <= fileSize) {
-        quint32 fa = 0; memcpy(&fa, d.constData() + akao, 4);
-        int aa = sd + static_cast<int>(fa);
-        if (aa > offt && aa < walkEnd) walkEnd = aa;
-    }
-    if (walkEnd > fileSize || walkEnd <= offt) walkEnd = fileSize;
+    FieldScript::ScriptRange range;
+    if (!FieldScript::scriptRange(d, range)) return 0;
+    const int sd = range.dataStart, nb = range.entityCount;
+    const int names = range.namesStart, offt = range.offsetTable, walkEnd = range.walkEnd;
 
     int nopped = 0;
     QSet<quint16> seen;
@@ -1322,7 +1861,7 @@ static int nopFieldScriptSplits(QByteArray& d, const QString& fieldName, QTextSt
             int pos = sd + static_cast<int>(slot[s]), g = 0;
             while (pos < walkEnd && g++ < 4000) {
                 quint8 op = static_cast<quint8>(d.at(pos));
-                int len = fieldOpcodeLength(d, pos, fileSize);
+                int len = FieldScript::opcodeLength(d, pos, fileSize);
                 if (len <= 0) break;
                 if (op == 0x09) {                       // SPLIT -> NOP all bytes
                     for (int k = 0; k < len; ++k) d[pos + k] = static_cast<char>(0x5F);
@@ -1354,25 +1893,10 @@ static int neuterInnGoScript(QByteArray& d, const QString& fieldName,
                              QTextStream& dbg)
 {
     const int fileSize = d.size();
-    const int HEADER_SIZE = 6 + 9 * 4;
-    if (fileSize < HEADER_SIZE) return 0;
-    quint32 sp[9]; memcpy(sp, d.constData() + 6, 36);
-    int sd = static_cast<int>(sp[0]) + 4;
-    if (sd + 32 > fileSize) return 0;
-    quint8 nb = static_cast<quint8>(d.at(sd + 2));
-    quint16 wstr = 0, nak = 0;
-    memcpy(&wstr, d.constData() + sd + 4, 2);
-    memcpy(&nak,  d.constData() + sd + 6, 2);
-    if (nb == 0) return 0;
-    int names = sd + 32, akao = names + 8 * nb, offt = akao + 4 * nak;
-    if (offt + 64 * nb > fileSize) return 0;
-    int walkEnd = sd + static_cast<int>(wstr);
-    if (nak > 0 && akao + 4 <= fileSize) {
-        quint32 fa = 0; memcpy(&fa, d.constData() + akao, 4);
-        int aa = sd + static_cast<int>(fa);
-        if (aa > offt && aa < walkEnd) walkEnd = aa;
-    }
-    if (walkEnd > fileSize || walkEnd <= offt) walkEnd = fileSize;
+    FieldScript::ScriptRange range;
+    if (!FieldScript::scriptRange(d, range)) return 0;
+    const int sd = range.dataStart, nb = range.entityCount;
+    const int names = range.namesStart, offt = range.offsetTable, walkEnd = range.walkEnd;
 
     // Find the entity by its (NUL-padded, 8-byte) name.
     int entIdx = -1;
@@ -1401,7 +1925,7 @@ static int neuterInnGoScript(QByteArray& d, const QString& fieldName,
         int pos = sd + static_cast<int>(slot[s]), g = 0;
         while (pos >= 0 && pos < walkEnd && g++ < 4000) {
             quint8 op = static_cast<quint8>(d.at(pos));
-            int len = fieldOpcodeLength(d, pos, fileSize);
+            int len = FieldScript::opcodeLength(d, pos, fileSize);
             if (len <= 0) break;
             if (op == 0x00) break;                   // RET: end of this script
             if (op == 0x82 && pos + 3 < fileSize &&
@@ -1425,7 +1949,7 @@ static int neuterInnGoScript(QByteArray& d, const QString& fieldName,
     int pos = targetStart, g = 0, nopped = 0, kept = 0;
     while (pos >= 0 && pos < walkEnd && g++ < 4000) {
         quint8 op = static_cast<quint8>(d.at(pos));
-        int len = fieldOpcodeLength(d, pos, fileSize);
+        int len = FieldScript::opcodeLength(d, pos, fileSize);
         if (len <= 0) break;
         if (op == 0x00) break;                       // RET: keep, end of script
         if (op == 0x82) { ++kept; pos += len; continue; }  // BITON: keep the var line
@@ -1439,50 +1963,220 @@ static int neuterInnGoScript(QByteArray& d, const QString& fieldName,
     return nopped;
 }
 
-bool FieldPickupRandomizer_ff7tk::injectFreeRoamMapJump(
-    QByteArray& decompressed,
-    const QString& fieldName,
-    QTextStream& debugStream)
-{
-    debugStream << "  MAPJUMP_DBG: enter, fileSize=" << decompressed.size() << "\n";
-    const int fileSize = decompressed.size();
-    const int HEADER_SIZE = 6 + 9 * 4;
-    if (fileSize < HEADER_SIZE) {
-        debugStream << "  MAPJUMP_DBG: too small\n";
-        return false;
-    }
-
-    quint32 sectionPositions[9];
-    memcpy(sectionPositions, decompressed.constData() + 6, 9 * 4);
-
-    quint32 sec0 = sectionPositions[0];
-    debugStream << "  MAPJUMP_DBG: sec0=" << sec0 << "\n";
-    if (sec0 + 4 >= static_cast<quint32>(fileSize)) {
-        debugStream << "  MAPJUMP_DBG: sec0 out of range\n";
-        return false;
-    }
+// ============================================================================
+// Free Roam new-game sequence
+//
+// What injectFreeRoamMapJump() writes into md1stin after the opening PRTYE.
+// Built once (freeRoamStartSequence) and checked with the helper scripts at
+// startup (validateHelperScripts).
+// ============================================================================
 
-    int sec0DataStart = static_cast<int>(sec0) + 4;
-    debugStream << "  MAPJUMP_DBG: sec0DataStart=" << sec0DataStart << "\n";
-    if (sec0DataStart + 8 > fileSize) {
-        debugStream << "  MAPJUMP_DBG: sec0DataStart+8 out of range\n";
-        return false;
-    }
+namespace {
+// Injected sequence (after PRTYE, before transferring to the world map).
+// The skipped Midgar intro normally performs all of this setup; on a NEW
+// GAME none of it happens, so we replicate the essentials here:
+//   MENU 6        - Cloud name-entry screen. Sets Cloud's name (default
+//                   "Cloud" instead of the kernel placeholder "EX-SOLDIER")
+//                   and initialises the party's average level.
+//   SETWORD x2    - menu visibility = 0x03FF (all standard commands shown,
+//                   incl. Materia = bit 2) and locking = 0x0000 (none).
+//   SETBYTE       - Kalm conversation flags = 0x03 (NPC-spoken bits) to
+//                   avoid the Kalm progression lock.
+//   SETWORD       - game moment = 1603.
+//   MAPJUMP + RET - transfer to wm1 and halt the script cleanly.
+//
+// Field memory banks (cf. FF7 savemap): bank 1 maps to savemap 0x0BA4.
+//   8-bit bank id 0x1 / 16-bit bank id 0x2.
+//   game moment        = Var[2][0]   (savemap 0x0BA4)
+//   menu visibility    = Var[2][0x1C](savemap 0x0BC0)
+//   menu locking       = Var[2][0x1E](savemap 0x0BC2)
+//   Kalm conv. flags   = Var[1][0x80](savemap 0x0C24)
+// SET* bank byte = (Dest<<4)|Source; Source 0 = write literal value V.
+constexpr quint8  kMenuBank16    = 0x20;   // dest 16-bit bank 1, literal src
+constexpr quint8  kMenuBank8     = 0x10;   // dest 8-bit  bank 1, literal src
+constexpr quint16 kGameMoment    = 1997;
+constexpr quint16 kMenuVisible   = 0x03FF; // Item..Save all visible
+constexpr quint16 kMenuLocking   = 0x0000; // nothing locked
+constexpr quint8  kKalmFlagsAddr = 0x80;   // Var[1][128]
+constexpr quint8  kKalmFlags     = 0x03;   // bits 0 + 1
+// NOTE: the current disc (savemap 0x0EA4) is NOT field-settable — fields change
+// it via the DSKCG opcode (engine-handled), never a direct SETBYTE. Free Roam's
+// "disc 3" is forced by the client writing 0x0EA4 instead (see FF7Client.py).
+
+// BITON Var[3][128] bit 1 — marks the psdun_2 (Mythril Mines) line-trigger
+// party-split event as "already played" so Free Roam doesn't fire it and
+// boot the player back. bank 3 = 8-bit half of the 2nd bank pair; the engine
+// resolves the savemap offset, so we only encode the bank nibble here.
+//   banks byte = (addrBank 3 << 4) | (bitSrc 0 = literal) = 0x30
+constexpr quint8  kBitOnBanks    = 0x30;   // addr bank 3, literal bit
+constexpr quint8  kFreeRoamFlagAddr = 0x80; // Var[3][128]
+constexpr quint8  kFreeRoamFlagBit  = 0x01; // "bitON 1" = bit index 1
+
+// BITON Var[3][130] bit 3 — marks the Rocket Town first-visit intro as
+// already played. The rckt/rckt2 'cloud' init runs
+//   IFUB Var[3][130] bitOFF 3 -> UC(01) [disable control] + MENU2(01)
+// expecting the intro cutscene to re-enable control. On a moment-1603 Free
+// Roam the bit is OFF and that cutscene never fires, soft-locking the player
+// on entry. Setting the bit makes the IFUB take the skip branch.
+constexpr quint8  kRocketFlagAddr = 0x82; // Var[3][130]
+constexpr quint8  kRocketFlagBit  = 0x03; // "bitON 3" = bit index 3
+
+// MAPJUMP to wm1 (field ID 2 = outside Kalm).
+// X/Y/triangle/direction are ignored by the WM engine for wm* dummy fields.
+constexpr quint16 kFieldId  = 2;   // wm1 = Outside Kalm
+constexpr qint16  kSpawnX   = 0;
+constexpr qint16  kSpawnY   = 0;
+constexpr quint16 kTriangle = 0;
+constexpr quint8  kDir      = 0;
+
+QByteArray buildFreeRoamStartSequence()
+{
+    // NOTE: intro music (MUSIC opcode) + a welcome MESSAGE were reverted — they
+    // crashed the field right after the intro movie. The field MUSIC index and a
+    // blocking MESSAGE in this early (pre-interactive) script context are not safe
+    // here without in-game verification (md1stin has only 2 akao entries, so a bad
+    // MUSIC index hard-crashes). overwriteFieldDialog() is kept below for a future,
+    // tested re-add. This is the proven new-game -> world-map injection.
+    auto put16 = [](QByteArray& b, quint16 v) {
+        b.append(static_cast<char>(v & 0xFF));
+        b.append(static_cast<char>((v >> 8) & 0xFF));
+    };
 
-    // FF7SCRIPTHEADER layout (all offsets from sec0DataStart):
-    //   +0  u16 unknown1
-    //   +2  u8  nEntities
-    //   +3  u8  nModels
-    //   +4  u16 wStringOffset
-    //   +6  u16 nAkaoOffsets
-    //   +8..+31 scale + blanks + creator + name  (24 bytes)
-    //   = 32 bytes fixed header
-    //   then: szEntities[nEntities][8]
-    //   then: dwAkaoOffsets[nAkaoOffsets] (u32 each = 4 bytes)
-    //   then: vEntityScripts[nEntities][32] (u16 each)
-    if (sec0DataStart + 8 > fileSize) return false;
-    quint8  nbEntities   = static_cast<quint8>(decompressed.at(sec0DataStart + 2));
-    quint16 nAkaoOffsets = 0;
+    QByteArray seq;
+    // MENU 6 — name entry for Cloud (char id 0); B=0 (literal), T=6, P=0
+    seq.append(static_cast<char>(0x49));
+    seq.append(static_cast<char>(0x00));
+    seq.append(static_cast<char>(0x06));
+    seq.append(static_cast<char>(0x00));
+    // SETWORD menu visibility (Var[2][0x1C]) = 0x03FF
+    seq.append(static_cast<char>(0x81)); seq.append(static_cast<char>(kMenuBank16));
+    seq.append(static_cast<char>(0x1C)); put16(seq, kMenuVisible);
+    // SETWORD menu locking (Var[2][0x1E]) = 0x0000
+    seq.append(static_cast<char>(0x81)); seq.append(static_cast<char>(kMenuBank16));
+    seq.append(static_cast<char>(0x1E)); put16(seq, kMenuLocking);
+    // SETBYTE Kalm conversation flags (Var[1][0x80]) = 0x03
+    seq.append(static_cast<char>(0x80)); seq.append(static_cast<char>(kMenuBank8));
+    seq.append(static_cast<char>(kKalmFlagsAddr)); seq.append(static_cast<char>(kKalmFlags));
+    // SETWORD game moment (Var[2][0]) = 1997
+    seq.append(static_cast<char>(0x81)); seq.append(static_cast<char>(kMenuBank16));
+    seq.append(static_cast<char>(0x00)); put16(seq, kGameMoment);
+    // BITON Var[3][128] bit 1 — skip psdun_2 (Mythril Mines) party-split trigger
+    seq.append(static_cast<char>(0x82)); seq.append(static_cast<char>(kBitOnBanks));
+    seq.append(static_cast<char>(kFreeRoamFlagAddr)); seq.append(static_cast<char>(kFreeRoamFlagBit));
+    // BITON Var[3][130] bit 3 — skip Rocket Town (rckt/rckt2) entry soft-lock
+    seq.append(static_cast<char>(0x82)); seq.append(static_cast<char>(kBitOnBanks));
+    seq.append(static_cast<char>(kRocketFlagAddr)); seq.append(static_cast<char>(kRocketFlagBit));
+    // MAPJUMP wm1
+    seq.append(static_cast<char>(0x60));
+    put16(seq, kFieldId);
+    put16(seq, static_cast<quint16>(kSpawnX)); put16(seq, static_cast<quint16>(kSpawnY));
+    put16(seq, kTriangle);
+    seq.append(static_cast<char>(kDir));
+    // RET — halt script cleanly after queuing the jump
+    seq.append(static_cast<char>(0x00));
+    return seq;
+}
+
+const QByteArray& freeRoamStartSequence()
+{
+    static const QByteArray seq = buildFreeRoamStartSequence();
+    return seq;
+}
+
+bool isHexText(const QByteArray& hex)
+{
+    if (hex.isEmpty() || hex.size() % 2 != 0)
+        return false;
+    for (char c : hex) {
+        if (!((c >= '0' && c <= '9') || (c >= 'a' && c <= 'f') || (c >= 'A' && c <= 'F')))
+            return false;
+    }
+    return true;
+}
+} // namespace
+
+QStringList FieldPickupRandomizer_ff7tk::validateHelperScripts()
+{
+    static const QStringList problems = [] {
+        QStringList found;
+        // Walks the bytes as field opcodes. Anchors are IF tests cut before
+        // their jump byte, so their last opcode is allowed to run past the end.
+        auto checkOpcodes = [&found](const QString& name, const QByteArray& bytes, bool anchor) {
+            const QByteArray padded = bytes + QByteArray(16, '\0');
+            int pos = 0;
+            while (pos < bytes.size()) {
+                int len = FieldScript::opcodeLength(padded, pos, padded.size());
+                if (len <= 0) {
+                    found << QString("helper script '%1': unknown opcode 0x%2 at byte %3")
+                                 .arg(name).arg(quint8(bytes.at(pos)), 2, 16, QChar('0')).arg(pos);
+                    return;
+                }
+                pos += len;
+            }
+            if (pos > bytes.size() && !anchor)
+                found << QString("helper script '%1': last opcode is %2 byte(s) short")
+                             .arg(name).arg(pos - bytes.size());
+        };
+
+        for (int i = 0; i < HelperScriptCount; ++i) {
+            const HelperScriptDef& def = kHelperScripts[i];
+            // fromHex() silently skips bad characters, so check the text itself
+            if (!isHexText(QByteArray(def.hex))) {
+                found << QString("helper script '%1': malformed hex \"%2\"").arg(def.name, def.hex);
+                continue;
+            }
+            checkOpcodes(def.name, helperScript(static_cast<HelperScript>(i)), def.anchor);
+        }
+        checkOpcodes("Free Roam new-game sequence", freeRoamStartSequence(), false);
+        return found;
+    }();
+    return problems;
+}
+
+bool FieldPickupRandomizer_ff7tk::injectFreeRoamMapJump(
+    QByteArray& decompressed,
+    const QString& fieldName,
+    QTextStream& debugStream)
+{
+    debugStream << "  MAPJUMP_DBG: enter, fileSize=" << decompressed.size() << "\n";
+    const int fileSize = decompressed.size();
+    const int HEADER_SIZE = 6 + 9 * 4;
+    if (fileSize < HEADER_SIZE) {
+        debugStream << "  MAPJUMP_DBG: too small\n";
+        return false;
+    }
+
+    quint32 sectionPositions[9];
+    memcpy(sectionPositions, decompressed.constData() + 6, 9 * 4);
+
+    quint32 sec0 = sectionPositions[0];
+    debugStream << "  MAPJUMP_DBG: sec0=" << sec0 << "\n";
+    if (sec0 >= static_cast<quint32>(fileSize - 4)) {
+        debugStream << "  MAPJUMP_DBG: sec0 out of range\n";
+        return false;
+    }
+
+    int sec0DataStart = static_cast<int>(sec0) + 4;
+    debugStream << "  MAPJUMP_DBG: sec0DataStart=" << sec0DataStart << "\n";
+    if (sec0DataStart + 8 > fileSize) {
+        debugStream << "  MAPJUMP_DBG: sec0DataStart+8 out of range\n";
+        return false;
+    }
+
+    // FF7SCRIPTHEADER layout (all offsets from sec0DataStart):
+    //   +0  u16 unknown1
+    //   +2  u8  nEntities
+    //   +3  u8  nModels
+    //   +4  u16 wStringOffset
+    //   +6  u16 nAkaoOffsets
+    //   +8..+31 scale + blanks + creator + name  (24 bytes)
+    //   = 32 bytes fixed header
+    //   then: szEntities[nEntities][8]
+    //   then: dwAkaoOffsets[nAkaoOffsets] (u32 each = 4 bytes)
+    //   then: vEntityScripts[nEntities][32] (u16 each)
+    if (sec0DataStart + 8 > fileSize) return false;
+    quint8  nbEntities   = static_cast<quint8>(decompressed.at(sec0DataStart + 2));
+    quint16 nAkaoOffsets = 0;
     memcpy(&nAkaoOffsets, decompressed.constData() + sec0DataStart + 6, 2);
     debugStream << "  MAPJUMP_DBG: nbEntities=" << nbEntities
                 << " nAkaoOffsets=" << nAkaoOffsets << "\n";
@@ -1545,7 +2239,7 @@ bool FieldPickupRandomizer_ff7tk::injectFreeRoamMapJump(
     int injectAt = -1;
     int guard = 0;
     while (pos < walkEnd && guard++ < 100000) {
-        int len = fieldOpcodeLength(decompressed, pos, fileSize);
+        int len = FieldScript::opcodeLength(decompressed, pos, fileSize);
         if (len <= 0) {
             debugStream << "  MAPJUMP_DBG: invalid opcode 0x"
                         << QString::number(static_cast<quint8>(decompressed.at(pos)), 16)
@@ -1569,107 +2263,7 @@ bool FieldPickupRandomizer_ff7tk::injectFreeRoamMapJump(
         debugStream << "  MAPJUMP_DBG: PRTYE (New party: Cloud) not found - aborting\n";
         return false;
     }
-    // Injected sequence (after PRTYE, before transferring to the world map).
-    // The skipped Midgar intro normally performs all of this setup; on a NEW
-    // GAME none of it happens, so we replicate the essentials here:
-    //   MENU 6        - Cloud name-entry screen. Sets Cloud's name (default
-    //                   "Cloud" instead of the kernel placeholder "EX-SOLDIER")
-    //                   and initialises the party's average level.
-    //   SETWORD x2    - menu visibility = 0x03FF (all standard commands shown,
-    //                   incl. Materia = bit 2) and locking = 0x0000 (none).
-    //   SETBYTE       - Kalm conversation flags = 0x03 (NPC-spoken bits) to
-    //                   avoid the Kalm progression lock.
-    //   SETWORD       - game moment = 1603.
-    //   MAPJUMP + RET - transfer to wm1 and halt the script cleanly.
-    //
-    // Field memory banks (cf. FF7 savemap): bank 1 maps to savemap 0x0BA4.
-    //   8-bit bank id 0x1 / 16-bit bank id 0x2.
-    //   game moment        = Var[2][0]   (savemap 0x0BA4)
-    //   menu visibility    = Var[2][0x1C](savemap 0x0BC0)
-    //   menu locking       = Var[2][0x1E](savemap 0x0BC2)
-    //   Kalm conv. flags   = Var[1][0x80](savemap 0x0C24)
-    // SET* bank byte = (Dest<<4)|Source; Source 0 = write literal value V.
-    static constexpr quint8  kMenuBank16    = 0x20;   // dest 16-bit bank 1, literal src
-    static constexpr quint8  kMenuBank8     = 0x10;   // dest 8-bit  bank 1, literal src
-    static constexpr quint16 kGameMoment    = 1997;
-    static constexpr quint16 kMenuVisible   = 0x03FF; // Item..Save all visible
-    static constexpr quint16 kMenuLocking   = 0x0000; // nothing locked
-    static constexpr quint8  kKalmFlagsAddr = 0x80;   // Var[1][128]
-    static constexpr quint8  kKalmFlags     = 0x03;   // bits 0 + 1
-    // NOTE: the current disc (savemap 0x0EA4) is NOT field-settable — fields change
-    // it via the DSKCG opcode (engine-handled), never a direct SETBYTE. Free Roam's
-    // "disc 3" is forced by the client writing 0x0EA4 instead (see FF7Client.py).
-
-    // BITON Var[3][128] bit 1 — marks the psdun_2 (Mythril Mines) line-trigger
-    // party-split event as "already played" so Free Roam doesn't fire it and
-    // boot the player back. bank 3 = 8-bit half of the 2nd bank pair; the engine
-    // resolves the savemap offset, so we only encode the bank nibble here.
-    //   banks byte = (addrBank 3 << 4) | (bitSrc 0 = literal) = 0x30
-    static constexpr quint8  kBitOnBanks    = 0x30;   // addr bank 3, literal bit
-    static constexpr quint8  kFreeRoamFlagAddr = 0x80; // Var[3][128]
-    static constexpr quint8  kFreeRoamFlagBit  = 0x01; // "bitON 1" = bit index 1
-
-    // BITON Var[3][130] bit 3 — marks the Rocket Town first-visit intro as
-    // already played. The rckt/rckt2 'cloud' init runs
-    //   IFUB Var[3][130] bitOFF 3 -> UC(01) [disable control] + MENU2(01)
-    // expecting the intro cutscene to re-enable control. On a moment-1603 Free
-    // Roam the bit is OFF and that cutscene never fires, soft-locking the player
-    // on entry. Setting the bit makes the IFUB take the skip branch.
-    static constexpr quint8  kRocketFlagAddr = 0x82; // Var[3][130]
-    static constexpr quint8  kRocketFlagBit  = 0x03; // "bitON 3" = bit index 3
-
-    // MAPJUMP to wm1 (field ID 2 = outside Kalm).
-    // X/Y/triangle/direction are ignored by the WM engine for wm* dummy fields.
-    static constexpr quint16 kFieldId  = 2;   // wm1 = Outside Kalm
-    static constexpr qint16  kSpawnX   = 0;
-    static constexpr qint16  kSpawnY   = 0;
-    static constexpr quint16 kTriangle = 0;
-    static constexpr quint8  kDir      = 0;
-
-    // NOTE: intro music (MUSIC opcode) + a welcome MESSAGE were reverted — they
-    // crashed the field right after the intro movie. The field MUSIC index and a
-    // blocking MESSAGE in this early (pre-interactive) script context are not safe
-    // here without in-game verification (md1stin has only 2 akao entries, so a bad
-    // MUSIC index hard-crashes). overwriteFieldDialog() is kept below for a future,
-    // tested re-add. This is the proven new-game -> world-map injection.
-    auto put16 = [](QByteArray& b, quint16 v) {
-        b.append(static_cast<char>(v & 0xFF));
-        b.append(static_cast<char>((v >> 8) & 0xFF));
-    };
-
-    QByteArray seq;
-    // MENU 6 — name entry for Cloud (char id 0); B=0 (literal), T=6, P=0
-    seq.append(static_cast<char>(0x49));
-    seq.append(static_cast<char>(0x00));
-    seq.append(static_cast<char>(0x06));
-    seq.append(static_cast<char>(0x00));
-    // SETWORD menu visibility (Var[2][0x1C]) = 0x03FF
-    seq.append(static_cast<char>(0x81)); seq.append(static_cast<char>(kMenuBank16));
-    seq.append(static_cast<char>(0x1C)); put16(seq, kMenuVisible);
-    // SETWORD menu locking (Var[2][0x1E]) = 0x0000
-    seq.append(static_cast<char>(0x81)); seq.append(static_cast<char>(kMenuBank16));
-    seq.append(static_cast<char>(0x1E)); put16(seq, kMenuLocking);
-    // SETBYTE Kalm conversation flags (Var[1][0x80]) = 0x03
-    seq.append(static_cast<char>(0x80)); seq.append(static_cast<char>(kMenuBank8));
-    seq.append(static_cast<char>(kKalmFlagsAddr)); seq.append(static_cast<char>(kKalmFlags));
-    // SETWORD game moment (Var[2][0]) = 1997
-    seq.append(static_cast<char>(0x81)); seq.append(static_cast<char>(kMenuBank16));
-    seq.append(static_cast<char>(0x00)); put16(seq, kGameMoment);
-    // BITON Var[3][128] bit 1 — skip psdun_2 (Mythril Mines) party-split trigger
-    seq.append(static_cast<char>(0x82)); seq.append(static_cast<char>(kBitOnBanks));
-    seq.append(static_cast<char>(kFreeRoamFlagAddr)); seq.append(static_cast<char>(kFreeRoamFlagBit));
-    // BITON Var[3][130] bit 3 — skip Rocket Town (rckt/rckt2) entry soft-lock
-    seq.append(static_cast<char>(0x82)); seq.append(static_cast<char>(kBitOnBanks));
-    seq.append(static_cast<char>(kRocketFlagAddr)); seq.append(static_cast<char>(kRocketFlagBit));
-    // MAPJUMP wm1
-    seq.append(static_cast<char>(0x60));
-    put16(seq, kFieldId);
-    put16(seq, static_cast<quint16>(kSpawnX)); put16(seq, static_cast<quint16>(kSpawnY));
-    put16(seq, kTriangle);
-    seq.append(static_cast<char>(kDir));
-    // RET — halt script cleanly after queuing the jump
-    seq.append(static_cast<char>(0x00));
-
+    const QByteArray& seq = freeRoamStartSequence();
     if (injectAt + seq.size() > walkEnd || injectAt + seq.size() > fileSize) {
         debugStream << "  MAPJUMP_DBG: not enough room after PRTYE for "
                     << seq.size() << " bytes - aborting\n";
@@ -1735,33 +2329,17 @@ void FieldPickupRandomizer_ff7tk::dumpFieldScripts(
     debugStream << "\n=== FIELD_DUMP " << fieldName << " (size=" << fileSize << ") ===\n";
     if (fileSize < HEADER_SIZE) { debugStream << "  too small\n"; return; }
 
-    quint32 sectionPositions[9];
-    memcpy(sectionPositions, decompressed.constData() + 6, 9 * 4);
-    int sec0DataStart = static_cast<int>(sectionPositions[0]) + 4;
-    if (sec0DataStart + 32 > fileSize) { debugStream << "  sec0 out of range\n"; return; }
-
-    quint8  nbEntities   = static_cast<quint8>(decompressed.at(sec0DataStart + 2));
-    quint16 wStringOffset = 0, nAkaoOffsets = 0;
-    memcpy(&wStringOffset, decompressed.constData() + sec0DataStart + 4, 2);
-    memcpy(&nAkaoOffsets,  decompressed.constData() + sec0DataStart + 6, 2);
-    if (nbEntities == 0) { debugStream << "  no entities\n"; return; }
-
-    int namesStart       = sec0DataStart + 32;
-    int akaoTableStart   = namesStart + 8 * static_cast<int>(nbEntities);
-    int offsetTableStart = akaoTableStart + 4 * static_cast<int>(nAkaoOffsets);
-    if (offsetTableStart + 64 * static_cast<int>(nbEntities) > fileSize) {
-        debugStream << "  script table out of range\n"; return;
-    }
-
-    // Walk bound: start of strings or first AKAO block, whichever is first.
-    int walkEnd = sec0DataStart + static_cast<int>(wStringOffset);
-    if (nAkaoOffsets > 0 && akaoTableStart + 4 <= fileSize) {
-        quint32 firstAkao = 0;
-        memcpy(&firstAkao, decompressed.constData() + akaoTableStart, 4);
-        int akaoAbs = sec0DataStart + static_cast<int>(firstAkao);
-        if (akaoAbs > offsetTableStart && akaoAbs < walkEnd) walkEnd = akaoAbs;
+    FieldScript::ScriptRange range;
+    if (!FieldScript::scriptRange(decompressed, range)) {
+        debugStream << "  script section out of range or no entities\n"; return;
     }
-    if (walkEnd > fileSize || walkEnd <= offsetTableStart) walkEnd = fileSize;
+    const int sec0DataStart    = range.dataStart;
+    const int nbEntities       = range.entityCount;
+    const int namesStart       = range.namesStart;
+    const int offsetTableStart = range.offsetTable;
+    const int walkEnd          = range.walkEnd;
+    quint16 nAkaoOffsets = 0;
+    memcpy(&nAkaoOffsets, decompressed.constData() + sec0DataStart + 6, 2);
 
     debugStream << "  nbEntities=" << nbEntities << " nAkao=" << nAkaoOffsets
                 << " walkEnd=" << walkEnd << "\n";
@@ -1796,7 +2374,7 @@ void FieldPickupRandomizer_ff7tk::dumpFieldScripts(
 
             int pos = start, guard = 0;
             while (pos < walkEnd && guard++ < 600) {
-                int len = fieldOpcodeLength(decompressed, pos, fileSize);
+                int len = FieldScript::opcodeLength(decompressed, pos, fileSize);
                 if (len <= 0) { debugStream << "      @" << pos << " BAD op\n"; break; }
                 quint8 op = static_cast<quint8>(decompressed.at(pos));
                 QString name = ff7OpcodeName(op);
@@ -1831,8 +2409,8 @@ int FieldPickupRandomizer_ff7tk::overwriteFieldDialog(
     if (fileSize < 6 + 9 * 4) return -1;
     quint32 sectionPositions[9];
     memcpy(sectionPositions, decompressed.constData() + 6, 9 * 4);
+    if (sectionPositions[0] > static_cast<quint32>(fileSize - 12)) return -1;
     const int sec0DataStart = static_cast<int>(sectionPositions[0]) + 4;
-    if (sec0DataStart + 8 > fileSize) return -1;
 
     quint16 wStringOffset = 0;
     memcpy(&wStringOffset, decompressed.constData() + sec0DataStart + 4, 2);
@@ -1874,6 +2452,381 @@ int FieldPickupRandomizer_ff7tk::overwriteFieldDialog(
     return bestId;
 }
 
+// ============================================================================
+// Script ownership  –  which entity / script slot an opcode belongs to
+//
+// Section 0 stores, per entity, 32 u16 script entry points (relative to the
+// section data). Scripts are laid out back to back, so the owner of an opcode
+// is the script whose entry point is the last one at or before it. Slots that
+// share an entry point report the lowest slot, as Makou does.
+// ============================================================================
+
+namespace {
+struct ScriptOwner { int entity = -1; int script = -1; };
+
+QMap<int, ScriptOwner> scriptEntryPoints(const QByteArray& fieldData)
+{
+    QMap<int, ScriptOwner> entries;   // absolute start -> owner
+    FieldScript::ScriptRange range;
+    if (!FieldScript::scriptRange(fieldData, range)) return entries;
+    const int sec0DataStart    = range.dataStart;
+    const int nbEntities       = range.entityCount;
+    const int offsetTableStart = range.offsetTable;
+
+    for (int e = 0; e < nbEntities; ++e) {
+        for (int s = 0; s < 32; ++s) {
+            quint16 rel = 0;
+            memcpy(&rel, fieldData.constData() + offsetTableStart + 64 * e + 2 * s, 2);
+            const int start = sec0DataStart + rel;
+            if (!entries.contains(start)) entries.insert(start, ScriptOwner{e, s});
+        }
+    }
+    return entries;
+}
+
+ScriptOwner scriptOwnerAt(const QMap<int, ScriptOwner>& entries, int offset)
+{
+    auto it = entries.upperBound(offset);
+    if (it == entries.constBegin()) return ScriptOwner();
+    return (--it).value();
+}
+
+// Offset of an opcode from its owning script's entry point, or -1
+int scriptOffsetAt(const QMap<int, ScriptOwner>& entries, int offset)
+{
+    auto it = entries.upperBound(offset);
+    if (it == entries.constBegin()) return -1;
+    return offset - (--it).key();
+}
+
+// " (entity 16, script 19)" for log lines, empty when unresolved
+QString ownerTag(int entity, int script)
+{
+    if (entity < 0) return QString();
+    return QString(" (entity %1, script %2)").arg(entity).arg(script);
+}
+
+// The flag a pickup's script sets along with the grant (the "already
+// opened" bit of a chest): the first BITON after the opcode within its
+// script, else the last one before it. Key item bits (Var[1/2][0x40-0x46])
+// are skipped. Returns false when the script sets no bit.
+bool pickupFlagAt(const QByteArray& d, const QMap<int, ScriptOwner>& entries, int offset,
+                  int& bank, int& address, int& bit)
+{
+    auto owner = entries.upperBound(offset);
+    if (owner == entries.constBegin()) return false;
+    const int end = owner == entries.constEnd() ? d.size() : owner.key();
+    const int start = (--owner).key();
+
+    int before = -1, after = -1;
+    int pos = start, guard = 0;
+    while (pos < end && pos < d.size() && guard++ < 4000) {
+        const int len = FieldScript::opcodeLength(d, pos, d.size());
+        if (len <= 0) break;
+        if (static_cast<quint8>(d.at(pos)) == 0x82 && pos + 3 < d.size()) {   // BITON
+            const int destBank = (static_cast<quint8>(d.at(pos + 1)) >> 4) & 0x0F;
+            const int addr = static_cast<quint8>(d.at(pos + 2));
+            const bool keyItemBit = destBank <= 2 && addr >= 0x40 && addr <= 0x46;
+            if (destBank != 0 && !keyItemBit) {
+                if (pos < offset) before = pos;
+                else if (after < 0) after = pos;
+            }
+        }
+        pos += len;
+    }
+    const int flag = after >= 0 ? after : before;
+    if (flag < 0) return false;
+    bank    = (static_cast<quint8>(d.at(flag + 1)) >> 4) & 0x0F;
+    address = static_cast<quint8>(d.at(flag + 2));
+    bit     = static_cast<quint8>(d.at(flag + 3));
+    return true;
+}
+} // namespace
+
+// ============================================================================
+// Protected flags
+//
+// Bank 1/2 bytes hold unrelated flags side by side: the last key item byte
+// (0x46) has Black Materia, Mythril and Snowboard in bits 0-2 and game state
+// above them, and the NPC quest-state bytes (0xA0-0xA5, 0xE1-0xE2) are the
+// ones json_export.py already keeps AP locations away from. Rewrites only
+// ever need the key item bits, so everything else listed here is off limits.
+// ============================================================================
+
+namespace {
+struct ProtectedFlag { int address; quint8 mask; };   // banks 1/2
+
+// End of the last script: the text section follows the scripts
+int scriptSectionEnd(const QByteArray& fieldData)
+{
+    if (fieldData.size() < 6 + 9 * 4) return fieldData.size();
+    quint32 sec0 = 0;
+    memcpy(&sec0, fieldData.constData() + 6, 4);
+    if (sec0 > static_cast<quint32>(fieldData.size() - 12)) return fieldData.size();
+    const int sec0DataStart = static_cast<int>(sec0) + 4;
+    quint16 posTexts = 0;
+    memcpy(&posTexts, fieldData.constData() + sec0DataStart + 4, 2);
+    return qMin(sec0DataStart + posTexts, static_cast<int>(fieldData.size()));
+}
+
+const ProtectedFlag kProtectedFlags[] = {
+    { 0x46, 0xF8 },   // above Snowboard: minigame unlock / date state
+    { 0xA0, 0xFF }, { 0xA1, 0xFF }, { 0xA2, 0xFF },
+    { 0xA3, 0xFF }, { 0xA4, 0xFF }, { 0xA5, 0xFF },
+    { 0xE1, 0xFF }, { 0xE2, 0xFF },
+};
+} // namespace
+
+bool FieldPickupRandomizer_ff7tk::isProtectedFlag(int bank, int address, int bit)
+{
+    if (bank < 1 || bank > 2 || bit < 0 || bit > 7) return false;
+    for (const ProtectedFlag& flag : kProtectedFlags) {
+        if (flag.address == address && (flag.mask & (1 << bit)))
+            return true;
+    }
+    return false;
+}
+
+QMap<quint32, int> FieldPickupRandomizer_ff7tk::protectedFlagWrites(const QByteArray& decompressed)
+{
+    QMap<quint32, int> writes;
+    const QMap<int, ScriptOwner> owners = scriptEntryPoints(decompressed);
+    if (owners.isEmpty()) return writes;
+
+    const int scriptsEnd = scriptSectionEnd(decompressed);
+
+    for (auto it = owners.constBegin(); it != owners.constEnd(); ++it) {
+        auto next = std::next(it);
+        const int end = next == owners.constEnd() ? scriptsEnd : next.key();
+        int pos = it.key(), guard = 0;
+        while (pos < end && guard++ < 4000) {
+            const int len = FieldScript::opcodeLength(decompressed, pos, decompressed.size());
+            if (len <= 0) break;
+            const quint8 op = static_cast<quint8>(decompressed.at(pos));
+            if (op >= 0x82 && op <= 0x84 && len == 4) {   // BITON / BITOFF / BITXOR
+                const quint8 banks = static_cast<quint8>(decompressed.at(pos + 1));
+                const int bank = (banks >> 4) & 0x0F;
+                const int addr = static_cast<quint8>(decompressed.at(pos + 2));
+                const int bit  = static_cast<quint8>(decompressed.at(pos + 3));
+                // A non-zero source bank makes the bit a variable; only
+                // constant bits can be checked
+                if ((banks & 0x0F) == 0 && isProtectedFlag(bank, addr, bit))
+                    ++writes[(static_cast<quint32>(bank) << 16) | (addr << 8) | bit];
+            }
+            pos += len;
+        }
+    }
+    return writes;
+}
+
+// ============================================================================
+// Counted key item checks
+//
+// Midgar Parts: each part is handed to the 65th floor model by a script that
+// clears its key item bit (Var[1][0x44] bits 3-7) and bumps a counter; the
+// chest holding Keycard 66 opens once that counter compares equal to five.
+// Huge Materia: Bugenhagen's observatory scripts read the four Huge Materia
+// bits (Var[1][0x42] bits 4-7) and tally them the same way before the
+// rewards. The counter is found from the scripts that write those bits
+// rather than hardcoded, then every IFUB/IFUBL/IFSW/IFSWL/IFUW/IFUWL
+// comparing it against the vanilla count is retargeted to the configured
+// one. Returns the number of checks patched. randomize() fails the run when a
+// lowered count patched nothing, and the field log names each field where the
+// bits are written but no counter or compare was found.
+// ============================================================================
+
+int FieldPickupRandomizer_ff7tk::patchMidgarPartsCheck(QByteArray& decompressed,
+                                                       const QString& fieldName,
+                                                       int required, QTextStream& debugStream)
+{
+    // Scripts that take a part: BITOFF only
+    return patchCountedCheck(decompressed, fieldName, 0x44, 0xF8, 0x83, 0x83,
+                             Config::MIDGAR_PARTS_COUNT, required, "MIDGAR_PARTS", debugStream);
+}
+
+int FieldPickupRandomizer_ff7tk::patchHugeMateriaCheck(QByteArray& decompressed,
+                                                       const QString& fieldName,
+                                                       int required, QTextStream& debugStream)
+{
+    // Scripts that hand over or set aside a Huge Materia: BITON or BITOFF
+    return patchCountedCheck(decompressed, fieldName, 0x42, 0xF0, 0x82, 0x83,
+                             Config::HUGE_MATERIA_COUNT, required, "HUGE_MATERIA", debugStream);
+}
+
+int FieldPickupRandomizer_ff7tk::patchCountedCheck(QByteArray& decompressed,
+                                                   const QString& fieldName,
+                                                   int address, quint8 bitMask,
+                                                   quint8 firstOpcode, quint8 lastOpcode,
+                                                   int vanillaCount, int required,
+                                                   const char* tag, QTextStream& debugStream)
+{
+    const QMap<int, ScriptOwner> owners = scriptEntryPoints(decompressed);
+    if (owners.isEmpty()) return 0;
+    const int scriptsEnd = scriptSectionEnd(decompressed);
+    const int fileSize = decompressed.size();
+    auto byteAt = [&](int pos) { return static_cast<quint8>(decompressed.at(pos)); };
+
+    // Visits every opcode with its script's range
+    auto forEachOpcode = [&](const std::function<void(int, int, int, int)>& visit) {
+        for (auto it = owners.constBegin(); it != owners.constEnd(); ++it) {
+            auto next = std::next(it);
+            const int end = next == owners.constEnd() ? scriptsEnd : next.key();
+            int pos = it.key(), guard = 0;
+            while (pos < end && guard++ < 4000) {
+                const int len = FieldScript::opcodeLength(decompressed, pos, fileSize);
+                if (len <= 0) break;
+                visit(it.key(), end, pos, len);
+                pos += len;
+            }
+        }
+    };
+
+    // Scripts that write one of the counted bits, then the variables they
+    // increment
+    QSet<int> partScripts;
+    forEachOpcode([&](int start, int, int pos, int len) {
+        if (byteAt(pos) < firstOpcode || byteAt(pos) > lastOpcode || len != 4) return;
+        const quint8 banks = byteAt(pos + 1);
+        const int bank = (banks >> 4) & 0x0F;
+        if ((bank == 1 || bank == 2) && (banks & 0x0F) == 0
+            && byteAt(pos + 2) == address && byteAt(pos + 3) <= 7
+            && (bitMask & (1 << byteAt(pos + 3))))
+            partScripts.insert(start);
+    });
+    QSet<int> counters;   // bank << 8 | address
+    forEachOpcode([&](int start, int, int pos, int len) {
+        if (!partScripts.contains(start)) return;
+        const quint8 op = byteAt(pos);
+        const quint8 banks = byteAt(pos + 1);
+        const bool inc  = (op == 0x7A || op == 0x7B) && len == 3;            // INC / INC2
+        const bool plus = op == 0x76 && len == 4 && (banks & 0x0F) == 0
+                          && byteAt(pos + 3) == 1;                            // PLUS var, 1
+        if (inc || plus)
+            counters.insert(((banks >> 4) & 0x0F) << 8 | byteAt(pos + 2));
+    });
+    if (partScripts.isEmpty()) return 0;
+    if (counters.isEmpty()) {
+        debugStream << "  " << tag << " in " << fieldName << ": " << partScripts.size()
+                    << " script(s) write the bits but bump no counter\n";
+        return 0;
+    }
+
+    int patched = 0;
+    forEachOpcode([&](int, int, int pos, int len) {
+        const quint8 op = byteAt(pos);
+        if (op < 0x14 || op > 0x19) return;
+        const bool word = op >= 0x16;
+        if (len != (word ? 8 : 6) + (op & 1)) return;
+        const quint8 banks = byteAt(pos + 1);
+        const int width = word ? 2 : 1;
+        const int leftPos = pos + 2, rightPos = pos + 2 + width;
+        auto operand = [&](int at) {
+            return word ? (byteAt(at) | (byteAt(at + 1) << 8)) : byteAt(at);
+        };
+        // Counter on one side (low byte of a word address), the vanilla
+        // count on the other
+        int constPos = -1;
+        if ((banks & 0x0F) == 0 && operand(rightPos) == vanillaCount
+            && counters.contains(((banks >> 4) & 0x0F) << 8 | byteAt(leftPos)))
+            constPos = rightPos;
+        else if ((banks & 0xF0) == 0 && operand(leftPos) == vanillaCount
+                 && counters.contains((banks & 0x0F) << 8 | byteAt(rightPos)))
+            constPos = leftPos;
+        if (constPos < 0) return;
+        decompressed[constPos] = static_cast<char>(required);
+        if (word) decompressed[constPos + 1] = 0;
+        debugStream << "  " << tag << " @" << pos << " in " << fieldName
+                    << ": count check " << vanillaCount << " -> " << required << "\n";
+        ++patched;
+    });
+    if (patched == 0)
+        debugStream << "  " << tag << " in " << fieldName << ": counter found but never compared"
+                    << " against " << vanillaCount << "\n";
+    return patched;
+}
+
+// ============================================================================
+// Progressive keycards
+//
+// The Shinra HQ keycards are Var[1][0x43] bits 6-7 and Var[1][0x44] bits 0-2
+// (60, 62, 65, 66, 68). In progressive mode every BITON granting one becomes
+// INC of a single counter (PROGRESSIVE_KEYCARD_BANK/VAR) plus a NOP, and every
+// IFUB/IFUBL bit test on one becomes a compare of that counter against the
+// card's tier: "Keycard 65 on" reads "counter >= 3". Whichever card is found
+// n-th opens the doors up to tier n. Both rewrites keep the opcode length.
+// Returns the number of opcodes patched.
+// ============================================================================
+
+int FieldPickupRandomizer_ff7tk::patchProgressiveKeycards(QByteArray& decompressed,
+                                                          const QString& fieldName,
+                                                          QTextStream& debugStream)
+{
+    const QMap<int, ScriptOwner> owners = scriptEntryPoints(decompressed);
+    if (owners.isEmpty()) return 0;
+    const int scriptsEnd = scriptSectionEnd(decompressed);
+    const int fileSize = decompressed.size();
+    auto byteAt = [&](int pos) { return static_cast<quint8>(decompressed.at(pos)); };
+
+    // Tier 1-5 of a keycard bit, 0 for anything else
+    auto tierOf = [](int address, int bit) {
+        if (address == 0x43 && (bit == 6 || bit == 7)) return bit - 5;
+        if (address == 0x44 && bit >= 0 && bit <= 2)   return bit + 3;
+        return 0;
+    };
+    const quint8 counterBanks = static_cast<quint8>(PROGRESSIVE_KEYCARD_BANK << 4);
+
+    int patched = 0;
+    for (auto it = owners.constBegin(); it != owners.constEnd(); ++it) {
+        auto next = std::next(it);
+        const int end = next == owners.constEnd() ? scriptsEnd : next.key();
+        int pos = it.key(), guard = 0;
+        while (pos < end && guard++ < 4000) {
+            const int len = FieldScript::opcodeLength(decompressed, pos, fileSize);
+            if (len <= 0) break;
+            const quint8 op = byteAt(pos);
+            const quint8 banks = len >= 2 ? byteAt(pos + 1) : 0;
+            const int bank = (banks >> 4) & 0x0F;
+            const bool keyBank = (bank == 1 || bank == 2) && (banks & 0x0F) == 0;
+
+            if (op == BITON_OPCODE && len == 4 && keyBank) {
+                const int tier = tierOf(byteAt(pos + 2), byteAt(pos + 3));
+                if (tier > 0) {
+                    decompressed[pos]     = static_cast<char>(0x7A);   // INC
+                    decompressed[pos + 1] = static_cast<char>(counterBanks);
+                    decompressed[pos + 2] = static_cast<char>(PROGRESSIVE_KEYCARD_VAR);
+                    decompressed[pos + 3] = static_cast<char>(0x5F);   // NOP
+                    debugStream << "  KEYCARD @" << pos << " in " << fieldName
+                                << ": grant of tier " << tier << " -> next tier\n";
+                    ++patched;
+                }
+            } else if ((op == 0x14 || op == 0x15) && len == 6 + (op & 1) && keyBank) {
+                // IFUB/IFUBL var, value, operator: 9 = bit on, 10 = bit off,
+                // 6 = AND with a one-bit mask
+                const int address = byteAt(pos + 2);
+                const quint8 value = byteAt(pos + 3);
+                const quint8 compare = byteAt(pos + 4);
+                int bit = -1;
+                if (compare == 9 || compare == 10) bit = value;
+                else if (compare == 6 && value && !(value & (value - 1)))
+                    bit = qCountTrailingZeroBits(value);
+                const int tier = bit >= 0 ? tierOf(address, bit) : 0;
+                if (tier > 0) {
+                    decompressed[pos + 1] = static_cast<char>(counterBanks);
+                    decompressed[pos + 2] = static_cast<char>(PROGRESSIVE_KEYCARD_VAR);
+                    decompressed[pos + 3] = static_cast<char>(tier);
+                    decompressed[pos + 4] = static_cast<char>(compare == 10 ? 3 : 4);   // < / >=
+                    debugStream << "  KEYCARD @" << pos << " in " << fieldName
+                                << ": check of tier " << tier << " -> counter "
+                                << (compare == 10 ? "< " : ">= ") << tier << "\n";
+                    ++patched;
+                }
+            }
+            pos += len;
+        }
+    }
+    return patched;
+}
+
 // ============================================================================
 // scanForSTITM  –  parse the field file section table (like Makou Reactor)
 //                   then scan ONLY section 0 (scripts) for 0x58 opcodes.
@@ -1913,7 +2866,7 @@ QVector<STITMInfo> FieldPickupRandomizer_ff7tk::scanForSTITM(
     quint32 sec0 = sectionPositions[0];
     quint32 sec1 = sectionPositions[1];
 
-    if (sec0 + 4 >= static_cast<quint32>(fileSize) ||
+    if (sec0 >= static_cast<quint32>(fileSize - 4) ||
         sec1 > static_cast<quint32>(fileSize) ||
         sec1 <= sec0 + 4) {
         debugStream << fieldName << ": invalid section positions (sec0="
@@ -1943,6 +2896,7 @@ QVector<STITMInfo> FieldPickupRandomizer_ff7tk::scanForSTITM(
                 << " (" << (scriptEnd - scriptStart) << " bytes)\n";
 
     // --- scan only the script bytecode for STITM (0x58) ---------------------
+    const QMap<int, ScriptOwner> owners = scriptEntryPoints(fieldData);
     for (int i = scriptStart; i <= scriptEnd - STITM_SIZE; ++i) {
         if (static_cast<quint8>(fieldData[i]) != STITM_OPCODE) continue;
 
@@ -1955,6 +2909,10 @@ QVector<STITMInfo> FieldPickupRandomizer_ff7tk::scanForSTITM(
         info.originalItemID  = raw->itemID;   // little-endian on x86
         info.originalQuantity = raw->quantity;
         info.isDirectValue   = (raw->banks == 0x00);
+        const ScriptOwner owner = scriptOwnerAt(owners, i);
+        info.entityIndex     = owner.entity;
+        info.scriptIndex     = owner.script;
+        info.scriptOffset    = scriptOffsetAt(owners, i);
 
         results.append(info);
     }
@@ -2005,7 +2963,7 @@ bool FieldPickupRandomizer_ff7tk::applySTITMRandomization(
     raw->itemID = newItemID;
     // banks and quantity are left untouched
 
-    debugStream << "  STITM @" << info.offset
+    debugStream << "  STITM @" << info.offset << ownerTag(info.entityIndex, info.scriptIndex)
                 << "  " << getItemName(info.originalItemID)
                 << " (" << info.originalItemID << ")"
                 << " -> " << getItemName(newItemID)
@@ -2036,7 +2994,7 @@ QVector<SMTRAInfo> FieldPickupRandomizer_ff7tk::scanForSMTRA(
     quint32 sec0 = sectionPositions[0];
     quint32 sec1 = sectionPositions[1];
 
-    if (sec0 + 4 >= static_cast<quint32>(fileSize) ||
+    if (sec0 >= static_cast<quint32>(fileSize - 4) ||
         sec1 > static_cast<quint32>(fileSize) ||
         sec1 <= sec0 + 4) {
         return results;
@@ -2056,6 +3014,7 @@ QVector<SMTRAInfo> FieldPickupRandomizer_ff7tk::scanForSMTRA(
 
     if (scriptStart >= scriptEnd || scriptEnd > fileSize) return results;
 
+    const QMap<int, ScriptOwner> owners = scriptEntryPoints(fieldData);
     for (int i = scriptStart; i <= scriptEnd - SMTRA_SIZE; ++i) {
         if (static_cast<quint8>(fieldData[i]) != SMTRA_OPCODE) continue;
 
@@ -2071,6 +3030,9 @@ QVector<SMTRAInfo> FieldPickupRandomizer_ff7tk::scanForSMTRA(
         info.originalAP[1]    = raw->APCount[1];
         info.originalAP[2]    = raw->APCount[2];
         info.isDirectValue    = (raw->banks[0] == 0x00 && raw->banks[1] == 0x00);
+        const ScriptOwner owner = scriptOwnerAt(owners, i);
+        info.entityIndex      = owner.entity;
+        info.scriptIndex      = owner.script;
 
         results.append(info);
     }
@@ -2115,7 +3077,7 @@ bool FieldPickupRandomizer_ff7tk::applySMTRARandomization(
     raw->materiaID = newMateriaID;
     // banks and AP are left untouched
 
-    debugStream << "  SMTRA @" << info.offset
+    debugStream << "  SMTRA @" << info.offset << ownerTag(info.entityIndex, info.scriptIndex)
                 << "  " << getMateriaName(info.originalMateriaID)
                 << " (" << info.originalMateriaID << ")"
                 << " -> " << getMateriaName(newMateriaID)
@@ -2162,7 +3124,14 @@ bool FieldPickupRandomizer_ff7tk::loadApJson(
         QString itemText = p["item_text"].toString().toLower().trimmed();
         if (itemText.isEmpty()) continue;
 
-        // Strip "keyitem: " prefix so item_text matches getItemName() output
+        if (isProtectedFlag(bank, address, bit)) {
+            debugStream << "AP JSON: " << itemText << " targets protected flag Var["
+                        << bank << "][0x" << QString::number(address, 16) << "] bit "
+                        << bit << ", location left untracked\n";
+            continue;
+        }
+
+        // Strip "keyitem: " prefix so item_text matches getCanonicalItemName() output
         if (itemText.startsWith("keyitem: "))
             itemText = itemText.mid(9);
 
@@ -2216,7 +3185,7 @@ bool FieldPickupRandomizer_ff7tk::applySTITMAsArchipelago(
 {
     if (info.offset + STITM_SIZE > fieldData.size()) return false;
 
-    QString itemName = getItemName(info.originalItemID).toLower().trimmed();
+    QString itemName = getCanonicalItemName(info.originalItemID).toLower().trimmed();
     QString key      = fieldName.toLower().trimmed() + QChar('|') + itemName;
 
     ApBitonCoord biton;
@@ -2230,9 +3199,9 @@ bool FieldPickupRandomizer_ff7tk::applySTITMAsArchipelago(
         biton = m_apJsonLastBiton[key];
         reusedBiton = true;
     } else {
-        debugStream << "  AP_STITM @" << info.offset
+        debugStream << "  AP_STITM @" << info.offset << ownerTag(info.entityIndex, info.scriptIndex)
                     << " WARN: no JSON entry for ("
-                    << fieldName << ", " << getItemName(info.originalItemID)
+                    << fieldName << ", " << getCanonicalItemName(info.originalItemID)
                     << ") – location will not be tracked\n";
         return false;
     }
@@ -2254,13 +3223,15 @@ bool FieldPickupRandomizer_ff7tk::applySTITMAsArchipelago(
     entry.offset         = info.offset;
     entry.isMateria      = false;
     entry.originalItemId = info.originalItemID;
-    entry.originalName   = getItemName(info.originalItemID);
+    entry.originalName   = getCanonicalItemName(info.originalItemID);
     entry.bankByte       = bankByte;
     entry.address        = addr;
     entry.bit            = bit;
+    entry.entityIndex    = info.entityIndex;
+    entry.scriptIndex    = info.scriptIndex;
     m_apBitonEntries.append(entry);
 
-    debugStream << "  AP_STITM @" << info.offset
+    debugStream << "  AP_STITM @" << info.offset << ownerTag(info.entityIndex, info.scriptIndex)
                 << "  " << entry.originalName
                 << " (" << info.originalItemID << ")"
                 << " -> BITON bank=" << destBank
@@ -2283,7 +3254,7 @@ bool FieldPickupRandomizer_ff7tk::applySMTRAAsArchipelago(
 {
     if (info.offset + SMTRA_SIZE > fieldData.size()) return false;
 
-    QString materiaName = getMateriaName(info.originalMateriaID).toLower().trimmed();
+    QString materiaName = getCanonicalMateriaName(info.originalMateriaID).toLower().trimmed();
     QString key         = fieldName.toLower().trimmed() + QChar('|') + materiaName;
 
     ApBitonCoord biton;
@@ -2297,9 +3268,9 @@ bool FieldPickupRandomizer_ff7tk::applySMTRAAsArchipelago(
         biton = m_apJsonLastBiton[key];
         reusedBiton = true;
     } else {
-        debugStream << "  AP_SMTRA @" << info.offset
+        debugStream << "  AP_SMTRA @" << info.offset << ownerTag(info.entityIndex, info.scriptIndex)
                     << " WARN: no JSON entry for ("
-                    << fieldName << ", " << getMateriaName(info.originalMateriaID)
+                    << fieldName << ", " << getCanonicalMateriaName(info.originalMateriaID)
                     << ") – location will not be tracked\n";
         return false;
     }
@@ -2321,13 +3292,15 @@ bool FieldPickupRandomizer_ff7tk::applySMTRAAsArchipelago(
     entry.offset           = info.offset;
     entry.isMateria        = true;
     entry.originalMateriaId = info.originalMateriaID;
-    entry.originalName     = getMateriaName(info.originalMateriaID);
+    entry.originalName     = getCanonicalMateriaName(info.originalMateriaID);
     entry.bankByte         = bankByte;
     entry.address          = addr;
     entry.bit              = bit;
+    entry.entityIndex      = info.entityIndex;
+    entry.scriptIndex      = info.scriptIndex;
     m_apBitonEntries.append(entry);
 
-    debugStream << "  AP_SMTRA @" << info.offset
+    debugStream << "  AP_SMTRA @" << info.offset << ownerTag(info.entityIndex, info.scriptIndex)
                 << "  " << entry.originalName
                 << " (" << info.originalMateriaID << ")"
                 << " -> BITON bank=" << destBank
@@ -2391,7 +3364,7 @@ int FieldPickupRandomizer_ff7tk::replaceVanillaBitonsForAP(
     quint32 sectionPositions[9];
     memcpy(sectionPositions, decompressed.constData() + 6, 9 * 4);
     quint32 sec0off = sectionPositions[0];
-    if (sec0off + 4 >= static_cast<quint32>(fileSize)) return 0;
+    if (sec0off >= static_cast<quint32>(fileSize - 4)) return 0;
 
     int sec0DataStart = static_cast<int>(sec0off) + 4;
     quint8 nbEnt = static_cast<quint8>(decompressed.at(sec0DataStart + 2));
@@ -2413,6 +3386,7 @@ int FieldPickupRandomizer_ff7tk::replaceVanillaBitonsForAP(
     QMap<QString, ApBitonCoord> keyItemBitons;
 
     // Scan script section for BITON opcodes
+    const QMap<int, ScriptOwner> owners = scriptEntryPoints(decompressed);
     for (int i = scriptStart; i < scriptEnd - 4; ++i) {
         quint8 opcode = static_cast<quint8>(decompressed.at(i));
 
@@ -2472,8 +3446,10 @@ int FieldPickupRandomizer_ff7tk::replaceVanillaBitonsForAP(
                         decompressed[i + 1] = static_cast<char>(newBankByte);
                         decompressed[i + 2] = static_cast<char>(apBiton.address);
                         decompressed[i + 3] = static_cast<char>(apBiton.bit);
+                        const ScriptOwner owner = scriptOwnerAt(owners, i);
 
                         debugStream << "  AP_VANILLA_BITON @" << i
+                                    << ownerTag(owner.entity, owner.script)
                                     << " " << keyItemName
                                     << " -> bank=" << apBiton.bank
                                     << " addr=0x" << QString::number(apBiton.address, 16)
@@ -2494,6 +3470,8 @@ int FieldPickupRandomizer_ff7tk::replaceVanillaBitonsForAP(
                         entry.bankByte = newBankByte;
                         entry.address = apBiton.address;
                         entry.bit = apBiton.bit;
+                        entry.entityIndex = owner.entity;
+                        entry.scriptIndex = owner.script;
                         m_apBitonEntries.append(entry);
 
                         modified++;
@@ -2532,7 +3510,9 @@ int FieldPickupRandomizer_ff7tk::replaceVanillaBitonsForAP(
 //         "original_name": "Hi-Potion",
 //         "bank": 1,
 //         "address": 128,
-//         "bit": 0
+//         "bit": 0,
+//         "entity": 3,
+//         "script": 1
 //       }, ...
 //     ]
 //   }
@@ -2561,6 +3541,8 @@ void FieldPickupRandomizer_ff7tk::writeArchipelagoSidecar(
         obj["bank"]           = static_cast<int>((e.bankByte >> 4) & 0x0F);
         obj["address"]        = static_cast<int>(e.address);
         obj["bit"]            = static_cast<int>(e.bit);
+        obj["entity"]         = e.entityIndex;
+        obj["script"]         = e.scriptIndex;
         arr.append(obj);
     }
 
@@ -2580,6 +3562,715 @@ void FieldPickupRandomizer_ff7tk::writeArchipelagoSidecar(
     }
 }
 
+// ============================================================================
+// recordTrackerEntries / writeTrackerMap  –  emit tracker_map.json
+//
+// For every randomized location: where it is (field, offset, entity, script),
+// what it now holds, and the savemap bit that flips when it is collected.
+//   {
+//     "savemapBanks": { "1": 2980, ... },       // field bank -> savemap offset
+//     "inventory": { "items": 1276, "materia": 1916 },
+//     "locations": [
+//       { "field": "mds7st1", "offset": 2908, "entity": 3, "script": 1,
+//         "kind": "item", "id": 32, "name": "Hi-Potion",
+//         "flag": { "bank": 3, "address": 128, "bit": 0, "savemapOffset": 3364 } },
+//       ...
+//     ]
+//   }
+// "flag" is null when the script sets no bit; trackers then look for the
+// item in the inventory (320 u16 item slots / 200 u32 materia slots).
+// ============================================================================
+
+void FieldPickupRandomizer_ff7tk::recordTrackerEntries(
+    const QByteArray& decompressed, const QString& fieldName,
+    const QVector<OpcodeModification>& modifications,
+    const KeyItemFieldMod* keyItemMod,
+    QVector<TrackerEntry>& entries) const
+{
+    const QMap<int, ScriptOwner> owners = scriptEntryPoints(decompressed);
+    auto addEntry = [&](int offset, const QString& kind, int placedId, const QString& name) {
+        const ScriptOwner owner = scriptOwnerAt(owners, offset);
+        TrackerEntry e;
+        e.field       = fieldName;
+        e.offset      = offset;
+        e.entityIndex = owner.entity;
+        e.scriptIndex = owner.script;
+        e.kind        = kind;
+        e.placedId    = placedId;
+        e.placedName  = name;
+        e.bank = e.address = e.bit = -1;   // left as is when no flag is found
+        pickupFlagAt(decompressed, owners, offset, e.bank, e.address, e.bit);
+        entries.append(e);
+    };
+
+    for (const OpcodeModification& mod : modifications) {
+        if (mod.isMateria) {
+            const auto* raw = reinterpret_cast<const OpcodeSMTRARaw*>(decompressed.constData() + mod.opcodeOffset);
+            addEntry(mod.opcodeOffset, QStringLiteral("materia"), raw->materiaID, mod.newName);
+        } else {
+            const auto* raw = reinterpret_cast<const OpcodeSTITMRaw*>(decompressed.constData() + mod.opcodeOffset);
+            addEntry(mod.opcodeOffset, QStringLiteral("item"), raw->itemID, mod.newName);
+        }
+    }
+
+    // A placed key item is its own flag: the possession bit it sets
+    if (keyItemMod) {
+        for (const KeyItemPlacement& p : keyItemMod->placements) {
+            const ScriptOwner owner = scriptOwnerAt(owners, p.targetOffset);
+            TrackerEntry e;
+            e.field       = fieldName;
+            e.offset      = p.targetOffset;
+            e.entityIndex = owner.entity;
+            e.scriptIndex = owner.script;
+            e.kind        = QStringLiteral("keyItem");
+            e.placedId    = -1;
+            e.placedName  = p.keyName;
+            e.bank        = (p.keyItem.bankByte >> 4) & 0x0F;
+            e.address     = p.keyItem.address;
+            e.bit         = p.keyItem.bit;
+            entries.append(e);
+        }
+    }
+}
+
+void FieldPickupRandomizer_ff7tk::writeTrackerMap(const QString& outputPath,
+                                                   QTextStream& debugStream) const
+{
+    const QString path = outputPath + "/tracker_map.json";
+
+    QJsonObject banks;
+    for (int bank = 1; bank <= 15; ++bank) {
+        if (SaveBootstrap::bankOffset(bank) >= 0)
+            banks[QString::number(bank)] = SaveBootstrap::bankOffset(bank);
+    }
+    QJsonObject inventory;
+    inventory["items"]   = 0x04FC;
+    inventory["materia"] = 0x077C;
+
+    QJsonArray locations;
+    int flagged = 0;
+    for (const TrackerEntry& e : m_trackerEntries) {
+        QJsonObject obj;
+        obj["field"]  = e.field;
+        obj["offset"] = e.offset;
+        obj["entity"] = e.entityIndex;
+        obj["script"] = e.scriptIndex;
+        obj["kind"]   = e.kind;
+        obj["id"]     = e.placedId;
+        obj["name"]   = e.placedName;
+        if (e.bank >= 0) {
+            QJsonObject flag;
+            flag["bank"]          = e.bank;
+            flag["address"]       = e.address;
+            flag["bit"]           = e.bit;
+            flag["savemapOffset"] = SaveBootstrap::bankOffset(e.bank) + e.address;
+            obj["flag"] = flag;
+            ++flagged;
+        } else {
+            obj["flag"] = QJsonValue::Null;
+        }
+        locations.append(obj);
+    }
+
+    QJsonObject root;
+    root["savemapBanks"] = banks;
+    root["inventory"]    = inventory;
+    root["locations"]    = locations;
+
+    QSaveFile f(path);
+    if (f.open(QIODevice::WriteOnly) && f.write(QJsonDocument(root).toJson()) >= 0 && f.commit()) {
+        debugStream << "\nTracker map written: " << path << "  (" << m_trackerEntries.size()
+                    << " locations, " << flagged << " with a savemap flag)\n";
+    } else {
+        debugStream << "\nERROR: could not write tracker map: " << path << "\n";
+        qDebug() << "ERROR writing tracker map:" << path;
+    }
+}
+
+// ============================================================================
+// Field patch log  –  field_patch_log.json
+// ============================================================================
+
+// Splits the difference between two versions of a field into byte ranges.
+// Same-size edits are split at every run of 4+ unchanged bytes; an edit that
+// resizes the field is logged as one range between the common prefix and
+// suffix.
+void FieldPickupRandomizer_ff7tk::appendPatchRecords(const QString& fieldName,
+                                                     const QByteArray& before,
+                                                     const QByteArray& after,
+                                                     const QString& reason,
+                                                     QVector<PatchRecord>& out)
+{
+    if (before.size() != after.size()) {
+        const int common = qMin(before.size(), after.size());
+        int prefix = 0;
+        while (prefix < common && before.at(prefix) == after.at(prefix))
+            ++prefix;
+        int suffix = 0;
+        while (suffix < common - prefix
+               && before.at(before.size() - 1 - suffix) == after.at(after.size() - 1 - suffix))
+            ++suffix;
+        out.append({fieldName, prefix,
+                    before.mid(prefix, before.size() - prefix - suffix),
+                    after.mid(prefix, after.size() - prefix - suffix), reason});
+        return;
+    }
+
+    const int kMergeGap = 4;
+    int pos = 0;
+    while (pos < before.size()) {
+        if (before.at(pos) == after.at(pos)) {
+            ++pos;
+            continue;
+        }
+        const int start = pos;
+        int end = pos + 1;      // one past the last changed byte
+        for (int k = end; k < before.size() && k - end < kMergeGap; ++k) {
+            if (before.at(k) != after.at(k))
+                end = k + 1;
+        }
+        out.append({fieldName, start, before.mid(start, end - start),
+                    after.mid(start, end - start), reason});
+        pos = end;
+    }
+}
+
+void FieldPickupRandomizer_ff7tk::writePatchLog(const QString& outputPath,
+                                                QTextStream& debugStream) const
+{
+    const QString path = outputPath + "/field_patch_log.json";
+
+    QJsonObject fields;
+    for (const PatchRecord& r : m_patchLog) {
+        QJsonObject obj;
+        obj["offset"] = r.offset;
+        obj["before"] = QString::fromLatin1(r.before.toHex(' '));
+        obj["after"]  = QString::fromLatin1(r.after.toHex(' '));
+        obj["reason"] = r.reason;
+        QJsonArray patches = fields[r.field].toArray();
+        patches.append(obj);
+        fields[r.field] = patches;
+    }
+
+    QJsonObject root;
+    root["note"]   = "Offsets are into the decompressed field, after the earlier patches of the same field";
+    root["fields"] = fields;
+
+    QSaveFile f(path);
+    if (f.open(QIODevice::WriteOnly) && f.write(QJsonDocument(root).toJson()) >= 0 && f.commit()) {
+        debugStream << "Field patch log written: " << path << "  (" << m_patchLog.size()
+                    << " ranges in " << fields.size() << " fields)\n";
+    } else {
+        debugStream << "ERROR: could not write field patch log: " << path << "\n";
+        qDebug() << "ERROR writing field patch log:" << path;
+    }
+}
+
+// ============================================================================
+// writeArchipelagoExport  –  emit archipelago_export.json
+//
+// The placement of a normal (non-AP) seed in the shape a multiworld generator
+// reads: every randomized location under a stable name, what it holds, and
+// that item's Archipelago classification.
+//   {
+//     "game": "Final Fantasy VII", "formatVersion": 2,
+//     "locations": [
+//       { "location": "mds7st1 STITM #2", "item": "Hi-Potion", "kind": "item",
+//         "id": 1, "classification": "filler" },
+//       ...
+//     ],
+//     "locationTable": { "mds7st1 STITM #2": { "field": "mds7st1", "offset": 2908,
+//                          "opcode": "STITM", "entity": 3, "script": 1,
+//                          "vanilla": "Potion" }, ... }
+//   }
+// The table lists every candidate slot of the vanilla fields, randomized this
+// run or not: direct STITMs and SMTRAs, and the key item BITONs that can host
+// a key item. A slot is named after its field, opcode and rank by offset
+// among that field's vanilla opcodes of the same kind. Rewrites never resize
+// scripts, so the table is the same for every seed and setting.
+// ============================================================================
+
+QMap<int, FieldPickupRandomizer_ff7tk::ExportLocation>
+FieldPickupRandomizer_ff7tk::exportLocations(const QByteArray& decompressed, const QString& fieldName)
+{
+    QString discarded;
+    QTextStream scanLog(&discarded);
+    QMap<int, ExportLocation> slots;
+
+    int ordinal = 0;
+    for (const STITMInfo& info : scanForSTITM(decompressed, fieldName, scanLog)) {
+        if (!validateSTITM(info)) continue;
+        slots.insert(info.offset, {QString("%1 STITM #%2").arg(fieldName).arg(++ordinal),
+                                   QStringLiteral("STITM"), info.entityIndex, info.scriptIndex,
+                                   getItemName(info.originalItemID)});
+    }
+    ordinal = 0;
+    for (const SMTRAInfo& info : scanForSMTRA(decompressed, fieldName, scanLog)) {
+        if (!validateSMTRA(info)) continue;
+        slots.insert(info.offset, {QString("%1 SMTRA #%2").arg(fieldName).arg(++ordinal),
+                                   QStringLiteral("SMTRA"), info.entityIndex, info.scriptIndex,
+                                   getMateriaName(info.originalMateriaID)});
+    }
+
+    // Key item BITONs, matched the way collectKeyItemsAndStitm() finds them
+    FieldScript::ScriptRange range;
+    if (!FieldScript::scriptRange(decompressed, range)) return slots;
+    quint16 posTexts = 0;
+    memcpy(&posTexts, decompressed.constData() + range.dataStart + 4, 2);
+    const int scriptStart = range.offsetTable + 64 * range.entityCount;
+    const int scriptEnd   = qMin(range.dataStart + static_cast<int>(posTexts),
+                                 static_cast<int>(decompressed.size()));
+    const QMap<int, ScriptOwner> owners = scriptEntryPoints(decompressed);
+    ordinal = 0;
+    for (int i = scriptStart; i + 3 < scriptEnd; ++i) {
+        if (static_cast<quint8>(decompressed.at(i)) != BITON_OPCODE) continue;
+        const quint8 bankByte = static_cast<quint8>(decompressed.at(i + 1));
+        const quint8 destBank = (bankByte >> 4) & 0x0F;
+        const quint8 address  = static_cast<quint8>(decompressed.at(i + 2));
+        const quint8 bitNum   = static_cast<quint8>(decompressed.at(i + 3));
+        if (destBank < 1 || destBank > 2 || (bankByte & 0x0F) != 0
+            || address < 0x40 || address > 0x46 || bitNum > 7
+            || isProtectedFlag(destBank, address, bitNum))
+            continue;
+        const ScriptOwner owner = scriptOwnerAt(owners, i);
+        slots.insert(i, {QString("%1 BITON #%2").arg(fieldName).arg(++ordinal),
+                         QStringLiteral("BITON"), owner.entity, owner.script,
+                         getKeyItemName(0x0BA4 + address, bitNum)});
+    }
+    return slots;
+}
+
+QString FieldPickupRandomizer_ff7tk::archipelagoClassification(const TrackerEntry& entry) const
+{
+    if (entry.kind == QLatin1String("keyItem")) return QStringLiteral("progression");
+    if (entry.kind == QLatin1String("item") && entry.placedId == ITEM_BATTERY)
+        return QStringLiteral("progression");   // the wire climb needs them
+    if (entry.kind == QLatin1String("materia")
+        || isTopTierItem(static_cast<quint16>(entry.placedId)))
+        return QStringLiteral("useful");
+    return QStringLiteral("filler");
+}
+
+void FieldPickupRandomizer_ff7tk::writeArchipelagoExport(const QString& outputPath,
+                                                         QTextStream& debugStream) const
+{
+    const QString path = outputPath + "/archipelago_export.json";
+
+    QJsonObject table;
+    int slotCount = 0;
+    for (auto field = m_exportLocations.cbegin(); field != m_exportLocations.cend(); ++field) {
+        for (auto slot = field.value().cbegin(); slot != field.value().cend(); ++slot) {
+            QJsonObject where;
+            where["field"]   = field.key();
+            where["offset"]  = slot.key();
+            where["opcode"]  = slot.value().opcode;
+            where["entity"]  = slot.value().entityIndex;
+            where["script"]  = slot.value().scriptIndex;
+            where["vanilla"] = slot.value().vanilla;
+            table[slot.value().name] = where;
+            ++slotCount;
+        }
+    }
+
+    QVector<const TrackerEntry*> entries;
+    for (const TrackerEntry& e : m_trackerEntries)
+        entries.append(&e);
+    std::stable_sort(entries.begin(), entries.end(), [](const TrackerEntry* a, const TrackerEntry* b) {
+        return a->field != b->field ? a->field < b->field : a->offset < b->offset;
+    });
+
+    QJsonArray locations;
+    QMap<QString, int> counts;
+    for (const TrackerEntry* entry : entries) {
+        const TrackerEntry& e = *entry;
+        const QString name = m_exportLocations.value(e.field).value(e.offset).name;
+        if (name.isEmpty()) {
+            debugStream << "  WARNING: Archipelago export: " << e.field << " @" << e.offset
+                        << " is not a vanilla candidate slot, left out\n";
+            continue;
+        }
+        const QString classification = archipelagoClassification(e);
+        ++counts[classification];
+
+        QJsonObject location;
+        location["location"]       = name;
+        location["item"]           = e.placedName;
+        location["kind"]           = e.kind;
+        location["id"]             = e.placedId;
+        location["classification"] = classification;
+        locations.append(location);
+    }
+
+    QJsonObject root;
+    root["game"]          = QStringLiteral("Final Fantasy VII");
+    root["formatVersion"] = 2;
+    root["locations"]     = locations;
+    root["locationTable"] = table;
+
+    QSaveFile f(path);
+    if (f.open(QIODevice::WriteOnly) && f.write(QJsonDocument(root).toJson()) >= 0 && f.commit()) {
+        debugStream << "\nArchipelago export written: " << path << "  (" << slotCount
+                    << " slots, " << locations.size() << " randomized: " << counts.value("progression") << " progression, "
+                    << counts.value("useful") << " useful, " << counts.value("filler") << " filler)\n";
+    } else {
+        debugStream << "\nERROR: could not write Archipelago export: " << path << "\n";
+        qDebug() << "ERROR writing Archipelago export:" << path;
+    }
+}
+
+// ============================================================================
+// applyKeyItemSoundCue  –  distinct sound for a placed key item
+// ============================================================================
+
+bool FieldPickupRandomizer_ff7tk::applyKeyItemSoundCue(QByteArray& decompressed, int targetOffset,
+                                                       QTextStream& debugStream)
+{
+    auto isLiteralSound = [&decompressed](int pos) {
+        return pos >= 0 && pos + 4 < decompressed.size()
+            && static_cast<quint8>(decompressed.at(pos)) == SOUND_OPCODE
+            && static_cast<quint8>(decompressed.at(pos + 1)) == 0x00;
+    };
+
+    // Walk the owning script up to the grant to find the opcode right before
+    // it, then step over the grant and its NOP padding to the one right after
+    const QMap<int, ScriptOwner> owners = scriptEntryPoints(decompressed);
+    auto owner = owners.upperBound(targetOffset);
+    int sound = -1;
+    if (owner != owners.constBegin()) {
+        const int end = owner == owners.constEnd() ? decompressed.size() : owner.key();
+        int pos = (--owner).key(), previous = -1, guard = 0;
+        while (pos < targetOffset && guard++ < 4000) {
+            const int len = FieldScript::opcodeLength(decompressed, pos, decompressed.size());
+            if (len <= 0) break;
+            previous = pos;
+            pos += len;
+        }
+        if (pos == targetOffset) {
+            if (isLiteralSound(previous)) {
+                sound = previous;
+            } else {
+                int next = targetOffset + BITON_SIZE;
+                while (next < end && static_cast<quint8>(decompressed.at(next)) == 0x5F)
+                    ++next;
+                if (next < end && isLiteralSound(next))
+                    sound = next;
+            }
+        }
+    }
+    if (sound < 0) {
+        debugStream << "  KEY_ITEM_SOUND @" << targetOffset << ": no SOUND next to the grant, uncued\n";
+        return false;
+    }
+
+    quint16 oldId = 0;
+    memcpy(&oldId, decompressed.constData() + sound + 2, 2);
+    const quint16 newId = KEY_ITEM_SOUND_ID;
+    memcpy(decompressed.data() + sound + 2, &newId, 2);
+    debugStream << "  KEY_ITEM_SOUND @" << sound << ": sound " << oldId << " -> " << newId << "\n";
+    return true;
+}
+
+// ============================================================================
+// scaleFieldModels  –  cosmetic per-map model scale
+//
+// Section 3 (model loader) layout, offsets from sectionPositions[2]:
+//   +0  u32 section size
+//   +4  u16 unknown
+//   +6  u16 nbModels
+//   +8  u16 global scale
+//   then per model:
+//     u16 nameLength, name[nameLength], u16 unknown, char hrc[8],
+//     char scale[4] (ASCII decimal, e.g. "512"), u16 nbAnimations,
+//     30 bytes of light data,
+//     then per animation: u16 nameLength, name[nameLength], u16 unknown
+//
+// The whole loader is walked first and nothing is written unless every model
+// parses inside the section. New scales keep the digit count of the original
+// so the edit is length-preserving.
+// ============================================================================
+
+bool FieldPickupRandomizer_ff7tk::scaleFieldModels(QByteArray& decompressed,
+                                                   const QString& fieldName,
+                                                   QRandomGenerator& rng,
+                                                   QTextStream& debugStream)
+{
+    const int fileSize = decompressed.size();
+    if (fileSize < 6 + 9 * 4) return false;
+
+    quint32 sectionPositions[9];
+    memcpy(sectionPositions, decompressed.constData() + 6, 9 * 4);
+    const qint64 secStart = sectionPositions[2];
+    const qint64 secEnd = sectionPositions[3];
+    if (secStart + 10 > secEnd || secEnd > fileSize) return false;
+
+    auto u16At = [&](qint64 pos) {
+        quint16 v = 0;
+        memcpy(&v, decompressed.constData() + pos, 2);
+        return v;
+    };
+
+    const quint16 nbModels = u16At(secStart + 6);
+    QVector<int> scaleOffsets;
+    qint64 pos = secStart + 10;
+    for (int m = 0; m < nbModels; ++m) {
+        if (pos + 2 > secEnd) return false;
+        pos += 2 + u16At(pos);                 // name
+        pos += 2 + 8;                          // unknown + HRC name
+        if (pos + 4 + 2 + 30 > secEnd) return false;
+        scaleOffsets.append(static_cast<int>(pos));
+        pos += 4;
+        const quint16 nbAnims = u16At(pos);
+        pos += 2 + 30;                         // animation count + lights
+        for (int a = 0; a < nbAnims; ++a) {
+            if (pos + 2 > secEnd) return false;
+            pos += 2 + u16At(pos) + 2;         // name + unknown
+        }
+        if (pos > secEnd) return false;
+    }
+    if (scaleOffsets.isEmpty()) return false;
+
+    // One factor per map so a field's cast stays consistent with each other
+    const int percent = MODEL_SCALE_MIN_PERCENT
+        + static_cast<int>(rng.bounded(MODEL_SCALE_MAX_PERCENT - MODEL_SCALE_MIN_PERCENT + 1));
+    int scaled = 0;
+    for (int off : scaleOffsets) {
+        int digits = 0;
+        while (digits < 4 && decompressed.at(off + digits) >= '0'
+               && decompressed.at(off + digits) <= '9')
+            ++digits;
+        if (digits < 3) continue;              // unexpected text, leave it vanilla
+        const int oldScale = decompressed.mid(off, digits).toInt();
+        const int lo = digits == 3 ? 100 : 1000;
+        const int hi = digits == 3 ? 999 : 9999;
+        const int newScale = qBound(lo, oldScale * percent / 100, hi);
+        const QByteArray text = QByteArray::number(newScale);
+        memcpy(decompressed.data() + off, text.constData(), digits);
+        ++scaled;
+    }
+    if (scaled == 0) return false;
+    debugStream << "  MODEL_SCALE " << fieldName << ": " << scaled << " model(s) at "
+                << percent << "%\n";
+    return true;
+}
+
+// ============================================================================
+// Field encounter tables  –  section 7
+//
+// Section 7 holds two 24-byte tables (the script switches between them),
+// offsets from sectionPositions[6]:
+//   +0  u32 section size
+//   then per table:
+//     u8  enabled, u8 encounter rate
+//     u16 normal battles[6], u16 back attacks[2], u16 side attack,
+//     u16 pincer attack, u16 padding
+// Each battle is (probability << 10) | battle ID, the probabilities in 64ths.
+//
+// Only normal battles with a non-zero probability move; back/side/pincer
+// slots name formations built for that attack and stay as they are. Battle
+// IDs are scene.bin formations (4 per scene), so a boss scene is spotted
+// with BossList and never moved either. Probabilities stay on their slots.
+// ============================================================================
+
+namespace {
+const int ENCOUNTER_TABLE_COUNT  = 2;
+const int ENCOUNTER_TABLE_SIZE   = 24;
+const int ENCOUNTER_NORMAL_SLOTS = 6;
+
+// Absolute offset of the first encounter table, or -1 when section 7 is
+// missing or too short for both tables
+int encounterTablesAt(const QByteArray& d)
+{
+    if (d.size() < 6 + 9 * 4) return -1;
+    quint32 sectionPositions[9];
+    memcpy(sectionPositions, d.constData() + 6, 9 * 4);
+    const qint64 start = static_cast<qint64>(sectionPositions[6]) + 4;
+    const qint64 end = qMin<qint64>(sectionPositions[7], d.size());
+    if (start + ENCOUNTER_TABLE_COUNT * ENCOUNTER_TABLE_SIZE > end) return -1;
+    return static_cast<int>(start);
+}
+
+quint16 encounterSlotAt(const QByteArray& d, int pos)
+{
+    quint16 v = 0;
+    memcpy(&v, d.constData() + pos, 2);
+    return v;
+}
+
+// Normal battle slots of an enabled table that may move: non-zero
+// probability, not a boss formation
+QVector<int> movableEncounterSlots(const QByteArray& d, int table)
+{
+    QVector<int> slots;
+    if (d.at(table) == 0) return slots;
+    for (int i = 0; i < ENCOUNTER_NORMAL_SLOTS; ++i) {
+        const int pos = table + 2 + 2 * i;
+        const quint16 v = encounterSlotAt(d, pos);
+        if ((v >> 10) == 0) continue;
+        if (BossList::instance().isBossScene((v & 0x3FF) / SceneCatalog::FORMATIONS_PER_SCENE)) continue;
+        slots.append(pos);
+    }
+    return slots;
+}
+} // namespace
+
+void FieldPickupRandomizer_ff7tk::collectFieldEncounterPool(MakouLgpManager& lgp,
+                                                            const QStringList& allFiles,
+                                                            QTextStream& debugStream)
+{
+    m_fieldEncounterPool.clear();
+    const InputCache* cache = m_parent ? m_parent->inputCache() : nullptr;
+    int battles = 0;
+    for (const QString& fileName : allFiles) {
+        if (fileName.startsWith("blackbg") || fileName == "onna_5") continue;
+        const int sphere = getFieldSphere(fileName.toLower());
+        if (sphere == UNKNOWN_SPHERE) continue;
+        const QByteArray raw = cache ? cache->fieldData(fileName) : lgp.fileData(fileName);
+        const QByteArray decompressed = LZS::decompressAllWithHeader(raw);
+        const int tables = encounterTablesAt(decompressed);
+        if (tables < 0) continue;
+        QVector<quint16>& pool = m_fieldEncounterPool[sphere];
+        for (int t = 0; t < ENCOUNTER_TABLE_COUNT; ++t) {
+            for (int pos : movableEncounterSlots(decompressed, tables + t * ENCOUNTER_TABLE_SIZE)) {
+                const quint16 battleId = encounterSlotAt(decompressed, pos) & 0x3FF;
+                if (!pool.contains(battleId)) {
+                    pool.append(battleId);
+                    ++battles;
+                }
+            }
+        }
+    }
+    debugStream << "ENCOUNTERS: " << battles << " field battle(s) in "
+                << m_fieldEncounterPool.size() << " sphere(s) for rerolls\n\n";
+}
+
+bool FieldPickupRandomizer_ff7tk::randomizeFieldEncounters(QByteArray& decompressed,
+                                                           const QString& fieldName,
+                                                           QRandomGenerator& rng,
+                                                           QTextStream& debugStream)
+{
+    const int tables = encounterTablesAt(decompressed);
+    if (tables < 0) return false;
+
+    const int mode = m_parent->m_config.getFieldEncounterMode();
+    const bool rerollRates = m_parent->m_config.getRandomizeFieldEncounterRates();
+
+    // Fields outside the sphere list have no pool to draw from and are
+    // shuffled instead
+    const int sphere = getFieldSphere(fieldName.toLower());
+    QVector<quint16> candidates;
+    if (mode == Config::FieldEncountersReroll && sphere != UNKNOWN_SPHERE) {
+        for (int s = sphere - FIELD_ENCOUNTER_SPHERE_SPREAD; s <= sphere + FIELD_ENCOUNTER_SPHERE_SPREAD; ++s) {
+            for (quint16 battleId : m_fieldEncounterPool.value(s)) {
+                if (!candidates.contains(battleId))
+                    candidates.append(battleId);
+            }
+        }
+    }
+
+    bool changed = false;
+    for (int t = 0; t < ENCOUNTER_TABLE_COUNT; ++t) {
+        const int table = tables + t * ENCOUNTER_TABLE_SIZE;
+        if (decompressed.at(table) == 0) continue;
+
+        const QVector<int> slots = movableEncounterSlots(decompressed, table);
+        QVector<quint16> oldIds;
+        for (int pos : slots)
+            oldIds.append(encounterSlotAt(decompressed, pos) & 0x3FF);
+        QVector<quint16> newIds = oldIds;
+        if (mode == Config::FieldEncountersReroll && !candidates.isEmpty()) {
+            for (quint16& battleId : newIds)
+                battleId = candidates[rng.bounded(static_cast<int>(candidates.size()))];
+        } else if (mode != Config::FieldEncountersVanilla) {
+            for (int i = newIds.size() - 1; i > 0; --i)
+                newIds.swapItemsAt(i, rng.bounded(i + 1));
+        }
+        for (int i = 0; i < slots.size(); ++i) {
+            const quint16 v = static_cast<quint16>((encounterSlotAt(decompressed, slots[i]) & 0xFC00) | newIds[i]);
+            memcpy(decompressed.data() + slots[i], &v, 2);
+        }
+
+        const quint8 oldRate = static_cast<quint8>(decompressed.at(table + 1));
+        quint8 newRate = oldRate;
+        if (rerollRates && oldRate > 0) {
+            const int percent = FIELD_ENCOUNTER_RATE_MIN_PERCENT
+                + static_cast<int>(rng.bounded(FIELD_ENCOUNTER_RATE_MAX_PERCENT - FIELD_ENCOUNTER_RATE_MIN_PERCENT + 1));
+            newRate = static_cast<quint8>(qBound(1, oldRate * percent / 100, 255));
+            decompressed[table + 1] = static_cast<char>(newRate);
+        }
+
+        if (newIds == oldIds && newRate == oldRate) continue;
+        changed = true;
+        auto idList = [](const QVector<quint16>& ids) {
+            QStringList parts;
+            for (quint16 id : ids) parts << QString::number(id);
+            return parts.join(",");
+        };
+        debugStream << "  ENCOUNTERS " << fieldName << " table " << (t + 1) << ": battles "
+                    << idList(oldIds) << " -> " << idList(newIds)
+                    << ", rate " << static_cast<int>(oldRate) << " -> " << static_cast<int>(newRate) << "\n";
+    }
+    return changed;
+}
+
+// ============================================================================
+// randomizeScriptedBattles  –  BATTLE (0x70) formations in field scripts
+//
+// A fight only moves to a formation that ScriptedBattleTable lists in the
+// same group: being rolled by some encounter table near the field says
+// nothing about whether the script around the fight still works with
+// another one. Unlisted formations stay put, as do boss scenes (BossList)
+// and BATTLEs whose formation comes from a variable. Scripts are walked
+// opcode by opcode so an operand byte is never taken for a BATTLE.
+// ============================================================================
+
+int FieldPickupRandomizer_ff7tk::randomizeScriptedBattles(QByteArray& decompressed,
+                                                          const QString& fieldName,
+                                                          QRandomGenerator& rng,
+                                                          QTextStream& debugStream)
+{
+    const ScriptedBattleTable& table = ScriptedBattleTable::instance();
+    if (table.groupCount() == 0) return 0;
+    FieldScript::ScriptRange range;
+    if (!FieldScript::scriptRange(decompressed, range)) return 0;
+
+    // One replacement per formation, so every branch that starts the same
+    // fight still starts the same one
+    QMap<quint16, quint16> remap;
+    QSet<int> seen;
+    int changed = 0;
+    const QMap<int, ScriptOwner> owners = scriptEntryPoints(decompressed);
+    for (auto it = owners.constBegin(); it != owners.constEnd(); ++it) {
+        const auto next = std::next(it);
+        const int scriptEnd = next == owners.constEnd() ? range.walkEnd
+                                                        : qMin(next.key(), range.walkEnd);
+        for (int pos = it.key(); pos < scriptEnd;) {
+            const int len = FieldScript::opcodeLength(decompressed, pos, scriptEnd);
+            if (len <= 0) break;
+            if (static_cast<quint8>(decompressed.at(pos)) == BATTLE_OPCODE && len == BATTLE_SIZE
+                && decompressed.at(pos + 1) == 0 && !seen.contains(pos)) {
+                seen.insert(pos);
+                quint16 formation = 0;
+                memcpy(&formation, decompressed.constData() + pos + 2, 2);
+                const QVector<int> equivalents = table.equivalents(formation);
+                if (!equivalents.isEmpty()
+                    && !BossList::instance().isBossScene(formation / SceneCatalog::FORMATIONS_PER_SCENE)) {
+                    if (!remap.contains(formation)) {
+                        const int pick = equivalents[rng.bounded(static_cast<int>(equivalents.size()))];
+                        remap.insert(formation, static_cast<quint16>(pick));
+                    }
+                    const quint16 newFormation = remap.value(formation);
+                    memcpy(decompressed.data() + pos + 2, &newFormation, 2);
+                    debugStream << "  BATTLE " << fieldName << ownerTag(it.value().entity, it.value().script)
+                                << " @" << pos << ": formation " << formation << " -> " << newFormation << "\n";
+                    ++changed;
+                }
+            }
+            pos += len;
+        }
+    }
+    return changed;
+}
+
 // ============================================================================
 // updateFieldTexts  –  parse text section in section 0, replace item/materia
 //                      names, rebuild text section with correct offsets.
@@ -2604,10 +4295,63 @@ void FieldPickupRandomizer_ff7tk::writeArchipelagoSidecar(
 //   [text data: encoded strings separated by 0xFF terminators]
 // ============================================================================
 
+QString FieldPickupRandomizer_ff7tk::fieldLayoutIssue(const QByteArray& decompressed)
+{
+    // Same walk as updateFieldTexts, but reporting what it would bail on
+    const int fileSize = decompressed.size();
+    const int FIELD_HEADER_SIZE = 6 + 9 * 4;
+    if (fileSize < FIELD_HEADER_SIZE) return QStringLiteral("truncated header");
+
+    quint32 sectionCount;
+    memcpy(&sectionCount, decompressed.constData() + 2, 4);
+    if (sectionCount != 9)
+        return QString("%1 sections instead of 9").arg(sectionCount);
+
+    quint32 sectionPositions[9];
+    memcpy(sectionPositions, decompressed.constData() + 6, 9 * 4);
+    for (int i = 0; i < 9; ++i) {
+        if (sectionPositions[i] >= static_cast<quint32>(fileSize)
+            || (i > 0 && sectionPositions[i] <= sectionPositions[i - 1]))
+            return QString("section %1 offset out of order").arg(i);
+    }
+
+    const int sec0DataStart = static_cast<int>(sectionPositions[0]) + 4;
+    const int sec0DataLen   = static_cast<int>(sectionPositions[1]) - sec0DataStart;
+    if (sec0DataLen < 32) return QStringLiteral("script section too small");
+
+    quint16 posTexts;
+    memcpy(&posTexts, decompressed.constData() + sec0DataStart + 4, 2);
+    if (posTexts + 4 > sec0DataLen) return QStringLiteral("text table outside the script section");
+
+    quint16 firstTextOff;
+    memcpy(&firstTextOff, decompressed.constData() + sec0DataStart + posTexts + 2, 2);
+    const int textCount = firstTextOff / 2 - 1;
+    if (textCount <= 0 || textCount > 255)
+        return QString("%1 texts (expected 1-255)").arg(textCount);
+
+    // Retranslations re-pack the texts; offsets must ascend, stay in the
+    // section, and each text must end on the 0xFF terminator
+    quint16 previous = 0;
+    for (int i = 0; i < textCount; ++i) {
+        quint16 offset;
+        const int entry = sec0DataStart + posTexts + 2 + i * 2;
+        if (entry + 2 > fileSize) return QStringLiteral("text offset table truncated");
+        memcpy(&offset, decompressed.constData() + entry, 2);
+        if (offset < previous || posTexts + offset >= sec0DataLen)
+            return QString("text %1 offset out of range").arg(i);
+        if (i > 0 && offset > previous
+            && static_cast<quint8>(decompressed.at(sec0DataStart + posTexts + offset - 1)) != 0xFF)
+            return QString("text %1 not 0xFF-terminated").arg(i - 1);
+        previous = offset;
+    }
+    return QString();
+}
+
 bool FieldPickupRandomizer_ff7tk::updateFieldTexts(
     QByteArray& decompressed,
     const QVector<OpcodeModification>& modifications,
-    QTextStream& debugStream)
+    QTextStream& debugStream,
+    bool silent)
 {
     if (modifications.isEmpty()) return false;
 
@@ -2621,7 +4365,7 @@ bool FieldPickupRandomizer_ff7tk::updateFieldTexts(
 
     quint32 sec0off = sectionPositions[0];
     quint32 sec1off = sectionPositions[1];
-    if (sec0off + 4 >= static_cast<quint32>(fileSize) ||
+    if (sec0off >= static_cast<quint32>(fileSize - 4) ||
         sec1off > static_cast<quint32>(fileSize) ||
         sec1off <= sec0off + 4)
         return false;
@@ -2689,66 +4433,85 @@ bool FieldPickupRandomizer_ff7tk::updateFieldTexts(
     //   MESSAGE windowID textID  (show "Received X!")
     //   ...
     //   STITM itemID qty         (give item)
-    // So we search BOTH directions, pick the closest, and track used offsets
-    // to prevent multiple STITMs from claiming the same MESSAGE.
+    // So we search BOTH directions within the configured radius, pick the
+    // closest, and track used offsets to prevent multiple STITMs from
+    // claiming the same MESSAGE. By default only MESSAGE opcodes found by
+    // walking the pickup's own script count: a raw 0x40 byte can be an
+    // operand, and a MESSAGE in another script is usually story text. A
+    // pickup with no such MESSAGE is granted silently, its text unchanged.
+    const int searchRadius = m_parent ? m_parent->m_config.getMessageSearchRadius()
+                                      : Config::MESSAGE_SEARCH_RADIUS_DEFAULT;
+    const bool sameScriptOnly = !m_parent || m_parent->m_config.getMessageSearchSameScript();
+    const QMap<int, ScriptOwner> owners = sameScriptOnly ? scriptEntryPoints(decompressed)
+                                                         : QMap<int, ScriptOwner>();
     bool anyChanged = false;
     QVector<QByteArray> newTextEntries;       // new texts to append
     QVector<QPair<int, int>> messagePatches;  // (absOffset of MESSAGE textID byte, newTextID)
     QSet<int> usedMessageOffsets;             // prevent double-assignment
 
+    // The placement's address byte can equal MESSAGE_OPCODE (e.g. wardrobe
+    // key items use address 0x40 == MESSAGE), so the bytes we just wrote are
+    // never taken for a MESSAGE
+    const int skipPlacementBytes = 5; // covers 4-byte BITON or 5-byte STITM slot
+    auto usableMessage = [&](int pos, int opcodeOffset) {
+        if (pos + 2 >= scriptAbsEnd || usedMessageOffsets.contains(pos)) return false;
+        if (pos >= opcodeOffset && pos < opcodeOffset + skipPlacementBytes) return false;
+        if (qAbs(pos - opcodeOffset) > searchRadius) return false;
+        const quint8 winID = static_cast<quint8>(decompressed.at(pos + 1));
+        const quint8 txtID = static_cast<quint8>(decompressed.at(pos + 2));
+        return winID <= 15 && txtID < textCount;
+    };
+    // Closest wins; on a tie the MESSAGE before the pickup does
+    auto closer = [](int candidate, int best, int opcodeOffset) {
+        if (best < 0) return true;
+        const int d = qAbs(candidate - opcodeOffset), bestD = qAbs(best - opcodeOffset);
+        return d < bestD || (d == bestD && candidate < best);
+    };
+
     for (const auto& mod : modifications) {
-        int backOff = -1, fwdOff = -1;
+        int messageOff = -1;
 
-        // Search backward first (up to 500 bytes)
-        {
-            int searchStart = qMax(mod.opcodeOffset - 500, sec0DataStart);
-            for (int pos = mod.opcodeOffset - 1; pos >= searchStart; --pos) {
+        if (sameScriptOnly) {
+            auto next = owners.upperBound(mod.opcodeOffset);
+            if (next == owners.constBegin()) {
+                debugStream << "  SILENT @" << mod.opcodeOffset
+                            << ": not inside a script, text left unchanged\n";
+                continue;
+            }
+            const int scriptStart = std::prev(next).key();
+            const int scriptEnd = next == owners.constEnd() ? scriptAbsEnd
+                                                            : qMin(next.key(), scriptAbsEnd);
+            for (int pos = scriptStart; pos < scriptEnd;) {
+                const int len = FieldScript::opcodeLength(decompressed, pos, scriptEnd);
+                if (len <= 0) break;
                 if (static_cast<quint8>(decompressed.at(pos)) == MESSAGE_OPCODE
-                    && pos + 2 < scriptAbsEnd) {
-                    quint8 winID = static_cast<quint8>(decompressed.at(pos + 1));
-                    quint8 txtID = static_cast<quint8>(decompressed.at(pos + 2));
-                    if (winID <= 15 && txtID < textCount && !usedMessageOffsets.contains(pos)) {
-                        backOff = pos;
-                        break;
-                    }
-                }
+                    && usableMessage(pos, mod.opcodeOffset) && closer(pos, messageOff, mod.opcodeOffset))
+                    messageOff = pos;
+                pos += len;
             }
-        }
-
-        // Search forward (up to 500 bytes), but skip past the BITON/STITM bytes
-        // we just wrote. The placement's address byte can equal MESSAGE_OPCODE
-        // (e.g. wardrobe key items use address 0x40 == MESSAGE), which would
-        // otherwise produce a false-positive MESSAGE hit and clobber the byte
-        // immediately after our placement when its textID is patched.
-        {
-            const int skipPlacementBytes = 5; // covers 4-byte BITON or 5-byte STITM slot
-            int searchEnd = qMin(mod.opcodeOffset + 500, scriptAbsEnd - 2);
-            for (int pos = mod.opcodeOffset + skipPlacementBytes; pos < searchEnd; ++pos) {
-                if (static_cast<quint8>(decompressed.at(pos)) == MESSAGE_OPCODE) {
-                    quint8 winID = static_cast<quint8>(decompressed.at(pos + 1));
-                    quint8 txtID = static_cast<quint8>(decompressed.at(pos + 2));
-                    if (winID <= 15 && txtID < textCount && !usedMessageOffsets.contains(pos)) {
-                        fwdOff = pos;
-                        break;
-                    }
-                }
+        } else {
+            const int searchStart = qMax(mod.opcodeOffset - searchRadius, sec0DataStart);
+            const int searchEnd = qMin(mod.opcodeOffset + searchRadius + 1, scriptAbsEnd - 2);
+            for (int pos = searchStart; pos < searchEnd; ++pos) {
+                if (static_cast<quint8>(decompressed.at(pos)) == MESSAGE_OPCODE
+                    && usableMessage(pos, mod.opcodeOffset) && closer(pos, messageOff, mod.opcodeOffset))
+                    messageOff = pos;
             }
         }
 
-        // Pick the closest MESSAGE
-        int messageOff = -1;
-        if (backOff >= 0 && fwdOff >= 0) {
-            int backDist = mod.opcodeOffset - backOff;
-            int fwdDist  = fwdOff - mod.opcodeOffset;
-            messageOff = (backDist <= fwdDist) ? backOff : fwdOff;
-        } else if (backOff >= 0) {
-            messageOff = backOff;
-        } else if (fwdOff >= 0) {
-            messageOff = fwdOff;
+        if (messageOff < 0) {
+            debugStream << "  SILENT @" << mod.opcodeOffset << ": no MESSAGE within "
+                        << searchRadius << " bytes" << (sameScriptOnly ? " in its script" : "")
+                        << ", text left unchanged\n";
+            continue;
         }
 
-        if (messageOff < 0) {
-            debugStream << "  No MESSAGE near @" << mod.opcodeOffset << "\n";
+        if (silent) {
+            for (int i = 0; i < 3; ++i)
+                decompressed[messageOff + i] = static_cast<char>(0x5F);   // NOP
+            usedMessageOffsets.insert(messageOff);
+            anyChanged = true;
+            debugStream << "  MSG @" << messageOff << " removed (silent, now " << mod.newName << ")\n";
             continue;
         }
 
@@ -2777,7 +4540,7 @@ bool FieldPickupRandomizer_ff7tk::updateFieldTexts(
                     << " -> " << newTextID << "  " << newTextStr << "\n";
     }
 
-    if (!anyChanged) return false;
+    if (!anyChanged || silent) return anyChanged;
 
     // --- patch MESSAGE textID bytes in the decompressed data ----------------
     // (scripts are BEFORE the text section, so their offsets won't shift)
@@ -2921,6 +4684,137 @@ static const quint32 KEY_BLACK_MATERIA    = 0x4600;
 static const quint32 KEY_MYTHRIL          = 0x4601;
 static const quint32 KEY_SNOWBOARD        = 0x4602;
 
+// Key items that gate fields holding other key item slots, in dependency
+// order: the Keystone opens the Temple of the Ancients.
+static const QVector<quint32> KEY_ACCESS_ORDER = { KEY_KEYSTONE };
+
+void FieldPickupRandomizer_ff7tk::planBatterySlots(MakouLgpManager& lgp,
+                                                   const QStringList& allFiles,
+                                                   const QMap<QString, KeyItemFieldMod>& keyItemMods,
+                                                   QTextStream& debugStream)
+{
+    // Every valid pickup in an earlier sphere than the climb is a candidate;
+    // only literal quantities count, since a variable one can't be trusted
+    const int climbSphere = getFieldSphere(QStringLiteral("wcrimb_1"));
+    QVector<QPair<QString, int>> candidates;   // field, STITM ordinal
+    QVector<int> quantities;
+    for (const QString& fileName : allFiles) {
+        const QString name = fileName.toLower();
+        if (getFieldSphere(name) >= climbSphere) continue;
+        if (name == "md1stin" || name == "mkt_w" || name == "onna_5") continue;
+        if (keyItemMods.contains(fileName) || isSubmarineMissionField(name)) continue;
+
+        const InputCache* cache = m_parent ? m_parent->inputCache() : nullptr;
+        const QByteArray raw = cache ? cache->fieldData(fileName) : lgp.fileData(fileName);
+        const QByteArray decompressed = LZS::decompressAllWithHeader(raw);
+        if (decompressed.isEmpty()) continue;
+        QString discarded;
+        QTextStream scanLog(&discarded);
+        const QVector<STITMInfo> stitms = scanForSTITM(decompressed, fileName, scanLog);
+        int ordinal = 0;
+        for (const STITMInfo& info : stitms) {
+            if (!validateSTITM(info)) continue;
+            if (info.isDirectValue && info.originalQuantity > 0) {
+                candidates.append({fileName, ordinal});
+                quantities.append(info.originalQuantity);
+            }
+            ++ordinal;
+        }
+    }
+
+    int total = 0;
+    while (total < BATTERIES_FOR_CLIMB && !candidates.isEmpty()) {
+        const int pick = m_rng.bounded(candidates.size());
+        m_batterySlots[candidates[pick].first].insert(candidates[pick].second);
+        total += quantities[pick];
+        debugStream << "BATTERY: " << candidates[pick].first << " pickup #"
+                    << candidates[pick].second << " gives " << quantities[pick] << "\n";
+        candidates.removeAt(pick);
+        quantities.removeAt(pick);
+    }
+    if (total < BATTERIES_FOR_CLIMB)
+        debugStream << "BATTERY: WARNING only " << total << " of " << BATTERIES_FOR_CLIMB
+                    << " Batteries placed before the wire climb\n";
+    debugStream << "\n";
+}
+
+// Tiered equipment chains, weakest first: Cloud's swords by attack
+// (Buster Sword is already equipped) and the armlets by defense
+static const QVector<quint16> TIERED_SWORD_CHAIN = {
+    129, 130, 131, 132, 137, 133, 134, 140, 141, 142, 143
+};
+static const QVector<quint16> TIERED_ARMLET_CHAIN = {
+    257, 258, 259, 260, 261, 262, 263, 264
+};
+
+void FieldPickupRandomizer_ff7tk::planTieredSlots(MakouLgpManager& lgp,
+                                                       const QStringList& allFiles,
+                                                       const QMap<QString, KeyItemFieldMod>& keyItemMods,
+                                                       QTextStream& debugStream)
+{
+    // Single literal pickups in a classified sphere, outside the synced and
+    // handout fields, that aren't already a Battery
+    struct Slot { QString fileName; int ordinal; int sphere; };
+    QVector<Slot> candidates;
+    for (const QString& fileName : allFiles) {
+        const QString name = fileName.toLower();
+        const int sphere = getFieldSphere(name);
+        if (sphere == UNKNOWN_SPHERE) continue;
+        if (name == "md1stin" || name == "mkt_w" || name == "onna_5") continue;
+        if (keyItemMods.contains(fileName) || isCraterHandoutField(fileName)
+            || isLocationGroupOff(fileName) || isSubmarineMissionField(name))
+            continue;
+
+        const InputCache* cache = m_parent ? m_parent->inputCache() : nullptr;
+        const QByteArray raw = cache ? cache->fieldData(fileName) : lgp.fileData(fileName);
+        const QByteArray decompressed = LZS::decompressAllWithHeader(raw);
+        if (decompressed.isEmpty()) continue;
+        QString discarded;
+        QTextStream scanLog(&discarded);
+        const QVector<STITMInfo> stitms = scanForSTITM(decompressed, fileName, scanLog);
+        const QSet<int> batterySlots = m_batterySlots.value(fileName);
+        int ordinal = 0;
+        for (const STITMInfo& info : stitms) {
+            if (!validateSTITM(info)) continue;
+            if (info.isDirectValue && info.originalQuantity == 1
+                && !batterySlots.contains(ordinal)
+                && !isCouponExchangeGrant(fileName, info.originalItemID))
+                candidates.append({fileName, ordinal, sphere});
+            ++ordinal;
+        }
+    }
+
+    // Each chain takes one random slot from each of a random set of spheres,
+    // then hands out its tiers in sphere order. No two tiers share a sphere,
+    // so a stronger tier is never reachable before a weaker one
+    for (const QVector<quint16>* chain : { &TIERED_SWORD_CHAIN, &TIERED_ARMLET_CHAIN }) {
+        QList<int> spheres;
+        for (const Slot& slot : candidates)
+            if (!spheres.contains(slot.sphere)) spheres.append(slot.sphere);
+        QVector<Slot> picked;
+        while (picked.size() < chain->size() && !spheres.isEmpty()) {
+            const int sphere = spheres.takeAt(m_rng.bounded(spheres.size()));
+            QVector<int> inSphere;
+            for (int i = 0; i < candidates.size(); ++i)
+                if (candidates[i].sphere == sphere) inSphere.append(i);
+            picked.append(candidates.takeAt(inSphere[m_rng.bounded(inSphere.size())]));
+        }
+        std::sort(picked.begin(), picked.end(),
+                  [](const Slot& a, const Slot& b) { return a.sphere < b.sphere; });
+        for (int tier = 0; tier < picked.size(); ++tier) {
+            const quint16 itemId = chain->at(tier);
+            m_tieredSlots[picked[tier].fileName].insert(picked[tier].ordinal, itemId);
+            debugStream << "TIERED: " << getItemName(itemId) << " (tier " << tier + 1
+                        << ") -> " << picked[tier].fileName << " pickup #" << picked[tier].ordinal
+                        << " (sphere " << picked[tier].sphere << ")\n";
+        }
+        if (picked.size() < chain->size())
+            debugStream << "TIERED: WARNING only " << picked.size() << " of "
+                        << chain->size() << " tiers placed\n";
+    }
+    debugStream << "\n";
+}
+
 int FieldPickupRandomizer_ff7tk::getFieldSphere(const QString& fieldName)
 {
     static const QSet<QString> sphere0 = {
@@ -2939,7 +4833,7 @@ int FieldPickupRandomizer_ff7tk::getFieldSphere(const QString& fieldName)
     };
     static const QSet<QString> sphere2 = {
         "colne_1","colne_2","colne_3","colne_4","colne_5","colne_6",
-        "mds7st1","mds7st2",
+        "mds7st1","mds7st2","wcrimb_1","wcrimb_2",
     };
     static const QSet<QString> sphere3 = { "blin1","blin2_1","blin2_2","blin2_3","blin59" };
     static const QSet<QString> sphere4 = {
@@ -2987,7 +4881,9 @@ int FieldPickupRandomizer_ff7tk::getFieldSphere(const QString& fieldName)
         "utai_1","utai_2","utai_3","utai_4","utai_5",
         "utapb","utmin1","utmin2","utmin3","uttmpin1","uttmpin2","uttmpin3",
         "kuro_1","kuro_2","kuro_3","kuro_4","kuro_5","kuro_6","kuro_7","kuro_8",
-        "yougan","yougan2"
+        "yougan","yougan2",
+        "datiao_1","datiao_2","datiao_3","datiao_4","datiao_5","datiao_6",
+        "datiao_7","datiao_8"
     };
     static const QSet<QString> sphere11 = {
         "trnad_1","trnad_2","trnad_3","trnad_4",
@@ -3013,7 +4909,8 @@ int FieldPickupRandomizer_ff7tk::getFieldSphere(const QString& fieldName)
         "las0_1","las0_2","las0_3","las0_4","las0_5","las0_6","las0_7",
         "las1_1","las1_2","las1_3","las1_4","las2_1","las2_2","las2_3",
         "las3_1","las3_2","las3_3","las4_0","las4_1","las4_2","las4_3","las4_4",
-        "lastmap","lastcin"
+        "lastmap","lastcin",
+        "gnmk","gnmkf"
     };
 
     QString name = fieldName.toLower();
@@ -3033,7 +4930,7 @@ int FieldPickupRandomizer_ff7tk::getFieldSphere(const QString& fieldName)
     if (sphere13.contains(name)) return 13;
     if (sphere14.contains(name)) return 14;
     if (sphere15.contains(name)) return 15;
-    return 99;
+    return UNKNOWN_SPHERE;
 }
 
 int FieldPickupRandomizer_ff7tk::getKeyItemMinSphere(quint32 keyItemId)
@@ -3080,12 +4977,28 @@ int FieldPickupRandomizer_ff7tk::getKeyItemMaxSphere(quint32 keyItemId)
     case KEY_KEYSTONE:    return 10;
     case KEY_LUNAR_HARP:  return 11;
     case KEY_SNOWBOARD:   return 13;
+    // Handed over during the Whirlwind Maze sequence (trnad_*, sphere 11)
+    case KEY_BLACK_MATERIA: return 10;
+    // Traded in on the 63rd floor (blin63_1, sphere 4)
     case KEY_A_COUPON: case KEY_B_COUPON: case KEY_C_COUPON:
-        return 6;
+        return 4;
     default: return 99;
     }
 }
 
+bool FieldPickupRandomizer_ff7tk::isOptionalKeyItem(quint32 keyItemId)
+{
+    switch (keyItemId) {
+    case KEY_GOLD_TICKET: case KEY_LEVIATHAN_SCALES:
+    case KEY_A_COUPON: case KEY_B_COUPON: case KEY_C_COUPON:
+    case KEY_LETTER_TO_WIFE: case KEY_LETTER_TO_DAUGHTER:
+    case KEY_GLACIER_MAP: case KEY_MYTHRIL:
+        return true;
+    default:
+        return false;
+    }
+}
+
 int FieldPickupRandomizer_ff7tk::getKeyItemMinMoment(quint32 keyItemId)
 {
     switch (keyItemId) {
@@ -3144,6 +5057,151 @@ bool FieldPickupRandomizer_ff7tk::requiresMirroredBitons(const QString& fieldNam
     return mirroredFields.contains(fieldName.trimmed().toLower());
 }
 
+bool FieldPickupRandomizer_ff7tk::isChocoboFarmField(const QString& fieldName)
+{
+    // farm = Chocobo Farm exterior, frmin = Choco Billy's house (Chocobo Lure),
+    // frcyo/frcyo_2 = the stables (Choco/Mog from the fat chocobo).
+    static const QSet<QString> farmFields = {
+        QStringLiteral("farm"),
+        QStringLiteral("frmin"),
+        QStringLiteral("frcyo"),
+        QStringLiteral("frcyo_2")
+    };
+    return farmFields.contains(fieldName.trimmed().toLower());
+}
+
+QString FieldPickupRandomizer_ff7tk::materiaCaveChocobo(quint8 materiaId)
+{
+    // Each cave sits behind terrain only a bred chocobo crosses; Round Island
+    // (Knights of the Round) needs the gold one.
+    switch (materiaId) {
+    case MATERIA_HP_MP:        return QStringLiteral("mountain");
+    case MATERIA_MIME:         return QStringLiteral("river");
+    case MATERIA_QUADRA_MAGIC: return QStringLiteral("river/mountain");
+    case MATERIA_KOTR:         return QStringLiteral("gold");
+    default:                   return QString();
+    }
+}
+
+bool FieldPickupRandomizer_ff7tk::requiresChocoboCrossing(const QString& fieldName)
+{
+    // The Ancient Forest plateau is cut off by cliffs and rivers; without the
+    // Highwind it is only reachable on a river/mountain chocobo.
+    static const QSet<QString> crossingFields = {
+        QStringLiteral("anfrst_1"),
+        QStringLiteral("anfrst_2"),
+        QStringLiteral("anfrst_3"),
+        QStringLiteral("anfrst_4"),
+        QStringLiteral("anfrst_5")
+    };
+    return crossingFields.contains(fieldName.trimmed().toLower());
+}
+
+QString FieldPickupRandomizer_ff7tk::optionalDungeonName(const QString& fieldName)
+{
+    // anfrst = Ancient Forest, gnmk/gnmkf = Sunken Gelnika (hull and cargo
+    // bay), datiao = Da-chao statue and the fire cave behind it.
+    const QString name = fieldName.trimmed().toLower();
+    if (name.startsWith(QLatin1String("anfrst_")))
+        return QStringLiteral("Ancient Forest");
+    if (name == QLatin1String("gnmk") || name == QLatin1String("gnmkf"))
+        return QStringLiteral("Sunken Gelnika");
+    if (name.startsWith(QLatin1String("datiao_")))
+        return QStringLiteral("Da-chao");
+    return QString();
+}
+
+int FieldPickupRandomizer_ff7tk::locationGroupOf(const QString& fieldName)
+{
+    // Wutai: town, Turtle's Paradise pub, houses, the pagoda and Da-chao.
+    // Minigames: Wonder Square, Chocobo Square and the Battle Square.
+    // Missables: Shinra HQ (left for good after the escape), the Temple of
+    // the Ancients (kuro_*, it collapses), the Whirlwind Maze (trnad_*, gone
+    // once disc 1 ends) and the Rocket itself (it launches; Rocket Town
+    // around it stays open).
+    static const QSet<QString> minigameFields = {
+        "games_1","games_2","chorace","chorace2","coloin1","coloin2","coloss"
+    };
+    const QString name = fieldName.trimmed().toLower();
+    if (name.startsWith(QLatin1String("utai_")) || name.startsWith(QLatin1String("utmin"))
+        || name.startsWith(QLatin1String("uttmpin")) || name == QLatin1String("utapb")
+        || name.startsWith(QLatin1String("datiao_")))
+        return Config::LocationGroupWutai;
+    if (minigameFields.contains(name))
+        return Config::LocationGroupMinigames;
+    if (name.startsWith(QLatin1String("blin")) || name.startsWith(QLatin1String("kuro_"))
+        || name.startsWith(QLatin1String("trnad_"))
+        || name.startsWith(QLatin1String("rktmin")) || name == QLatin1String("rkt_i"))
+        return Config::LocationGroupMissables;
+    return -1;
+}
+
+bool FieldPickupRandomizer_ff7tk::isLocationGroupOff(const QString& fieldName) const
+{
+    const int group = locationGroupOf(fieldName);
+    return group >= 0 && m_parent
+        && !m_parent->m_config.isLocationGroupEnabled(static_cast<Config::LocationGroup>(group));
+}
+
+bool FieldPickupRandomizer_ff7tk::isPointOfNoReturnField(const QString& fieldName)
+{
+    // las* = the Northern Crater interior; once inside the game only lets
+    // the party back out through the save point before the final battle.
+    return fieldName.trimmed().toLower().startsWith(QLatin1String("las"));
+}
+
+QVector<quint32> FieldPickupRandomizer_ff7tk::getFieldAccessKeyItems(const QString& fieldName)
+{
+    // The Temple of the Ancients (kuro_*) opens once the Keystone is set at
+    // the altar. The Gold Saucer needs no key item: a ticket can always be
+    // bought at the entrance, so the Gold Ticket gates nothing.
+    const QString name = fieldName.trimmed().toLower();
+    if (name == QLatin1String("jtempl") || name.startsWith(QLatin1String("kuro_")))
+        return { KEY_KEYSTONE };
+    return {};
+}
+
+bool FieldPickupRandomizer_ff7tk::isCouponExchangeGrant(const QString& fieldName, quint16 itemId)
+{
+    return fieldName.compare(QStringLiteral("blin63_1"), Qt::CaseInsensitive) == 0
+           && (itemId == ITEM_STAR_PENDANT || itemId == ITEM_FOUR_SLOTS);
+}
+
+bool FieldPickupRandomizer_ff7tk::isCouponExchangeMateria(const QString& fieldName, quint8 materiaId)
+{
+    return fieldName.compare(QStringLiteral("blin63_1"), Qt::CaseInsensitive) == 0
+           && materiaId == MATERIA_ALL;
+}
+
+bool FieldPickupRandomizer_ff7tk::isCraterHandoutField(const QString& fieldName)
+{
+    // las1/las2 = the Northern Crater forks, las3 = where the party regroups
+    // and hands over what the other path picked up.
+    const QString name = fieldName.trimmed().toLower();
+    return name.startsWith(QLatin1String("las1_"))
+        || name.startsWith(QLatin1String("las2_"))
+        || name.startsWith(QLatin1String("las3_"));
+}
+
+bool FieldPickupRandomizer_ff7tk::requiresSubmarine(const QString& fieldName)
+{
+    return optionalDungeonName(fieldName) == QLatin1String("Sunken Gelnika");
+}
+
+bool FieldPickupRandomizer_ff7tk::isSubmarineMissionField(const QString& fieldName)
+{
+    const QString name = fieldName.trimmed().toLower();
+    return name == QLatin1String("subin_1a") || name == QLatin1String("subin_1b");
+}
+
+bool FieldPickupRandomizer_ff7tk::isBlackMateriaHandoffField(const QString& fieldName)
+{
+    // trnad_* = the Whirlwind Maze, crater_* = the Northern Crater scenes
+    // where Cloud takes the Black Materia back and hands it to Sephiroth
+    const QString name = fieldName.trimmed().toLower();
+    return name.startsWith(QLatin1String("trnad_")) || name.startsWith(QLatin1String("crater_"));
+}
+
 FieldPickupRandomizer_ff7tk::WardrobeCategory
 FieldPickupRandomizer_ff7tk::getWardrobeCategory(quint32 keyItemId)
 {
@@ -3219,6 +5277,7 @@ void FieldPickupRandomizer_ff7tk::collectKeyItemsAndStitm(
     const QByteArray& fieldData, int fileIndex, const QString& fieldName,
     QMap<quint32, GlobalKeyItem>& uniqueKeyItems,
     QVector<GlobalStitmLocation>& stitmLocations,
+    QMap<quint32, QVector<GlobalKeyItem>>& keyItemSources,
     QTextStream& debugStream)
 {
     if (fieldData.size() < 42) return;
@@ -3243,7 +5302,7 @@ void FieldPickupRandomizer_ff7tk::collectKeyItemsAndStitm(
     memcpy(sectionPositions, decompressed.constData() + 6, 9 * 4);
     quint32 sec0off = sectionPositions[0];
     quint32 sec1off = sectionPositions[1];
-    if (sec0off + 4 >= static_cast<quint32>(fileSize) ||
+    if (sec0off >= static_cast<quint32>(fileSize - 4) ||
         sec1off > static_cast<quint32>(fileSize) || sec1off <= sec0off + 4)
         return;
 
@@ -3267,7 +5326,9 @@ void FieldPickupRandomizer_ff7tk::collectKeyItemsAndStitm(
                 quint16 itemId;
                 memcpy(&itemId, decompressed.constData() + i + 2, 2);
                 quint8 qty = static_cast<quint8>(decompressed.at(i + 4));
-                if (itemId <= MAX_ITEM_ID && qty >= 1 && qty <= 99) {
+                if (itemId <= MAX_ITEM_ID && qty >= 1 && qty <= 99
+                    && !isCouponExchangeGrant(fieldName, itemId)
+                    && !isLocationGroupOff(fieldName)) {
                     QPair<int, int> window = getStitmMomentWindow(fieldName, i);
                     int minMoment = std::max(fieldWindow.first, window.first);
                     int maxMoment = std::min(fieldWindow.second, window.second);
@@ -3295,9 +5356,16 @@ void FieldPickupRandomizer_ff7tk::collectKeyItemsAndStitm(
             quint8 bitNum   = static_cast<quint8>(decompressed.at(i + 3));
 
             if (destBank >= 1 && destBank <= 2 && srcBank == 0 &&
-                address >= 0x40 && address <= 0x46 && bitNum <= 7) {
+                address >= 0x40 && address <= 0x46 && bitNum <= 7 &&
+                !isProtectedFlag(destBank, address, bitNum)) {
                 quint32 uniqueId = (static_cast<quint32>(address) << 8) | bitNum;
-                if (!uniqueKeyItems.contains(uniqueId)) {
+                if (uniqueId == KEY_BLACK_MATERIA && isBlackMateriaHandoffField(fieldName)) {
+                    // The maze/crater hand the Black Materia back before the
+                    // handoff clears it; that BITON stays put so the sequence
+                    // never runs with an empty slot
+                    debugStream << "  HANDOFF: Black Materia re-grant in " << fieldName
+                                << " @" << i << " stays vanilla\n";
+                } else if (!uniqueKeyItems.contains(uniqueId)) {
                     GlobalKeyItem item;
                     item.fileIndex    = fileIndex;
                     item.scriptOffset = i;
@@ -3305,6 +5373,7 @@ void FieldPickupRandomizer_ff7tk::collectKeyItemsAndStitm(
                     item.address      = address;
                     item.bit          = bitNum;
                     uniqueKeyItems.insert(uniqueId, item);
+                    keyItemSources[uniqueId].append(item);
 
                     quint16 saveOffset = 0x0BA4 + address;
                     debugStream << "  KEY_ITEM: '" << getKeyItemName(saveOffset, bitNum)
@@ -3317,6 +5386,16 @@ void FieldPickupRandomizer_ff7tk::collectKeyItemsAndStitm(
                     bitonLoc.maxGameMoment = fieldWindow.second;
                     bitonLoc.isBiton       = true;
                     stitmLocations.append(bitonLoc);
+                } else {
+                    // Another grant of a flag already seen: only the first
+                    // source is shuffled, auditKeyItemSources() deals with this one
+                    GlobalKeyItem item = uniqueKeyItems[uniqueId];
+                    item.fileIndex    = fileIndex;
+                    item.scriptOffset = i;
+                    item.bankByte     = bankByte;
+                    keyItemSources[uniqueId].append(item);
+                    debugStream << "  KEY_ITEM_DUP: '" << getKeyItemName(0x0BA4 + address, bitNum)
+                                << "' also granted in " << fieldName << " @" << i << "\n";
                 }
             }
             i += 3;
@@ -3325,6 +5404,94 @@ void FieldPickupRandomizer_ff7tk::collectKeyItemsAndStitm(
     }
 }
 
+// ============================================================================
+// BITON grant -> chest conversion
+//
+// A key item handed out by a BITON leaves its spot empty once the item is
+// moved elsewhere. The grants listed in BITON_CHEST_SOURCES are rewritten
+// as a STITM instead, which the pickup pass then randomizes like any chest.
+// Scripts are never resized, so the STITM (5 bytes) only fits where the
+// BITON (4) is followed by a byte the script can spare: a NOP, or a RET with
+// a second RET right behind it. A listed grant without one is NOPed as
+// before and logged.
+//
+// Onboarding a source is one table row (field, entity, script, key item);
+// check in Makou that the spare byte is there and that no jump lands on it.
+// ============================================================================
+
+namespace {
+struct BitonChestSource {
+    const char* field;        // nullptr = any field
+    int entity;               // -1 = match entityName instead
+    const char* entityName;   // as Makou shows it, case ignored
+    int script;
+    quint32 keyItem;          // (address << 8) | bit, as KEY_*
+};
+
+const BitonChestSource BITON_CHEST_SOURCES[] = {
+    { nullptr,    -1, "Dios",  4,  KEY_KEYSTONE },                 // Dio hands over the Keystone
+    { "subin_1b", 16, nullptr, 19, KEY_HUGE_MATERIA_UNDERWATER },  // red submarine win path
+};
+} // namespace
+
+bool FieldPickupRandomizer_ff7tk::convertBitonGrantToChest(
+    QByteArray& decompressed, const QString& fieldName, int offset, QRandomGenerator& rng,
+    QTextStream& debugStream)
+{
+    if (offset < 0 || offset + 4 > decompressed.size()
+        || static_cast<quint8>(decompressed.at(offset)) != BITON_OPCODE)
+        return false;
+    auto byteAt = [&decompressed](int pos) { return static_cast<quint8>(decompressed.at(pos)); };
+    const quint32 keyItem = (static_cast<quint32>(byteAt(offset + 2)) << 8) | byteAt(offset + 3);
+
+    FieldScript::ScriptRange range;
+    if (!FieldScript::scriptRange(decompressed, range)) return false;
+    const QMap<int, ScriptOwner> owners = scriptEntryPoints(decompressed);
+    const ScriptOwner owner = scriptOwnerAt(owners, offset);
+    if (owner.entity < 0) return false;
+    QByteArray rawName(decompressed.constData() + range.namesStart + 8 * owner.entity, 8);
+    const int nul = rawName.indexOf('\0');
+    if (nul >= 0) rawName.truncate(nul);
+    const QString entityName = QString::fromLatin1(rawName);
+
+    const BitonChestSource* source = nullptr;
+    for (const BitonChestSource& s : BITON_CHEST_SOURCES) {
+        if (s.keyItem != keyItem || s.script != owner.script) continue;
+        if (s.field && fieldName.compare(QLatin1String(s.field), Qt::CaseInsensitive) != 0) continue;
+        if (s.entity >= 0 ? s.entity != owner.entity
+                          : entityName.compare(QLatin1String(s.entityName), Qt::CaseInsensitive) != 0)
+            continue;
+        source = &s;
+        break;
+    }
+    if (!source) return false;
+
+    const QString keyName = getKeyItemName(0x0BA4 + (keyItem >> 8), keyItem & 0xFF);
+    const auto next = owners.upperBound(offset);
+    const int scriptEnd = next == owners.constEnd() ? range.walkEnd : next.key();
+    const bool nopSlack = offset + STITM_SIZE <= scriptEnd && byteAt(offset + 4) == 0x5F;
+    const bool retSlack = offset + STITM_SIZE < scriptEnd
+                       && byteAt(offset + 4) == 0x00 && byteAt(offset + 5) == 0x00;
+    if (!nopSlack && !retSlack) {
+        debugStream << "  CHEST_SKIP: '" << keyName << "' grant in " << fieldName
+                    << ownerTag(owner.entity, owner.script) << " @" << offset
+                    << " has no spare byte after its BITON - NOPed instead\n";
+        return false;
+    }
+
+    const quint16 itemId = drawFieldItem(fieldName, rng);
+    decompressed[offset]     = static_cast<char>(STITM_OPCODE);
+    decompressed[offset + 1] = static_cast<char>(0x00);   // literal item and quantity
+    decompressed[offset + 2] = static_cast<char>(itemId & 0xFF);
+    decompressed[offset + 3] = static_cast<char>(itemId >> 8);
+    decompressed[offset + 4] = static_cast<char>(1);
+    debugStream << "  CHEST: '" << keyName << "' grant in " << fieldName
+                << ownerTag(owner.entity, owner.script) << " @" << offset
+                << " -> STITM " << getItemName(itemId) << " ("
+                << (nopSlack ? "NOP" : "RET RET") << " slack)\n";
+    return true;
+}
+
 QMap<QString, FieldPickupRandomizer_ff7tk::KeyItemFieldMod>
 FieldPickupRandomizer_ff7tk::performKeyItemSwaps(
     QMap<quint32, GlobalKeyItem>& uniqueKeyItems,
@@ -3333,7 +5500,50 @@ FieldPickupRandomizer_ff7tk::performKeyItemSwaps(
     QTextStream& debugStream)
 {
     debugStream << "\n=== KEY ITEM SWAP (SPHERE-AWARE) ===\n";
+
+    // Optional key items left out of the shuffle keep their vanilla BITON,
+    // so that BITON is no longer a placement slot either. The same goes for
+    // key items found in a switched-off location group.
+    if (m_parent) {
+        const bool keepOptional = !m_parent->m_config.getOptionalKeyItems();
+        for (auto it = uniqueKeyItems.begin(); it != uniqueKeyItems.end();) {
+            const GlobalKeyItem& item = it.value();
+            const bool optional = keepOptional && isOptionalKeyItem(it.key());
+            if (!optional && !isLocationGroupOff(allFileNames[item.fileIndex])) { ++it; continue; }
+            for (int i = stitmLocations.size() - 1; i >= 0; --i) {
+                if (stitmLocations[i].isBiton && stitmLocations[i].fileIndex == item.fileIndex
+                    && stitmLocations[i].scriptOffset == item.scriptOffset)
+                    stitmLocations.removeAt(i);
+            }
+            debugStream << "  VANILLA: '" << getKeyItemName(0x0BA4 + item.address, item.bit)
+                        << (optional ? "' (optional key item)" : "' (location group disabled)")
+                        << " stays in " << allFileNames[item.fileIndex] << "\n";
+            it = uniqueKeyItems.erase(it);
+        }
+    }
+
     debugStream << "Unique key items: " << uniqueKeyItems.size() << "\n";
+
+    LogicOverrides& overrides = LogicOverrides::instance();
+    overrides.reload(m_parent ? m_parent->getFF7Path() : QString());
+    if (!overrides.isEmpty())
+        debugStream << "Logic overrides: " << overrides.ruleCount() << " rules from "
+                    << overrides.sources().join(", ") << "\n";
+    for (const QString& warning : overrides.warnings())
+        debugStream << "  WARNING: logic overrides: " << warning << "\n";
+
+    // The red submarine mission only sets the Underwater Huge Materia bit on the
+    // win path; losing the chase skips the grant for good. Keep key items out of
+    // the mission fields so the slot only ever holds filler, and move the Huge
+    // Materia itself somewhere that can't be failed (placement fails otherwise).
+    if (uniqueKeyItems.contains(KEY_HUGE_MATERIA_UNDERWATER)) {
+        debugStream << "Submarine mission: "
+                    << allFileNames[uniqueKeyItems[KEY_HUGE_MATERIA_UNDERWATER].fileIndex]
+                    << " (no key items placed in subin_1a/subin_1b)\n";
+    } else {
+        debugStream << "Submarine mission: WARNING Underwater Huge Materia grant not found,"
+                    << " it stays on the win path\n";
+    }
     debugStream << "STITM locations: " << stitmLocations.size() << "\n\n";
 
     // Build sphere-aware STITM location list
@@ -3362,126 +5572,472 @@ FieldPickupRandomizer_ff7tk::performKeyItemSwaps(
     std::array<bool, static_cast<int>(WardrobeCategory::Underwear) + 1> wardrobeCategoryUsed{};
     wardrobeCategoryUsed.fill(false);
 
-    // Sort key items by maxSphere (most restrictive first)
+    // Early PHS: the PHS is held to the first sphere so party swaps are
+    // available from Midgar on
+    const bool earlyPhs = m_parent && m_parent->m_config.getEarlyPhs();
+    // Midgar Parts beyond the turn-in count don't gate Keycard 66, so they
+    // are placed like filler: any sphere, any point in the game
+    const int partsRequired = m_parent ? m_parent->m_config.getMidgarPartsRequired()
+                                       : Config::MIDGAR_PARTS_COUNT;
+    auto isSurplusPart = [partsRequired](quint32 keyItemId) {
+        return keyItemId >= KEY_MIDGAR_PARTS_1 && keyItemId <= KEY_MIDGAR_PARTS_5
+            && static_cast<int>(keyItemId - KEY_MIDGAR_PARTS_1) >= partsRequired;
+    };
+    auto maxSphereOf = [earlyPhs, &isSurplusPart](quint32 keyItemId) {
+        if (isSurplusPart(keyItemId)) return 99;
+        return (earlyPhs && keyItemId == KEY_PHS) ? 0 : getKeyItemMaxSphere(keyItemId);
+    };
+
+    // Access items first, in dependency order, then the rest by maxSphere
+    // (most restrictive first). The order only affects the spread: the fill
+    // below is what keeps the seed beatable
+    auto accessRank = [](quint32 keyItemId) {
+        const int rank = KEY_ACCESS_ORDER.indexOf(keyItemId);
+        return rank < 0 ? static_cast<int>(KEY_ACCESS_ORDER.size()) : rank;
+    };
     QVector<QPair<quint32, GlobalKeyItem>> sorted;
     for (auto it = uniqueKeyItems.begin(); it != uniqueKeyItems.end(); ++it)
         sorted.append({it.key(), it.value()});
     std::sort(sorted.begin(), sorted.end(),
-              [](const QPair<quint32, GlobalKeyItem>& a,
-                 const QPair<quint32, GlobalKeyItem>& b) {
-                  return getKeyItemMaxSphere(a.first) < getKeyItemMaxSphere(b.first);
+              [&accessRank, &maxSphereOf](const QPair<quint32, GlobalKeyItem>& a,
+                                          const QPair<quint32, GlobalKeyItem>& b) {
+                  if (accessRank(a.first) != accessRank(b.first))
+                      return accessRank(a.first) < accessRank(b.first);
+                  return maxSphereOf(a.first) < maxSphereOf(b.first);
               });
 
+    // One item per wardrobe category is enough for the dress-up; the others
+    // stay vanilla and are left out of the logic
+    for (int i = 0; i < sorted.size();) {
+        const WardrobeCategory category = getWardrobeCategory(sorted[i].first);
+        const int index = static_cast<int>(category);
+        if (category == WardrobeCategory::None || !wardrobeCategoryUsed[index]) {
+            if (category != WardrobeCategory::None) wardrobeCategoryUsed[index] = true;
+            ++i;
+            continue;
+        }
+        const GlobalKeyItem& item = sorted[i].second;
+        debugStream << "  SKIP: '" << getKeyItemName(0x0BA4 + item.address, item.bit)
+                    << "' – wardrobe category '" << wardrobeCategoryName(category)
+                    << "' already satisfied\n";
+        sorted.removeAt(i);
+    }
+
+    // --- Logic model ---------------------------------------------------------
+    // A key item is needed for every sphere past its maxSphere, and the
+    // access items for the fields they open. A location counts as reachable
+    // once every logic item it needs is in hand. Key items that are not
+    // shuffled keep their vanilla BITON and are not part of the logic.
+    QSet<quint32> logicItems;
+    for (const auto& kv : sorted) logicItems.insert(kv.first);
+
+    auto requirementsMet = [&](int sphere, const QString& fieldName, const QSet<quint32>& have) {
+        for (quint32 item : logicItems) {
+            if (maxSphereOf(item) < sphere && !have.contains(item)) return false;
+        }
+        for (quint32 required : getFieldAccessKeyItems(fieldName)) {
+            if (logicItems.contains(required) && !have.contains(required)) return false;
+        }
+        return true;
+    };
+
+    // Where each logic item can be picked up: a placement, or its vanilla
+    // BITON when no slot was found for it
+    struct Holding { quint32 item; int sphere; QString fieldName; };
+    QVector<Holding> holdings;
+
+    // Everything collectable starting from `have`, picking up held items
+    // until nothing new opens
+    auto collect = [&](QSet<quint32> have) {
+        bool grew = true;
+        while (grew) {
+            grew = false;
+            for (const Holding& h : holdings) {
+                if (have.contains(h.item) || !requirementsMet(h.sphere, h.fieldName, have)) continue;
+                have.insert(h.item);
+                grew = true;
+            }
+        }
+        return have;
+    };
+
+    QMap<quint32, int> placedLocByKey;   // randomized key item -> sphereLocs index
+
     // Compute all placements (no LGP modification — that happens in the per-file loop)
     QMap<QString, KeyItemFieldMod> fieldMods;
     QSet<int> usedLocIndices;
+    QSet<quint32> filled;
+    QMap<QString, QString> fallbacks;   // key item left on its vanilla grant -> why
     int placed = 0;
 
-    for (const auto& kv : sorted) {
-        quint32 keyItemId     = kv.first;
-        const GlobalKeyItem& keyItem = kv.second;
-        int minSphere = getKeyItemMinSphere(keyItemId);
-        int maxSphere = getKeyItemMaxSphere(keyItemId);
-        int minMoment = getKeyItemMinMoment(keyItemId);
-        int maxMoment = getKeyItemMaxMoment(keyItemId);
-        WardrobeCategory wardrobeCategory = getWardrobeCategory(keyItemId);
-        int wardrobeIndex = static_cast<int>(wardrobeCategory);
-
-        quint16 saveOffset = 0x0BA4 + keyItem.address;
-        QString keyName = getKeyItemName(saveOffset, keyItem.bit);
-
-        if (wardrobeCategory != WardrobeCategory::None && wardrobeCategoryUsed[wardrobeIndex]) {
-            debugStream << "  SKIP: '" << keyName << "' – wardrobe category '"
-                        << wardrobeCategoryName(wardrobeCategory)
-                        << "' already satisfied\n";
-            continue;
-        }
-
-        QVector<int> validIndices;
+    // An item with no slot stays on its vanilla BITON, unless an earlier
+    // item was already placed there: that write replaces it and the item is
+    // lost from the seed
+    auto vanillaLocOf = [&sphereLocs](const GlobalKeyItem& item) {
         for (int i = 0; i < sphereLocs.size(); ++i) {
-            if (usedLocIndices.contains(i)) continue;
-            const SphereStitm& candidate = sphereLocs[i];
-            int s = candidate.sphere;
-            if (s < minSphere || s > maxSphere)
-                continue;
-            if (candidate.maxMoment < minMoment || candidate.minMoment > maxMoment)
-                continue;
-            validIndices.append(i);
+            if (sphereLocs[i].isBiton && sphereLocs[i].fileIndex == item.fileIndex
+                && sphereLocs[i].scriptOffset == item.scriptOffset)
+                return i;
         }
+        return -1;
+    };
 
-        if (validIndices.isEmpty()) {
-            debugStream << "  SKIP: '" << keyName << "' – no valid STITM in spheres "
-                        << minSphere << "-" << maxSphere
-                        << ", moments " << minMoment << "-" << maxMoment << "\n";
-            continue;
-        }
+    // --- Assumed fill ----------------------------------------------------------
+    // Each item goes to a slot reachable while holding every item not yet
+    // placed. The items placed later can then always be reached in turn, so
+    // no key item ends up behind itself (Keycard 62 behind the Keycard 62
+    // door) or behind a loop of items that need each other.
+    // One pass over `order`; returns the key items it lost (see vanillaLocOf)
+    auto fill = [&](const QVector<QPair<quint32, GlobalKeyItem>>& order, QTextStream& out) {
+        placedLocByKey.clear();
+        fieldMods.clear();
+        usedLocIndices.clear();
+        filled.clear();
+        holdings.clear();
+        fallbacks.clear();
+        placed = 0;
+        QStringList lost;
+        for (const auto& kv : order) {
+            quint32 keyItemId     = kv.first;
+            const GlobalKeyItem& keyItem = kv.second;
+            int minSphere = getKeyItemMinSphere(keyItemId);
+            int maxSphere = maxSphereOf(keyItemId);
+            int minMoment = getKeyItemMinMoment(keyItemId);
+            int maxMoment = isSurplusPart(keyItemId) ? MOMENT_FOREVER : getKeyItemMaxMoment(keyItemId);
+
+            quint16 saveOffset = 0x0BA4 + keyItem.address;
+            QString keyName = getKeyItemName(saveOffset, keyItem.bit);
+            QString srcFieldName = allFileNames[keyItem.fileIndex];
 
-        QVector<int> filteredIndices = validIndices;
+            QSet<quint32> assumed = logicItems;
+            assumed.subtract(filled);
+            assumed.remove(keyItemId);
+            const QSet<quint32> have = collect(assumed);
+
+            QVector<int> validIndices;
+            for (int i = 0; i < sphereLocs.size(); ++i) {
+                if (usedLocIndices.contains(i)) continue;
+                const SphereStitm& candidate = sphereLocs[i];
+                int s = candidate.sphere;
+                if (overrides.forbids(keyName, candidate.fieldName))
+                    continue;
+                const bool relaxed = overrides.allows(keyName, candidate.fieldName);
+                if (!relaxed && (s < minSphere || s > maxSphere))
+                    continue;
+                if (requiresSubmarine(candidate.fieldName))
+                    continue;
+                if (isSubmarineMissionField(candidate.fieldName))
+                    continue;
+                if (!requirementsMet(s, candidate.fieldName, have))
+                    continue;
+                if (!relaxed && (candidate.maxMoment < minMoment || candidate.minMoment > maxMoment))
+                    continue;
+                validIndices.append(i);
+            }
 
-        // Pre-filter: if blin63_1 already has a different key item placed,
-        // exclude all blin63_1 slots so we never conflict and silently drop items.
-        if (!fieldMods["blin63_1"].placements.isEmpty()) {
-            QVector<int> noBlin63;
-            for (int i : filteredIndices) {
-                if (sphereLocs[i].fieldName.toLower() != "blin63_1")
-                    noBlin63.append(i);
+            filled.insert(keyItemId);
+            if (validIndices.isEmpty() && keyItemId == KEY_HUGE_MATERIA_UNDERWATER) {
+                // Its vanilla slot is the win path, which is missable
+                out << "  LOST: '" << keyName << "' – no reachable STITM in spheres "
+                    << minSphere << "-" << maxSphere
+                    << ", and staying on the submarine win path would be missable\n";
+                lost << keyName;
+                continue;
+            }
+            if (validIndices.isEmpty()) {
+                const int vanillaLoc = vanillaLocOf(keyItem);
+                if (vanillaLoc >= 0 && usedLocIndices.contains(vanillaLoc)) {
+                    out << "  LOST: '" << keyName << "' – no reachable STITM in spheres "
+                        << minSphere << "-" << maxSphere
+                        << ", moments " << minMoment << "-" << maxMoment
+                        << ", and its vanilla BITON in " << srcFieldName
+                        << " already hosts another key item\n";
+                    lost << keyName;
+                    continue;
+                }
+                if (vanillaLoc >= 0) usedLocIndices.insert(vanillaLoc);
+                holdings.append({keyItemId, getFieldSphere(srcFieldName), srcFieldName});
+                fallbacks.insert(keyName, QString("no eligible slot in spheres %1-%2")
+                                              .arg(minSphere).arg(maxSphere));
+                out << "  SKIP: '" << keyName << "' – no reachable STITM in spheres "
+                    << minSphere << "-" << maxSphere
+                    << ", moments " << minMoment << "-" << maxMoment
+                    << "; stays vanilla in " << srcFieldName << "\n";
+                continue;
             }
-            if (!noBlin63.isEmpty())
-                filteredIndices = noBlin63;
-        }
 
-        int pick = filteredIndices[m_rng.bounded(filteredIndices.size())];
-        usedLocIndices.insert(pick);
-        const SphereStitm& target = sphereLocs[pick];
+            QVector<int> filteredIndices = validIndices;
 
-        {
-            // Record NOP-out of original BITON in source field
-            QString srcFieldName = allFileNames[keyItem.fileIndex];
-            fieldMods[srcFieldName].bitonNopOffsets.append(keyItem.scriptOffset);
-
-            // Record new BITON placement in target field
-            KeyItemPlacement p;
-            p.keyItem      = keyItem;
-            p.keyName      = keyName;
-            p.targetOffset = target.scriptOffset;
-            p.targetIsBiton = target.isBiton;
-            fieldMods[target.fieldName].placements.append(p);
-
-            if (target.isBiton && requiresMirroredBitons(target.fieldName)) {
-                for (int j = 0; j < sphereLocs.size(); ++j) {
-                    if (j == pick) continue;
-                    const SphereStitm& mirror = sphereLocs[j];
-                    if (!mirror.isBiton) continue;
-                    if (!mirror.fieldName.compare(target.fieldName, Qt::CaseInsensitive) == 0)
-                        continue;
-                    if (usedLocIndices.contains(j)) continue;
-                    usedLocIndices.insert(j);
-                    KeyItemPlacement mirrorPlacement = p;
-                    mirrorPlacement.targetOffset = mirror.scriptOffset;
-                    mirrorPlacement.targetIsBiton = true;
-                    fieldMods[mirror.fieldName].placements.append(mirrorPlacement);
-                    debugStream << "    MIRROR: '" << keyName << "' duplicated in "
-                                << mirror.fieldName << " @" << mirror.scriptOffset << "\n";
+            // Pre-filter: if blin63_1 already has a different key item placed,
+            // exclude all blin63_1 slots so we never conflict and silently drop items.
+            if (!fieldMods["blin63_1"].placements.isEmpty()) {
+                QVector<int> noBlin63;
+                for (int i : filteredIndices) {
+                    if (sphereLocs[i].fieldName.toLower() != "blin63_1")
+                        noBlin63.append(i);
                 }
+                if (!noBlin63.isEmpty())
+                    filteredIndices = noBlin63;
+            }
+
+            int pick = filteredIndices[m_rng.bounded(filteredIndices.size())];
+            usedLocIndices.insert(pick);
+            placedLocByKey[keyItemId] = pick;
+            const SphereStitm& target = sphereLocs[pick];
+            holdings.append({keyItemId, target.sphere, target.fieldName});
+            for (quint32 required : getFieldAccessKeyItems(target.fieldName)) {
+                out << "    ACCESS: " << target.fieldName << " needs "
+                    << (logicItems.contains(required)
+                            ? QStringLiteral("a shuffled key item")
+                            : QStringLiteral("a vanilla key item"))
+                    << " (0x" << QString::number(required, 16) << ")\n";
             }
 
-            if (wardrobeCategory != WardrobeCategory::None)
-                wardrobeCategoryUsed[wardrobeIndex] = true;
+            {
+                // Record NOP-out of original BITON in source field
+                fieldMods[srcFieldName].bitonNopOffsets.append(keyItem.scriptOffset);
+
+                // Record new BITON placement in target field
+                KeyItemPlacement p;
+                p.keyItem      = keyItem;
+                p.keyName      = keyName;
+                p.targetOffset = target.scriptOffset;
+                p.targetIsBiton = target.isBiton;
+                fieldMods[target.fieldName].placements.append(p);
+
+                if (target.isBiton && requiresMirroredBitons(target.fieldName)) {
+                    for (int j = 0; j < sphereLocs.size(); ++j) {
+                        if (j == pick) continue;
+                        const SphereStitm& mirror = sphereLocs[j];
+                        if (!mirror.isBiton) continue;
+                        if (!mirror.fieldName.compare(target.fieldName, Qt::CaseInsensitive) == 0)
+                            continue;
+                        if (usedLocIndices.contains(j)) continue;
+                        usedLocIndices.insert(j);
+                        KeyItemPlacement mirrorPlacement = p;
+                        mirrorPlacement.targetOffset = mirror.scriptOffset;
+                        mirrorPlacement.targetIsBiton = true;
+                        fieldMods[mirror.fieldName].placements.append(mirrorPlacement);
+                        out << "    MIRROR: '" << keyName << "' duplicated in "
+                            << mirror.fieldName << " @" << mirror.scriptOffset << "\n";
+                    }
+                }
+
+                placed++;
+                out << "  PLACED: '" << keyName << "' -> " << target.fieldName
+                    << " (sphere " << target.sphere << ") @" << target.scriptOffset
+                    << "  [src: " << srcFieldName << " @" << keyItem.scriptOffset << "]\n";
+            }
+        }
+        return lost;
+    };
 
-            placed++;
-            debugStream << "  PLACED: '" << keyName << "' -> " << target.fieldName
-                        << " (sphere " << target.sphere << ") @" << target.scriptOffset
-                        << "  [src: " << srcFieldName << " @" << keyItem.scriptOffset << "]\n";
+    // An early pick can take the last slot a later item could use, or the
+    // vanilla BITON a later item falls back to. The fill is retried with the
+    // items it could not make collectable moved to the front and the rest
+    // reshuffled; the first attempt keeps the order above, so seeds that fit
+    // first time are unchanged. Only the attempt that is kept gets logged.
+    QVector<QPair<quint32, GlobalKeyItem>> order = sorted;
+    QStringList lost;
+    QString fillLog;
+    QStringList names;   // uncollectable after the latest attempt
+    for (int attempt = 1;; ++attempt) {
+        fillLog.clear();
+        QTextStream fillStream(&fillLog);
+        lost = fill(order, fillStream);
+        fillStream.flush();
+
+        const QSet<quint32> reachable = collect({});
+        QVector<QPair<quint32, GlobalKeyItem>> failed;
+        QVector<QPair<quint32, GlobalKeyItem>> rest;
+        for (const auto& kv : order)
+            (reachable.contains(kv.first) ? rest : failed).append(kv);
+        names.clear();
+        for (const auto& kv : failed)
+            names << getKeyItemName(0x0BA4 + kv.second.address, kv.second.bit);
+        if (failed.isEmpty() || attempt == KEY_ITEM_PLACEMENT_ATTEMPTS) break;
+
+        debugStream << "  RETRY: attempt " << attempt << " / " << KEY_ITEM_PLACEMENT_ATTEMPTS
+                    << " left " << names.join(", ") << " uncollectable – reordering\n";
+        for (int i = rest.size() - 1; i > 0; --i)
+            rest.swapItemsAt(i, m_rng.bounded(i + 1));
+        order = failed + rest;
+    }
+    debugStream << fillLog;
+    if (!names.isEmpty())
+        debugStream << "  FAILED: " << names.join(", ") << " still uncollectable after "
+                    << KEY_ITEM_PLACEMENT_ATTEMPTS << " attempts\n";
+    m_uncollectableKeyItems = names;
+    m_lostKeyItems = lost;
+    m_vanillaKeyItems = fallbacks;
+
+    // --- Forward check ---------------------------------------------------------
+    // Replay the seed from an empty inventory, one round at a time, and make
+    // sure every logic item gets collected. Vanilla fallbacks and lost items
+    // are the only way this can fail.
+    {
+        debugStream << "\n--- Logic playthrough ---\n";
+        QSet<quint32> have;
+        int round = 0;
+        for (;;) {
+            QSet<quint32> found;
+            for (const Holding& h : holdings) {
+                if (!have.contains(h.item) && requirementsMet(h.sphere, h.fieldName, have))
+                    found.insert(h.item);
+            }
+            if (found.isEmpty()) break;
+            QStringList names;
+            for (quint32 item : found) {
+                const GlobalKeyItem& k = uniqueKeyItems[item];
+                names << getKeyItemName(0x0BA4 + k.address, k.bit);
+            }
+            names.sort();
+            debugStream << "  Round " << ++round << ": " << names.join(", ") << "\n";
+            have.unite(found);
+        }
+        m_keyItemLogicOk = have.size() == logicItems.size();
+        for (quint32 item : logicItems) {
+            if (have.contains(item)) continue;
+            const GlobalKeyItem& k = uniqueKeyItems[item];
+            debugStream << "  UNREACHABLE: '" << getKeyItemName(0x0BA4 + k.address, k.bit) << "'\n";
         }
+        debugStream << "Logic: " << (m_keyItemLogicOk ? "beatable" : "NOT beatable") << " ("
+                    << have.size() << " / " << logicItems.size() << " key items reachable)\n";
     }
 
     debugStream << "\nKey items placed: " << placed << " / " << uniqueKeyItems.size() << "\n";
 
+    // All three coupons must be in hand on the one Shinra HQ visit; an
+    // unplaced coupon keeps its vanilla BITON on the 63rd floor
+    for (quint32 coupon : { KEY_A_COUPON, KEY_B_COUPON, KEY_C_COUPON }) {
+        if (!uniqueKeyItems.contains(coupon)) continue;
+        const GlobalKeyItem& item = uniqueKeyItems[coupon];
+        debugStream << "COUPON: '" << getKeyItemName(0x0BA4 + item.address, item.bit) << "' "
+                    << (placedLocByKey.contains(coupon)
+                            ? "in " + sphereLocs[placedLocByKey[coupon]].fieldName
+                            : "vanilla in " + allFileNames[item.fileIndex])
+                    << "\n";
+    }
+
     // Modifications are returned; they will be applied in the per-file loop
     // alongside STITM/SMTRA randomization so nothing gets overwritten.
     return fieldMods;
 }
 
+QString FieldPickupRandomizer_ff7tk::keyItemSlotProblem(const QByteArray& decompressed,
+                                                       const QString& fieldName,
+                                                       int offset, bool isBiton)
+{
+    const int size = isBiton ? 4 : STITM_SIZE;
+    const int opcode = isBiton ? BITON_OPCODE : STITM_OPCODE;
+    if (decompressed.isEmpty())
+        return QString("%1 does not decompress").arg(fieldName);
+    if (offset < 0 || offset + size > decompressed.size()
+        || static_cast<quint8>(decompressed.at(offset)) != opcode)
+        return QString("no %1 at %2 @%3").arg(isBiton ? "BITON" : "STITM", fieldName).arg(offset);
+    return QString();
+}
+
+void FieldPickupRandomizer_ff7tk::dropUnwritableKeyItemSlots(
+    QVector<GlobalStitmLocation>& stitmLocations,
+    const QMap<QString, QByteArray>& slotFields,
+    const QStringList& allFileNames,
+    QTextStream& debugStream)
+{
+    for (int i = stitmLocations.size() - 1; i >= 0; --i) {
+        const GlobalStitmLocation& loc = stitmLocations[i];
+        const QString fieldName = allFileNames[loc.fileIndex];
+        const QString problem = keyItemSlotProblem(slotFields.value(fieldName), fieldName,
+                                                   loc.scriptOffset, loc.isBiton);
+        if (problem.isEmpty()) continue;
+        debugStream << "SLOT DROPPED: " << problem << "\n";
+        stitmLocations.removeAt(i);
+    }
+}
+
+QStringList FieldPickupRandomizer_ff7tk::verifyKeyItemPlacements(
+    const QMap<QString, KeyItemFieldMod>& fieldMods,
+    const QMap<QString, QByteArray>& slotFields,
+    QTextStream& debugStream)
+{
+    // Key item -> why one of its slots can't take the new BITON
+    QMap<QString, QString> failed;
+    for (auto it = fieldMods.cbegin(); it != fieldMods.cend(); ++it) {
+        const QByteArray decompressed = slotFields.value(it.key());
+        for (const KeyItemPlacement& p : it.value().placements) {
+            const QString problem = keyItemSlotProblem(decompressed, it.key(),
+                                                       p.targetOffset, p.targetIsBiton);
+            if (!problem.isEmpty() && !failed.contains(p.keyName))
+                failed.insert(p.keyName, problem);
+        }
+    }
+
+    QStringList unwritable;
+    for (auto it = failed.cbegin(); it != failed.cend(); ++it) {
+        debugStream << "  UNWRITABLE: '" << it.key() << "' – " << it.value() << "\n";
+        unwritable << QString("%1 (%2)").arg(it.key(), it.value());
+    }
+    return unwritable;
+}
+
+bool FieldPickupRandomizer_ff7tk::auditKeyItemSources(
+    QMap<QString, KeyItemFieldMod>& fieldMods,
+    const QMap<quint32, QVector<GlobalKeyItem>>& keyItemSources,
+    const QStringList& allFileNames,
+    QTextStream& debugStream)
+{
+    // A moved key item whose other grants stay live can be collected twice,
+    // or early enough to skip the logic the plan assumed
+    QStringList live;
+    for (auto it = keyItemSources.cbegin(); it != keyItemSources.cend(); ++it) {
+        const QVector<GlobalKeyItem>& sources = it.value();
+        if (sources.size() < 2) continue;
+        if (m_keyItemSources.isEmpty())
+            debugStream << "\n--- Duplicate key item sources ---\n";
+
+        const GlobalKeyItem& primary = sources.first();
+        const QString keyName = getKeyItemName(0x0BA4 + primary.address, primary.bit);
+        const bool moved = fieldMods.value(allFileNames[primary.fileIndex])
+                               .bitonNopOffsets.contains(primary.scriptOffset);
+        QStringList lines;
+        for (int i = 0; i < sources.size(); ++i) {
+            const GlobalKeyItem& source = sources[i];
+            const QString fieldName = allFileNames[source.fileIndex];
+            QString status;
+            if (!moved) {
+                status = QStringLiteral("kept (item stays vanilla)");
+            } else if (i == 0) {
+                status = QStringLiteral("neutralised (item moved)");
+            } else {
+                KeyItemFieldMod& mod = fieldMods[fieldName];
+                bool hosted = false;
+                for (const KeyItemPlacement& p : mod.placements)
+                    hosted = hosted || (p.targetOffset > source.scriptOffset - STITM_SIZE
+                                        && p.targetOffset < source.scriptOffset + BITON_SIZE);
+                if (hosted) {
+                    status = QStringLiteral("LIVE (a key item placement overlaps it)");
+                    live << QString("%1 in %2 @%3").arg(keyName, fieldName).arg(source.scriptOffset);
+                } else {
+                    if (!mod.bitonNopOffsets.contains(source.scriptOffset))
+                        mod.bitonNopOffsets.append(source.scriptOffset);
+                    status = QStringLiteral("neutralised");
+                }
+            }
+            lines << QString("%1 @%2: %3").arg(fieldName).arg(source.scriptOffset).arg(status);
+            debugStream << "  SOURCE: '" << keyName << "' " << lines.last() << "\n";
+        }
+        m_keyItemSources.insert(keyName, lines);
+    }
+    if (live.isEmpty()) return true;
+
+    const QString message = QString("%1 still granted by a vanilla source after the item was moved")
+                                .arg(live.join(", "));
+    debugStream << "LOGIC: key item " << message << " – aborting\n";
+    if (m_parent)
+        m_parent->reportPlanError("Key item audit: " + message);
+    return false;
+}
+
 // ============================================================================
 // Item pool management
 // ============================================================================
@@ -3527,6 +6083,12 @@ void FieldPickupRandomizer_ff7tk::buildItemPools()
         m_veryRareItems.append(i);
     }
 
+    // ----- Weighted pool categories (Config::PickupCategory order) ----------
+    m_categoryItems.clear();
+    m_categoryItems.resize(Config::PickupCategoryCount);
+    for (int c = 0; c < Config::PickupCategoryCount; ++c)
+        m_categoryItems[c] = pickupCategoryItems(c);
+
     qDebug() << "Item pools built:"
              << "common=" << m_commonItems.size()
              << "uncommon=" << m_uncommonItems.size()
@@ -3534,7 +6096,23 @@ void FieldPickupRandomizer_ff7tk::buildItemPools()
              << "veryRare=" << m_veryRareItems.size();
 }
 
-quint16 FieldPickupRandomizer_ff7tk::getRandomItem(int rarityMode)
+QVector<quint16> FieldPickupRandomizer_ff7tk::pickupCategoryItems(int category)
+{
+    static const int categoryRanges[Config::PickupCategoryCount][2] = {
+        {   0,  31 },   // consumables
+        {  32,  63 },   // battle items
+        { 128, 255 },   // weapons
+        { 256, 287 },   // armor
+        { 288, 319 },   // accessories
+    };
+    QVector<quint16> items;
+    if (category < 0 || category >= Config::PickupCategoryCount) return items;
+    for (int i = categoryRanges[category][0]; i <= categoryRanges[category][1]; ++i)
+        items.append(static_cast<quint16>(i));
+    return items;
+}
+
+quint16 FieldPickupRandomizer_ff7tk::getRandomItem(int rarityMode, QRandomGenerator& rng)
 {
     QVector<quint16> pool;
 
@@ -3554,7 +6132,58 @@ quint16 FieldPickupRandomizer_ff7tk::getRandomItem(int rarityMode)
     }
 
     if (pool.isEmpty()) return 0;
-    return pool[m_rng.bounded(pool.size())];
+    return pool[rng.bounded(pool.size())];
+}
+
+quint16 FieldPickupRandomizer_ff7tk::getWeightedItem(QRandomGenerator& rng)
+{
+    if (!m_parent || m_categoryItems.size() != Config::PickupCategoryCount)
+        return getRandomItem(1, rng);
+
+    const Config& config = m_parent->m_config;
+    int totalWeight = 0;
+    for (int c = 0; c < Config::PickupCategoryCount; ++c) {
+        auto category = static_cast<Config::PickupCategory>(c);
+        if (config.isPickupCategoryEnabled(category) && !m_categoryItems[c].isEmpty())
+            totalWeight += config.getPickupCategoryWeight(category);
+    }
+    if (totalWeight <= 0)
+        return getRandomItem(1, rng);
+
+    int roll = rng.bounded(totalWeight);
+    for (int c = 0; c < Config::PickupCategoryCount; ++c) {
+        auto category = static_cast<Config::PickupCategory>(c);
+        if (!config.isPickupCategoryEnabled(category) || m_categoryItems[c].isEmpty())
+            continue;
+        roll -= config.getPickupCategoryWeight(category);
+        if (roll < 0) {
+            const QVector<quint16>& pool = m_categoryItems[c];
+            return pool[rng.bounded(pool.size())];
+        }
+    }
+    return getRandomItem(1, rng);
+}
+
+quint16 FieldPickupRandomizer_ff7tk::drawFieldItem(const QString& fieldName, QRandomGenerator& rng)
+{
+    quint16 itemId = getWeightedItem(rng);
+    if (!m_parent || !isPointOfNoReturnField(fieldName))
+        return itemId;
+
+    // One roll per pickup decides whether top-tier loot may land here;
+    // otherwise redraw a bounded number of times (a pool of only equipment
+    // keeps its last draw)
+    const int density = m_parent->m_config.getFinalDungeonLootDensity();
+    if (density >= 100 || static_cast<int>(rng.bounded(100)) < density)
+        return itemId;
+    for (int tries = 0; tries < 16 && isTopTierItem(itemId); ++tries)
+        itemId = getWeightedItem(rng);
+    return itemId;
+}
+
+bool FieldPickupRandomizer_ff7tk::isTopTierItem(quint16 itemId) const
+{
+    return m_rareItems.contains(itemId) || m_veryRareItems.contains(itemId);
 }
 
 void FieldPickupRandomizer_ff7tk::buildMateriaPool()
@@ -3576,13 +6205,13 @@ void FieldPickupRandomizer_ff7tk::buildMateriaPool()
     qDebug() << "Materia pool built:" << m_materiaPool.size() << "materia";
 }
 
-quint8 FieldPickupRandomizer_ff7tk::getRandomMateria()
+quint8 FieldPickupRandomizer_ff7tk::getRandomMateria(QRandomGenerator& rng)
 {
     if (m_materiaPool.isEmpty()) return 0;
-    return m_materiaPool[m_rng.bounded(m_materiaPool.size())];
+    return m_materiaPool[rng.bounded(m_materiaPool.size())];
 }
 
-QString FieldPickupRandomizer_ff7tk::getMateriaName(quint8 materiaId) const
+QString FieldPickupRandomizer_ff7tk::getCanonicalMateriaName(quint8 materiaId)
 {
     // Authoritative table from ff7tk FF7Materia.h (KERNEL.bin layout).
     // Note the gaps at 0x16, 0x26, 0x2D-0x2F, 0x3F, 0x42-0x43 (placeholder
@@ -3633,18 +6262,11 @@ QString FieldPickupRandomizer_ff7tk::findFlevelPath() const
     if (!m_parent) return QString();
 
     QString ff7Path = m_parent->getFF7Path();
-    QStringList candidates = {
-        ff7Path + "/data/field/flevel.lgp",
-        ff7Path + "/data/flevel/flevel.lgp",
-        ff7Path + "/field/flevel.lgp",
-    };
-
-    for (const QString& p : candidates) {
-        if (QFile::exists(p)) return p;
-    }
+    QString outputPath = m_parent->getOutputPath();
+    QString found = GameFileLocator::findFlevelLgp(ff7Path, outputPath);
+    if (!found.isEmpty()) return found;
 
     // Also check if user placed it in the output folder already
-    QString outputPath = m_parent->getOutputPath();
     QStringList outputCandidates = {
         outputPath + "/data/field/flevel.lgp",
         outputPath + "/data/flevel/flevel.lgp",
@@ -3656,10 +6278,30 @@ QString FieldPickupRandomizer_ff7tk::findFlevelPath() const
     return QString();
 }
 
-QString FieldPickupRandomizer_ff7tk::getItemName(quint16 itemId) const
+QString FieldPickupRandomizer_ff7tk::getCanonicalItemName(quint16 itemId)
 {
     // Use ff7tk's authoritative item name table
     QString name = FF7Item::name(itemId);
     if (!name.isEmpty()) return name;
     return QString("Item_%1").arg(itemId);
 }
+
+QString FieldPickupRandomizer_ff7tk::getItemName(quint16 itemId) const
+{
+    // Prefer the install's own kernel2.bin text so logs and "Received" dialogs
+    // match localized / renamed games
+    if (m_parent) {
+        QString name = m_parent->installNames().itemName(itemId);
+        if (!name.isEmpty()) return name;
+    }
+    return getCanonicalItemName(itemId);
+}
+
+QString FieldPickupRandomizer_ff7tk::getMateriaName(quint8 materiaId) const
+{
+    if (m_parent) {
+        QString name = m_parent->installNames().materiaName(materiaId);
+        if (!name.isEmpty()) return name;
+    }
+    return getCanonicalMateriaName(materiaId);
+}
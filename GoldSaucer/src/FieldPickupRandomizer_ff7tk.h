@@ -2,6 +2,7 @@
 
 #include <QObject>
 #include <QString>
+#include <QStringList>
 #include <QByteArray>
 #include <QTextStream>
 #include <QVector>
@@ -51,9 +52,13 @@ struct STITMInfo {
     quint8 originalQuantity;
     quint8 banks;
     bool isDirectValue;       // true when banks==0 (literal, not variable ref)
+    int entityIndex;          // owning entity / script slot (-1 if unresolved),
+    int scriptIndex;          // as Makou shows them ("Entity ID16, Script 19")
+    int scriptOffset;         // offset from the owning script's entry point (-1 if unresolved)
 
     STITMInfo() : offset(-1), originalItemID(0), originalQuantity(0),
-                  banks(0), isDirectValue(false) {}
+                  banks(0), isDirectValue(false), entityIndex(-1), scriptIndex(-1),
+                  scriptOffset(-1) {}
 };
 
 // Holds a found SMTRA opcode and its location within field data
@@ -63,9 +68,12 @@ struct SMTRAInfo {
     quint8 originalAP[3];
     quint8 banks[2];
     bool isDirectValue;       // true when both banks==0
+    int entityIndex;          // owning entity / script slot (-1 if unresolved)
+    int scriptIndex;
 
     SMTRAInfo() : offset(-1), originalMateriaID(0), banks{0, 0},
-                  isDirectValue(false) { originalAP[0] = originalAP[1] = originalAP[2] = 0; }
+                  isDirectValue(false), entityIndex(-1), scriptIndex(-1)
+                  { originalAP[0] = originalAP[1] = originalAP[2] = 0; }
 };
 
 // Tracks a single opcode modification for text updating
@@ -83,6 +91,7 @@ struct OpcodeModification {
 class FieldPickupRandomizer_ff7tk : public QObject
 {
     Q_OBJECT
+    friend class EconomyBalancer;   // tiers gil pickups by getFieldSphere()
 
 public:
     explicit FieldPickupRandomizer_ff7tk(Randomizer* parent = nullptr);
@@ -93,14 +102,53 @@ public:
 
     // Item pool helpers (public so tests can call them)
     void initializeItemPools();
-    quint16 getRandomItem(int rarityMode);
+    quint16 getRandomItem(int rarityMode, QRandomGenerator& rng);
+    // Draws from the Item Pool tab's weighted categories; falls back to
+    // getRandomItem(1) when every category is excluded.
+    quint16 getWeightedItem(QRandomGenerator& rng);
+    // Item ids in one Item Pool tab category (Config::PickupCategory order);
+    // shared with the shop location slots so both draw from the same pool.
+    static QVector<quint16> pickupCategoryItems(int category);
+    // getWeightedItem() for one field pickup, thinning rare/very rare loot
+    // in the final dungeon by Config::getFinalDungeonLootDensity()
+    quint16 drawFieldItem(const QString& fieldName, QRandomGenerator& rng);
+
+    // Built-in English names; AP JSON locations are keyed on these
+    static QString getCanonicalItemName(quint16 itemId);
+    static QString getCanonicalMateriaName(quint8 materiaId);
+    // Key item held at savemap saveOffset (0x0BE4-0x0BEA), bit 0-7
+    static QString getKeyItemName(quint16 saveOffset, quint8 bit);
+
+    // Fields from the last run whose layout didn't match vanilla (Reunion,
+    // retranslations), keyed by name with the reason; their pickups are still
+    // randomized but their "Received" texts are left alone
+    const QMap<QString, QString>& moddedFields() const { return m_moddedFields; }
+
+    // Shuffled key items the last run had to leave vanilla, keyed by name
+    // with the reason; listed in the seed report
+    const QMap<QString, QString>& vanillaKeyItems() const { return m_vanillaKeyItems; }
+
+    // Key items the last run found granted in several places, keyed by name:
+    // one "<field> @<offset>: <status>" line per source; listed in the seed
+    // report
+    const QMap<QString, QStringList>& keyItemSources() const { return m_keyItemSources; }
+
+    // Work done by the last run, for the seed report: fields read from
+    // flevel.lgp and the compressed size of the fields written back
+    int fieldsScanned() const { return m_fieldsScanned; }
+    qint64 bytesRecompressed() const { return m_bytesRecompressed; }
 
-    void setDebugMode(bool enabled) { m_debugMode = enabled; }
+    // Why a decompressed field doesn't look like a vanilla one (section
+    // count, text table layout), or an empty string if it does
+    static QString fieldLayoutIssue(const QByteArray& decompressed);
+
+    // Problems with the built-in field patch bytes (bad hex, unknown or
+    // truncated opcodes); empty when they're all sound. Checked once and cached.
+    static QStringList validateHelperScripts();
 
 private:
     Randomizer* m_parent;
     QRandomGenerator m_rng;
-    bool m_debugMode;
 
     // Item pools by rarity tier
     QVector<quint16> m_commonItems;
@@ -108,9 +156,69 @@ private:
     QVector<quint16> m_rareItems;
     QVector<quint16> m_veryRareItems;
 
+    // Item pools by Config::PickupCategory (weighted pool)
+    QVector<QVector<quint16>> m_categoryItems;
+
     // Materia pool
     QVector<quint8> m_materiaPool;
 
+    // Set when the key item plan puts an item behind a chocobo crossing; the
+    // Chocobo Farm Lure grant is then kept so the crossing stays reachable.
+    bool m_chocoboLureRequired = false;
+
+    // Result of the key item plan's forward check: false when some shuffled
+    // key item can't be collected from a new game (Config::getValidateLogic
+    // then fails the run instead of only logging it).
+    bool m_keyItemLogicOk = true;
+
+    // Shuffled key items the plan could not keep in the seed after every
+    // placement attempt; any of these fails the run
+    QStringList m_lostKeyItems;
+
+    // Shuffled key items still uncollectable after the last placement
+    // attempt; any of these fails the run
+    QStringList m_uncollectableKeyItems;
+
+    // Shuffled key items this run left on their vanilla grant, with the
+    // reason (no eligible slot)
+    QMap<QString, QString> m_vanillaKeyItems;
+
+    // Duplicate key item sources and whether each was neutralised
+    // (see auditKeyItemSources)
+    QMap<QString, QStringList> m_keyItemSources;
+
+    // 65th floor checks retargeted by patchMidgarPartsCheck() this run; a
+    // lowered Midgar Parts count with none patched fails the run
+    int m_midgarPartsChecksPatched = 0;
+
+    // Observatory checks retargeted by patchHugeMateriaCheck() this run; a
+    // lowered Huge Materia count with none patched fails the run
+    int m_hugeMateriaChecksPatched = 0;
+
+    // Placed key items that got no sound cue (no SOUND next to the grant),
+    // as "field @offset"; listed in the summary of the field log
+    QStringList m_uncuedKeyItems;
+
+    // Battle IDs found in the field encounter tables, keyed by the field's
+    // sphere; FieldEncountersReroll draws from the spheres around a field
+    QMap<int, QVector<quint16>> m_fieldEncounterPool;
+
+    // The wcrimb wire climb to Shinra HQ uses three Batteries. Before the
+    // field pass, planBatterySlots() picks pickups reachable ahead of the
+    // climb that together hand out at least that many; each field's entry
+    // holds the ordinals of its valid STITMs that give Batteries instead of
+    // a random draw.
+    QMap<QString, QSet<int>> m_batterySlots;
+
+    // Tiered equipment (Config::getTieredEquipment): STITM ordinal
+    // -> the sword or armlet tier that pickup hands out, planned by
+    // planTieredSlots() so the tiers rise with the sphere
+    QMap<QString, QMap<int, quint16>> m_tieredSlots;
+
+    QMap<QString, QString> m_moddedFields;
+    int m_fieldsScanned = 0;
+    qint64 m_bytesRecompressed = 0;
+
     // --- Archipelago BITON mode ---
     struct ApBitonEntry {
         QString  field;
@@ -122,8 +230,58 @@ private:
         quint8   bankByte;          // 0x10 (key items, bank 1) or 0x30 (bank 3)
         quint8   address;           // savemap address within the dest bank
         quint8   bit;               // bit 0..7
+        int      entityIndex;       // owning entity / script (-1 if unresolved)
+        int      scriptIndex;
     };
     QVector<ApBitonEntry> m_apBitonEntries;
+
+    // --- Auto-tracker map (tracker_map.json) ---
+    // One entry per randomized location: the savemap bit the game sets when
+    // it is collected, so trackers reading game memory can mark it. bank is
+    // -1 when the script sets no flag; trackers then watch the inventory.
+    struct TrackerEntry {
+        QString field;
+        int     offset;
+        int     entityIndex;
+        int     scriptIndex;
+        QString kind;               // "item", "materia" or "keyItem"
+        int     placedId;           // item / materia id (key items: -1)
+        QString placedName;
+        int     bank;               // field bank (1-15) of the flag, or -1
+        int     address;
+        int     bit;
+    };
+    QVector<TrackerEntry> m_trackerEntries;
+
+    // --- Multiworld location table (archipelago_export.json) ---
+    // Every candidate slot of the vanilla fields: direct STITMs and SMTRAs
+    // and key item BITONs, named after the field, opcode and vanilla ordinal
+    // ("mds7_w1 STITM #2"). Built from the vanilla bytes only, so a name
+    // points at the same check whatever the seed or settings.
+    struct ExportLocation {
+        QString name;
+        QString opcode;             // "STITM", "SMTRA" or "BITON"
+        int     entityIndex;
+        int     scriptIndex;
+        QString vanilla;            // what the slot holds in the vanilla game
+    };
+    QMap<QString, QMap<int, ExportLocation>> m_exportLocations;   // field -> offset -> slot
+
+    // One changed byte range of a field's decompressed data and the patch
+    // step that changed it; written to field_patch_log.json when enabled
+    struct PatchRecord {
+        QString    field;
+        int        offset;
+        QByteArray before;
+        QByteArray after;
+        QString    reason;
+    };
+    QVector<PatchRecord> m_patchLog;
+    bool m_patchLogEnabled = false;
+    static void appendPatchRecords(const QString& fieldName, const QByteArray& before,
+                                   const QByteArray& after, const QString& reason,
+                                   QVector<PatchRecord>& out);
+    void writePatchLog(const QString& outputPath, QTextStream& debugStream) const;
     // JSON-driven lookup: key = "fieldname|item_text" (both lowercased)
     // Value is a queue of (bank, address, bit) triples — multiple items with
     // the same name in the same field are consumed in the order they appear
@@ -181,10 +339,64 @@ private:
         Underwear,
     };
 
+    // What processFieldFile() produces for one field besides its bytes.
+    // Fields are patched in parallel, each drawing from its own generator,
+    // and randomize() merges these into the members in file order.
+    struct FieldResult {
+        QRandomGenerator          rng;              // seeded from streamSeed("field <name>")
+        QVector<TrackerEntry>     trackerEntries;
+        QVector<PatchRecord>      patchRecords;
+        QMap<int, ExportLocation> exportLocations;  // Archipelago Export only
+        QString                   layoutIssue;      // m_moddedFields entry, if any
+        QString                   inputError;       // for Randomizer::reportInputError
+        QStringList               uncuedKeyItems;
+        int                       midgarPartsChecksPatched = 0;
+        int                       hugeMateriaChecksPatched = 0;
+    };
+
     // --- Core workflow ---
+    // Patches one LZS-compressed field. Returns true when anything changed;
+    // fieldData then holds the patched field DEcompressed. Touches no member
+    // state (everything it finds goes to `result`), so randomize() runs it
+    // for many fields at once.
     bool processFieldFile(const QString& fieldName, QByteArray& fieldData,
-                          QTextStream& debugStream,
-                          const KeyItemFieldMod* keyItemMod = nullptr);
+                          QTextStream& debugStream, FieldResult& result,
+                          const KeyItemFieldMod* keyItemMod = nullptr,
+                          QTextStream* stitmIndex = nullptr);
+    // The tracker_map.json entries for a field that ships randomized
+    void recordTrackerEntries(const QByteArray& decompressed, const QString& fieldName,
+                              const QVector<OpcodeModification>& modifications,
+                              const KeyItemFieldMod* keyItemMod,
+                              QVector<TrackerEntry>& entries) const;
+    void writeTrackerMap(const QString& outputPath, QTextStream& debugStream) const;
+    // archipelago_export.json from the same entries: stable location names,
+    // placed items and their progression/useful/filler classification
+    QString archipelagoClassification(const TrackerEntry& entry) const;
+    QMap<int, ExportLocation> exportLocations(const QByteArray& decompressed, const QString& fieldName);
+    void writeArchipelagoExport(const QString& outputPath, QTextStream& debugStream) const;
+    // Key item sound cue: swaps the sound of a SOUND opcode directly next to
+    // a placed key item (the opcode right before the grant, or right after it
+    // and its NOP padding) for KEY_ITEM_SOUND_ID. Scripts aren't resized, so
+    // no SOUND is inserted: a grant with no SOUND next to it is left uncued
+    // (false), and processFieldFile() lists it in FieldResult::uncuedKeyItems.
+    bool applyKeyItemSoundCue(QByteArray& decompressed, int targetOffset,
+                              QTextStream& debugStream);
+    // Model scale gag: rescales every model in the field's model loader
+    // (section 3) by one random factor per map. Cosmetic only.
+    bool scaleFieldModels(QByteArray& decompressed, const QString& fieldName,
+                          QRandomGenerator& rng, QTextStream& debugStream);
+    // Field encounters: collects every field's random battles by sphere
+    // (reroll pool), then shuffles or rerolls the normal battle slots and
+    // optionally the rate of each table in section 7
+    void collectFieldEncounterPool(MakouLgpManager& lgp, const QStringList& allFiles,
+                                   QTextStream& debugStream);
+    bool randomizeFieldEncounters(QByteArray& decompressed, const QString& fieldName,
+                                  QRandomGenerator& rng, QTextStream& debugStream);
+    // Scripted fights: remaps constant BATTLE formations listed in a
+    // ScriptedBattleTable group to another formation of that group, the same
+    // way for the whole field
+    int randomizeScriptedBattles(QByteArray& decompressed, const QString& fieldName,
+                                 QRandomGenerator& rng, QTextStream& debugStream);
 
     // --- STITM scanning ---
     QVector<STITMInfo> scanForSTITM(const QByteArray& fieldData,
@@ -208,15 +420,18 @@ private:
                                    QTextStream& debugStream);
 
     // --- Text section update ---
+    // silent: NOP each pickup's MESSAGE instead of giving it a new text;
+    // the text section is left as it is
     bool updateFieldTexts(QByteArray& decompressed,
                           const QVector<OpcodeModification>& modifications,
-                          QTextStream& debugStream);
+                          QTextStream& debugStream, bool silent = false);
     static const int MESSAGE_OPCODE = 0x40;
 
     void collectKeyItemsAndStitm(const QByteArray& fieldData, int fileIndex,
                                   const QString& fieldName,
                                   QMap<quint32, GlobalKeyItem>& uniqueKeyItems,
                                   QVector<GlobalStitmLocation>& stitmLocations,
+                                  QMap<quint32, QVector<GlobalKeyItem>>& keyItemSources,
                                   QTextStream& debugStream);
     QMap<QString, KeyItemFieldMod> performKeyItemSwaps(
                              QMap<quint32, GlobalKeyItem>& uniqueKeyItems,
@@ -224,24 +439,146 @@ private:
                              const QStringList& allFileNames,
                              QTextStream& debugStream);
 
+    // Why a slot can't take a key item's BITON, or an empty string when it can
+    static QString keyItemSlotProblem(const QByteArray& decompressed, const QString& fieldName,
+                                      int offset, bool isBiton);
+    // Drops slots whose field doesn't hold the scanned STITM/BITON at their
+    // offset (slotFields: decompressed vanilla bytes), so the plan and its
+    // logic playthrough only ever see slots that can be written.
+    void dropUnwritableKeyItemSlots(QVector<GlobalStitmLocation>& stitmLocations,
+                                    const QMap<QString, QByteArray>& slotFields,
+                                    const QStringList& allFileNames,
+                                    QTextStream& debugStream);
+
+    // Rechecks each planned slot against slotFields before anything is
+    // written. Returns the key items with a slot that can't take their
+    // BITON; the playthrough didn't see that, so any of these fails the run.
+    QStringList verifyKeyItemPlacements(const QMap<QString, KeyItemFieldMod>& fieldMods,
+                                        const QMap<QString, QByteArray>& slotFields,
+                                        QTextStream& debugStream);
+
+    // Key flags granted by more than one vanilla BITON (keyItemSources, first
+    // entry = the one the plan moves): once the item is moved, every other
+    // source is NOPed too. Each source and what happened to it goes to
+    // m_keyItemSources; false when a source of a moved item would still
+    // grant it.
+    bool auditKeyItemSources(QMap<QString, KeyItemFieldMod>& fieldMods,
+                             const QMap<quint32, QVector<GlobalKeyItem>>& keyItemSources,
+                             const QStringList& allFileNames,
+                             QTextStream& debugStream);
+
+    // Rewrites the key item BITON at offset as a STITM for a random field
+    // item when it is listed in BITON_CHEST_SOURCES and the script has a
+    // spare byte after it; false leaves the bytes untouched
+    bool convertBitonGrantToChest(QByteArray& decompressed, const QString& fieldName,
+                                  int offset, QRandomGenerator& rng, QTextStream& debugStream);
+
+    // Fills m_batterySlots (normal mode only). Fields touched by the key
+    // item plan and the synced-entity fields are left out so the STITM
+    // ordinals stay the same when the field is processed.
+    void planBatterySlots(MakouLgpManager& lgp, const QStringList& allFiles,
+                          const QMap<QString, KeyItemFieldMod>& keyItemMods,
+                          QTextStream& debugStream);
+    // Fills m_tieredSlots, after the Batteries and with the same field
+    // exclusions
+    void planTieredSlots(MakouLgpManager& lgp, const QStringList& allFiles,
+                              const QMap<QString, KeyItemFieldMod>& keyItemMods,
+                              QTextStream& debugStream);
+
     static int getFieldSphere(const QString& fieldName);
     static int getKeyItemMinSphere(quint32 keyItemId);
     static int getKeyItemMaxSphere(quint32 keyItemId);
     static int getKeyItemMinMoment(quint32 keyItemId);
     static int getKeyItemMaxMoment(quint32 keyItemId);
+    // Key items no story gate checks for; only shuffled when
+    // Config::getOptionalKeyItems() is on
+    static bool isOptionalKeyItem(quint32 keyItemId);
     static QPair<int, int> getStitmMomentWindow(const QString& fieldName, int scriptOffset);
     static QPair<int, int> getFieldMomentWindow(const QString& fieldName);
     static WardrobeCategory getWardrobeCategory(quint32 keyItemId);
     static QString wardrobeCategoryName(WardrobeCategory category);
     static bool requiresMirroredBitons(const QString& fieldName);
-    static QString getKeyItemName(quint16 saveOffset, quint8 bit);
+    // Savemap bits that share bytes with flags we do write but hold minigame,
+    // date or quest state. No rewrite may set, clear or flip them: AP coords
+    // landing on one are dropped, and a field whose rewritten scripts touch
+    // one more often than vanilla fails the run before flevel.lgp is saved.
+    static bool isProtectedFlag(int bank, int address, int bit);
+    // Constant-bit BITON/BITOFF/BITXOR writes to protected flags, keyed
+    // bank << 16 | address << 8 | bit, with their counts
+    static QMap<quint32, int> protectedFlagWrites(const QByteArray& decompressed);
+    // Midgar Parts turn-in: retargets the 65th floor model's "five parts
+    // placed" check to Config::getMidgarPartsRequired()
+    int patchMidgarPartsCheck(QByteArray& decompressed, const QString& fieldName,
+                              int required, QTextStream& debugStream);
+    // Huge Materia hunt: retargets Bugenhagen's observatory count of Huge
+    // Materia to Config::getHugeMateriaRequired()
+    int patchHugeMateriaCheck(QByteArray& decompressed, const QString& fieldName,
+                              int required, QTextStream& debugStream);
+    // Shared by both: finds the counter bumped by scripts that BITON/BITOFF
+    // (firstOpcode-lastOpcode) a bit of bitMask at Var[1/2][address], then
+    // retargets compares of it against vanillaCount
+    int patchCountedCheck(QByteArray& decompressed, const QString& fieldName,
+                          int address, quint8 bitMask, quint8 firstOpcode, quint8 lastOpcode,
+                          int vanillaCount, int required, const char* tag,
+                          QTextStream& debugStream);
+    // Progressive keycards: keycard grants bump one counter and the door
+    // checks compare it against the card's tier (Config::getProgressiveKeycards).
+    // The counter sits next to the 0xFE BITON sink in bank 3.
+    static const int    PROGRESSIVE_KEYCARD_BANK = 3;
+    static const quint8 PROGRESSIVE_KEYCARD_VAR  = 0xFF;
+    int patchProgressiveKeycards(QByteArray& decompressed, const QString& fieldName,
+                                 QTextStream& debugStream);
+    // Chocobo Farm fields whose SMTRA grants (Chocobo Lure, Choco/Mog) are
+    // treated as chocobo checks, and fields only reachable by river/mountain/
+    // ocean chocobo (so a placed key item there makes the Lure mandatory).
+    static bool isChocoboFarmField(const QString& fieldName);
+    static bool requiresChocoboCrossing(const QString& fieldName);
+    // Optional dungeons with strong vanilla pickups: Ancient Forest,
+    // Sunken Gelnika and the Da-chao fire cave. Returns the dungeon name,
+    // or an empty string for any other field.
+    static QString optionalDungeonName(const QString& fieldName);
+    // The Sunken Gelnika is unreachable until the submarine, so a key item
+    // placed there could lock progression behind it.
+    static bool requiresSubmarine(const QString& fieldName);
+    // The red submarine mission (subin_1a/subin_1b). Losing the chase skips
+    // its grants, so no progression item is ever placed there.
+    static bool isSubmarineMissionField(const QString& fieldName);
+    // Whirlwind Maze and Northern Crater fields that run the Black Materia
+    // handoff. Its BITONs there are story re-grants, never placement slots.
+    static bool isBlackMateriaHandoffField(const QString& fieldName);
+    // Key items needed to enter a field (the Keystone for the Temple of the
+    // Ancients). Key item placement only uses a slot once
+    // every randomized item gating it is placed somewhere reachable first.
+    static QVector<quint32> getFieldAccessKeyItems(const QString& fieldName);
+    // The Shinra HQ 63rd floor coupon exchange (blin63_1): the Star Pendant,
+    // Four Slots and All grants handed out for the A/B/C Coupons. They are
+    // randomized as ordinary pickups but never hold a key item, since a
+    // coupon placed there could only be bought with itself.
+    static bool isCouponExchangeGrant(const QString& fieldName, quint16 itemId);
+    static bool isCouponExchangeMateria(const QString& fieldName, quint8 materiaId);
+    // Northern Crater split-path fields, whose one-time handouts are
+    // duplicated per party member and randomized as one location each
+    // (copies share a script slot and their offset within it).
+    static bool isCraterHandoutField(const QString& fieldName);
+    // Config::LocationGroup a field's checks belong to (Wutai, Gold Saucer
+    // minigames, missables), or -1. isLocationGroupOff() is true when that
+    // group is switched off: its pickups and key items then stay vanilla.
+    static int locationGroupOf(const QString& fieldName);
+    bool isLocationGroupOff(const QString& fieldName) const;
+    // Northern Crater interior, where top-tier loot is thinned out
+    static bool isPointOfNoReturnField(const QString& fieldName);
+    // The four materia cave rewards, by their vanilla materia id (the game
+    // hands these out nowhere else). Returns the chocobo needed to reach the
+    // cave, or an empty string for any other materia.
+    static QString materiaCaveChocobo(quint8 materiaId);
 
     // --- Free Roam MAPJUMP injection ---
     bool injectFreeRoamMapJump(QByteArray& decompressed, const QString& fieldName,
                                QTextStream& debugStream);
     // Debug-only: dump a field's section-0 entity script table and a decoded
     // opcode listing for each script, to diagnose autonomous entry events
-    // (e.g. the Rocket Town soft-lock at game moment 1603).
+    // (e.g. the Rocket Town soft-lock at game moment 1603). Written for every
+    // changed field when Config::DebugScriptDumps is on.
     void dumpFieldScripts(const QByteArray& decompressed, const QString& fieldName,
                           QTextStream& debugStream);
     // Overwrite an existing (never-shown in Free Roam) field dialog in place with
@@ -253,20 +590,52 @@ private:
     // --- Helpers ---
     void buildItemPools();
     void buildMateriaPool();
-    quint8 getRandomMateria();
+    quint8 getRandomMateria(QRandomGenerator& rng);
+    bool isTopTierItem(quint16 itemId) const;
+    // Display names (install's kernel2.bin when loaded, else built-in English)
     QString getItemName(quint16 itemId) const;
     QString getMateriaName(quint8 materiaId) const;
     QString findFlevelPath() const;
 
     // --- Constants ---
     static const int    MAX_ITEM_ID        = 319;
+    static const int    KEY_ITEM_PLACEMENT_ATTEMPTS = 20;
     static const int    MAX_MATERIA_ID     = 90;
     static const int    STITM_OPCODE       = 0x58;
     static const int    STITM_SIZE         = 5;
     static const int    SMTRA_OPCODE       = 0x5B;
     static const int    SMTRA_SIZE         = 7;
+    static const quint8 MATERIA_CHOCOBO_LURE = 0x09;
+    static const quint8 MATERIA_HP_MP        = 0x12;
+    static const quint8 MATERIA_QUADRA_MAGIC = 0x23;
+    static const quint8 MATERIA_MIME         = 0x2B;
+    static const quint8 MATERIA_KOTR         = 0x59;
+    static const quint16 ITEM_BATTERY        = 0x55;
+    static const quint16 ITEM_FOUR_SLOTS     = 277;
+    static const quint16 ITEM_STAR_PENDANT   = 298;
+    static const quint8 MATERIA_ALL          = 0x17;
+    static const int    BATTERIES_FOR_CLIMB  = 3;    // used on the wcrimb wire climb
     static const int    BITON_OPCODE       = 0x82;
     static const int    BITON_SIZE         = 4;
+    // SOUND (0xF1): banks, sound id (u16), direction. Key item sound cues
+    // retarget the SOUND next to the grant to KEY_ITEM_SOUND_ID.
+    static const int    SOUND_OPCODE       = 0xF1;
+    static const quint16 KEY_ITEM_SOUND_ID = 0x00A4;
+    // Model scale gag bounds, as a percentage of each model's vanilla scale.
+    // Kept tight enough that models still fit doorways and walkmesh gates.
+    static const int    MODEL_SCALE_MIN_PERCENT = 80;
+    static const int    MODEL_SCALE_MAX_PERCENT = 125;
+    // Field encounter tables: a reroll may pull battles from this many
+    // spheres either side of the field, and a rerolled rate stays within
+    // these percentages of the vanilla one
+    static const int    FIELD_ENCOUNTER_SPHERE_SPREAD = 1;
+    // getFieldSphere() of a field outside the sphere lists
+    static const int    UNKNOWN_SPHERE = 99;
+    static const int    FIELD_ENCOUNTER_RATE_MIN_PERCENT = 50;
+    static const int    FIELD_ENCOUNTER_RATE_MAX_PERCENT = 150;
+    // BATTLE (0x70): banks, formation ID (u16)
+    static const int    BATTLE_OPCODE      = 0x70;
+    static const int    BATTLE_SIZE        = 4;
     // AP_BITON bank/address are sourced per-placement from the .apff7 JSON
     // (see ApBitonCoord).  The default for auto-allocated locations is bank 1
     // (see json_export.py), with a blacklist of known NPC quest-state addresses
@@ -0,0 +1,159 @@
+#include "FieldScript.h"
+
+#include <cstring>
+
+// Returns the total byte length (including the opcode byte) of the FF7 field
+// script opcode at `pos`, or -1 if the opcode is invalid/unknown or would run
+// past the end of the buffer. Operand counts are from the standard FF7 opcode
+// table (cf. PyFF7 / Makou Reactor). SPECIAL (0x0F) and KAWAI (0x28) are
+// variable length and handled explicitly.
+int FieldScript::opcodeLength(const QByteArray& d, int pos, int fileSize)
+{
+    // Operand byte counts (excluding the 1-byte opcode). -1 = invalid opcode.
+    static const int kOperands[256] = {
+        /*00*/  0, 2, 2, 2, 2, 2, 2, 1,  1,14, 5, 5,-1,-1, 1, 0,
+        /*10*/  1, 2, 1, 2, 5, 6, 7, 8,  7, 8,-1,-1,-1,-1,-1,-1,
+        /*20*/ 10, 1, 4, 2, 2, 8, 1, 1,  0, 0, 1, 1, 4, 6, 1, 9,
+        /*30*/  3, 3, 3, 1, 1, 3, 4, 7,  5, 5, 5, 3, 0, 0, 0, 0,
+        /*40*/  2, 4, 5, 1,-1, 4,-1, 4,  6, 3, 1, 1,-1, 4,-1, 4,
+        /*50*/  9, 5, 3, 1, 1, 2, 6, 6,  4, 4, 4, 6, 7, 9, 7, 0,
+        /*60*/  9, 1, 4, 5, 5, 0, 8, 0,  8, 1, 6, 8, 0, 3, 2, 5,
+        /*70*/  3, 1, 2, 3, 3, 7, 3, 4,  3, 4, 2, 2, 2, 2, 1, 2,
+        /*80*/  3, 4, 3, 3, 3, 3, 4, 3,  4, 3, 4, 3, 4, 3, 4, 3,
+        /*90*/  4, 3, 4, 3, 4, 2, 2, 2,  2, 2, 3, 4, 5, 6, 6,10,
+        /*a0*/  1, 1, 2, 2, 1,10, 8, 8,  5, 5, 1, 3, 0, 5, 2, 2,
+        /*b0*/  4, 4, 3, 2, 5, 5, 1, 3,  4, 3, 2, 4, 4, 3,-1, 1,
+        /*c0*/ 10, 7,14,11, 0, 2, 2, 1,  1, 1, 3, 2, 2, 2, 1, 1,
+        /*d0*/ 12, 1, 1,15, 9, 9, 3, 3,  2, 0,14, 1, 3, 0, 0,10,
+        /*e0*/  3, 3, 2, 2, 2, 4, 4, 4,  6, 9, 9, 4, 4, 7, 7,10,
+        /*f0*/  1, 4,13, 1, 1, 1, 1, 3,  1, 0, 2, 1, 1, 5, 2, 0,
+    };
+
+    // Callers pass a walk bound; never trust it past the buffer
+    fileSize = qMin(fileSize, static_cast<int>(d.size()));
+    if (pos < 0 || pos >= fileSize)
+        return -1;
+    quint8 op = static_cast<quint8>(d.at(pos));
+
+    if (op == 0x0F) {  // SPECIAL: 2-byte header (0x0F + sub) + sub operands
+        if (pos + 1 >= fileSize)
+            return -1;
+        quint8 sub = static_cast<quint8>(d.at(pos + 1));
+        int subOps;
+        switch (sub) {
+            case 0xF5: subOps = 1; break;  // arrow
+            case 0xF6: subOps = 4; break;  // pname
+            case 0xF7: subOps = 2; break;  // gmspd
+            case 0xF8: subOps = 2; break;  // smspd
+            case 0xF9: subOps = 0; break;  // flmat
+            case 0xFA: subOps = 0; break;  // flitm
+            case 0xFB: subOps = 1; break;  // btlck
+            case 0xFC: subOps = 1; break;  // mvlck
+            case 0xFD: subOps = 2; break;  // spcnm
+            case 0xFE: subOps = 0; break;  // rsglb
+            case 0xFF: subOps = 0; break;  // clitm
+            default:   return -1;
+        }
+        int len = 2 + subOps;
+        return (pos + len <= fileSize) ? len : -1;
+    }
+
+    if (op == 0x28) {  // KAWAI: total length is encoded in the second byte
+        if (pos + 1 >= fileSize)
+            return -1;
+        int len = static_cast<quint8>(d.at(pos + 1));
+        if (len < 2)
+            return -1;
+        return (pos + len <= fileSize) ? len : -1;
+    }
+
+    int ops = kOperands[op];
+    if (ops < 0)
+        return -1;
+    int len = 1 + ops;
+    return (pos + len <= fileSize) ? len : -1;
+}
+
+// Every offset is checked as unsigned before it becomes an int, so a garbage
+// section 0 offset can't turn negative and read before the buffer.
+bool FieldScript::scriptRange(const QByteArray& d, ScriptRange& range)
+{
+    const int fileSize = d.size();
+    const int HEADER_SIZE = 6 + 9 * 4;
+    if (fileSize < HEADER_SIZE) return false;
+
+    quint32 sec0 = 0;
+    memcpy(&sec0, d.constData() + 6, 4);
+    if (sec0 > static_cast<quint32>(fileSize - 4 - 32)) return false;
+    const int dataStart = static_cast<int>(sec0) + 4;
+
+    const int entityCount = static_cast<quint8>(d.at(dataStart + 2));
+    quint16 wStringOffset = 0, nAkaoOffsets = 0;
+    memcpy(&wStringOffset, d.constData() + dataStart + 4, 2);
+    memcpy(&nAkaoOffsets,  d.constData() + dataStart + 6, 2);
+    if (entityCount == 0) return false;
+
+    range.dataStart   = dataStart;
+    range.entityCount = entityCount;
+    range.namesStart  = dataStart + 32;
+    range.akaoStart   = range.namesStart + 8 * entityCount;
+    range.offsetTable = range.akaoStart + 4 * static_cast<int>(nAkaoOffsets);
+    if (range.offsetTable + 64 * entityCount > fileSize) return false;
+
+    // Walk bound: start of strings or first AKAO block, whichever is first
+    int walkEnd = dataStart + static_cast<int>(wStringOffset);
+    if (nAkaoOffsets > 0) {
+        quint32 firstAkao = 0;
+        memcpy(&firstAkao, d.constData() + range.akaoStart, 4);
+        if (firstAkao < static_cast<quint32>(fileSize)) {
+            const int akaoAbs = dataStart + static_cast<int>(firstAkao);
+            if (akaoAbs > range.offsetTable && akaoAbs < walkEnd) walkEnd = akaoAbs;
+        }
+    }
+    if (walkEnd > fileSize || walkEnd <= range.offsetTable) walkEnd = fileSize;
+    range.walkEnd = walkEnd;
+    return true;
+}
+
+// Game-compatible FF7 LZS decompressor (Okumura: ring N=4096, r starts at N-F=4078,
+// 0x00 init). Matches the GAME's / vanilla decoder. We need our OWN decoder to VERIFY
+// recompression: ff7tk's LZS::decompressAllWithHeader agrees with ff7tk's compressor
+// (its round-trip always "passes") but ff7tk's compressed output for large fields is
+// NOT what the game decodes — so verifying with ff7tk's decoder is useless. Verifying
+// with this one catches the game-incompatible output. Input includes the 4-byte LE
+// length header.
+QByteArray FieldScript::lzsDecompress(const QByteArray& blob)
+{
+    if (blob.size() < 4) return QByteArray();
+    const unsigned char* p = reinterpret_cast<const unsigned char*>(blob.constData());
+    quint32 fsize = quint32(p[0]) | (quint32(p[1]) << 8) | (quint32(p[2]) << 16) | (quint32(p[3]) << 24);
+    int n = blob.size() - 4;
+    if (static_cast<int>(fsize) < n) n = static_cast<int>(fsize);
+    const unsigned char* data = p + 4;
+    const int N = 4096;
+    unsigned char tb[4096];
+    memset(tb, 0, sizeof(tb));
+    int r = N - 18;
+    QByteArray out;
+    int i = 0;
+    while (i < n) {
+        unsigned char ctrl = data[i++];
+        for (int b = 0; b < 8 && i < n; ++b) {
+            if (ctrl & 1) {
+                unsigned char c = data[i++];
+                out.append(char(c)); tb[r] = c; r = (r + 1) & (N - 1);
+            } else {
+                if (i + 1 >= n) break;
+                unsigned char b1 = data[i], b2 = data[i + 1]; i += 2;
+                int pos = b1 | ((b2 & 0xF0) << 4);
+                int cnt = (b2 & 0x0F) + 3;
+                for (int k = 0; k < cnt; ++k) {
+                    unsigned char c = tb[(pos + k) & (N - 1)];
+                    out.append(char(c)); tb[r] = c; r = (r + 1) & (N - 1);
+                }
+            }
+            ctrl >>= 1;
+        }
+    }
+    return out;
+}
@@ -0,0 +1,38 @@
+#pragma once
+
+#include <QByteArray>
+
+/**
+ * FieldScript
+ *
+ * Low-level readers for decompressed FF7 PC field files, shared by every pass
+ * that walks field scripts: the LZS decoder, the opcode length table and the
+ * section 0 layout. All three take untrusted bytes (modded installs ship
+ * hand-edited fields) and must never read out of bounds; fuzz/field_script_fuzz.cpp
+ * feeds them arbitrary input.
+ */
+class FieldScript
+{
+public:
+    // Section 0 (scripts) layout, as absolute positions in the field file.
+    // Script entry points in the offset table are relative to dataStart.
+    struct ScriptRange {
+        int dataStart   = 0;   // section 0 data (after its 4-byte size)
+        int entityCount = 0;
+        int namesStart  = 0;   // 8-byte entity names
+        int akaoStart   = 0;   // u32 AKAO offsets
+        int offsetTable = 0;   // 32 × u16 script entry points per entity
+        int walkEnd     = 0;   // opcodes stop here (texts or first AKAO block)
+    };
+
+    // Total byte length of the opcode at pos (opcode byte included), or -1
+    // if it is unknown or runs past fileSize (clamped to d.size())
+    static int opcodeLength(const QByteArray& d, int pos, int fileSize);
+
+    // Section 0 layout of a decompressed field; false if the header or the
+    // entity tables don't fit in the file, or the field has no entities
+    static bool scriptRange(const QByteArray& d, ScriptRange& range);
+
+    // Game-compatible LZS decoder; blob includes the 4-byte length header
+    static QByteArray lzsDecompress(const QByteArray& blob);
+};
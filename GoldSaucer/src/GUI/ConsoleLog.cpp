@@ -0,0 +1,88 @@
+#include "ConsoleLog.h"
+
+#include <QSaveFile>
+#include <QTextStream>
+
+ConsoleLog::ConsoleLog(int capacity)
+    : m_entries(qMax(1, capacity))
+{
+}
+
+ConsoleLog::Level ConsoleLog::levelOf(const QString& message)
+{
+    if (message.startsWith("ERROR", Qt::CaseInsensitive))
+        return Error;
+    if (message.startsWith("WARNING", Qt::CaseInsensitive) || message.startsWith("Cancelled"))
+        return Warning;
+    return Info;
+}
+
+QString ConsoleLog::levelName(Level level)
+{
+    switch (level) {
+    case Warning: return "WARN";
+    case Error:   return "ERROR";
+    case Info:    break;
+    }
+    return "INFO";
+}
+
+const ConsoleLog::Entry& ConsoleLog::append(Level level, const QString& subsystem, const QString& message)
+{
+    int slot;
+    if (m_size < m_entries.size()) {
+        slot = (m_head + m_size) % m_entries.size();
+        ++m_size;
+    } else {
+        // Full: overwrite the oldest
+        slot = m_head;
+        m_head = (m_head + 1) % m_entries.size();
+        ++m_dropped;
+    }
+    Entry& entry = m_entries[slot];
+    entry.level = level;
+    entry.time = QDateTime::currentDateTime();
+    entry.subsystem = subsystem;
+    entry.message = message;
+    return entry;
+}
+
+void ConsoleLog::clear()
+{
+    m_head = 0;
+    m_size = 0;
+    m_dropped = 0;
+}
+
+const ConsoleLog::Entry& ConsoleLog::at(int index) const
+{
+    return m_entries[(m_head + index) % m_entries.size()];
+}
+
+QString ConsoleLog::formatLine(const Entry& entry)
+{
+    QString line = entry.time.toString("hh:mm:ss") + " [" + levelName(entry.level) + "] ";
+    if (!entry.subsystem.isEmpty())
+        line += entry.subsystem + ": ";
+    return line + entry.message;
+}
+
+bool ConsoleLog::exportText(const QString& path, QString* error) const
+{
+    QSaveFile file(path);
+    if (!file.open(QIODevice::WriteOnly | QIODevice::Text)) {
+        if (error) *error = QString("Cannot write %1: %2").arg(path, file.errorString());
+        return false;
+    }
+    QTextStream out(&file);
+    if (m_dropped > 0)
+        out << "(" << m_dropped << " older entries dropped)\n";
+    for (int i = 0; i < m_size; ++i)
+        out << formatLine(at(i)) << "\n";
+    out.flush();
+    if (!file.commit()) {
+        if (error) *error = QString("Cannot write %1: %2").arg(path, file.errorString());
+        return false;
+    }
+    return true;
+}
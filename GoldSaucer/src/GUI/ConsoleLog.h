@@ -0,0 +1,58 @@
+#pragma once
+
+#include <QDateTime>
+#include <QString>
+#include <QVector>
+
+// ConsoleLog — the GUI console's backing store. A fixed-capacity ring buffer
+// of structured entries (level, time, subsystem, message); once full the
+// oldest entries are dropped, so a long run never grows the log without
+// bound. The console widget renders from it with level/text filters, and
+// exportText() writes the retained entries to a file.
+class ConsoleLog
+{
+public:
+    enum Level {
+        Info,
+        Warning,
+        Error
+    };
+
+    struct Entry {
+        Level level = Info;
+        QDateTime time;
+        QString subsystem;
+        QString message;
+    };
+
+    explicit ConsoleLog(int capacity = DEFAULT_CAPACITY);
+
+    // Level from the message's own prefix ("ERROR", "WARNING"), as the run
+    // steps already write them
+    static Level levelOf(const QString& message);
+    static QString levelName(Level level);
+
+    const Entry& append(Level level, const QString& subsystem, const QString& message);
+    void clear();
+
+    int size() const { return m_size; }
+    int capacity() const { return m_entries.size(); }
+    int dropped() const { return m_dropped; }
+
+    // Oldest first; 0 <= index < size()
+    const Entry& at(int index) const;
+
+    // One line: "hh:mm:ss [LEVEL] subsystem: message"
+    static QString formatLine(const Entry& entry);
+
+    // Write every retained entry, oldest first; on failure *error says why
+    bool exportText(const QString& path, QString* error = nullptr) const;
+
+    static const int DEFAULT_CAPACITY = 5000;
+
+private:
+    QVector<Entry> m_entries;
+    int m_head = 0;    // index of the oldest entry
+    int m_size = 0;
+    int m_dropped = 0;
+};
@@ -0,0 +1,80 @@
+#include "ErrorReportDialog.h"
+#include "ConsoleLog.h"
+#include "../DiagnosticBundle.h"
+#include <QDialogButtonBox>
+#include <QDir>
+#include <QFileDialog>
+#include <QLabel>
+#include <QMessageBox>
+#include <QPlainTextEdit>
+#include <QPushButton>
+#include <QVBoxLayout>
+
+ErrorReportDialog::ErrorReportDialog(const SeedGenerator::Failure& failure, const Config& config,
+                                     const QString& ff7Path, const ConsoleLog& log, QWidget* parent)
+    : QDialog(parent)
+    , m_failure(failure)
+    , m_config(config)
+    , m_ff7Path(ff7Path)
+{
+    setWindowTitle("Randomization Failed");
+    resize(640, 440);
+    for (int i = 0; i < log.size(); ++i)
+        m_consoleLines << ConsoleLog::formatLine(log.at(i));
+
+    QVBoxLayout* layout = new QVBoxLayout(this);
+
+    QLabel* headline = new QLabel(QString("<b>%1 failed</b>")
+                                      .arg(failure.step.isEmpty() ? QStringLiteral("Randomization")
+                                                                  : failure.step.toHtmlEscaped()), this);
+    layout->addWidget(headline);
+    QLabel* message = new QLabel(failure.message, this);
+    message->setWordWrap(true);
+    message->setTextInteractionFlags(Qt::TextSelectableByMouse);
+    layout->addWidget(message);
+
+    // Damaged install files are the one thing the user can fix themselves
+    if (!failure.inputErrors.isEmpty()) {
+        QLabel* inputs = new QLabel("Game files that could not be read (verifying or reinstalling "
+                                    "the game usually fixes these):\n  "
+                                    + failure.inputErrors.join("\n  "), this);
+        inputs->setWordWrap(true);
+        inputs->setTextInteractionFlags(Qt::TextSelectableByMouse);
+        inputs->setStyleSheet("color: #ff5555;");
+        layout->addWidget(inputs);
+    }
+
+    layout->addWidget(new QLabel("Last console lines:", this));
+    QPlainTextEdit* details = new QPlainTextEdit(this);
+    details->setReadOnly(true);
+    details->setPlainText(m_consoleLines.mid(qMax(0, m_consoleLines.size() - CONSOLE_LINES_SHOWN)).join('\n'));
+    layout->addWidget(details, 1);
+
+    QLabel* hint = new QLabel("To report this, save a diagnostic bundle and attach it to a GitHub issue. "
+                              "It holds your settings (including the install path), the console and the "
+                              "run's debug logs.", this);
+    hint->setWordWrap(true);
+    layout->addWidget(hint);
+
+    QDialogButtonBox* buttons = new QDialogButtonBox(QDialogButtonBox::Close, this);
+    QPushButton* saveButton = buttons->addButton("Save Diagnostic Bundle...", QDialogButtonBox::ActionRole);
+    connect(saveButton, &QPushButton::clicked, this, &ErrorReportDialog::saveBundle);
+    connect(buttons, &QDialogButtonBox::rejected, this, &QDialog::reject);
+    layout->addWidget(buttons);
+}
+
+void ErrorReportDialog::saveBundle()
+{
+    const QString filePath = QFileDialog::getSaveFileName(this, "Save Diagnostic Bundle",
+        QDir(QDir::homePath()).filePath(DiagnosticBundle::defaultFileName(m_config)),
+        "Text Files (*.txt);;All Files (*)");
+    if (filePath.isEmpty())
+        return;
+
+    QString error;
+    if (!DiagnosticBundle::write(filePath, m_failure, m_config, m_ff7Path, m_consoleLines, &error)) {
+        QMessageBox::warning(this, "Save Failed", error);
+        return;
+    }
+    QMessageBox::information(this, "Bundle Saved", "Diagnostic bundle saved to:\n" + filePath);
+}
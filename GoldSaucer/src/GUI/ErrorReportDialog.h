@@ -0,0 +1,28 @@
+#pragma once
+
+#include <QDialog>
+#include "../Config.h"
+#include "../SeedGenerator.h"
+
+class ConsoleLog;
+
+// ErrorReportDialog — shown when a run fails, in place of a bare message box:
+// the step that failed, its message and any game files it could not read,
+// the last console lines, and a button that saves a DiagnosticBundle for a
+// GitHub issue.
+class ErrorReportDialog : public QDialog
+{
+public:
+    ErrorReportDialog(const SeedGenerator::Failure& failure, const Config& config,
+                      const QString& ff7Path, const ConsoleLog& log, QWidget* parent = nullptr);
+
+    static const int CONSOLE_LINES_SHOWN = 40;
+
+private:
+    void saveBundle();
+
+    SeedGenerator::Failure m_failure;
+    Config m_config;
+    QString m_ff7Path;
+    QStringList m_consoleLines;
+};
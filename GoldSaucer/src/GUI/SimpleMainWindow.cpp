@@ -16,15 +16,26 @@
 #include <QDebug>
 #include <QRandomGenerator>
 #include <QTextEdit>
+#include <QTextDocument>
 #include <QScrollBar>
 #include <QCoreApplication>
 #include <QGroupBox>
 #include <QSlider>
+#include <QTabWidget>
+#include <QPlainTextEdit>
+#include <QClipboard>
+#include <QGridLayout>
 #include <QJsonDocument>
 #include <QJsonObject>
 #include <QJsonArray>
+#include <QElapsedTimer>
 #include <QJsonParseError>
 #include <QFileInfo>
+#include <QFile>
+#include <QDateTime>
+#include <QListWidget>
+#include <QThread>
+#include <QCloseEvent>
 #include "SimpleMainWindow.h"
 // REMOVED: Text replacement includes - no longer needed
 // #include "../TextReplacementConfig.h"
@@ -35,6 +46,12 @@
 #include "../Randomizer.h"
 #include "../Config.h"
 #include "../IroExporter.h"
+#include "../SeedCard.h"
+#include "../SeedSummary.h"
+#include "../GameFileLocator.h"
+#include "../SeedGenerator.h"
+#include "../DiagnosticBundle.h"
+#include "ErrorReportDialog.h"
 
 SimpleMainWindow::SimpleMainWindow(QWidget *parent)
     : QMainWindow(parent)
@@ -43,6 +60,15 @@ SimpleMainWindow::SimpleMainWindow(QWidget *parent)
     setupUI();
     loadConfig();
     
+    // Auto-populate the install path from Steam (Windows, Linux/Proton, macOS)
+    if (m_ff7PathEdit->text().isEmpty()) {
+        const QString detected = GameFileLocator::detectFF7Install();
+        if (!detected.isEmpty()) {
+            m_ff7PathEdit->setText(detected);
+            appendConsoleMessage("Detected FF7 installation: " + detected);
+        }
+    }
+    
     // Add initial message
     appendConsoleMessage("Gold Saucer FF7 Randomizer GUI started");
     appendConsoleMessage("Version 1.0.0");
@@ -70,6 +96,11 @@ void SimpleMainWindow::setupUI()
     titleLabel->setAlignment(Qt::AlignCenter);
     mainLayout->addWidget(titleLabel);
     
+    // Tabs: general settings + item pool editor
+    QTabWidget* tabs = new QTabWidget(this);
+    QWidget* generalTab = new QWidget(this);
+    QVBoxLayout* generalLayout = new QVBoxLayout(generalTab);
+    
     // FF7 Path Selection
     QHBoxLayout* pathLayout = new QHBoxLayout();
     QLabel* pathLabel = new QLabel("FF7 Installation Path:", this);
@@ -83,7 +114,7 @@ void SimpleMainWindow::setupUI()
     pathLayout->addWidget(pathLabel);
     pathLayout->addWidget(m_ff7PathEdit);
     pathLayout->addWidget(browseButton);
-    mainLayout->addLayout(pathLayout);
+    generalLayout->addLayout(pathLayout);
     
     // Output Folder Selection
     QHBoxLayout* outputLayout = new QHBoxLayout();
@@ -95,15 +126,25 @@ void SimpleMainWindow::setupUI()
     QPushButton* browseOutputButton = new QPushButton("Browse...", this);
     browseOutputButton->setToolTip("Browse for output directory to save randomized files.");
     
+    m_outputCollisionCombo = new QComboBox(this);
+    m_outputCollisionCombo->addItem("Ask");          // Config::OutputCollision order
+    m_outputCollisionCombo->addItem("Overwrite");
+    m_outputCollisionCombo->addItem("New Folder");
+    m_outputCollisionCombo->setToolTip("What to do when the output folder already contains files:\n"
+                                       "Ask = prompt before each such run, Overwrite = write over them,\n"
+                                       "New Folder = write to <folder>_2, <folder>_3, ... instead.\n"
+                                       "A run that fails midway removes any folder it created.");
+    
     outputLayout->addWidget(outputLabel);
     outputLayout->addWidget(m_outputFolderEdit);
     outputLayout->addWidget(browseOutputButton);
-    mainLayout->addLayout(outputLayout);
+    outputLayout->addWidget(m_outputCollisionCombo);
+    generalLayout->addLayout(outputLayout);
     
     // Features
     QLabel* featuresLabel = new QLabel("Randomization Features:", this);
     featuresLabel->setStyleSheet("font-weight: bold;");
-    mainLayout->addWidget(featuresLabel);
+    generalLayout->addWidget(featuresLabel);
     
     QVBoxLayout* featuresLayout = new QVBoxLayout();
     m_shopCheckBox = new QCheckBox("Shop Randomization", this);
@@ -112,19 +153,141 @@ void SimpleMainWindow::setupUI()
     m_fieldCheckBox->setToolTip("Randomizes items and materia found in field pickups.\nChests, treasure chests, and field rewards are randomized.");
     m_keyItemCheckBox = new QCheckBox("Key Item Randomization (Experimental)", this);
     m_keyItemCheckBox->setToolTip("Swaps key items with regular item pickups within the same field.\nWARNING: May cause softlocks if key items become inaccessible!");
+    m_earlyPhsCheckBox = new QCheckBox("Early PHS", this);
+    m_earlyPhsCheckBox->setToolTip("With key item randomization, the PHS is always placed in the first sphere (Midgar),\nso party swaps are available from the start instead of possibly only near the end.");
+    QHBoxLayout* midgarPartsLayout = new QHBoxLayout();
+    QLabel* midgarPartsLabel = new QLabel("Midgar Parts Needed:", this);
+    m_midgarPartsSpin = new QSpinBox(this);
+    m_midgarPartsSpin->setRange(1, Config::MIDGAR_PARTS_COUNT);
+    m_midgarPartsSpin->setValue(Config::MIDGAR_PARTS_COUNT);
+    const QString midgarPartsTip("How many Midgar Parts the 65th floor model needs before it opens the Keycard 66 chest.\nWith key item randomization, the parts beyond this are placed like filler anywhere in the game.\nNeeds Field Pickup Randomization.");
+    midgarPartsLabel->setToolTip(midgarPartsTip);
+    m_midgarPartsSpin->setToolTip(midgarPartsTip);
+    midgarPartsLayout->addWidget(midgarPartsLabel);
+    midgarPartsLayout->addWidget(m_midgarPartsSpin);
+    midgarPartsLayout->addStretch();
+    QHBoxLayout* hugeMateriaLayout = new QHBoxLayout();
+    QLabel* hugeMateriaLabel = new QLabel("Huge Materia Needed:", this);
+    m_hugeMateriaSpin = new QSpinBox(this);
+    m_hugeMateriaSpin->setRange(1, Config::HUGE_MATERIA_COUNT);
+    m_hugeMateriaSpin->setValue(Config::HUGE_MATERIA_COUNT);
+    const QString hugeMateriaTip("How many Huge Materia Bugenhagen's observatory needs before it hands out its rewards,\nfor Huge Materia hunt seeds. Needs Field Pickup Randomization.");
+    hugeMateriaLabel->setToolTip(hugeMateriaTip);
+    m_hugeMateriaSpin->setToolTip(hugeMateriaTip);
+    hugeMateriaLayout->addWidget(hugeMateriaLabel);
+    hugeMateriaLayout->addWidget(m_hugeMateriaSpin);
+    hugeMateriaLayout->addStretch();
+    m_progressiveKeycardsCheckBox = new QCheckBox("Progressive Keycards", this);
+    m_progressiveKeycardsCheckBox->setToolTip("Every Shinra HQ keycard you find opens the next tier (60, 62, 65, 66, 68),\nwhichever card it was. The doors count keycards, so the cards no longer show in the\nkey item menu. Needs Field Pickup Randomization.");
+    m_tieredEquipmentCheckBox = new QCheckBox("Tiered Equipment", this);
+    m_tieredEquipmentCheckBox->setToolTip("Some pickups are set aside for Cloud's swords and the armlets, one tier each:\nthe earlier the sphere, the weaker the upgrade. Each pickup always gives its own tier,\nso one found out of route order is not the next upgrade. The other pickups stay random.\nNeeds Field Pickup Randomization.");
+    m_keyItemSoundCheckBox = new QCheckBox("Key Item Sound Cue", this);
+    m_keyItemSoundCheckBox->setToolTip("With key item randomization, pickups that now hold a key item play a distinct sound,\nso you can hear that you found something important.\nNo sound is added: the cue replaces a sound played directly before or after the grant.\nPickups with no sound next to the grant stay silent and are listed in the field log.");
+    m_modelScaleCheckBox = new QCheckBox("Model Scale Gag", this);
+    m_modelScaleCheckBox->setToolTip("Cosmetic only: every field map shrinks or stretches its character models\nby a random amount (80%-125%). Needs Field Pickup Randomization.");
+    m_silentPickupsCheckBox = new QCheckBox("Silent Pickups", this);
+    m_silentPickupsCheckBox->setToolTip("Randomized pickups hand over their item without a \"Received\" message\n(faster, and the field's text data is never rebuilt). Check the spoiler log\nor your inventory to see what you got. Needs Field Pickup Randomization.");
+    QHBoxLayout* fieldEncounterLayout = new QHBoxLayout();
+    QLabel* fieldEncounterLabel = new QLabel("Field Encounters:", this);
+    m_fieldEncounterCombo = new QComboBox(this);
+    m_fieldEncounterCombo->addItems({"Vanilla", "Shuffle", "Reroll"});
+    const QString fieldEncounterTip("Random battles in field maps (not the world map).\nShuffle = each map's battles swap how common they are\nReroll = each map draws battles from maps of the same part of the story\nBosses and back/side/pincer attacks are untouched. Needs Field Pickup Randomization.");
+    fieldEncounterLabel->setToolTip(fieldEncounterTip);
+    m_fieldEncounterCombo->setToolTip(fieldEncounterTip);
+    m_fieldEncounterRatesCheckBox = new QCheckBox("Random rates", this);
+    m_fieldEncounterRatesCheckBox->setToolTip("Each field map's encounter rate is rerolled between half and one and a half times\nthe vanilla rate. Maps without random battles stay that way.");
+    fieldEncounterLayout->addWidget(fieldEncounterLabel);
+    fieldEncounterLayout->addWidget(m_fieldEncounterCombo);
+    m_scriptedBattlesCheckBox = new QCheckBox("Random scripted fights", this);
+    m_scriptedBattlesCheckBox->setToolTip("Forced fights started by field scripts get another battle listed as interchangeable\nin scripted_battles.json. Boss fights and unlisted fights are untouched.\nNeeds Field Pickup Randomization.");
+    fieldEncounterLayout->addWidget(m_fieldEncounterRatesCheckBox);
+    fieldEncounterLayout->addWidget(m_scriptedBattlesCheckBox);
+    fieldEncounterLayout->addStretch();
+    m_optionalKeyItemsCheckBox = new QCheckBox("Shuffle Optional Key Items", this);
+    m_optionalKeyItemsCheckBox->setToolTip("With key item randomization, also shuffles the key items the story doesn't need\n(Gold Ticket, Leviathan Scales, A/B/C Coupons, letters, Glacier Map, Mythril).\nCoupons are always placed before the Shinra HQ 63rd floor exchange.\nUnchecked = these stay in their vanilla locations.");
+    m_chocoboCheckBox = new QCheckBox("Chocobo Farm Checks", this);
+    m_chocoboCheckBox->setToolTip("Randomizes the materia handed out at the Chocobo Farm (Chocobo Lure, Choco/Mog).\nThe Chocobo Lure is kept whenever a key item lands behind a chocobo-only crossing.");
+    m_materiaCaveCheckBox = new QCheckBox("Materia Cave Checks", this);
+    m_materiaCaveCheckBox->setToolTip("Randomizes the four materia cave rewards (Knights of the Round, Mime, HP<->MP, Quadra Magic).\nThe caves still need the right bred chocobo, so the Chocobo Lure grant is kept and never placed in a cave\n(the Chocobo Farm randomization then leaves the Lure alone).\nUnchecked = the caves give their vanilla materia.");
+    static const char* const groupNames[Config::LocationGroupCount] = {
+        "Wutai Checks", "Gold Saucer Minigame Checks", "Missable Checks"
+    };
+    static const char* const groupTips[Config::LocationGroupCount] = {
+        "Randomizes the pickups in Wutai (town, pagoda, Da-chao).\nUnchecked = Wutai gives its vanilla items and never holds a key item.",
+        "Randomizes the Gold Saucer prizes found in field scripts (Wonder Square, Chocobo Square, Battle Square).\nUnchecked = these stay vanilla and never hold a key item.",
+        "Randomizes pickups that are lost once the story moves on (Shinra HQ, Temple of the Ancients,\nthe Whirlwind Maze, the Rocket).\nUnchecked = these stay vanilla and never hold a key item."
+    };
+    for (int i = 0; i < Config::LocationGroupCount; ++i) {
+        m_locationGroupCheck[i] = new QCheckBox(groupNames[i], this);
+        m_locationGroupCheck[i]->setToolTip(groupTips[i]);
+    }
+    m_installNamesCheckBox = new QCheckBox("Use Item Names From Install", this);
+    m_installNamesCheckBox->setToolTip("Reads item and materia names from the game's kernel2.bin so the spoiler log and\n\"Received\" texts match non-English or renamed installs.\nFalls back to the built-in English names if kernel2.bin can't be read.");
+    m_archipelagoExportCheckBox = new QCheckBox("Archipelago Export", this);
+    m_archipelagoExportCheckBox->setToolTip("Also writes archipelago_export.json: every randomized location under a stable name (the same for every seed),\nwhat it holds and whether that item is progression, useful or filler, for multiworld tools.\nNeeds Field Pickup Randomization. Not written in Archipelago mode.");
     m_equipmentCheckBox = new QCheckBox("Starting Equipment Randomization", this);
     m_equipmentCheckBox->setToolTip("Randomizes equipment given to characters at game start.\nCharacters will receive random equipment of the selected tier.");
+    QHBoxLayout* armorLayout = new QHBoxLayout();
+    QLabel* armorStatsLabel = new QLabel("Armor Stats:", this);
+    m_armorStatsCombo = new QComboBox(this);
+    m_armorStatsCombo->addItems({"Vanilla", "Shuffle", "Reroll"});
+    const QString armorStatsTip("Defense, magic defense and their evade chances of every armor.\nShuffle = armors of similar defense swap their stats\nReroll = each stat rolled between 75% and 125% of its own\nElemental/status protection, stat boosts and who can equip it never change.");
+    armorStatsLabel->setToolTip(armorStatsTip);
+    m_armorStatsCombo->setToolTip(armorStatsTip);
+    QLabel* armorSlotsLabel = new QLabel("Slots:", this);
+    m_armorSlotsCombo = new QComboBox(this);
+    m_armorSlotsCombo->addItems({"Vanilla", "Shuffle", "Reroll"});
+    const QString armorSlotsTip("Materia slots of every armor.\nShuffle = armors of similar defense swap their slot layouts and growth\nReroll = one slot and one link more or fewer than vanilla, links placed at random");
+    armorSlotsLabel->setToolTip(armorSlotsTip);
+    m_armorSlotsCombo->setToolTip(armorSlotsTip);
+    armorLayout->addWidget(armorStatsLabel);
+    armorLayout->addWidget(m_armorStatsCombo);
+    armorLayout->addWidget(armorSlotsLabel);
+    armorLayout->addWidget(m_armorSlotsCombo);
+    armorLayout->addStretch();
+    m_enemyStatsCheckBox = new QCheckBox("Enemy Stat Randomization", this);
+    m_enemyStatsCheckBox->setToolTip("Rolls every enemy's level, HP, MP, stats, EXP, AP and gil around its own values\n(statsVariance in the config, default +/-30%). Bosses vary far less while boss protection is on,\nand AI checks against the enemy's own HP are scaled along with it.");
+    m_encounterCheckBox = new QCheckBox("Encounter Shuffle", this);
+    m_encounterCheckBox->setToolTip("Random battles swap with battles of similar strength (by average enemy HP).\nOnly battles some field map or world map region rolls are shuffled;\nscripted fights and the Battle Square are left alone.");
+    m_scaleEncountersCheckBox = new QCheckBox("Scale to new slot", this);
+    m_scaleEncountersCheckBox->setToolTip("With the encounter shuffle, a battle moved to another place is rescaled to the party level\nexpected there: level, Strength, Magic and HP through the damage formulas, and MP with HP\n(never below what the enemy's priciest move needs). Needs Encounter Shuffle.");
+    m_enemySwapCheckBox = new QCheckBox("Swap enemies", this);
+    m_enemySwapCheckBox->setToolTip("With the encounter shuffle, battles keep their place, background and camera and the\nenemies in them are swapped instead, each for one of similar strength and size\n(size = how much room the vanilla formations give it). Needs Encounter Shuffle.");
+    QHBoxLayout* encounterLayout = new QHBoxLayout();
+    encounterLayout->addWidget(m_encounterCheckBox);
+    encounterLayout->addWidget(m_enemySwapCheckBox);
+    encounterLayout->addWidget(m_scaleEncountersCheckBox);
+    encounterLayout->addStretch();
     
     featuresLayout->addWidget(m_shopCheckBox);
     featuresLayout->addWidget(m_fieldCheckBox);
     featuresLayout->addWidget(m_keyItemCheckBox);
+    featuresLayout->addWidget(m_earlyPhsCheckBox);
+    featuresLayout->addLayout(midgarPartsLayout);
+    featuresLayout->addLayout(hugeMateriaLayout);
+    featuresLayout->addWidget(m_progressiveKeycardsCheckBox);
+    featuresLayout->addWidget(m_tieredEquipmentCheckBox);
+    featuresLayout->addWidget(m_keyItemSoundCheckBox);
+    featuresLayout->addWidget(m_modelScaleCheckBox);
+    featuresLayout->addWidget(m_silentPickupsCheckBox);
+    featuresLayout->addLayout(fieldEncounterLayout);
+    featuresLayout->addWidget(m_optionalKeyItemsCheckBox);
+    featuresLayout->addWidget(m_chocoboCheckBox);
+    featuresLayout->addWidget(m_materiaCaveCheckBox);
+    for (int i = 0; i < Config::LocationGroupCount; ++i)
+        featuresLayout->addWidget(m_locationGroupCheck[i]);
+    featuresLayout->addWidget(m_installNamesCheckBox);
+    featuresLayout->addWidget(m_archipelagoExportCheckBox);
     featuresLayout->addWidget(m_equipmentCheckBox);
-    mainLayout->addLayout(featuresLayout);
+    featuresLayout->addLayout(armorLayout);
+    featuresLayout->addWidget(m_enemyStatsCheckBox);
+    featuresLayout->addLayout(encounterLayout);
+    generalLayout->addLayout(featuresLayout);
     
     // Archipelago Section
     QLabel* archipelagoLabel = new QLabel("Archipelago Multiworld:", this);
     archipelagoLabel->setStyleSheet("font-weight: bold;");
-    mainLayout->addWidget(archipelagoLabel);
+    generalLayout->addWidget(archipelagoLabel);
     
     QVBoxLayout* archipelagoLayout = new QVBoxLayout();
     
@@ -157,7 +320,7 @@ void SimpleMainWindow::setupUI()
     );
     archipelagoLayout->addWidget(m_freeRoamCheckBox);
 
-    mainLayout->addLayout(archipelagoLayout);
+    generalLayout->addLayout(archipelagoLayout);
     
     // Text Replacement Section - REMOVED (now handled automatically by FF7TK field randomization)
     // setupTextReplacementControls();
@@ -166,7 +329,7 @@ void SimpleMainWindow::setupUI()
     // Settings
     QLabel* settingsLabel = new QLabel("Settings:", this);
     settingsLabel->setStyleSheet("font-weight: bold;");
-    mainLayout->addWidget(settingsLabel);
+    generalLayout->addWidget(settingsLabel);
     
     QGridLayout* settingsLayout = new QGridLayout();
     
@@ -179,6 +342,20 @@ void SimpleMainWindow::setupUI()
     m_shopPoolSpin->setValue(50);
     m_shopPoolSpin->setToolTip("Number of random items available for shop inventories.\nLarger pools = more variety, smaller pools = more repeats.");
     settingsLayout->addWidget(m_shopPoolSpin, 0, 1);
+    m_shopLocationSlotCheckBox = new QCheckBox("Extra pickup-pool slot per shop", this);
+    m_shopLocationSlotCheckBox->setToolTip("Adds one slot to every item, weapon and accessory shop stocked from the field pickup pool\n(weighted by the Item Pool tab), so shops can sell items their category never would.\nIgnored in Archipelago mode, where AP shop slots are the checks.");
+    QHBoxLayout* shopOptionsLayout = new QHBoxLayout();
+    shopOptionsLayout->addWidget(m_shopLocationSlotCheckBox);
+    QLabel* shopTierLabel = new QLabel("Shop tiers:", this);
+    m_shopTierCombo = new QComboBox(this);
+    m_shopTierCombo->addItems({"Off", "Loose", "Strict"});
+    m_shopTierCombo->setCurrentIndex(2);
+    const QString shopTierTip("How closely shop stock follows the shop's place in the game.\nStrict = each shop sells only its own tier (no Ribbons in Midgar)\nLoose = a shop may also sell anything from earlier tiers\nOff = any tier in any shop");
+    shopTierLabel->setToolTip(shopTierTip);
+    m_shopTierCombo->setToolTip(shopTierTip);
+    shopOptionsLayout->addWidget(shopTierLabel);
+    shopOptionsLayout->addWidget(m_shopTierCombo);
+    settingsLayout->addLayout(shopOptionsLayout, 0, 2);
     
     QLabel* shopPriceLabel = new QLabel("Shop Price Variance (%):", this);
     shopPriceLabel->setToolTip("Maximum percentage that shop prices can vary from original.\n0% = no change, 100% = prices can be 0-200% of original.");
@@ -187,7 +364,15 @@ void SimpleMainWindow::setupUI()
     m_shopPriceSpin->setRange(0, 100);
     m_shopPriceSpin->setValue(50);
     m_shopPriceSpin->setToolTip("Maximum percentage that shop prices can vary from original.\n0% = no change, 100% = prices can be 0-200% of original.");
-    settingsLayout->addWidget(m_shopPriceSpin, 1, 1);
+    m_randomizeShopPricesCheckBox = new QCheckBox("Randomize prices", this);
+    m_randomizeShopPricesCheckBox->setToolTip("Rerolls the price of every item shops sell within the variance.\nPrices stay round and at least 20 gil, and consumables never cost more\nthan the first shop that sells them can expect (300 / 1500 / 5000 gil for early / mid / late shops)\nunless they already did in vanilla.");
+    QHBoxLayout* shopPriceLayout = new QHBoxLayout();
+    shopPriceLayout->addWidget(m_shopPriceSpin);
+    shopPriceLayout->addWidget(m_randomizeShopPricesCheckBox);
+    settingsLayout->addLayout(shopPriceLayout, 1, 1);
+    m_balanceEconomyCheckBox = new QCheckBox("Balance gil economy", this);
+    m_balanceEconomyCheckBox->setToolTip("Compares gil from enemy drops and field pickups against vanilla for each part of the game.\nRaises gil pickups when randomized enemies pay less, and scales shop prices by what's left.\nSee economy_report.txt in the output folder.");
+    settingsLayout->addWidget(m_balanceEconomyCheckBox, 1, 2);
     
     // Field pickup settings
     QLabel* pickupLabel = new QLabel("Field Pickup Rarity:", this);
@@ -197,6 +382,17 @@ void SimpleMainWindow::setupUI()
     m_pickupCombo->addItems({"Balanced", "Random", "High-tier Only"});
     m_pickupCombo->setToolTip("Controls the quality of items found in field pickups.\nBalanced = mix of common/rare items\nRandom = completely random\nHigh-tier Only = only rare/powerful items");
     settingsLayout->addWidget(m_pickupCombo, 2, 1);
+    QHBoxLayout* finalDungeonLayout = new QHBoxLayout();
+    QLabel* finalDungeonLabel = new QLabel("Crater top-tier loot (%):", this);
+    m_finalDungeonLootSpin = new QSpinBox(this);
+    m_finalDungeonLootSpin->setRange(0, 100);
+    m_finalDungeonLootSpin->setValue(100);
+    const QString finalDungeonTip("Chance that a Northern Crater pickup may roll rare or very rare loot (equipment, accessories).\nLower values keep the best rewards in the open world, where completionists can still reach them.\n100% = no restriction.");
+    finalDungeonLabel->setToolTip(finalDungeonTip);
+    m_finalDungeonLootSpin->setToolTip(finalDungeonTip);
+    finalDungeonLayout->addWidget(finalDungeonLabel);
+    finalDungeonLayout->addWidget(m_finalDungeonLootSpin);
+    settingsLayout->addLayout(finalDungeonLayout, 2, 2);
     
     // Starting equipment settings
     QLabel* equipmentLabel = new QLabel("Starting Equipment Tier:", this);
@@ -207,22 +403,136 @@ void SimpleMainWindow::setupUI()
     m_equipmentCombo->setCurrentIndex(1);
     m_equipmentCombo->setToolTip("Quality of equipment given to characters at game start.\nWeak = basic equipment\nBalanced = standard equipment\nStrong = advanced equipment");
     settingsLayout->addWidget(m_equipmentCombo, 3, 1);
+    static const char* const characterNames[Config::STARTING_CHARACTER_COUNT] = {
+        "Cloud", "Barret", "Tifa", "Aerith", "Red XIII", "Yuffie", "Cait Sith", "Vincent", "Cid"
+    };
+    QGridLayout* startingCharacterLayout = new QGridLayout();
+    int startingCharacterCell = 0;
+    for (int i = 0; i < Config::STARTING_CHARACTER_COUNT; ++i) {
+        m_startingCharacterCheck[i] = nullptr;
+        if (!Config::hasStartingCharacterToggle(i))
+            continue;
+        m_startingCharacterCheck[i] = new QCheckBox(characterNames[i], this);
+        m_startingCharacterCheck[i]->setToolTip("Reroll this character's starting weapon, armor, accessory and materia.\nUnchecked = the character keeps the vanilla loadout.");
+        startingCharacterLayout->addWidget(m_startingCharacterCheck[i], startingCharacterCell / 4, startingCharacterCell % 4);
+        ++startingCharacterCell;
+    }
+    settingsLayout->addLayout(startingCharacterLayout, 3, 2);
+    
+    // Enemy name cosmetics
+    QLabel* enemyNameLabel = new QLabel("Enemy Names:", this);
+    enemyNameLabel->setToolTip("Cosmetic only - stats, AI and drops are untouched.\nShuffle = enemies swap names with each other\nReplace = silly generated names (\"Sleepy Cactuar\")");
+    settingsLayout->addWidget(enemyNameLabel, 4, 0);
+    m_enemyNameCombo = new QComboBox(this);
+    m_enemyNameCombo->addItems({"Vanilla", "Shuffle", "Replace"});
+    m_enemyNameCombo->setToolTip("Cosmetic only - stats, AI and drops are untouched.\nShuffle = enemies swap names with each other\nReplace = silly generated names (\"Sleepy Cactuar\")");
+    settingsLayout->addWidget(m_enemyNameCombo, 4, 1);
+    m_preserveBossNamesCheckBox = new QCheckBox("Keep boss names", this);
+    m_preserveBossNamesCheckBox->setChecked(true);
+    m_preserveBossNamesCheckBox->setToolTip("Bosses keep their real names when enemy names are shuffled or replaced.");
+    m_enemyStealsCheckBox = new QCheckBox("Shuffle steals", this);
+    m_enemyStealsCheckBox->setToolTip("Enemies swap what can be stolen from them (item and steal rate together).\nEach enemy keeps its number of steal slots; drops are untouched.");
+    m_stealDropConversionCheckBox = new QCheckBox("Mix steals and drops", this);
+    m_stealDropConversionCheckBox->setToolTip("Drops join the steal shuffle and each item keeps whether it is a steal or a drop,\nso a steal slot can become a drop slot and the other way round\n(enemy_steals_debug.txt marks every converted slot). Needs Shuffle steals.");
+    m_chocoboRatingsCheckBox = new QCheckBox("Shuffle chocobos", this);
+    m_chocoboRatingsCheckBox->setToolTip("World map chocobo battles swap the rating of the chocobo they give\n(which tracks have wonderful chocobos changes per seed).\nEvery region with chocobo tracks keeps at least one great or wonderful chocobo.");
+    m_enemyMorphsCheckBox = new QCheckBox("Random morphs", this);
+    m_enemyMorphsCheckBox->setToolTip("Enemies that can be morphed turn into a random item from a curated pool\n(Sources, Elixirs, stat drinks...). Every Source item stays morphable somewhere.");
+    m_enemyAttacksCheckBox = new QCheckBox("Shuffle attacks", this);
+    m_enemyAttacksCheckBox->setToolTip("Enemy attacks swap power and MP cost with other attacks of the same tier.\nAnimations, effects and AI are untouched; enemies get the MP to use their new costs.");
+    QHBoxLayout* enemyOptionsLayout = new QHBoxLayout();
+    enemyOptionsLayout->addWidget(m_preserveBossNamesCheckBox);
+    enemyOptionsLayout->addWidget(m_enemyStealsCheckBox);
+    enemyOptionsLayout->addWidget(m_stealDropConversionCheckBox);
+    enemyOptionsLayout->addWidget(m_enemyMorphsCheckBox);
+    enemyOptionsLayout->addWidget(m_enemyAttacksCheckBox);
+    enemyOptionsLayout->addWidget(m_chocoboRatingsCheckBox);
+    settingsLayout->addLayout(enemyOptionsLayout, 4, 2);
+
+    // Boss stat curve
+    QLabel* bossDifficultyLabel = new QLabel("Boss Difficulty:", this);
+    bossDifficultyLabel->setToolTip("HP, Strength and Magic of bosses only; regular enemies are untouched.\nEasy / Hard = flat nerf / buff\nScaled = easier early in the game, tougher late (by where the boss is fought)");
+    settingsLayout->addWidget(bossDifficultyLabel, 5, 0);
+    m_bossDifficultyCombo = new QComboBox(this);
+    m_bossDifficultyCombo->addItems({"Normal", "Easy", "Hard", "Scaled"});
+    m_bossDifficultyCombo->setToolTip("HP, Strength and Magic of bosses only; regular enemies are untouched.\nEasy / Hard = flat nerf / buff\nScaled = easier early in the game, tougher late (by where the boss is fought)");
+    settingsLayout->addWidget(m_bossDifficultyCombo, 5, 1);
+    // Battle rewards, independent of any enemy randomization
+    const QString rewardTip("Multiplies the EXP, AP and Gil every enemy gives (1.0 = vanilla).\nWorks without any enemy randomization; gil balancing does not offset it.");
+    QHBoxLayout* rewardLayout = new QHBoxLayout();
+    QDoubleSpinBox** rewardSpins[] = { &m_expMultiplierSpin, &m_apMultiplierSpin, &m_gilMultiplierSpin };
+    const char* const rewardNames[] = { "EXP x", "AP x", "Gil x" };
+    for (int i = 0; i < 3; ++i) {
+        QDoubleSpinBox* spin = new QDoubleSpinBox(this);
+        spin->setRange(0.0, Config::REWARD_MULTIPLIER_MAX);
+        spin->setSingleStep(0.5);
+        spin->setDecimals(1);
+        spin->setValue(1.0);
+        spin->setToolTip(rewardTip);
+        QLabel* label = new QLabel(rewardNames[i], this);
+        label->setToolTip(rewardTip);
+        rewardLayout->addWidget(label);
+        rewardLayout->addWidget(spin);
+        *rewardSpins[i] = spin;
+    }
+    m_apJitterSpin = new QSpinBox(this);
+    m_apJitterSpin->setRange(0, Config::AP_JITTER_MAX);
+    m_apJitterSpin->setSuffix("%");
+    m_apJitterSpin->setToolTip("Random spread of each enemy's AP on top of the AP multiplier, drawn from the seed.\n0% = every enemy gets exactly the multiplier, 50% = anywhere from half to one and a half times it.");
+    QLabel* apJitterLabel = new QLabel("AP +/-", this);
+    apJitterLabel->setToolTip(m_apJitterSpin->toolTip());
+    rewardLayout->addWidget(apJitterLabel);
+    rewardLayout->addWidget(m_apJitterSpin);
+    settingsLayout->addLayout(rewardLayout, 5, 2);
     
     // Seed
     QLabel* seedLabel = new QLabel("Random Seed:", this);
     seedLabel->setToolTip("Seed value for randomization.\nSame seed = same results, different seed = different randomization.");
-    settingsLayout->addWidget(seedLabel, 4, 0);
+    settingsLayout->addWidget(seedLabel, 6, 0);
     m_seedSpin = new QSpinBox(this);
     m_seedSpin->setRange(0, 999999);
     m_seedSpin->setValue(12345);
     m_seedSpin->setToolTip("Seed value for randomization.\nSame seed = same results, different seed = different randomization.");
-    settingsLayout->addWidget(m_seedSpin, 4, 1);
+    settingsLayout->addWidget(m_seedSpin, 6, 1);
     
     QPushButton* randomSeedButton = new QPushButton("Random Seed", this);
     randomSeedButton->setToolTip("Generate a random seed value.");
-    settingsLayout->addWidget(randomSeedButton, 4, 2);
-    
-    mainLayout->addLayout(settingsLayout);
+    settingsLayout->addWidget(randomSeedButton, 6, 2);
+    
+    generalLayout->addLayout(settingsLayout);
+    
+    // Seed / settings string import
+    QLabel* seedStringLabel = new QLabel("Seed / Settings String:", this);
+    seedStringLabel->setStyleSheet("font-weight: bold;");
+    generalLayout->addWidget(seedStringLabel);
+    
+    QHBoxLayout* seedStringLayout = new QHBoxLayout();
+    m_seedStringEdit = new QPlainTextEdit(this);
+    m_seedStringEdit->setPlaceholderText("Paste a GS1: settings string, preset JSON, or a seed number...");
+    m_seedStringEdit->setToolTip("Paste a settings string shared by another player (GS1:...), a preset JSON\nobject, or just a seed number, then press Apply to fill in every option.");
+    m_seedStringEdit->setMaximumHeight(60);
+    QPushButton* applySeedStringButton = new QPushButton("Apply", this);
+    applySeedStringButton->setToolTip("Validate the pasted text and apply it to all settings.");
+    QPushButton* copySeedStringButton = new QPushButton("Copy Current", this);
+    copySeedStringButton->setToolTip("Copy the current settings as a shareable GS1: string.");
+    QVBoxLayout* seedStringButtons = new QVBoxLayout();
+    seedStringButtons->addWidget(applySeedStringButton);
+    seedStringButtons->addWidget(copySeedStringButton);
+    seedStringLayout->addWidget(m_seedStringEdit);
+    seedStringLayout->addLayout(seedStringButtons);
+    generalLayout->addLayout(seedStringLayout);
+    
+    m_seedStringStatus = new QLabel(this);
+    m_seedStringStatus->setWordWrap(true);
+    m_seedStringStatus->setVisible(false);
+    generalLayout->addWidget(m_seedStringStatus);
+    generalLayout->addStretch();
+    
+    tabs->addTab(generalTab, "General");
+    tabs->addTab(createItemPoolTab(), "Item Pool");
+    tabs->addTab(createDebugTab(), "Debug");
+    tabs->addTab(createRunQueueTab(), "Run Queue");
+    mainLayout->addWidget(tabs);
     
     // Progress
     m_progressBar = new QProgressBar(this);
@@ -234,17 +544,41 @@ void SimpleMainWindow::setupUI()
     mainLayout->addWidget(m_statusLabel);
     
     // Console Output
+    QHBoxLayout* consoleHeader = new QHBoxLayout();
     QLabel* consoleLabel = new QLabel("Console Output:", this);
     consoleLabel->setStyleSheet("font-weight: bold;");
-    mainLayout->addWidget(consoleLabel);
+    consoleHeader->addWidget(consoleLabel);
+    consoleHeader->addStretch();
+    m_consoleLevelCombo = new QComboBox(this);
+    m_consoleLevelCombo->addItem("All messages", ConsoleLog::Info);
+    m_consoleLevelCombo->addItem("Warnings and errors", ConsoleLog::Warning);
+    m_consoleLevelCombo->addItem("Errors only", ConsoleLog::Error);
+    consoleHeader->addWidget(m_consoleLevelCombo);
+    m_consoleFilterEdit = new QLineEdit(this);
+    m_consoleFilterEdit->setPlaceholderText("Filter...");
+    m_consoleFilterEdit->setClearButtonEnabled(true);
+    m_consoleFilterEdit->setToolTip("Only show console lines containing this text (matches the step name too)");
+    consoleHeader->addWidget(m_consoleFilterEdit);
+    QPushButton* exportLogButton = new QPushButton("Export Log...", this);
+    exportLogButton->setToolTip(QString("Save the console log to a text file (the last %1 messages are kept)")
+                                    .arg(ConsoleLog::DEFAULT_CAPACITY));
+    consoleHeader->addWidget(exportLogButton);
+    mainLayout->addLayout(consoleHeader);
     
     m_consoleOutput = new QTextEdit(this);
     m_consoleOutput->setMaximumHeight(150);
     m_consoleOutput->setMinimumHeight(100);
     m_consoleOutput->setReadOnly(true);
     m_consoleOutput->setStyleSheet("background-color: #2b2b2b; color: #00ff00; font-family: 'Courier New', monospace;");
+    // Matches the log's capacity so the widget stays bounded too
+    m_consoleOutput->document()->setMaximumBlockCount(ConsoleLog::DEFAULT_CAPACITY);
     mainLayout->addWidget(m_consoleOutput);
     
+    connect(m_consoleLevelCombo, QOverload<int>::of(&QComboBox::currentIndexChanged),
+            this, &SimpleMainWindow::refreshConsole);
+    connect(m_consoleFilterEdit, &QLineEdit::textChanged, this, &SimpleMainWindow::refreshConsole);
+    connect(exportLogButton, &QPushButton::clicked, this, &SimpleMainWindow::exportConsoleLog);
+    
     // Buttons
     QHBoxLayout* buttonLayout = new QHBoxLayout();
     
@@ -252,8 +586,8 @@ void SimpleMainWindow::setupUI()
     QPushButton* saveButton = new QPushButton("Save Config", this);
     QPushButton* resetButton = new QPushButton("Reset", this);
     
-    QPushButton* startButton = new QPushButton("Start Randomization", this);
-    startButton->setStyleSheet("background-color: #00cc66; color: white; font-weight: bold; padding: 10px;");
+    m_startButton = new QPushButton("Start Randomization", this);
+    m_startButton->setStyleSheet("background-color: #00cc66; color: white; font-weight: bold; padding: 10px;");
 
     m_iroCheckBox = new QCheckBox("Export as .IRO (7th Heaven)", this);
     m_iroCheckBox->setToolTip(
@@ -265,24 +599,178 @@ void SimpleMainWindow::setupUI()
     buttonLayout->addWidget(resetButton);
     buttonLayout->addStretch();
     buttonLayout->addWidget(m_iroCheckBox);
-    buttonLayout->addWidget(startButton);
+    buttonLayout->addWidget(m_startButton);
     
     mainLayout->addLayout(buttonLayout);
     
     // Connect signals
     connect(browseButton, &QPushButton::clicked, this, &SimpleMainWindow::browseFF7Path);
     connect(browseOutputButton, &QPushButton::clicked, this, &SimpleMainWindow::browseOutputFolder);
-    connect(startButton, &QPushButton::clicked, this, &SimpleMainWindow::startRandomization);
+    connect(m_startButton, &QPushButton::clicked, this, &SimpleMainWindow::startRandomization);
     connect(loadButton, &QPushButton::clicked, this, &SimpleMainWindow::loadConfig);
     connect(saveButton, &QPushButton::clicked, this, &SimpleMainWindow::saveConfig);
     connect(resetButton, &QPushButton::clicked, this, &SimpleMainWindow::resetToDefaults);
     connect(randomSeedButton, &QPushButton::clicked, this, &SimpleMainWindow::randomSeed);
+    connect(applySeedStringButton, &QPushButton::clicked, this, &SimpleMainWindow::importSeedString);
+    connect(copySeedStringButton, &QPushButton::clicked, this, &SimpleMainWindow::copySeedString);
+    connect(m_seedStringEdit, &QPlainTextEdit::textChanged, this, [this]() {
+        m_seedStringStatus->setVisible(false);
+    });
     
     // Archipelago connections
     connect(m_importArchipelagoButton, &QPushButton::clicked, this, &SimpleMainWindow::importArchipelagoJSON);
     connect(m_archipelagoCheckBox, &QCheckBox::toggled, this, &SimpleMainWindow::toggleArchipelagoMode);
 }
 
+QWidget* SimpleMainWindow::createItemPoolTab()
+{
+    QWidget* tab = new QWidget(this);
+    QVBoxLayout* layout = new QVBoxLayout(tab);
+    
+    QLabel* introLabel = new QLabel(
+        "Choose which item categories field pickups can become, and how often.\n"
+        "Weights are relative: a category at 10 is picked twice as often as one at 5.", this);
+    introLabel->setWordWrap(true);
+    layout->addWidget(introLabel);
+    
+    static const char* const categoryNames[Config::PickupCategoryCount] = {
+        "Consumables (Potion ... War Gong)",
+        "Battle Items (items 32-63)",
+        "Weapons",
+        "Armor",
+        "Accessories"
+    };
+    
+    QGridLayout* grid = new QGridLayout();
+    grid->addWidget(new QLabel("Include", this), 0, 0);
+    grid->addWidget(new QLabel("Weight", this), 0, 1);
+    for (int i = 0; i < Config::PickupCategoryCount; ++i) {
+        m_poolCategoryCheck[i] = new QCheckBox(categoryNames[i], this);
+        m_poolCategoryCheck[i]->setToolTip("Allow field pickups to become items from this category.");
+        m_poolWeightSlider[i] = new QSlider(Qt::Horizontal, this);
+        m_poolWeightSlider[i]->setRange(1, 10);
+        m_poolWeightSlider[i]->setValue(5);
+        m_poolWeightSlider[i]->setToolTip("Relative chance of this category being picked (1-10).");
+        m_poolWeightLabel[i] = new QLabel("5", this);
+        m_poolWeightLabel[i]->setMinimumWidth(20);
+        
+        grid->addWidget(m_poolCategoryCheck[i], i + 1, 0);
+        grid->addWidget(m_poolWeightSlider[i], i + 1, 1);
+        grid->addWidget(m_poolWeightLabel[i], i + 1, 2);
+        
+        QLabel* weightLabel = m_poolWeightLabel[i];
+        QSlider* weightSlider = m_poolWeightSlider[i];
+        connect(m_poolWeightSlider[i], &QSlider::valueChanged, this, [weightLabel](int value) {
+            weightLabel->setText(QString::number(value));
+        });
+        connect(m_poolCategoryCheck[i], &QCheckBox::toggled, weightSlider, &QSlider::setEnabled);
+    }
+    layout->addLayout(grid);
+    
+    QLabel* noteLabel = new QLabel(
+        "If every category is excluded, the built-in pool (consumables + battle items) is used.", this);
+    noteLabel->setWordWrap(true);
+    noteLabel->setStyleSheet("color: gray;");
+    layout->addWidget(noteLabel);
+    layout->addStretch();
+    
+    return tab;
+}
+
+QWidget* SimpleMainWindow::createDebugTab()
+{
+    QWidget* tab = new QWidget(this);
+    QVBoxLayout* layout = new QVBoxLayout(tab);
+    
+    QLabel* introLabel = new QLabel(
+        "Extra files written to the output folder. Leave these off unless a maintainer asks for one;\n"
+        "they don't change the randomized game or the seed hash.", this);
+    introLabel->setWordWrap(true);
+    layout->addWidget(introLabel);
+    
+    static const char* const outputNames[Config::DebugOutputCount] = {
+        "Field pickup log (field_randomization_debug.txt)",
+        "Field STITM/SMTRA index (field_stitm_index.txt)",
+        "Key item bit scan (key_bit_scan.txt)",
+        "Enemy scene dump (scene_dump.csv)",
+        "Field script dumps (field_script_dump.txt)",
+        "Field patch log (field_patch_log.json)"
+    };
+    static const char* const outputTips[Config::DebugOutputCount] = {
+        "Every field pickup change, key item placement and special-case patch.",
+        "Every item/materia opcode found in every field, with its vanilla contents.",
+        "Key item BITON collection pass and the post-placement verification scan.",
+        "Stats, drops, steals and morphs of every enemy in the final scene.bin (same as --inspect-enemies).",
+        "Decoded entity scripts of every field the randomizer changed. Large.",
+        "Every byte range changed in every field: offset, bytes before and after, and the patch step responsible."
+    };
+    for (int i = 0; i < Config::DebugOutputCount; ++i) {
+        m_debugOutputCheck[i] = new QCheckBox(outputNames[i], this);
+        m_debugOutputCheck[i]->setToolTip(outputTips[i]);
+        layout->addWidget(m_debugOutputCheck[i]);
+    }
+    layout->addStretch();
+    
+    return tab;
+}
+
+QWidget* SimpleMainWindow::createRunQueueTab()
+{
+    QWidget* tab = new QWidget(this);
+    QVBoxLayout* layout = new QVBoxLayout(tab);
+    
+    QLabel* introLabel = new QLabel(
+        "Queue several seeds or presets and generate them one after another in the background.\n"
+        "Each entry keeps the settings it was added with and gets its own output folder (an existing folder\n"
+        "gets a numbered suffix), seed report and, when its settings export one, .iro archive; the seed card is skipped.\n"
+        "Closing the window finishes the seed in progress and leaves the rest pending.", this);
+    introLabel->setWordWrap(true);
+    layout->addWidget(introLabel);
+    
+    m_queueList = new QListWidget(this);
+    m_queueList->setToolTip("Double-click a failed entry for the error and a diagnostic bundle");
+    layout->addWidget(m_queueList);
+    
+    QHBoxLayout* queueButtons = new QHBoxLayout();
+    QPushButton* addButton = new QPushButton("Add Current Settings", this);
+    addButton->setToolTip("Queue a run with the settings and seed shown in the other tabs");
+    QPushButton* addRandomButton = new QPushButton("Add With Random Seed", this);
+    addRandomButton->setToolTip("Queue a run with the current settings and a fresh random seed");
+    QPushButton* removeButton = new QPushButton("Remove", this);
+    QPushButton* clearButton = new QPushButton("Clear", this);
+    m_queueRunButton = new QPushButton("Run Queue", this);
+    m_queueRunButton->setToolTip("Generate every pending entry, in order");
+    queueButtons->addWidget(addButton);
+    queueButtons->addWidget(addRandomButton);
+    queueButtons->addWidget(removeButton);
+    queueButtons->addWidget(clearButton);
+    queueButtons->addStretch();
+    queueButtons->addWidget(m_queueRunButton);
+    layout->addLayout(queueButtons);
+    
+    connect(addButton, &QPushButton::clicked, this, [this]() { addToRunQueue(false); });
+    connect(addRandomButton, &QPushButton::clicked, this, [this]() { addToRunQueue(true); });
+    connect(removeButton, &QPushButton::clicked, this, [this]() {
+        const int row = m_queueList->currentRow();
+        if (m_queueThread || row < 0) return;
+        m_runQueue.remove(row);
+        delete m_queueList->takeItem(row);
+    });
+    connect(clearButton, &QPushButton::clicked, this, [this]() {
+        if (m_queueThread) return;
+        m_runQueue.clear();
+        m_queueList->clear();
+    });
+    connect(m_queueRunButton, &QPushButton::clicked, this, &SimpleMainWindow::runQueue);
+    connect(m_queueList, &QListWidget::itemDoubleClicked, this, [this](QListWidgetItem* item) {
+        const int row = m_queueList->row(item);
+        if (row < 0 || m_runQueue[row].state != QueuedRun::Failed) return;
+        showRunFailure(m_runQueue[row].failure, m_runQueue[row].config);
+    });
+    
+    return tab;
+}
+
 void SimpleMainWindow::browseFF7Path()
 {
     QString path = QFileDialog::getExistingDirectory(this, 
@@ -307,6 +795,10 @@ void SimpleMainWindow::browseOutputFolder()
 
 void SimpleMainWindow::startRandomization()
 {
+    if (m_queueThread) {
+        QMessageBox::information(this, "Run Queue", "Wait for the run queue to finish first.");
+        return;
+    }
     QString ff7Path = m_ff7PathEdit->text();
     if (ff7Path.isEmpty()) {
         QMessageBox::warning(this, "Error", "Please select FF7 installation path");
@@ -327,6 +819,8 @@ void SimpleMainWindow::startRandomization()
     }
     
     // Clear console and add header
+    m_consoleLog.clear();
+    m_consoleSubsystem.clear();
     m_consoleOutput->clear();
     appendConsoleMessage("=== Starting Randomization ===");
     appendConsoleMessage("FF7 Path: " + ff7Path);
@@ -335,82 +829,90 @@ void SimpleMainWindow::startRandomization()
     // Update config
     updateConfig();
 
-    // Create randomizer and run
+    // Create randomizer and run. It outlives the try so a failure can still
+    // read the debug logs before the partial output is removed
+    Randomizer randomizer(ff7Path, m_config);
+    const auto reportFailure = [&](const QString& message) {
+        SeedGenerator::Failure failure;
+        failure.step = m_consoleSubsystem;
+        failure.message = randomizer.planError().isEmpty()
+                              ? message : message + ": " + randomizer.planError();
+        failure.inputErrors = randomizer.inputErrors();
+        failure.outputPath = randomizer.getOutputPath();
+        failure.logs = DiagnosticBundle::collectLogs(failure.outputPath);
+        showRunFailure(failure, m_config);
+    };
     try {
-        Randomizer randomizer(ff7Path, m_config);
-        
         m_progressBar->setVisible(true);
         m_progressBar->setValue(0);
+        m_consoleSubsystem = "Output";
         m_statusLabel->setText("Preparing output directory...");
         appendConsoleMessage("Preparing output directory...");
         QApplication::processEvents();
         
-        if (!randomizer.copyOriginalFiles()) {
-            appendConsoleMessage("ERROR: Failed to copy original files to output directory");
-            QMessageBox::critical(this, "Error", "Failed to copy original files to output directory");
+        // Checked before the overwrite prompt, which can't help here
+        QString outputError = GameFileLocator::writeTargetProblem(randomizer.getOutputPath());
+        if (!outputError.isEmpty()) {
+            appendConsoleMessage("ERROR: " + outputError);
+            QMessageBox::critical(this, "Output Folder Not Writable", outputError);
+            m_progressBar->setVisible(false);
             return;
         }
-        appendConsoleMessage("Original files copied successfully");
-        
-        if (m_config.isFeatureEnabled(Config::ShopRandomization)) {
-            m_progressBar->setValue(25);
-            m_statusLabel->setText("Randomizing Shops...");
-            appendConsoleMessage("Randomizing Shops...");
-            QApplication::processEvents();
-            
-            if (!randomizer.randomizeShops()) {
-                appendConsoleMessage("ERROR: Shop randomization failed");
-                QMessageBox::critical(this, "Error", "Shop randomization failed");
-                return;
+        if (!randomizer.claimOutputFolder(m_config.getOutputCollision(), &outputError)) {
+            int choice = -1;
+            if (m_config.getOutputCollision() == Config::OutputCollisionError
+                && QDir(randomizer.getOutputPath()).exists()) {
+                QMessageBox prompt(QMessageBox::Question, "Output Folder In Use",
+                                   outputError + ".\nOverwrite it, or write this seed to a new folder?",
+                                   QMessageBox::Cancel, this);
+                QPushButton* overwriteButton = prompt.addButton("Overwrite", QMessageBox::DestructiveRole);
+                QPushButton* newFolderButton = prompt.addButton("New Folder", QMessageBox::AcceptRole);
+                prompt.exec();
+                if (prompt.clickedButton() == overwriteButton) choice = Config::OutputCollisionOverwrite;
+                else if (prompt.clickedButton() == newFolderButton) choice = Config::OutputCollisionSuffix;
+                else {
+                    appendConsoleMessage("Cancelled: output folder already contains files");
+                    m_progressBar->setVisible(false);
+                    return;
+                }
             }
-            appendConsoleMessage("Shop randomization completed successfully");
-        }
-        
-        if (m_config.isFeatureEnabled(Config::FieldPickupRandomization)) {
-            m_progressBar->setValue(50);
-            m_statusLabel->setText("Randomizing Field Pickups...");
-            appendConsoleMessage("Randomizing Field Pickups...");
-            QApplication::processEvents();
-            
-            if (!randomizer.randomizeFieldPickups()) {
-                appendConsoleMessage("ERROR: Field pickup randomization failed");
-                QMessageBox::critical(this, "Error", "Field pickup randomization failed");
+            if (choice < 0 || !randomizer.claimOutputFolder(choice, &outputError)) {
+                appendConsoleMessage("ERROR: " + outputError);
+                QMessageBox::critical(this, "Error", outputError);
                 return;
             }
-            appendConsoleMessage("Field pickup randomization completed successfully");
         }
+        appendConsoleMessage("Writing to: " + randomizer.getOutputPath());
         
-        if (m_config.isFeatureEnabled(Config::StartingEquipmentRandomization)) {
-            m_progressBar->setValue(75);
-            m_statusLabel->setText("Randomizing Starting Equipment...");
-            appendConsoleMessage("Randomizing Starting Equipment...");
-            QApplication::processEvents();
-            
-            if (!randomizer.randomizeStartingEquipment()) {
-                appendConsoleMessage("ERROR: Starting equipment randomization failed");
-                QMessageBox::critical(this, "Error", "Starting equipment randomization failed");
-                return;
-            }
-            appendConsoleMessage("Starting equipment randomization completed successfully");
-        }
-
-        if (m_config.getFreeRoam()) {
-            m_progressBar->setValue(90);
+        // The same passes in the same order as SeedGenerator
+        for (const SeedGenerator::Step& step : SeedGenerator::steps(m_config)) {
+            if (!step.enabled) continue;
+            const QString title = QString::fromLatin1(step.title);
+            m_progressBar->setValue(step.progress);
+            m_consoleSubsystem = title;
+            m_statusLabel->setText(title + "...");
+            appendConsoleMessage(title + "...");
             QApplication::processEvents();
 
-            appendConsoleMessage("Reactivating Northern Crater barrier (goal gate)...");
-            QApplication::processEvents();
-            if (!randomizer.applyCraterBarrier()) {
-                appendConsoleMessage("WARNING: Crater barrier patch failed — world_us.lgp not found or unrecognised; "
-                                     "crater will remain open");
-            } else {
-                appendConsoleMessage("Crater barrier patch applied to world_us.lgp");
+            if ((randomizer.*step.run)()) {
+                appendConsoleMessage(title + " completed successfully");
+                continue;
             }
+            if (step.warning) {
+                appendConsoleMessage(QString("WARNING: %1 failed - %2").arg(title, QString::fromLatin1(step.warning)));
+                continue;
+            }
+            appendConsoleMessage("ERROR: " + title + " failed");
+            if (!randomizer.planError().isEmpty())
+                appendConsoleMessage("ERROR: " + randomizer.planError());
+            reportFailure(title + " failed");
+            return;
         }
 
         // Optional: pack the randomized output into a 7th Heaven .iro archive.
         if (m_config.getExportIro()) {
             m_progressBar->setValue(95);
+            m_consoleSubsystem = "IRO";
             m_statusLabel->setText("Exporting .iro...");
             appendConsoleMessage("Exporting 7th Heaven .iro archive...");
             QApplication::processEvents();
@@ -421,7 +923,10 @@ void SimpleMainWindow::startRandomization()
 
             IroExporter iro(ff7Path, outDir);
             QStringList iroLog;
+            QElapsedTimer iroTimer;
+            iroTimer.start();
             bool iroOk = iro.exportIro(iroPath, m_config, iroLog);
+            randomizer.recordPhase("iro export", iroTimer.elapsed());
             for (const QString& line : iroLog)
                 appendConsoleMessage(line);
             if (iroOk)
@@ -430,7 +935,50 @@ void SimpleMainWindow::startRandomization()
                 appendConsoleMessage("WARNING: IRO export produced no archive (see notes above)");
         }
 
+        m_consoleSubsystem = "Report";
+        for (const QString& inputError : randomizer.inputErrors())
+            appendConsoleMessage("WARNING: " + inputError);
+        if (!randomizer.writeDebugArtifacts())
+            appendConsoleMessage("WARNING: some debug files could not be written");
+
+        // Spoiler-free card for race lobbies (hash + settings, no seed)
+        {
+            const QString cardPath = QDir(randomizer.getOutputPath()).filePath("seed_card.png");
+            QString cardError;
+            if (SeedCard::save(m_config, cardPath, &cardError))
+                appendConsoleMessage(QString("Seed card (hash %1) saved: %2")
+                                         .arg(SeedCard::settingsHash(m_config), cardPath));
+            else
+                appendConsoleMessage("WARNING: " + cardError);
+
+            // Structured record; `--summary` turns it into a race-thread post
+            const QMap<QString, QString> modded = randomizer.moddedFields();
+            if (!modded.isEmpty())
+                appendConsoleMessage(QString("WARNING: %1 modded field(s) detected - pickup texts left unchanged "
+                                             "(listed in %2)").arg(modded.size()).arg(SeedSummary::REPORT_FILE_NAME));
+            const QMap<QString, QString> vanillaKeyItems = randomizer.vanillaKeyItems();
+            for (auto it = vanillaKeyItems.constBegin(); it != vanillaKeyItems.constEnd(); ++it)
+                appendConsoleMessage(QString("WARNING: key item %1 left vanilla (%2)").arg(it.key(), it.value()));
+            const QJsonObject report = SeedSummary::buildReport(m_config, ff7Path, QDateTime::currentDateTime(),
+                                                                modded, randomizer.kernelRoundtrip(),
+                                                                randomizer.performance(), vanillaKeyItems,
+                                                                randomizer.keyItemSources());
+            for (const QString& line : SeedSummary::performanceLines(report))
+                appendConsoleMessage(line);
+            const QDir outDir(randomizer.getOutputPath());
+            QString reportError;
+            if (!SeedSummary::writeReport(report, outDir.filePath(SeedSummary::REPORT_FILE_NAME), &reportError)) {
+                appendConsoleMessage("WARNING: " + reportError);
+            } else {
+                QFile summaryFile(outDir.filePath("seed_summary.md"));
+                if (summaryFile.open(QIODevice::WriteOnly | QIODevice::Text))
+                    summaryFile.write(SeedSummary::markdown(report).toUtf8());
+                appendConsoleMessage("Seed summary written: " + summaryFile.fileName());
+            }
+        }
+
         // Complete
+        randomizer.finishOutput();
         m_progressBar->setValue(100);
         m_statusLabel->setText("Randomization Complete!");
         appendConsoleMessage("=== Randomization Complete ===");
@@ -441,13 +989,173 @@ void SimpleMainWindow::startRandomization()
         
     } catch (const std::exception& e) {
         appendConsoleMessage("ERROR: " + QString(e.what()));
-        QMessageBox::critical(this, "Error", QString("Randomization failed: %1").arg(e.what()));
+        reportFailure(QString("Randomization crashed: %1").arg(e.what()));
+    } catch (...) {
+        appendConsoleMessage("ERROR: unknown exception");
+        reportFailure("Randomization crashed with an unknown exception");
     }
     
+    m_consoleSubsystem.clear();
     m_progressBar->setVisible(false);
     m_statusLabel->setText("Ready");
 }
 
+void SimpleMainWindow::addToRunQueue(bool randomSeed)
+{
+    if (m_queueThread) return;
+    updateConfig();
+    QueuedRun run;
+    run.config = m_config;
+    if (randomSeed)
+        run.config.setSeed(QRandomGenerator::global()->bounded(999999));
+    // Nobody is there to answer the overwrite prompt
+    if (run.config.getOutputCollision() == Config::OutputCollisionError)
+        run.config.setOutputCollision(Config::OutputCollisionSuffix);
+    m_runQueue.append(run);
+    m_queueList->addItem(QString());
+    refreshQueueItem(m_runQueue.size() - 1);
+}
+
+void SimpleMainWindow::refreshQueueItem(int index)
+{
+    const QueuedRun& run = m_runQueue[index];
+    static const char* const stateNames[] = { "pending", "running...", "done", "FAILED" };
+    QString text = QString("Seed %1 (hash %2) - %3")
+                       .arg(run.config.getSeed())
+                       .arg(SeedCard::settingsHash(run.config), QString::fromLatin1(stateNames[run.state]));
+    if (!run.result.isEmpty())
+        text += ": " + run.result;
+    m_queueList->item(index)->setText(text);
+}
+
+void SimpleMainWindow::runQueue()
+{
+    if (m_queueThread) return;
+    
+    QVector<int> pending;
+    QVector<Config> configs;
+    for (int i = 0; i < m_runQueue.size(); ++i) {
+        if (m_runQueue[i].state == QueuedRun::Pending) {
+            pending.append(i);
+            configs.append(m_runQueue[i].config);
+        }
+    }
+    if (pending.isEmpty()) {
+        appendConsoleMessage("Run queue: nothing pending");
+        return;
+    }
+    
+    const QString ff7Path = m_ff7PathEdit->text();
+    if (ff7Path.isEmpty() || !QDir(ff7Path).exists()) {
+        QMessageBox::warning(this, "Error", "Please select a valid FF7 installation path");
+        return;
+    }
+    
+    m_startButton->setEnabled(false);
+    m_queueRunButton->setEnabled(false);
+    m_progressBar->setVisible(true);
+    m_progressBar->setValue(0);
+    m_consoleSubsystem = "Queue";
+    appendConsoleMessage(QString("=== Running %1 queued seed(s) ===").arg(pending.size()));
+    
+    // One SeedGenerator for the whole queue, so the install is read only once
+    m_queueCancel = false;
+    m_queueThread = QThread::create([this, ff7Path, pending, configs]() {
+        SeedGenerator generator(ff7Path);
+        QString loadError;
+        const bool loaded = generator.load(&loadError);
+        const int total = pending.size();
+        for (int i = 0; i < total && !m_queueCancel; ++i) {
+            const int index = pending[i];
+            QMetaObject::invokeMethod(this, [this, index, i, total]() {
+                queueRunStarted(index, i, total);
+            }, Qt::QueuedConnection);
+            
+            QString outputPath, error, logText;
+            bool ok = false;
+            SeedGenerator::Failure failure;
+            if (loaded) {
+                QTextStream log(&logText);
+                ok = generator.generate(configs[i], &outputPath, &error, &log);
+                log.flush();
+                failure = generator.lastFailure();
+            } else {
+                error = loadError;
+                failure.step = QStringLiteral("load inputs");
+                failure.message = loadError;
+            }
+            const QString result = ok ? outputPath : error;
+            QMetaObject::invokeMethod(this, [this, index, i, total, ok, result, logText, failure]() {
+                queueRunFinished(index, i, total, ok, result, logText, failure);
+            }, Qt::QueuedConnection);
+        }
+    });
+    m_queueThread->setParent(this);
+    connect(m_queueThread, &QThread::finished, this, [this, pending]() {
+        m_queueThread->deleteLater();
+        m_queueThread = nullptr;
+        m_startButton->setEnabled(true);
+        m_queueRunButton->setEnabled(true);
+        m_progressBar->setVisible(false);
+        m_statusLabel->setText("Ready");
+        appendConsoleMessage("=== Run queue finished ===");
+        m_consoleSubsystem.clear();
+        // One dialog for this pass's first failure; the rest open from the
+        // list. None when the queue was stopped by closing the window
+        for (int index : pending) {
+            if (m_queueCancel) break;
+            const QueuedRun& run = m_runQueue[index];
+            if (run.state == QueuedRun::Failed && !run.failure.isEmpty()) {
+                showRunFailure(run.failure, run.config);
+                break;
+            }
+        }
+    });
+    m_queueThread->start();
+}
+
+void SimpleMainWindow::closeEvent(QCloseEvent* event)
+{
+    if (m_queueThread) {
+        m_queueCancel = true;
+        m_statusLabel->setText("Finishing the current queued seed before closing...");
+        m_statusLabel->repaint();
+        m_queueThread->wait();
+    }
+    QMainWindow::closeEvent(event);
+}
+
+void SimpleMainWindow::queueRunStarted(int index, int position, int total)
+{
+    m_runQueue[index].state = QueuedRun::Running;
+    refreshQueueItem(index);
+    m_statusLabel->setText(QString("Queue: generating %1 of %2...").arg(position + 1).arg(total));
+    m_consoleSubsystem = QString("Queue %1/%2").arg(position + 1).arg(total);
+    appendConsoleMessage(QString("Generating seed %1").arg(m_runQueue[index].config.getSeed()));
+}
+
+void SimpleMainWindow::queueRunFinished(int index, int position, int total, bool ok,
+                                        const QString& result, const QString& log,
+                                        const SeedGenerator::Failure& failure)
+{
+    QueuedRun& run = m_runQueue[index];
+    run.state = ok ? QueuedRun::Done : QueuedRun::Failed;
+    run.result = result;
+    run.failure = failure;
+    refreshQueueItem(index);
+    
+    for (const QString& line : log.split('\n', Qt::SkipEmptyParts))
+        appendConsoleMessage(line);
+    appendConsoleMessage(ok ? "Written to: " + result : "ERROR: " + result);
+    m_progressBar->setValue((position + 1) * 100 / total);
+}
+
+void SimpleMainWindow::showRunFailure(const SeedGenerator::Failure& failure, const Config& config)
+{
+    ErrorReportDialog dialog(failure, config, m_ff7PathEdit->text(), m_consoleLog, this);
+    dialog.exec();
+}
+
 void SimpleMainWindow::loadConfig()
 {
     QString configPath = QCoreApplication::applicationDirPath() + "/randomizer_config.json";
@@ -478,29 +1186,116 @@ void SimpleMainWindow::randomSeed()
     m_seedSpin->setValue(QRandomGenerator::global()->bounded(999999));
 }
 
+void SimpleMainWindow::importSeedString()
+{
+    // Validate against a copy so a bad paste leaves the current settings untouched
+    updateConfig();
+    Config imported = m_config;
+    
+    QString error;
+    if (!imported.loadFromShareString(m_seedStringEdit->toPlainText(), &error)) {
+        m_seedStringStatus->setStyleSheet("color: #ff5555;");
+        m_seedStringStatus->setText("Error: " + error);
+        m_seedStringStatus->setVisible(true);
+        return;
+    }
+    
+    m_config = imported;
+    applyConfigToUI();
+    m_seedStringStatus->setStyleSheet("color: #00cc66;");
+    m_seedStringStatus->setText(QString("Settings applied (seed %1)").arg(m_config.getSeed()));
+    m_seedStringStatus->setVisible(true);
+    appendConsoleMessage(QString("Settings imported from string (seed %1)").arg(m_config.getSeed()));
+}
+
+void SimpleMainWindow::copySeedString()
+{
+    updateConfig();
+    const QString shareString = m_config.toShareString();
+    QApplication::clipboard()->setText(shareString);
+    m_seedStringEdit->setPlainText(shareString);
+    m_seedStringStatus->setStyleSheet("color: #00cc66;");
+    m_seedStringStatus->setText("Settings string copied to clipboard");
+    m_seedStringStatus->setVisible(true);
+    appendConsoleMessage("Settings string copied to clipboard");
+}
+
 void SimpleMainWindow::updateConfig()
 {
     // Features
     m_config.setFeatureEnabled(Config::ShopRandomization, m_shopCheckBox->isChecked());
     m_config.setFeatureEnabled(Config::FieldPickupRandomization, m_fieldCheckBox->isChecked());
     m_config.setKeyItemRandomization(m_keyItemCheckBox->isChecked());
+    m_config.setEarlyPhs(m_earlyPhsCheckBox->isChecked());
+    m_config.setMidgarPartsRequired(m_midgarPartsSpin->value());
+    m_config.setHugeMateriaRequired(m_hugeMateriaSpin->value());
+    m_config.setProgressiveKeycards(m_progressiveKeycardsCheckBox->isChecked());
+    m_config.setTieredEquipment(m_tieredEquipmentCheckBox->isChecked());
+    m_config.setKeyItemSoundCue(m_keyItemSoundCheckBox->isChecked());
+    m_config.setModelScaleGag(m_modelScaleCheckBox->isChecked());
+    m_config.setSilentPickups(m_silentPickupsCheckBox->isChecked());
+    m_config.setFieldEncounterMode(m_fieldEncounterCombo->currentIndex());
+    m_config.setRandomizeFieldEncounterRates(m_fieldEncounterRatesCheckBox->isChecked());
+    m_config.setRandomizeScriptedBattles(m_scriptedBattlesCheckBox->isChecked());
+    m_config.setOptionalKeyItems(m_optionalKeyItemsCheckBox->isChecked());
+    m_config.setChocoboChecks(m_chocoboCheckBox->isChecked());
+    m_config.setMateriaCaveChecks(m_materiaCaveCheckBox->isChecked());
+    for (int i = 0; i < Config::LocationGroupCount; ++i)
+        m_config.setLocationGroupEnabled(static_cast<Config::LocationGroup>(i), m_locationGroupCheck[i]->isChecked());
+    m_config.setUseInstallNames(m_installNamesCheckBox->isChecked());
+    m_config.setArchipelagoExport(m_archipelagoExportCheckBox->isChecked());
     m_config.setFeatureEnabled(Config::StartingEquipmentRandomization, m_equipmentCheckBox->isChecked());
+    m_config.setFeatureEnabled(Config::EnemyStatsRandomization, m_enemyStatsCheckBox->isChecked());
+    m_config.setFeatureEnabled(Config::EnemyEncounterRandomization, m_encounterCheckBox->isChecked());
+    m_config.setEncounterEnemySwap(m_enemySwapCheckBox->isChecked());
+    m_config.setScaleShuffledEncounters(m_scaleEncountersCheckBox->isChecked());
     
     // Text replacement settings - REMOVED (now handled automatically by FF7TK field randomization)
     // saveTextReplacementSettings();
     m_config.setFeatureEnabled(Config::ArchipelagoIntegration, m_archipelagoCheckBox->isChecked());
     m_config.setFreeRoam(m_freeRoamCheckBox->isChecked());
     m_config.setExportIro(m_iroCheckBox->isChecked());
+    for (int i = 0; i < Config::DebugOutputCount; ++i)
+        m_config.setDebugOutputEnabled(static_cast<Config::DebugOutput>(i), m_debugOutputCheck[i]->isChecked());
 
     // Settings
     m_config.setShopItemPoolSize(m_shopPoolSpin->value());
     m_config.setShopPriceVariance(m_shopPriceSpin->value() / 100.0);
+    m_config.setRandomizeShopPrices(m_randomizeShopPricesCheckBox->isChecked());
+    m_config.setBalanceEconomy(m_balanceEconomyCheckBox->isChecked());
+    m_config.setShopLocationSlot(m_shopLocationSlotCheckBox->isChecked());
+    m_config.setShopTierStrictness(m_shopTierCombo->currentIndex());
     m_config.setPickupRarityMode(m_pickupCombo->currentIndex());
+    m_config.setFinalDungeonLootDensity(m_finalDungeonLootSpin->value());
+    for (int i = 0; i < Config::PickupCategoryCount; ++i) {
+        auto category = static_cast<Config::PickupCategory>(i);
+        m_config.setPickupCategoryEnabled(category, m_poolCategoryCheck[i]->isChecked());
+        m_config.setPickupCategoryWeight(category, m_poolWeightSlider[i]->value());
+    }
     m_config.setStartingEquipmentTier(m_equipmentCombo->currentIndex());
+    m_config.setRandomizeArmorStats(m_armorStatsCombo->currentIndex());
+    m_config.setRandomizeArmorSlots(m_armorSlotsCombo->currentIndex());
+    for (int i = 0; i < Config::STARTING_CHARACTER_COUNT; ++i) {
+        if (m_startingCharacterCheck[i])
+            m_config.setStartingCharacterRandomized(i, m_startingCharacterCheck[i]->isChecked());
+    }
+    m_config.setEnemyNameMode(m_enemyNameCombo->currentIndex());
+    m_config.setPreserveBossNames(m_preserveBossNamesCheckBox->isChecked());
+    m_config.setRandomizeEnemySteals(m_enemyStealsCheckBox->isChecked());
+    m_config.setStealDropConversion(m_stealDropConversionCheckBox->isChecked());
+    m_config.setRandomizeChocoboRatings(m_chocoboRatingsCheckBox->isChecked());
+    m_config.setRandomizeEnemyMorphs(m_enemyMorphsCheckBox->isChecked());
+    m_config.setShuffleEnemyAttacks(m_enemyAttacksCheckBox->isChecked());
+    m_config.setBossDifficulty(m_bossDifficultyCombo->currentIndex());
+    m_config.setExpMultiplier(m_expMultiplierSpin->value());
+    m_config.setApMultiplier(m_apMultiplierSpin->value());
+    m_config.setGilMultiplier(m_gilMultiplierSpin->value());
+    m_config.setApJitter(m_apJitterSpin->value());
     m_config.setSeed(m_seedSpin->value());
     
     // Paths
     m_config.setOutputFolder(m_outputFolderEdit->text());
+    m_config.setOutputCollision(m_outputCollisionCombo->currentIndex());
     m_config.setFF7Path(m_ff7PathEdit->text());
     
     // Archipelago settings
@@ -516,7 +1311,29 @@ void SimpleMainWindow::applyConfigToUI()
     m_shopCheckBox->setChecked(m_config.isFeatureEnabled(Config::ShopRandomization));
     m_fieldCheckBox->setChecked(m_config.isFeatureEnabled(Config::FieldPickupRandomization));
     m_keyItemCheckBox->setChecked(m_config.getKeyItemRandomization());
+    m_earlyPhsCheckBox->setChecked(m_config.getEarlyPhs());
+    m_midgarPartsSpin->setValue(m_config.getMidgarPartsRequired());
+    m_hugeMateriaSpin->setValue(m_config.getHugeMateriaRequired());
+    m_progressiveKeycardsCheckBox->setChecked(m_config.getProgressiveKeycards());
+    m_tieredEquipmentCheckBox->setChecked(m_config.getTieredEquipment());
+    m_keyItemSoundCheckBox->setChecked(m_config.getKeyItemSoundCue());
+    m_modelScaleCheckBox->setChecked(m_config.getModelScaleGag());
+    m_silentPickupsCheckBox->setChecked(m_config.getSilentPickups());
+    m_fieldEncounterCombo->setCurrentIndex(m_config.getFieldEncounterMode());
+    m_fieldEncounterRatesCheckBox->setChecked(m_config.getRandomizeFieldEncounterRates());
+    m_scriptedBattlesCheckBox->setChecked(m_config.getRandomizeScriptedBattles());
+    m_optionalKeyItemsCheckBox->setChecked(m_config.getOptionalKeyItems());
+    m_chocoboCheckBox->setChecked(m_config.getChocoboChecks());
+    m_materiaCaveCheckBox->setChecked(m_config.getMateriaCaveChecks());
+    for (int i = 0; i < Config::LocationGroupCount; ++i)
+        m_locationGroupCheck[i]->setChecked(m_config.isLocationGroupEnabled(static_cast<Config::LocationGroup>(i)));
+    m_installNamesCheckBox->setChecked(m_config.getUseInstallNames());
+    m_archipelagoExportCheckBox->setChecked(m_config.getArchipelagoExport());
     m_equipmentCheckBox->setChecked(m_config.isFeatureEnabled(Config::StartingEquipmentRandomization));
+    m_enemyStatsCheckBox->setChecked(m_config.isFeatureEnabled(Config::EnemyStatsRandomization));
+    m_encounterCheckBox->setChecked(m_config.isFeatureEnabled(Config::EnemyEncounterRandomization));
+    m_enemySwapCheckBox->setChecked(m_config.getEncounterEnemySwap());
+    m_scaleEncountersCheckBox->setChecked(m_config.getScaleShuffledEncounters());
     
     // Text replacement settings - REMOVED (now handled automatically by FF7TK field randomization)
     // loadTextReplacementSettings();
@@ -540,29 +1357,110 @@ void SimpleMainWindow::applyConfigToUI()
 
     m_freeRoamCheckBox->setChecked(m_config.getFreeRoam());
     m_iroCheckBox->setChecked(m_config.getExportIro());
+    for (int i = 0; i < Config::DebugOutputCount; ++i)
+        m_debugOutputCheck[i]->setChecked(m_config.isDebugOutputEnabled(static_cast<Config::DebugOutput>(i)));
     
     // Settings
     m_shopPoolSpin->setValue(m_config.getShopItemPoolSize());
     m_shopPriceSpin->setValue(static_cast<int>(m_config.getShopPriceVariance() * 100));
+    m_randomizeShopPricesCheckBox->setChecked(m_config.getRandomizeShopPrices());
+    m_balanceEconomyCheckBox->setChecked(m_config.getBalanceEconomy());
+    m_shopLocationSlotCheckBox->setChecked(m_config.getShopLocationSlot());
+    m_shopTierCombo->setCurrentIndex(m_config.getShopTierStrictness());
     m_pickupCombo->setCurrentIndex(m_config.getPickupRarityMode());
+    m_finalDungeonLootSpin->setValue(m_config.getFinalDungeonLootDensity());
+    for (int i = 0; i < Config::PickupCategoryCount; ++i) {
+        auto category = static_cast<Config::PickupCategory>(i);
+        m_poolCategoryCheck[i]->setChecked(m_config.isPickupCategoryEnabled(category));
+        m_poolWeightSlider[i]->setValue(m_config.getPickupCategoryWeight(category));
+        m_poolWeightSlider[i]->setEnabled(m_config.isPickupCategoryEnabled(category));
+    }
     m_equipmentCombo->setCurrentIndex(m_config.getStartingEquipmentTier());
+    m_armorStatsCombo->setCurrentIndex(m_config.getRandomizeArmorStats());
+    m_armorSlotsCombo->setCurrentIndex(m_config.getRandomizeArmorSlots());
+    for (int i = 0; i < Config::STARTING_CHARACTER_COUNT; ++i) {
+        if (m_startingCharacterCheck[i])
+            m_startingCharacterCheck[i]->setChecked(m_config.isStartingCharacterRandomized(i));
+    }
+    m_enemyNameCombo->setCurrentIndex(m_config.getEnemyNameMode());
+    m_preserveBossNamesCheckBox->setChecked(m_config.getPreserveBossNames());
+    m_enemyStealsCheckBox->setChecked(m_config.getRandomizeEnemySteals());
+    m_stealDropConversionCheckBox->setChecked(m_config.getStealDropConversion());
+    m_chocoboRatingsCheckBox->setChecked(m_config.getRandomizeChocoboRatings());
+    m_enemyMorphsCheckBox->setChecked(m_config.getRandomizeEnemyMorphs());
+    m_enemyAttacksCheckBox->setChecked(m_config.getShuffleEnemyAttacks());
+    m_bossDifficultyCombo->setCurrentIndex(m_config.getBossDifficulty());
+    m_expMultiplierSpin->setValue(m_config.getExpMultiplier());
+    m_apMultiplierSpin->setValue(m_config.getApMultiplier());
+    m_gilMultiplierSpin->setValue(m_config.getGilMultiplier());
+    m_apJitterSpin->setValue(m_config.getApJitter());
     m_seedSpin->setValue(m_config.getSeed());
     
     // Paths
     m_outputFolderEdit->setText(m_config.getOutputFolder());
+    m_outputCollisionCombo->setCurrentIndex(m_config.getOutputCollision());
     m_ff7PathEdit->setText(m_config.getFF7Path());
 }
 
 void SimpleMainWindow::appendConsoleMessage(const QString& message)
 {
-    if (m_consoleOutput) {
-        m_consoleOutput->append(message);
+    const ConsoleLog::Entry& entry =
+        m_consoleLog.append(ConsoleLog::levelOf(message), m_consoleSubsystem, message);
+    if (m_consoleOutput && consoleEntryVisible(entry)) {
+        renderConsoleEntry(entry);
         // Auto-scroll to bottom
         QScrollBar *scrollBar = m_consoleOutput->verticalScrollBar();
         scrollBar->setValue(scrollBar->maximum());
     }
 }
 
+bool SimpleMainWindow::consoleEntryVisible(const ConsoleLog::Entry& entry) const
+{
+    if (entry.level < m_consoleLevelCombo->currentData().toInt())
+        return false;
+    const QString filter = m_consoleFilterEdit->text().trimmed();
+    return filter.isEmpty()
+        || entry.message.contains(filter, Qt::CaseInsensitive)
+        || entry.subsystem.contains(filter, Qt::CaseInsensitive);
+}
+
+void SimpleMainWindow::renderConsoleEntry(const ConsoleLog::Entry& entry)
+{
+    const char* colour = entry.level == ConsoleLog::Error   ? "#ff5555"
+                       : entry.level == ConsoleLog::Warning ? "#ffcc00"
+                                                            : "#00ff00";
+    m_consoleOutput->append(QString("<span style=\"color: %1;\">%2</span>")
+                                .arg(colour, ConsoleLog::formatLine(entry).toHtmlEscaped()));
+}
+
+void SimpleMainWindow::refreshConsole()
+{
+    m_consoleOutput->clear();
+    for (int i = 0; i < m_consoleLog.size(); ++i) {
+        if (consoleEntryVisible(m_consoleLog.at(i)))
+            renderConsoleEntry(m_consoleLog.at(i));
+    }
+    QScrollBar *scrollBar = m_consoleOutput->verticalScrollBar();
+    scrollBar->setValue(scrollBar->maximum());
+}
+
+void SimpleMainWindow::exportConsoleLog()
+{
+    const QString defaultName = QString("goldsaucer_log_%1.txt")
+                                    .arg(QDateTime::currentDateTime().toString("yyyyMMdd_hhmmss"));
+    QString filePath = QFileDialog::getSaveFileName(this, "Export Console Log",
+        QDir(QDir::homePath()).filePath(defaultName), "Text Files (*.txt);;All Files (*)");
+    if (filePath.isEmpty())
+        return;
+
+    QString error;
+    if (!m_consoleLog.exportText(filePath, &error)) {
+        QMessageBox::warning(this, "Export Failed", error);
+        return;
+    }
+    appendConsoleMessage("Console log exported to: " + filePath);
+}
+
 void SimpleMainWindow::importArchipelagoJSON()
 {
     QString filePath = QFileDialog::getOpenFileName(this,
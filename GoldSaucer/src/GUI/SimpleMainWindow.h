@@ -4,6 +4,7 @@
 #include <QLineEdit>
 #include <QCheckBox>
 #include <QSpinBox>
+#include <QDoubleSpinBox>
 #include <QComboBox>
 #include <QProgressBar>
 #include <QLabel>
@@ -11,13 +12,27 @@
 #include <QPushButton>
 #include <QGroupBox>
 #include <QSlider>
+#include <QPlainTextEdit>
+#include <QTextStream>
+#include <QListWidget>
+#include <QThread>
+#include <QVector>
+#include <atomic>
 #include "../Config.h"
+#include "../SeedGenerator.h"
+#include "ConsoleLog.h"
 
 class SimpleMainWindow : public QMainWindow
 {
+    friend int runSettingsParityCheck(QTextStream& out);
 public:
     explicit SimpleMainWindow(QWidget *parent = nullptr);
 
+protected:
+    // Lets a running queue finish its current seed, skipping the rest, so
+    // the queue thread never calls back into a destroyed window
+    void closeEvent(QCloseEvent* event) override;
+
 private slots:
     void browseFF7Path();
     void browseOutputFolder();
@@ -26,23 +41,76 @@ private slots:
     void saveConfig();
     void resetToDefaults();
     void randomSeed();
+    void importSeedString();
+    void copySeedString();
     void appendConsoleMessage(const QString& message);
+    void refreshConsole();
+    void exportConsoleLog();
+    void runQueue();
     void importArchipelagoJSON();
     void toggleArchipelagoMode(bool enabled);
 
 private:
     void setupUI();
+    QWidget* createItemPoolTab();
+    QWidget* createDebugTab();
+    QWidget* createRunQueueTab();
+    void addToRunQueue(bool randomSeed);
+    void refreshQueueItem(int index);
+    void queueRunStarted(int index, int position, int total);
+    void queueRunFinished(int index, int position, int total, bool ok,
+                          const QString& result, const QString& log,
+                          const SeedGenerator::Failure& failure);
+    void showRunFailure(const SeedGenerator::Failure& failure, const Config& config);
     void updateConfig();
     void applyConfigToUI();
     bool validateArchipelagoJSON(const QString& filePath);
+    bool consoleEntryVisible(const ConsoleLog::Entry& entry) const;
+    void renderConsoleEntry(const ConsoleLog::Entry& entry);
     
     // UI Elements
     QLineEdit* m_ff7PathEdit;
     QLineEdit* m_outputFolderEdit;
+    QComboBox* m_outputCollisionCombo;
     QCheckBox* m_shopCheckBox;
     QCheckBox* m_fieldCheckBox;
     QCheckBox* m_keyItemCheckBox;
+    QCheckBox* m_earlyPhsCheckBox;
+    QSpinBox* m_midgarPartsSpin;
+    QSpinBox* m_hugeMateriaSpin;
+    QCheckBox* m_progressiveKeycardsCheckBox;
+    QCheckBox* m_tieredEquipmentCheckBox;
+    QCheckBox* m_archipelagoExportCheckBox;
+    QCheckBox* m_keyItemSoundCheckBox;
+    QCheckBox* m_modelScaleCheckBox;
+    QCheckBox* m_silentPickupsCheckBox;
+    QComboBox* m_fieldEncounterCombo;
+    QComboBox* m_armorStatsCombo;
+    QComboBox* m_armorSlotsCombo;
+    QCheckBox* m_fieldEncounterRatesCheckBox;
+    QCheckBox* m_scriptedBattlesCheckBox;
+    QCheckBox* m_optionalKeyItemsCheckBox;
+    QCheckBox* m_chocoboCheckBox;
+    QCheckBox* m_materiaCaveCheckBox;
+    QCheckBox* m_locationGroupCheck[Config::LocationGroupCount];
+    QCheckBox* m_installNamesCheckBox;
+    QComboBox* m_enemyNameCombo;
+    QCheckBox* m_preserveBossNamesCheckBox;
+    QCheckBox* m_enemyStealsCheckBox;
+    QCheckBox* m_stealDropConversionCheckBox;
+    QCheckBox* m_chocoboRatingsCheckBox;
+    QCheckBox* m_enemyMorphsCheckBox;
+    QCheckBox* m_enemyAttacksCheckBox;
+    QComboBox* m_bossDifficultyCombo;
+    QDoubleSpinBox* m_expMultiplierSpin;
+    QDoubleSpinBox* m_apMultiplierSpin;
+    QDoubleSpinBox* m_gilMultiplierSpin;
+    QSpinBox* m_apJitterSpin;
     QCheckBox* m_equipmentCheckBox;
+    QCheckBox* m_enemyStatsCheckBox;
+    QCheckBox* m_encounterCheckBox;
+    QCheckBox* m_enemySwapCheckBox;
+    QCheckBox* m_scaleEncountersCheckBox;
     QCheckBox* m_archipelagoCheckBox;
     QCheckBox* m_freeRoamCheckBox;
     QCheckBox* m_iroCheckBox;
@@ -55,12 +123,41 @@ private:
     QPushButton* m_importArchipelagoButton;
     QSpinBox* m_shopPoolSpin;
     QSpinBox* m_shopPriceSpin;
+    QSpinBox* m_finalDungeonLootSpin;
+    QCheckBox* m_balanceEconomyCheckBox;
+    QCheckBox* m_shopLocationSlotCheckBox;
+    QCheckBox* m_randomizeShopPricesCheckBox;
+    QComboBox* m_shopTierCombo;
     QSpinBox* m_seedSpin;
+    QPlainTextEdit* m_seedStringEdit;
+    QLabel* m_seedStringStatus;
     QComboBox* m_pickupCombo;
     QComboBox* m_equipmentCombo;
+    QCheckBox* m_startingCharacterCheck[Config::STARTING_CHARACTER_COUNT];
+    
+    // Item Pool tab
+    QCheckBox* m_poolCategoryCheck[Config::PickupCategoryCount];
+    QSlider* m_poolWeightSlider[Config::PickupCategoryCount];
+    QLabel* m_poolWeightLabel[Config::PickupCategoryCount];
+    
+    // Debug tab
+    QCheckBox* m_debugOutputCheck[Config::DebugOutputCount];
+    
+    // Run Queue tab
+    QListWidget* m_queueList;
+    QPushButton* m_queueRunButton;
+    
     QProgressBar* m_progressBar;
     QLabel* m_statusLabel;
     QTextEdit* m_consoleOutput;
+    QPushButton* m_startButton;
+    QComboBox* m_consoleLevelCombo;
+    QLineEdit* m_consoleFilterEdit;
+    
+    // Console log: structured entries, bounded; the subsystem tags each
+    // message with the run step that wrote it
+    ConsoleLog m_consoleLog;
+    QString m_consoleSubsystem;
     
     // Archipelago state
     bool m_archipelagoModeEnabled;
@@ -84,6 +181,20 @@ private:
     // void toggleIntelligentNaming(bool enabled);
     // void refreshPreview();
     
+    // Run queue: settings snapshots generated one after another on
+    // m_queueThread (null when idle; a child of the window). Setting
+    // m_queueCancel stops it before the next entry.
+    struct QueuedRun {
+        enum State { Pending, Running, Done, Failed };
+        Config config;
+        State state = Pending;
+        QString result;     // output folder, or the error
+        SeedGenerator::Failure failure;
+    };
+    QVector<QueuedRun> m_runQueue;
+    QThread* m_queueThread = nullptr;
+    std::atomic_bool m_queueCancel{false};
+    
     // Configuration
     Config m_config;
 };
@@ -0,0 +1,335 @@
+#include "GameFileLocator.h"
+
+#include <QDir>
+#include <QFileInfo>
+#include <QDebug>
+#include <QFile>
+#include <QTextStream>
+#include <QRegularExpression>
+#include <QTemporaryFile>
+
+QString GameFileLocator::resolveCaseInsensitive(const QString& root, const QString& relPath)
+{
+    // Fast path: exact case (always the case on Windows / NTFS)
+    const QString exact = QDir(root).filePath(relPath);
+    if (QFileInfo::exists(exact))
+        return exact;
+
+    QString current = root;
+    const QStringList parts = QDir::fromNativeSeparators(relPath).split('/', Qt::SkipEmptyParts);
+    for (const QString& part : parts) {
+        QDir dir(current);
+        const QString direct = dir.filePath(part);
+        if (QFileInfo::exists(direct)) {
+            current = direct;
+            continue;
+        }
+
+        QString match;
+        const QStringList entries = dir.entryList(QDir::AllEntries | QDir::NoDotAndDotDot | QDir::Hidden);
+        for (const QString& entry : entries) {
+            if (entry.compare(part, Qt::CaseInsensitive) == 0) {
+                match = entry;
+                break;
+            }
+        }
+        if (match.isEmpty())
+            return QString();
+        current = dir.filePath(match);
+    }
+    return current;
+}
+
+// Depth-limited recursive search. QDirIterator has no depth cap, and the
+// install root may contain large mod folders (7th Heaven, FFNx caches), so we
+// walk the tree ourselves.
+static QString searchTree(const QString& dirPath, const QString& fileName,
+                          const QString& excludeDir, int depth)
+{
+    QDir dir(dirPath);
+    const QStringList files = dir.entryList(QDir::Files | QDir::Hidden);
+    for (const QString& f : files) {
+        if (f.compare(fileName, Qt::CaseInsensitive) == 0)
+            return dir.filePath(f);
+    }
+    if (depth <= 0)
+        return QString();
+
+    const QStringList subdirs = dir.entryList(QDir::Dirs | QDir::NoDotAndDotDot | QDir::NoSymLinks);
+    for (const QString& sub : subdirs) {
+        const QString subPath = dir.filePath(sub);
+        if (!excludeDir.isEmpty()
+            && QFileInfo(subPath).canonicalFilePath() == QFileInfo(excludeDir).canonicalFilePath())
+            continue;
+        const QString hit = searchTree(subPath, fileName, excludeDir, depth - 1);
+        if (!hit.isEmpty())
+            return hit;
+    }
+    return QString();
+}
+
+QString GameFileLocator::findFile(const QString& root, const QStringList& relCandidates,
+                                  const QString& fileName, const QString& excludeDir,
+                                  int maxDepth)
+{
+    if (root.isEmpty() || !QDir(root).exists())
+        return QString();
+
+    for (const QString& rel : relCandidates) {
+        const QString hit = resolveCaseInsensitive(root, rel);
+        if (!hit.isEmpty() && QFileInfo(hit).isFile())
+            return hit;
+    }
+
+    const QString found = searchTree(root, fileName, excludeDir, maxDepth);
+    if (!found.isEmpty())
+        qDebug() << "GameFileLocator: found" << fileName << "by search at" << found;
+    return found;
+}
+
+QString GameFileLocator::findKernelBin(const QString& root, const QString& excludeDir)
+{
+    static const QStringList candidates = {
+        "data/lang-en/kernel/kernel.bin",   // Steam English
+        "data/lang-fr/kernel/kernel.bin",   // Steam French
+        "data/lang-de/kernel/kernel.bin",   // Steam German
+        "data/lang-es/kernel/kernel.bin",   // Steam Spanish
+        "data/kernel/kernel.bin",
+        "data/kernel.bin",                  // 1998 PC
+        "kernel.bin",                       // fallback
+    };
+    return findFile(root, candidates, "kernel.bin", excludeDir);
+}
+
+QString GameFileLocator::findKernel2Bin(const QString& root, const QString& excludeDir)
+{
+    // kernel2.bin sits next to kernel.bin and carries the (localized) text
+    static const QStringList candidates = {
+        "data/lang-en/kernel/kernel2.bin",
+        "data/lang-fr/kernel/kernel2.bin",
+        "data/lang-de/kernel/kernel2.bin",
+        "data/lang-es/kernel/kernel2.bin",
+        "data/kernel/kernel2.bin",
+        "data/kernel2.bin",
+        "kernel2.bin",
+    };
+    return findFile(root, candidates, "kernel2.bin", excludeDir);
+}
+
+QString GameFileLocator::findSceneBin(const QString& root, const QString& excludeDir)
+{
+    static const QStringList candidates = {
+        "data/lang-en/battle/scene.bin",
+        "data/lang-fr/battle/scene.bin",
+        "data/lang-de/battle/scene.bin",
+        "data/lang-es/battle/scene.bin",
+        "data/battle/scene.bin",
+    };
+    return findFile(root, candidates, "scene.bin", excludeDir);
+}
+
+QString GameFileLocator::findFlevelLgp(const QString& root, const QString& excludeDir)
+{
+    static const QStringList candidates = {
+        "data/field/flevel.lgp",
+        "data/flevel/flevel.lgp",
+        "field/flevel.lgp",
+    };
+    return findFile(root, candidates, "flevel.lgp", excludeDir);
+}
+
+QVector<GameFileLocator::OutputFile> GameFileLocator::originalFiles(const QString& root,
+                                                                    const QString& excludeDir)
+{
+    QVector<OutputFile> files;
+
+    const QString scene = findSceneBin(root, excludeDir);
+    if (!scene.isEmpty())
+        files.append({ scene, QStringLiteral("data/lang-en/battle/scene.bin") });
+
+    // The kernel is a folder of files in most installs, a lone kernel.bin in some
+    const QString kernelDir = resolveCaseInsensitive(root, "data/lang-en/kernel");
+    const QString kernelFile = resolveCaseInsensitive(root, "data/lang-en/kernel.bin");
+    if (!kernelDir.isEmpty() && QDir(kernelDir).exists()) {
+        const QDir dir(kernelDir);
+        // Lower-case in the output so later writers (kernel.bin) hit the same file
+        for (const QString& file : dir.entryList(QDir::Files))
+            files.append({ dir.filePath(file), "data/lang-en/kernel/" + file.toLower() });
+    } else if (!kernelFile.isEmpty() && QFile::exists(kernelFile)) {
+        files.append({ kernelFile, QStringLiteral("data/lang-en/kernel/kernel.bin") });
+    }
+
+    static const QStringList fieldPaths = { "data/field/flevel.lgp", "data/flevel/flevel.lgp" };
+    for (const QString& fieldPath : fieldPaths) {
+        const QString source = resolveCaseInsensitive(root, fieldPath);
+        if (!source.isEmpty() && QFileInfo(source).isFile()) {
+            files.append({ source, fieldPath });
+            break;
+        }
+    }
+    return files;
+}
+
+namespace {
+// FF7 (2013 Steam release) app id, used for the Proton compatdata prefix
+const char* const FF7_STEAM_APP_ID = "39140";
+}
+
+QStringList GameFileLocator::parseLibraryFoldersVdf(const QString& vdfPath)
+{
+    // libraryfolders.vdf is Valve KeyValues text. Every library entry carries a
+    //     "path"    "/home/user/.local/share/Steam"
+    // line (older files use "1" "D:\\SteamLibrary" instead), so a line regex is
+    // enough; we don't need a full KeyValues parser.
+    QStringList result;
+    QFile file(vdfPath);
+    if (!file.open(QIODevice::ReadOnly | QIODevice::Text))
+        return result;
+
+    static const QRegularExpression pathLine(
+        QStringLiteral("^\\s*\"(path|\\d+)\"\\s+\"(.+)\"\\s*$"));
+    QTextStream in(&file);
+    while (!in.atEnd()) {
+        const QRegularExpressionMatch m = pathLine.match(in.readLine());
+        if (!m.hasMatch())
+            continue;
+        QString path = m.captured(2);
+        path.replace(QStringLiteral("\\\\"), QStringLiteral("/"));   // escaped backslashes
+        path = QDir::fromNativeSeparators(path);
+        // The "apps" block also has numeric keys ("<appid>" "<size>"); skip those
+        if (!path.contains('/'))
+            continue;
+        if (QDir(path).exists() && !result.contains(path))
+            result << path;
+    }
+    return result;
+}
+
+QStringList GameFileLocator::steamLibraryFolders()
+{
+    const QString home = QDir::homePath();
+    QStringList steamRoots;
+#if defined(Q_OS_WIN)
+    steamRoots << "C:/Program Files (x86)/Steam" << "C:/Program Files/Steam";
+#elif defined(Q_OS_MACOS)
+    steamRoots << home + "/Library/Application Support/Steam";
+#else
+    steamRoots << home + "/.steam/steam"
+               << home + "/.local/share/Steam"
+               << home + "/.steam/root"
+               << home + "/.var/app/com.valvesoftware.Steam/.local/share/Steam";  // Flatpak
+#endif
+
+    QStringList libraries;
+    for (const QString& root : steamRoots) {
+        if (!QDir(root).exists())
+            continue;
+        const QString canonical = QFileInfo(root).canonicalFilePath();
+        if (!libraries.contains(canonical))
+            libraries << canonical;
+
+        const QString vdf = resolveCaseInsensitive(root, "steamapps/libraryfolders.vdf");
+        if (vdf.isEmpty())
+            continue;
+        for (const QString& lib : parseLibraryFoldersVdf(vdf)) {
+            const QString libCanonical = QFileInfo(lib).canonicalFilePath();
+            if (!libCanonical.isEmpty() && !libraries.contains(libCanonical))
+                libraries << libCanonical;
+        }
+    }
+    return libraries;
+}
+
+bool GameFileLocator::looksLikeFF7Root(const QString& path)
+{
+    if (!resolveCaseInsensitive(path, "ff7/workingdir/data").isEmpty())
+        return true;   // 2026 re-release
+    return !resolveCaseInsensitive(path, "data").isEmpty()
+        && (!resolveCaseInsensitive(path, "data/field/flevel.lgp").isEmpty()
+            || !resolveCaseInsensitive(path, "data/lang-en/kernel").isEmpty());
+}
+
+bool GameFileLocator::isSteamCloudPath(const QString& path)
+{
+    const QString absolute = QDir::cleanPath(QFileInfo(path).absoluteFilePath()) + '/';
+    if (absolute.contains("/Square Enix/FINAL FANTASY VII Steam/", Qt::CaseInsensitive))
+        return true;
+    static const QRegularExpression remote("/userdata/\\d+/\\d+/remote/",
+                                           QRegularExpression::CaseInsensitiveOption);
+    return remote.match(absolute).hasMatch();
+}
+
+QString GameFileLocator::writeTargetProblem(const QString& path)
+{
+    const QString absolute = QDir::cleanPath(QFileInfo(path).absoluteFilePath());
+    if (isSteamCloudPath(absolute))
+        return QString("%1 is synced by Steam Cloud, which can replace the randomized files with the "
+                       "cloud copy. Choose an output folder outside the save folder.").arg(absolute);
+
+    const QFileInfo info(absolute);
+    if (info.exists() && !info.isDir())
+        return QString("%1 is a file, not a folder. Choose another output folder.").arg(absolute);
+
+    // The folder may not exist yet: whoever creates it needs its parent
+    QString existing = absolute;
+    while (!QFileInfo(existing).exists()) {
+        const QString parent = QFileInfo(existing).path();
+        if (parent == existing) break;
+        existing = parent;
+    }
+    QTemporaryFile probe(QDir(existing).filePath(".goldsaucer_write_test_XXXXXX"));
+    if (!probe.open()) {
+        QString hint = "Choose a folder you can write to, such as one in your Documents";
+        if (existing.contains("Program Files", Qt::CaseInsensitive))
+            hint += " (folders under Program Files need administrator rights)";
+        else if (QFileInfo(existing).isDir() && !QFileInfo(existing).isWritable())
+            hint += ", or clear the folder's read-only attribute";
+        return QString("%1 is read-only (%2). %3.").arg(absolute, probe.errorString(), hint);
+    }
+    return QString();
+}
+
+bool GameFileLocator::looksLikePsxDisc(const QString& path)
+{
+    return !resolveCaseInsensitive(path, "SYSTEM.CNF").isEmpty()
+        && !resolveCaseInsensitive(path, "INIT/KERNEL.BIN").isEmpty()
+        && !resolveCaseInsensitive(path, "FIELD").isEmpty();
+}
+
+QString GameFileLocator::detectFF7Install()
+{
+    for (const QString& library : steamLibraryFolders()) {
+        // Native Steam installs: steamapps/common/FINAL FANTASY VII[ ...]
+        const QString common = resolveCaseInsensitive(library, "steamapps/common");
+        if (!common.isEmpty()) {
+            const QStringList games = QDir(common).entryList(QDir::Dirs | QDir::NoDotAndDotDot);
+            for (const QString& game : games) {
+                if (!game.startsWith("FINAL FANTASY VII", Qt::CaseInsensitive))
+                    continue;
+                const QString candidate = QDir(common).filePath(game);
+                if (looksLikeFF7Root(candidate)) {
+                    qDebug() << "GameFileLocator: detected FF7 install at" << candidate;
+                    return candidate;
+                }
+            }
+        }
+
+        // Proton prefix: a 1998 PC install copied into the game's Wine drive_c
+        const QString driveC = resolveCaseInsensitive(
+            library, QString("steamapps/compatdata/%1/pfx/drive_c").arg(FF7_STEAM_APP_ID));
+        if (driveC.isEmpty())
+            continue;
+        static const QStringList prefixCandidates = {
+            "Program Files (x86)/Square Soft, Inc/Final Fantasy VII",
+            "Program Files/Square Soft, Inc/Final Fantasy VII",
+        };
+        for (const QString& rel : prefixCandidates) {
+            const QString candidate = resolveCaseInsensitive(driveC, rel);
+            if (!candidate.isEmpty() && looksLikeFF7Root(candidate)) {
+                qDebug() << "GameFileLocator: detected FF7 install in Proton prefix at" << candidate;
+                return candidate;
+            }
+        }
+    }
+    return QString();
+}
@@ -0,0 +1,81 @@
+#pragma once
+
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+/**
+ * GameFileLocator
+ *
+ * Finds FF7 data files under an install root on any platform. The randomizers
+ * used to probe a handful of exact-case relative paths, which only works on
+ * Windows: Proton/Linux installs sit on case-sensitive filesystems where the
+ * same file may be KERNEL.BIN, Kernel.bin or kernel.bin depending on how the
+ * game was copied.
+ *
+ * Lookup order:
+ *   1. each relative candidate, resolved one path component at a time with a
+ *      case-insensitive directory match (so "data/lang-en/kernel/kernel.bin"
+ *      also finds "DATA/LANG-EN/KERNEL/KERNEL.BIN");
+ *   2. a recursive case-insensitive search for the bare file name under the
+ *      root, skipping `excludeDir` (the output folder, which defaults to a
+ *      subfolder of the install and would otherwise return our own output).
+ */
+class GameFileLocator
+{
+public:
+    // Resolve a '/'-separated relative path under root case-insensitively.
+    // Returns the real on-disk path, or an empty string if any component is
+    // missing. An exact-case hit is returned without listing directories.
+    static QString resolveCaseInsensitive(const QString& root, const QString& relPath);
+
+    // Try the candidates in order, then fall back to a recursive search for
+    // fileName (depth-limited). Returns an empty string if nothing is found.
+    static QString findFile(const QString& root, const QStringList& relCandidates,
+                            const QString& fileName,
+                            const QString& excludeDir = QString(),
+                            int maxDepth = 6);
+
+    // Convenience lookups for the three files every randomizer needs.
+    static QString findKernelBin(const QString& root, const QString& excludeDir = QString());
+    static QString findKernel2Bin(const QString& root, const QString& excludeDir = QString());
+    static QString findSceneBin(const QString& root, const QString& excludeDir = QString());
+    static QString findFlevelLgp(const QString& root, const QString& excludeDir = QString());
+
+    // The install files Randomizer::copyOriginalFiles() puts in every output
+    // folder before the passes run: scene.bin, the kernel files and
+    // flevel.lgp, each with its path relative to the output folder. Files
+    // that aren't found are left out.
+    struct OutputFile { QString source; QString target; };
+    static QVector<OutputFile> originalFiles(const QString& root, const QString& excludeDir = QString());
+
+    // Steam library roots on this machine: the default Steam install(s) for the
+    // platform plus every "path" listed in steamapps/libraryfolders.vdf.
+    static QStringList steamLibraryFolders();
+
+    // Best-guess FF7 install root across all Steam libraries (native Windows,
+    // Linux/macOS Steam, Flatpak Steam, and Proton compatdata prefixes).
+    // Returns an empty string if nothing that looks like FF7 is found.
+    static QString detectFF7Install();
+
+    // An extracted PlayStation disc (SYSTEM.CNF, FIELD/*.DAT, INIT/KERNEL.BIN)
+    // rather than a PC install. Only the PC layouts are supported, so this is
+    // used to say so instead of reporting missing PC files.
+    static bool looksLikePsxDisc(const QString& path);
+
+    // A folder Steam Cloud syncs: the PC save folder ("Square Enix/FINAL
+    // FANTASY VII Steam") or a Steam userdata ".../remote" folder. Steam can
+    // roll files written there back to the cloud copy on the next launch.
+    static bool isSteamCloudPath(const QString& path);
+
+    // Why generated files can't safely go to the folder at path, as a message
+    // that says what to do, or an empty string. Covers Steam Cloud folders, a
+    // file in the way and a folder (or, when it doesn't exist yet, its nearest
+    // existing parent) that can't be written to. Checked before a run starts
+    // so it fails up front rather than on its last write.
+    static QString writeTargetProblem(const QString& path);
+
+private:
+    static QStringList parseLibraryFoldersVdf(const QString& vdfPath);
+    static bool looksLikeFF7Root(const QString& path);
+};
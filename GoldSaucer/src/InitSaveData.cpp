@@ -0,0 +1,215 @@
+#include "InitSaveData.h"
+
+#include <cstring>
+
+InitSaveData::InitSaveData(QByteArray& data)
+    : m_data(data)
+{
+}
+
+bool InitSaveData::isValid() const
+{
+    return inRange(GIL_OFFSET, 4);
+}
+
+// --- characters ---------------------------------------------------------------
+
+int InitSaveData::characterOffset(int character) const
+{
+    if (character < 0 || character >= CHARACTER_COUNT) return -1;
+    const int offset = character * CHARACTER_RECORD_SIZE;
+    return inRange(offset, CHARACTER_RECORD_SIZE) ? offset : -1;
+}
+
+int InitSaveData::characterId(int character) const
+{
+    const int offset = characterOffset(character);
+    return offset < 0 ? -1 : static_cast<quint8>(m_data.at(offset + ID_OFFSET));
+}
+
+bool InitSaveData::isFlashbackRecord(int character) const
+{
+    const int id = characterId(character);
+    return id == YOUNG_CLOUD_ID || id == SEPHIROTH_ID;
+}
+
+int InitSaveData::weapon(int character) const
+{
+    const int offset = characterOffset(character);
+    return offset < 0 ? -1 : static_cast<quint8>(m_data.at(offset + WEAPON_OFFSET));
+}
+
+bool InitSaveData::setWeapon(int character, int weaponNumber)
+{
+    const int offset = characterOffset(character);
+    if (offset < 0 || weaponNumber < 0 || weaponNumber > 127) return false;
+    m_data[offset + WEAPON_OFFSET] = static_cast<char>(weaponNumber);
+    return true;
+}
+
+int InitSaveData::armor(int character) const
+{
+    const int offset = characterOffset(character);
+    return offset < 0 ? -1 : static_cast<quint8>(m_data.at(offset + ARMOR_OFFSET));
+}
+
+bool InitSaveData::setArmor(int character, int armorNumber)
+{
+    const int offset = characterOffset(character);
+    if (offset < 0 || armorNumber < 0 || armorNumber > 31) return false;
+    m_data[offset + ARMOR_OFFSET] = static_cast<char>(armorNumber);
+    return true;
+}
+
+int InitSaveData::accessory(int character) const
+{
+    const int offset = characterOffset(character);
+    return offset < 0 ? -1 : static_cast<quint8>(m_data.at(offset + ACCESSORY_OFFSET));
+}
+
+bool InitSaveData::setAccessory(int character, int accessoryNumber)
+{
+    const int offset = characterOffset(character);
+    if (offset < 0) return false;
+    if (accessoryNumber != NO_ACCESSORY && (accessoryNumber < 0 || accessoryNumber > 31)) return false;
+    m_data[offset + ACCESSORY_OFFSET] = static_cast<char>(accessoryNumber);
+    return true;
+}
+
+int InitSaveData::characterMateria(int character, int slot) const
+{
+    const int offset = characterOffset(character);
+    if (offset < 0 || slot < 0 || slot >= CHARACTER_MATERIA_SLOTS) return -1;
+    return static_cast<quint8>(m_data.at(offset + MATERIA_OFFSET + slot * 4));
+}
+
+bool InitSaveData::setCharacterMateria(int character, int slot, int materiaId, quint32 ap)
+{
+    const int offset = characterOffset(character);
+    if (offset < 0 || slot < 0 || slot >= CHARACTER_MATERIA_SLOTS) return false;
+    if (materiaId < 0 || materiaId >= NO_MATERIA || ap > 0xFFFFFF) return false;
+    writeU32(offset + MATERIA_OFFSET + slot * 4, static_cast<quint32>(materiaId) | (ap << 8));
+    return true;
+}
+
+bool InitSaveData::clearCharacterMateria(int character, int slot)
+{
+    const int offset = characterOffset(character);
+    if (offset < 0 || slot < 0 || slot >= CHARACTER_MATERIA_SLOTS) return false;
+    writeU32(offset + MATERIA_OFFSET + slot * 4, 0xFFFFFFFF);
+    return true;
+}
+
+// --- party --------------------------------------------------------------------
+
+int InitSaveData::partyMember(int position) const
+{
+    if (position < 0 || position >= PARTY_SIZE || !inRange(PARTY_OFFSET + position, 1)) return -1;
+    return static_cast<quint8>(m_data.at(PARTY_OFFSET + position));
+}
+
+bool InitSaveData::setPartyMember(int position, int character)
+{
+    if (position < 0 || position >= PARTY_SIZE || !inRange(PARTY_OFFSET + position, 1)) return false;
+    if (character != NO_PARTY_MEMBER && (character < 0 || character >= CHARACTER_COUNT)) return false;
+    m_data[PARTY_OFFSET + position] = static_cast<char>(character);
+    return true;
+}
+
+// --- inventory ----------------------------------------------------------------
+
+// Each slot is a u16: item id in bits 0-8, quantity in bits 9-15; 0xFFFF = empty
+
+int InitSaveData::inventoryItem(int slot) const
+{
+    if (slot < 0 || slot >= INVENTORY_SLOTS || !inRange(INVENTORY_OFFSET + slot * 2, 2)) return -1;
+    return readU16(INVENTORY_OFFSET + slot * 2) & 0x1FF;
+}
+
+int InitSaveData::inventoryQuantity(int slot) const
+{
+    if (inventoryItem(slot) < 0 || inventoryItem(slot) == NO_ITEM) return 0;
+    return readU16(INVENTORY_OFFSET + slot * 2) >> 9;
+}
+
+bool InitSaveData::setInventoryItem(int slot, int itemId, int quantity)
+{
+    if (slot < 0 || slot >= INVENTORY_SLOTS || !inRange(INVENTORY_OFFSET + slot * 2, 2)) return false;
+    if (itemId < 0 || itemId >= 320 || quantity < 1 || quantity > 127) return false;
+    writeU16(INVENTORY_OFFSET + slot * 2, static_cast<quint16>(itemId | (quantity << 9)));
+    return true;
+}
+
+bool InitSaveData::clearInventorySlot(int slot)
+{
+    if (slot < 0 || slot >= INVENTORY_SLOTS || !inRange(INVENTORY_OFFSET + slot * 2, 2)) return false;
+    writeU16(INVENTORY_OFFSET + slot * 2, 0xFFFF);
+    return true;
+}
+
+// --- materia stock ------------------------------------------------------------
+
+int InitSaveData::materiaStock(int slot) const
+{
+    if (slot < 0 || slot >= MATERIA_STOCK_SLOTS || !inRange(MATERIA_STOCK_OFFSET + slot * 4, 4)) return -1;
+    return static_cast<quint8>(m_data.at(MATERIA_STOCK_OFFSET + slot * 4));
+}
+
+bool InitSaveData::setMateriaStock(int slot, int materiaId, quint32 ap)
+{
+    if (slot < 0 || slot >= MATERIA_STOCK_SLOTS || !inRange(MATERIA_STOCK_OFFSET + slot * 4, 4)) return false;
+    if (materiaId < 0 || materiaId >= NO_MATERIA || ap > 0xFFFFFF) return false;
+    writeU32(MATERIA_STOCK_OFFSET + slot * 4, static_cast<quint32>(materiaId) | (ap << 8));
+    return true;
+}
+
+bool InitSaveData::clearMateriaStock(int slot)
+{
+    if (slot < 0 || slot >= MATERIA_STOCK_SLOTS || !inRange(MATERIA_STOCK_OFFSET + slot * 4, 4)) return false;
+    writeU32(MATERIA_STOCK_OFFSET + slot * 4, 0xFFFFFFFF);
+    return true;
+}
+
+// --- gil ----------------------------------------------------------------------
+
+quint32 InitSaveData::gil() const
+{
+    return inRange(GIL_OFFSET, 4) ? readU32(GIL_OFFSET) : 0;
+}
+
+void InitSaveData::setGil(quint32 gil)
+{
+    if (inRange(GIL_OFFSET, 4))
+        writeU32(GIL_OFFSET, gil);
+}
+
+// --- raw access ---------------------------------------------------------------
+
+bool InitSaveData::inRange(int offset, int size) const
+{
+    return offset >= 0 && offset + size <= m_data.size();
+}
+
+quint16 InitSaveData::readU16(int offset) const
+{
+    quint16 value = 0;
+    std::memcpy(&value, m_data.constData() + offset, 2);
+    return value;
+}
+
+quint32 InitSaveData::readU32(int offset) const
+{
+    quint32 value = 0;
+    std::memcpy(&value, m_data.constData() + offset, 4);
+    return value;
+}
+
+void InitSaveData::writeU16(int offset, quint16 value)
+{
+    std::memcpy(m_data.data() + offset, &value, 2);
+}
+
+void InitSaveData::writeU32(int offset, quint32 value)
+{
+    std::memcpy(m_data.data() + offset, &value, 4);
+}
@@ -0,0 +1,99 @@
+#pragma once
+
+#include <QByteArray>
+
+// InitSaveData — typed view over kernel.bin section 3, the initialization data
+// the game copies to the savemap on New Game (savemap 0x0054 onwards). It
+// edits the caller's buffer in place; every accessor is bounds-checked and
+// every setter rejects values the game can't hold, so passes that change the
+// starting state never poke raw offsets.
+//
+// Covered: the nine character records, the party, the item inventory, the
+// materia stock and gil. The game config (battle speed, ATB, ...) is not part
+// of the init data.
+class InitSaveData
+{
+public:
+    explicit InitSaveData(QByteArray& data);
+
+    // False when the buffer is too short to hold everything below
+    bool isValid() const;
+
+    // --- character records (Cloud, Barret, Tifa, Aerith, Red, Yuffie,
+    //     Cait Sith, Vincent, Cid) ---
+    static constexpr int CHARACTER_COUNT = 9;
+    static constexpr int CHARACTER_MATERIA_SLOTS = 16;  // 0-7 weapon, 8-15 armor
+    static constexpr quint8 NO_ACCESSORY = 0xFF;
+    static constexpr quint8 NO_MATERIA = 0xFF;
+
+    // Character id byte of a record. Slots 6 and 7 are shared: Cait Sith or
+    // Young Cloud, Vincent or Sephiroth, told apart by this id
+    static constexpr int YOUNG_CLOUD_ID = 9;
+    static constexpr int SEPHIROTH_ID = 10;
+    int characterId(int character) const;
+    // True for the Kalm flashback's Young Cloud and Sephiroth records
+    bool isFlashbackRecord(int character) const;
+
+    // Weapon number (item id - 0x80, 0-127)
+    int weapon(int character) const;
+    bool setWeapon(int character, int weaponNumber);
+    // Armor number (item id - 0x100, 0-31)
+    int armor(int character) const;
+    bool setArmor(int character, int armorNumber);
+    // Accessory number (item id - 0x120, 0-31) or NO_ACCESSORY
+    int accessory(int character) const;
+    bool setAccessory(int character, int accessoryNumber);
+
+    // Materia id in an equipment slot, NO_MATERIA when empty
+    int characterMateria(int character, int slot) const;
+    bool setCharacterMateria(int character, int slot, int materiaId, quint32 ap = 0);
+    bool clearCharacterMateria(int character, int slot);
+
+    // --- party: three character ids, 0xFF = empty ---
+    static constexpr int PARTY_SIZE = 3;
+    static constexpr quint8 NO_PARTY_MEMBER = 0xFF;
+    int partyMember(int position) const;
+    bool setPartyMember(int position, int character);
+
+    // --- item inventory: item id (0-319) and quantity (1-127) per slot ---
+    static constexpr int INVENTORY_SLOTS = 320;
+    static constexpr int NO_ITEM = 0x1FF;
+    int inventoryItem(int slot) const;        // NO_ITEM when empty
+    int inventoryQuantity(int slot) const;
+    bool setInventoryItem(int slot, int itemId, int quantity);
+    bool clearInventorySlot(int slot);
+
+    // --- materia stock (not equipped) ---
+    static constexpr int MATERIA_STOCK_SLOTS = 200;
+    int materiaStock(int slot) const;          // NO_MATERIA when empty
+    bool setMateriaStock(int slot, int materiaId, quint32 ap = 0);
+    bool clearMateriaStock(int slot);
+
+    quint32 gil() const;
+    void setGil(quint32 gil);
+
+    // Savemap offset the init data starts at; savemap offset = init offset + this
+    static constexpr int SAVEMAP_BASE = 0x0054;
+
+private:
+    // Offsets within the init data (savemap offset - SAVEMAP_BASE)
+    static constexpr int CHARACTER_RECORD_SIZE = 0x84;
+    static constexpr int ID_OFFSET = 0x00;
+    static constexpr int WEAPON_OFFSET = 0x1C;
+    static constexpr int ARMOR_OFFSET = 0x1D;
+    static constexpr int ACCESSORY_OFFSET = 0x1E;
+    static constexpr int MATERIA_OFFSET = 0x40;
+    static constexpr int PARTY_OFFSET = 0x04A4;          // savemap 0x04F8
+    static constexpr int INVENTORY_OFFSET = 0x04A8;      // savemap 0x04FC
+    static constexpr int MATERIA_STOCK_OFFSET = 0x0728;  // savemap 0x077C
+    static constexpr int GIL_OFFSET = 0x0B28;            // savemap 0x0B7C
+
+    int characterOffset(int character) const;
+    bool inRange(int offset, int size) const;
+    quint16 readU16(int offset) const;
+    quint32 readU32(int offset) const;
+    void writeU16(int offset, quint16 value);
+    void writeU32(int offset, quint32 value);
+
+    QByteArray& m_data;
+};
@@ -0,0 +1,39 @@
+#include "InputCache.h"
+#include "GameFileLocator.h"
+#include "MakouLgpManager.h"
+#include "SeedSummary.h"
+#include <QDebug>
+#include <QFile>
+
+bool InputCache::load(const QString& ff7Path, QString* error)
+{
+    const QString flevelPath = GameFileLocator::findFlevelLgp(ff7Path);
+    MakouLgpManager lgp;
+    if (flevelPath.isEmpty() || !lgp.open(flevelPath)) {
+        if (error) *error = QString("Cannot open flevel.lgp under %1").arg(ff7Path);
+        return false;
+    }
+
+    m_fileNames = lgp.fileList();
+    m_fields.clear();
+    m_fields.reserve(m_fileNames.size());
+    for (const QString& fileName : m_fileNames)
+        m_fields.insert(fileName, lgp.fileData(fileName));
+    lgp.close();
+
+    m_originalFiles.clear();
+    for (const GameFileLocator::OutputFile& file : GameFileLocator::originalFiles(ff7Path)) {
+        QFile source(file.source);
+        if (!source.open(QIODevice::ReadOnly)) {
+            if (error) *error = QString("Cannot read %1").arg(file.source);
+            return false;
+        }
+        m_originalFiles.insert(file.target, source.readAll());
+    }
+
+    m_inputHashes = SeedSummary::inputHashes(ff7Path);
+    m_flevelPath = flevelPath;
+    qDebug() << "InputCache: cached" << m_fields.size() << "flevel entries from" << flevelPath
+             << "and" << m_originalFiles.size() << "output files";
+    return true;
+}
@@ -0,0 +1,45 @@
+#pragma once
+
+#include <QString>
+#include <QStringList>
+#include <QByteArray>
+#include <QHash>
+#include <QJsonObject>
+#include <QMap>
+
+// InputCache — the vanilla game inputs, read once and kept in memory so many
+// seeds can be generated against them (see SeedGenerator). Holds every field
+// of the install's flevel.lgp as stored in the archive (still LZS-compressed),
+// the install files copied into each output folder (scene.bin, the kernel
+// files and the flevel.lgp archive itself), plus the input file hashes written
+// into each seed report.
+//
+// Randomizers given a cache (Randomizer::setInputCache) write the output's
+// starting files from it and take vanilla field data from it instead of
+// re-reading the install; the output's flevel.lgp is still opened for writing
+// the randomized copy.
+class InputCache
+{
+public:
+    bool load(const QString& ff7Path, QString* error = nullptr);
+    bool isLoaded() const { return !m_flevelPath.isEmpty(); }
+
+    QString flevelPath() const { return m_flevelPath; }
+    // Archive order, as MakouLgpManager::fileList() returns it
+    const QStringList& fileNames() const { return m_fileNames; }
+    // Raw (compressed) entry, or an empty array for an unknown name
+    QByteArray fieldData(const QString& fileName) const { return m_fields.value(fileName); }
+
+    // GameFileLocator::originalFiles() contents by output-relative path
+    const QMap<QString, QByteArray>& originalFiles() const { return m_originalFiles; }
+
+    // SeedSummary::inputHashes() of the install, computed at load
+    const QJsonObject& inputHashes() const { return m_inputHashes; }
+
+private:
+    QString m_flevelPath;
+    QStringList m_fileNames;
+    QHash<QString, QByteArray> m_fields;
+    QMap<QString, QByteArray> m_originalFiles;
+    QJsonObject m_inputHashes;
+};
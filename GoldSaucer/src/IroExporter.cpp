@@ -1,11 +1,13 @@
 #include "IroExporter.h"
 #include "Config.h"
 #include "MakouLgpManager.h"
+#include "GameFileLocator.h"
 
 #include <LZS>
 
 #include <QDir>
 #include <QFile>
+#include <QSaveFile>
 #include <QFileInfo>
 #include <QFileInfoList>
 #include <QDirIterator>
@@ -40,6 +42,7 @@
 //     The chunk file is the RAW decompressed section data — no compression and no
 //     4-byte section length prefix.
 //   - World-map script:  world_us.lgp/wm0.ev   (whole, uncompressed lgp entry)
+//   - World-map encounters: world_us.lgp/enc_w.bin (chocobo ratings), same way
 //   - Battle / kernel:    mirrored under their data-relative path (battle/, kernel/)
 //   - Shop (FFNx hext):   hext/** copied verbatim
 //   - mod.xml at the archive root.
@@ -67,14 +70,11 @@ IroExporter::IroExporter(const QString& ff7Path, const QString& outputPath)
 QString IroExporter::resolveOriginalFlevel() const
 {
     const QStringList candidates = {
-        m_ff7Path + "/ff7/workingdir/data/field/flevel.lgp",  // 2026 re-release
-        m_ff7Path + "/data/field/flevel.lgp",                 // classic Steam/1998
-        m_ff7Path + "/data/flevel/flevel.lgp",
+        "ff7/workingdir/data/field/flevel.lgp",  // 2026 re-release
+        "data/field/flevel.lgp",                 // classic Steam/1998
+        "data/flevel/flevel.lgp",
     };
-    for (const QString& c : candidates)
-        if (QFile::exists(c))
-            return c;
-    return QString();
+    return GameFileLocator::findFile(m_ff7Path, candidates, "flevel.lgp", m_outputPath);
 }
 
 bool IroExporter::stageBytes(const QString& modRelPath, const QByteArray& bytes)
@@ -141,6 +141,7 @@ int IroExporter::stageFields(QStringList& log)
 
 // world_us.lgp/wm0.ev — the crater-barrier patch lives in wm0.ev, stored
 // uncompressed in world_us.lgp (CraterBarrierPatcher edits it in place).
+// enc_w.bin carries the chocobo rating shuffle and is staged the same way.
 int IroExporter::stageWorldScript(QStringList& log)
 {
     const QString outWorld = m_outputPath + "/data/wm/world_us.lgp";
@@ -149,13 +150,18 @@ int IroExporter::stageWorldScript(QStringList& log)
     MakouLgpManager lgp;
     if (!lgp.open(outWorld))
         return 0;
+    int count = 0;
     QByteArray wm0 = lgp.fileData("wm0.ev");
-    if (wm0.isEmpty())
-        return 0;
-    if (!stageBytes("world_us.lgp/wm0.ev", wm0))
-        return 0;
-    log << "  IRO: staged world-map script override (world_us.lgp/wm0.ev)";
-    return 1;
+    if (!wm0.isEmpty() && stageBytes("world_us.lgp/wm0.ev", wm0)) {
+        log << "  IRO: staged world-map script override (world_us.lgp/wm0.ev)";
+        ++count;
+    }
+    QByteArray encounters = lgp.fileData("enc_w.bin");
+    if (!encounters.isEmpty() && stageBytes("world_us.lgp/enc_w.bin", encounters)) {
+        log << "  IRO: staged world-map encounter override (world_us.lgp/enc_w.bin)";
+        ++count;
+    }
+    return count;
 }
 
 // Copy a single output data file to its mod-relative override path.
@@ -255,8 +261,10 @@ bool IroExporter::packArchive(const QString& iroPath, int& fileCount, QStringLis
         return false;
     }
 
-    QFile out(iroPath);
-    if (!out.open(QIODevice::WriteOnly | QIODevice::Truncate)) {
+    // Written to a temp file and renamed on commit, so a failed export never
+    // leaves a truncated archive behind
+    QSaveFile out(iroPath);
+    if (!out.open(QIODevice::WriteOnly)) {
         log << "  IRO: could not create " + iroPath;
         return false;
     }
@@ -283,7 +291,7 @@ bool IroExporter::packArchive(const QString& iroPath, int& fileCount, QStringLis
         QFile in(e.abs);
         if (!in.open(QIODevice::ReadOnly)) {
             log << "  IRO: failed to read staged file " + e.abs;
-            out.close();
+            out.cancelWriting();
             return false;
         }
         out.write(in.readAll());
@@ -308,7 +316,10 @@ bool IroExporter::packArchive(const QString& iroPath, int& fileCount, QStringLis
     // Patch directoryOffset.
     out.seek(12);
     putI32(static_cast<qint32>(dirOffset));
-    out.close();
+    if (!out.commit()) {
+        log << "  IRO: could not finish " + iroPath;
+        return false;
+    }
     return true;
 }
 
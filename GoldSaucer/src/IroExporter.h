@@ -33,7 +33,7 @@ private:
     // Stage steps. Each returns the number of override files it added (0 = the
     // corresponding randomizer produced no output, which is fine).
     int  stageFields(QStringList& log);       // flevel.lgp/<field>.chunk.1
-    int  stageWorldScript(QStringList& log);  // world_us.lgp/wm0.ev
+    int  stageWorldScript(QStringList& log);  // world_us.lgp/wm0.ev, enc_w.bin
     int  stageDataFile(const QString& outRelPath,
                        const QString& modRelPath, QStringList& log);
     int  stageHext(QStringList& log);         // hext/** (verbatim)
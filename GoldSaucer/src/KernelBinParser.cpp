@@ -3,6 +3,7 @@
 #include "KernelBinValidator.h"
 #include "UserFeedback.h"
 #include <QFile>
+#include <QSaveFile>
 #include <QFileInfo>
 #include <QDir>
 #include <QDebug>
@@ -153,7 +154,7 @@ bool KernelBinParser::save(const QString& filePath)
         return false;
     }
     
-    QFile file(filePath);
+    QSaveFile file(filePath);
     if (!file.open(QIODevice::WriteOnly)) {
         m_lastError = "Could not open KERNEL.BIN for writing: " + filePath;
         qDebug() << m_lastError;
@@ -161,9 +162,7 @@ bool KernelBinParser::save(const QString& filePath)
     }
     
     qint64 bytesWritten = file.write(m_rawData);
-    file.close();
-    
-    if (bytesWritten != m_rawData.size()) {
+    if (bytesWritten != m_rawData.size() || !file.commit()) {
         m_lastError = "Could not write all data to KERNEL.BIN";
         qDebug() << m_lastError;
         return false;
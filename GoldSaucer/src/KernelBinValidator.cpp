@@ -5,6 +5,8 @@
 #include <QDateTime>
 #include <QDebug>
 #include <QFileInfo>
+#include <QStringList>
+#include <cstring>
 
 QString KernelBinValidator::s_lastError;
 
@@ -141,6 +143,71 @@ QByteArray KernelBinValidator::calculateChecksum(const QByteArray& data)
     return hash.result().toHex();
 }
 
+QString KernelBinValidator::kernelSectionName(int section)
+{
+    static const char* const NAMES[] = {
+        "command data", "attack data", "battle and growth data", "initial data", "item data",
+        "weapon data", "armor data", "accessory data", "materia data"
+    };
+    if (section >= 0 && section < int(sizeof(NAMES) / sizeof(NAMES[0])))
+        return QString("section %1 (%2)").arg(section).arg(NAMES[section]);
+    return QString("section %1").arg(section);
+}
+
+QStringList KernelBinValidator::checkRebuiltKernel(const QByteArray& vanilla, const QByteArray& rebuilt,
+                                                   int sectionCount)
+{
+    struct Header { quint16 compSize; quint16 decSize; quint16 type; };
+    auto readHeader = [](const QByteArray& data, int pos) {
+        Header h;
+        memcpy(&h.compSize, data.constData() + pos, 2);
+        memcpy(&h.decSize,  data.constData() + pos + 2, 2);
+        memcpy(&h.type,     data.constData() + pos + 4, 2);
+        return h;
+    };
+
+    QStringList errors;
+    int vpos = 0;
+    int rpos = 0;
+    for (int i = 0; i < sectionCount; ++i) {
+        const QString name = kernelSectionName(i);
+        if (vpos + KERNEL_SECTION_HEADER_SIZE > vanilla.size()) {
+            errors.append(QString("vanilla kernel.bin ends before %1").arg(name));
+            return errors;
+        }
+        if (rpos + KERNEL_SECTION_HEADER_SIZE > rebuilt.size()) {
+            errors.append(QString("%1: header missing, the rebuilt kernel.bin ends at %2 bytes")
+                              .arg(name).arg(rebuilt.size()));
+            return errors;
+        }
+        const Header v = readHeader(vanilla, vpos);
+        const Header r = readHeader(rebuilt, rpos);
+        if (r.compSize == 0)
+            errors.append(QString("%1: compressed data is empty").arg(name));
+        if (rpos + KERNEL_SECTION_HEADER_SIZE + r.compSize > rebuilt.size()) {
+            errors.append(QString("%1: %2 compressed bytes run past the end of the file")
+                              .arg(name).arg(r.compSize));
+            return errors;
+        }
+        if (r.decSize != v.decSize)
+            errors.append(QString("%1: decompresses to %2 bytes, the game expects %3")
+                              .arg(name).arg(r.decSize).arg(v.decSize));
+        if (r.type != v.type)
+            errors.append(QString("%1: file type %2, vanilla has %3")
+                              .arg(name).arg(r.type).arg(v.type));
+        vpos += KERNEL_SECTION_HEADER_SIZE + v.compSize;
+        rpos += KERNEL_SECTION_HEADER_SIZE + r.compSize;
+    }
+
+    // Everything past the walked sections is copied as is; a difference means
+    // a size field is off and the game would find the later sections elsewhere
+    if (rebuilt.mid(rpos) != vanilla.mid(vpos))
+        errors.append(QString("data after %1 does not match vanilla (%2 bytes, vanilla %3)")
+                          .arg(kernelSectionName(sectionCount - 1))
+                          .arg(rebuilt.size() - rpos).arg(vanilla.size() - vpos));
+    return errors;
+}
+
 QString KernelBinValidator::createBackup(const QString& filePath)
 {
     QFileInfo fileInfo(filePath);
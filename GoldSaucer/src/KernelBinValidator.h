@@ -43,6 +43,21 @@ public:
     
     static QString getLastError();
     
+    // Checks a kernel.bin rebuilt from `vanilla` (the PC layout: sections of
+    // a 6-byte header - u16 compressed size, u16 decompressed size, u16 file
+    // type - followed by gzip data, stored back to back). The first
+    // sectionCount sections are walked in both files; the game loads each
+    // one into a fixed slot, so its decompressed size and file type must
+    // stay as they were and the walk must end on the untouched remainder.
+    // Returns one message per problem, naming the section; empty when the
+    // rebuilt file is fine.
+    static QStringList checkRebuiltKernel(const QByteArray& vanilla, const QByteArray& rebuilt,
+                                          int sectionCount);
+    static QString kernelSectionName(int section);   // "section 3 (initial data)"
+    
+    static const int KERNEL_SECTION_HEADER_SIZE = 6;
+    static const int MAX_KERNEL_SECTION_SIZE = 0xFFFF;   // u16 size fields
+    
 private:
     static QString s_lastError;
     
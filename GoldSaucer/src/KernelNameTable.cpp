@@ -0,0 +1,125 @@
+#include "KernelNameTable.h"
+
+#include <QFile>
+#include <QDebug>
+#include <LZS>
+#include <ff7tk/data/FF7Text.h>
+
+static quint32 readU32(const QByteArray& data, int offset)
+{
+    const uchar* p = reinterpret_cast<const uchar*>(data.constData()) + offset;
+    return quint32(p[0]) | (quint32(p[1]) << 8) | (quint32(p[2]) << 16) | (quint32(p[3]) << 24);
+}
+
+static quint16 readU16(const QByteArray& data, int offset)
+{
+    const uchar* p = reinterpret_cast<const uchar*>(data.constData()) + offset;
+    return quint16(p[0] | (p[1] << 8));
+}
+
+bool KernelNameTable::load(const QString& kernel2Path)
+{
+    m_loaded = false;
+    m_sections.clear();
+
+    QFile file(kernel2Path);
+    if (!file.open(QIODevice::ReadOnly)) {
+        m_lastError = QString("Cannot open %1").arg(kernel2Path);
+        return false;
+    }
+    const QByteArray raw = file.readAll();
+    file.close();
+
+    QVector<QByteArray> sections = splitSections(raw);
+    if (sections.size() < SectionCount) {
+        // Not a plain section list - try the LZS-wrapped layout
+        const QByteArray decompressed = LZS::decompressAllWithHeader(raw);
+        sections = splitSections(decompressed);
+    }
+    if (sections.size() < SectionCount) {
+        m_lastError = QString("%1 is not a recognised kernel2.bin (%2 sections)")
+                          .arg(kernel2Path).arg(sections.size());
+        return false;
+    }
+
+    for (const QByteArray& section : sections)
+        m_sections.append(parseTextSection(section));
+
+    m_loaded = true;
+    m_lastError.clear();
+    qDebug() << "KernelNameTable: loaded" << m_sections[SectionItemNames].size() << "item names from"
+             << kernel2Path;
+    return true;
+}
+
+QVector<QByteArray> KernelNameTable::splitSections(const QByteArray& data)
+{
+    QVector<QByteArray> sections;
+    int pos = 0;
+    while (pos + 4 <= data.size()) {
+        const quint32 length = readU32(data, pos);
+        pos += 4;
+        if (length > quint32(data.size() - pos))
+            return {};   // length runs past the end: not this layout
+        sections.append(data.mid(pos, int(length)));
+        pos += int(length);
+    }
+    if (pos != data.size())
+        return {};
+    return sections;
+}
+
+QStringList KernelNameTable::parseTextSection(const QByteArray& section)
+{
+    // Text sections start with a u16 offset table; the first offset doubles as
+    // the table size. Each string is FF7-encoded and terminated by 0xFF.
+    QStringList strings;
+    if (section.size() < 2)
+        return strings;
+
+    const int count = readU16(section, 0) / 2;
+    if (count * 2 > section.size())
+        return strings;
+
+    for (int i = 0; i < count; ++i) {
+        const int offset = readU16(section, i * 2);
+        if (offset >= section.size()) {
+            strings.append(QString());
+            continue;
+        }
+        int end = section.indexOf(char(0xFF), offset);
+        if (end < 0)
+            end = section.size();
+        strings.append(FF7Text::toPC(section.mid(offset, end - offset)).trimmed());
+    }
+    return strings;
+}
+
+QString KernelNameTable::lookup(int section, int index) const
+{
+    if (!m_loaded || section >= m_sections.size())
+        return QString();
+    const QStringList& names = m_sections[section];
+    if (index < 0 || index >= names.size())
+        return QString();
+    return names[index];
+}
+
+QString KernelNameTable::itemName(quint16 itemId) const
+{
+    if (itemId < 128) return lookup(SectionItemNames, itemId);
+    if (itemId < 256) return lookup(SectionWeaponNames, itemId - 128);
+    if (itemId < 288) return lookup(SectionArmorNames, itemId - 256);
+    if (itemId < 320) return lookup(SectionAccessoryNames, itemId - 288);
+    return QString();
+}
+
+QString KernelNameTable::materiaName(quint8 materiaId) const
+{
+    return lookup(SectionMateriaNames, materiaId);
+}
+
+QString KernelNameTable::keyItemName(quint16 keyItemId) const
+{
+    return lookup(SectionKeyItemNames, keyItemId);
+}
@@ -0,0 +1,56 @@
+#pragma once
+
+#include <QString>
+#include <QStringList>
+#include <QVector>
+#include <QByteArray>
+
+/**
+ * KernelNameTable
+ *
+ * Item and materia names read from the install's own kernel2.bin, so spoiler
+ * logs and the "Received X!" texts we write into fields use the same names the
+ * game shows. ff7tk's FF7Item table (and our materia table) is English-only;
+ * French/German/Spanish installs and renamed-item mods would otherwise get
+ * English names in otherwise localized dialogs.
+ *
+ * kernel2.bin holds KERNEL.BIN text sections 10-27 back to back, each with a
+ * 4-byte little-endian length prefix. Some distributions ship the whole file
+ * LZS-compressed with a 4-byte header; both layouts are accepted.
+ *
+ * Lookups return an empty string when the table isn't loaded or the slot is
+ * empty, so callers can fall back to the built-in English names.
+ */
+class KernelNameTable
+{
+public:
+    bool load(const QString& kernel2Path);
+    bool isLoaded() const { return m_loaded; }
+    QString lastError() const { return m_lastError; }
+
+    // Unified item ID (0-127 items, 128-255 weapons, 256-287 armor,
+    // 288-319 accessories), as used by STITM and the pickup pools.
+    QString itemName(quint16 itemId) const;
+    QString materiaName(quint8 materiaId) const;
+    QString keyItemName(quint16 keyItemId) const;
+
+private:
+    // kernel2.bin section indices (KERNEL.BIN section N is kernel2 section N-10)
+    enum Section {
+        SectionItemNames      = 10,
+        SectionWeaponNames    = 11,
+        SectionArmorNames     = 12,
+        SectionAccessoryNames = 13,
+        SectionMateriaNames   = 14,
+        SectionKeyItemNames   = 15,
+        SectionCount          = 18
+    };
+
+    static QVector<QByteArray> splitSections(const QByteArray& data);
+    static QStringList parseTextSection(const QByteArray& section);
+    QString lookup(int section, int index) const;
+
+    QVector<QStringList> m_sections;
+    bool m_loaded = false;
+    QString m_lastError;
+};
@@ -0,0 +1,255 @@
+#include "LogicOverrides.h"
+
+#include <QCoreApplication>
+#include <QDir>
+#include <QFile>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QJsonObject>
+#include <QDebug>
+
+namespace {
+QRegularExpression wildcard(const QString& pattern)
+{
+    return QRegularExpression(QRegularExpression::wildcardToRegularExpression(pattern.trimmed()),
+                              QRegularExpression::CaseInsensitiveOption);
+}
+
+// Cuts a # comment, leaving any inside strings
+QString stripComment(const QString& line)
+{
+    QChar quote;
+    for (int i = 0; i < line.size(); ++i) {
+        const QChar c = line[i];
+        if (!quote.isNull()) {
+            if (c == '\\' && quote == '"') ++i;
+            else if (c == quote) quote = QChar();
+        } else if (c == '"' || c == '\'') {
+            quote = c;
+        } else if (c == '#') {
+            return line.left(i);
+        }
+    }
+    return line;
+}
+
+// One TOML string ("basic" with \" \\ \n \t escapes, or 'literal') at pos
+bool parseString(const QString& text, int& pos, QString& out)
+{
+    const QChar quote = text[pos++];
+    out.clear();
+    while (pos < text.size()) {
+        const QChar c = text[pos++];
+        if (c == quote) return true;
+        if (c == '\\' && quote == '"' && pos < text.size()) {
+            const QChar e = text[pos++];
+            out += e == 'n' ? QChar('\n') : e == 't' ? QChar('\t') : e;
+        } else {
+            out += c;
+        }
+    }
+    return false;
+}
+
+// A string or an array of strings; the rule file needs nothing else
+bool parseValue(const QString& text, QJsonValue& value)
+{
+    int pos = 0;
+    auto skipSpace = [&]() { while (pos < text.size() && text[pos].isSpace()) ++pos; };
+    skipSpace();
+    if (pos >= text.size()) return false;
+    QString str;
+    if (text[pos] == '"' || text[pos] == '\'') {
+        if (!parseString(text, pos, str)) return false;
+        value = str;
+    } else if (text[pos] == '[') {
+        ++pos;
+        QJsonArray array;
+        for (;;) {
+            skipSpace();
+            if (pos >= text.size()) return false;
+            if (text[pos] == ']') { ++pos; break; }
+            if ((text[pos] != '"' && text[pos] != '\'') || !parseString(text, pos, str)) return false;
+            array.append(str);
+            skipSpace();
+            if (pos < text.size() && text[pos] == ',') ++pos;
+            else if (pos >= text.size() || text[pos] != ']') return false;
+        }
+        value = array;
+    } else {
+        return false;
+    }
+    skipSpace();
+    return pos == text.size();
+}
+} // namespace
+
+LogicOverrides& LogicOverrides::instance()
+{
+    static LogicOverrides overrides;
+    return overrides;
+}
+
+QString LogicOverrides::tomlPath(const QString& inputDir)
+{
+    return QDir(inputDir).filePath("logic_overrides.toml");
+}
+
+QString LogicOverrides::overridePath()
+{
+    return QCoreApplication::applicationDirPath() + "/logic_overrides.json";
+}
+
+void LogicOverrides::reload(const QString& inputDir)
+{
+    m_rules.clear();
+    m_sources.clear();
+    m_warnings.clear();
+
+    QJsonObject root;
+    if (!inputDir.isEmpty() && QFile::exists(tomlPath(inputDir)) && readToml(tomlPath(inputDir), root))
+        addRules(root, tomlPath(inputDir));
+    if (QFile::exists(overridePath()) && readJson(overridePath(), root))
+        addRules(root, overridePath());
+}
+
+bool LogicOverrides::readToml(const QString& path, QJsonObject& root)
+{
+    QFile file(path);
+    if (!file.open(QIODevice::ReadOnly | QIODevice::Text)) {
+        m_warnings << QString("Cannot open %1").arg(path);
+        return false;
+    }
+    const QStringList lines = QString::fromUtf8(file.readAll()).split('\n');
+
+    QJsonArray rules;
+    QJsonObject rule;
+    bool inRule = false;
+    for (int n = 0; n < lines.size(); ++n) {
+        QString line = stripComment(lines[n]).trimmed();
+        if (line.isEmpty()) continue;
+        const int lineNumber = n + 1;
+        if (line == "[[rules]]") {
+            if (inRule) rules.append(rule);
+            rule = QJsonObject();
+            inRule = true;
+            continue;
+        }
+        const int eq = line.indexOf('=');
+        if (!inRule || eq <= 0) {
+            m_warnings << QString("%1:%2: expected [[rules]] or key = value").arg(path).arg(lineNumber);
+            return false;
+        }
+        const QString key = line.left(eq).trimmed();
+        QString valueText = line.mid(eq + 1).trimmed();
+        // An array may run over several lines
+        while (valueText.startsWith('[') && !valueText.endsWith(']') && n + 1 < lines.size())
+            valueText += ' ' + stripComment(lines[++n]).trimmed();
+        QJsonValue value;
+        if (!parseValue(valueText, value)) {
+            m_warnings << QString("%1:%2: \"%3\" needs a string or an array of strings")
+                              .arg(path).arg(lineNumber).arg(key);
+            return false;
+        }
+        rule[key] = value;
+    }
+    if (inRule) rules.append(rule);
+    root = QJsonObject();
+    root["rules"] = rules;
+    return true;
+}
+
+bool LogicOverrides::readJson(const QString& path, QJsonObject& root)
+{
+    QFile file(path);
+    if (!file.open(QIODevice::ReadOnly)) {
+        m_warnings << QString("Cannot open %1").arg(path);
+        return false;
+    }
+    QJsonParseError parseError;
+    const QJsonDocument doc = QJsonDocument::fromJson(file.readAll(), &parseError);
+    if (parseError.error != QJsonParseError::NoError || !doc.isObject()) {
+        m_warnings << QString("%1: %2").arg(path, parseError.errorString());
+        return false;
+    }
+    root = doc.object();
+    return true;
+}
+
+void LogicOverrides::addRules(const QJsonObject& root, const QString& path)
+{
+    const int before = m_rules.size();
+    const QJsonArray rules = root["rules"].toArray();
+    for (int i = 0; i < rules.size(); ++i)
+        addRule(rules[i].toObject(), path, i);
+    m_sources << path;
+    qDebug() << "LogicOverrides: applied" << path << "-" << m_rules.size() - before << "rules";
+}
+
+void LogicOverrides::addRule(const QJsonObject& rule, const QString& path, int index)
+{
+    Rule r;
+    r.item = rule["item"].toString().trimmed();
+    if (r.item.isEmpty()) {
+        m_warnings << QString("%1: rule %2: no \"item\", ignored").arg(path).arg(index + 1);
+        return;
+    }
+    r.itemPattern = wildcard(r.item);
+    for (const QJsonValue& v : rule["forbid"].toArray()) {
+        if (v.toString().trimmed().isEmpty()) continue;
+        r.forbid << v.toString().trimmed();
+        r.forbidPatterns.append(wildcard(v.toString()));
+    }
+    for (const QJsonValue& v : rule["allow"].toArray()) {
+        if (v.toString().trimmed().isEmpty()) continue;
+        r.allow << v.toString().trimmed();
+        r.allowPatterns.append(wildcard(v.toString()));
+    }
+    if (r.forbid.isEmpty() && r.allow.isEmpty()) {
+        m_warnings << QString("%1: rule %2 (%3): no \"forbid\" or \"allow\" fields, ignored")
+                          .arg(path).arg(index + 1).arg(r.item);
+        return;
+    }
+    m_rules.append(r);
+}
+
+bool LogicOverrides::anyMatch(const QVector<QRegularExpression>& patterns, const QString& text)
+{
+    for (const QRegularExpression& pattern : patterns) {
+        if (pattern.match(text).hasMatch()) return true;
+    }
+    return false;
+}
+
+bool LogicOverrides::forbids(const QString& keyItemName, const QString& fieldName) const
+{
+    for (const Rule& r : m_rules) {
+        if (r.itemPattern.match(keyItemName).hasMatch() && anyMatch(r.forbidPatterns, fieldName))
+            return true;
+    }
+    return false;
+}
+
+bool LogicOverrides::allows(const QString& keyItemName, const QString& fieldName) const
+{
+    if (forbids(keyItemName, fieldName))
+        return false;
+    for (const Rule& r : m_rules) {
+        if (r.itemPattern.match(keyItemName).hasMatch() && anyMatch(r.allowPatterns, fieldName))
+            return true;
+    }
+    return false;
+}
+
+QJsonArray LogicOverrides::toJson() const
+{
+    QJsonArray rules;
+    for (const Rule& r : m_rules) {
+        QJsonObject obj;
+        obj["item"] = r.item;
+        if (!r.forbid.isEmpty()) obj["forbid"] = QJsonArray::fromStringList(r.forbid);
+        if (!r.allow.isEmpty())  obj["allow"]  = QJsonArray::fromStringList(r.allow);
+        rules.append(obj);
+    }
+    return rules;
+}
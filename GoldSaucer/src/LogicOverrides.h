@@ -0,0 +1,87 @@
+#pragma once
+
+#include <QString>
+#include <QStringList>
+#include <QVector>
+#include <QRegularExpression>
+
+class QJsonArray;
+class QJsonObject;
+
+/**
+ * LogicOverrides
+ *
+ * Extra key item placement rules, merged with the built-in logic when key
+ * items are shuffled: a logic_overrides.toml next to the input (the FF7
+ * install folder) and a logic_overrides.json next to the executable, both
+ * read when present. Each rule names key items and field patterns
+ * (wildcards, case ignored):
+ *
+ *   [[rules]]                          { "rules": [
+ *   item = "Lunar Harp"                    { "item": "Lunar Harp",
+ *   forbid = ["gldst*", "ghotel*"]           "forbid": ["gldst*", "ghotel*"] },
+ *                                          { "item": "Keycard 6*",
+ *   [[rules]]                                "allow":  ["elm*"] }
+ *   item = "Keycard 6*"                ] }
+ *   allow = ["elm*"]
+ *
+ *   "forbid"  the items are never placed in those fields
+ *   "allow"   the items may go to those fields outside their usual sphere
+ *             range and game-moment window; field access still applies.
+ *             Only relaxes pickups that are already key item candidates:
+ *             a field with no usable pickup gains no location from it.
+ *
+ * The TOML reader takes only what the rules need: [[rules]] tables of
+ * strings and string arrays, with # comments. A field both forbidden and
+ * allowed for an item is forbidden. The rules in effect are echoed in
+ * seed_report.json as "logicOverrides".
+ */
+class LogicOverrides
+{
+public:
+    static LogicOverrides& instance();
+
+    bool forbids(const QString& keyItemName, const QString& fieldName) const;
+    bool allows(const QString& keyItemName, const QString& fieldName) const;
+    bool isEmpty() const { return m_rules.isEmpty(); }
+    int ruleCount() const { return m_rules.size(); }
+
+    // Paths of the user files (may not exist)
+    static QString tomlPath(const QString& inputDir);
+    static QString overridePath();
+    // Files the rules were read from by the last reload()
+    QStringList sources() const { return m_sources; }
+    // Problems found while reading the files (shown in the pickup log)
+    QStringList warnings() const { return m_warnings; }
+    // The rules as read, for the seed report
+    QJsonArray toJson() const;
+
+    // Re-read the files; done at the start of every field pass so edits
+    // apply to the next generation. An empty inputDir skips the TOML file.
+    void reload(const QString& inputDir = QString());
+
+private:
+    LogicOverrides() { reload(); }
+    ~LogicOverrides() = default;
+
+    LogicOverrides(const LogicOverrides&) = delete;
+    LogicOverrides& operator=(const LogicOverrides&) = delete;
+
+    struct Rule {
+        QString item;                       // as written, for the report
+        QRegularExpression itemPattern;
+        QStringList forbid;
+        QStringList allow;
+        QVector<QRegularExpression> forbidPatterns;
+        QVector<QRegularExpression> allowPatterns;
+    };
+    bool readToml(const QString& path, QJsonObject& root);
+    bool readJson(const QString& path, QJsonObject& root);
+    void addRules(const QJsonObject& root, const QString& path);
+    void addRule(const QJsonObject& rule, const QString& path, int index);
+    static bool anyMatch(const QVector<QRegularExpression>& patterns, const QString& text);
+
+    QVector<Rule> m_rules;
+    QStringList   m_sources;
+    QStringList   m_warnings;
+};
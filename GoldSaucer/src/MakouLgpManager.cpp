@@ -200,28 +200,36 @@ bool MakouLgpManager::save(const QString &outputPath)
             // ff7tk's Lgp::pack() crashes (access violation in ff7tkFormats.dll)
             // when the destination archive already exists and cannot be
             // replaced - it writes a .temp file then faults during the
-            // rename/replace. Remove any existing destination and stale .temp
-            // BEFORE packing, and abort cleanly if the destination is locked
-            // (commonly by 7th Heaven / a running FF7 or mod tool).
-            if (QFile::exists(outputPath)) {
-                if (!QFile::remove(outputPath)) {
-                    setError(QString(
-                        "Cannot overwrite '%1' - the file is locked by another "
-                        "process. Close 7th Heaven, the game, and any FF7 mod "
-                        "tools, then try again.").arg(outputPath));
-                    return false;
-                }
-            }
-            const QString tempPath = outputPath + ".temp";
-            if (QFile::exists(tempPath)) {
-                QFile::remove(tempPath);
+            // rename/replace. So pack to a fresh staging name (clearing stale
+            // ones first) and only swap it in once it is complete: a crash or
+            // failed pack never leaves a half-written archive at outputPath.
+            const QString stagingPath = outputPath + ".partial";
+            for (const QString& stale : { stagingPath, stagingPath + ".temp" }) {
+                if (QFile::exists(stale))
+                    QFile::remove(stale);
             }
 
             // Use ff7tk's pack method like Makou does
-            if (!_lgp.pack(outputPath, nullptr)) {
+            if (!_lgp.pack(stagingPath, nullptr)) {
+                QFile::remove(stagingPath);
                 setError(QString("Failed to pack LGP to: %1").arg(outputPath));
                 return false;
             }
+
+            // Abort cleanly if the destination is locked (commonly by
+            // 7th Heaven / a running FF7 or mod tool)
+            if (QFile::exists(outputPath) && !QFile::remove(outputPath)) {
+                QFile::remove(stagingPath);
+                setError(QString(
+                    "Cannot overwrite '%1' - the file is locked by another "
+                    "process. Close 7th Heaven, the game, and any FF7 mod "
+                    "tools, then try again.").arg(outputPath));
+                return false;
+            }
+            if (!QFile::rename(stagingPath, outputPath)) {
+                setError(QString("Failed to move packed LGP into place: %1").arg(outputPath));
+                return false;
+            }
             
             qDebug() << "MakouLgpManager: Successfully packed to different path:" << outputPath;
             return true;
@@ -4,9 +4,33 @@
 #include "FieldPickupRandomizer_ff7tk.h"
 #include "StartingEquipmentRandomizer.h"
 #include "CraterBarrierPatcher.h"
+#include "BossList.h"
+#include "GameFileLocator.h"
+#include "InputCache.h"
+#include "SeedSummary.h"
 #include <QFile>
 #include <QDir>
+#include <QFileInfo>
 #include <QDebug>
+#include <QTextStream>
+#include <QElapsedTimer>
+#include <QJsonArray>
+
+namespace {
+// Records the wall time of one pass when it goes out of scope, whichever
+// way the pass returns
+class PhaseTimer
+{
+public:
+    PhaseTimer(Randomizer& randomizer, const char* name)
+        : m_randomizer(randomizer), m_name(name) { m_timer.start(); }
+    ~PhaseTimer() { m_randomizer.recordPhase(QString::fromLatin1(m_name), m_timer.elapsed()); }
+private:
+    Randomizer& m_randomizer;
+    const char* m_name;
+    QElapsedTimer m_timer;
+};
+} // namespace
 
 QString Randomizer::resolveFF7Root(const QString& path)
 {
@@ -27,51 +51,124 @@ Randomizer::Randomizer(const QString& ff7Path, const Config& config)
     , m_shopRandomizer(nullptr)
     , m_fieldPickupRandomizer(nullptr)
     , m_startingEquipmentRandomizer(nullptr)
+    , m_armorRandomizer(nullptr)
     , m_craterBarrierPatcher(nullptr)
+    , m_economyBalancer(nullptr)
 {
     initializeRandomizers();
 }
 
 Randomizer::~Randomizer()
 {
+    // A folder that pre-existed (Overwrite) is left as is: the old files
+    // are already partly replaced and can't be restored
+    if (m_createdOutputFolder && !m_outputFinished) {
+        qDebug() << "Run did not finish - removing partial output" << getOutputPath();
+        QDir(getOutputPath()).removeRecursively();
+    }
     delete m_enemyRandomizer;
     delete m_shopRandomizer;
     delete m_fieldPickupRandomizer;
     delete m_startingEquipmentRandomizer;
+    delete m_armorRandomizer;
     delete m_craterBarrierPatcher;
+    delete m_economyBalancer;
+}
+
+quint32 Randomizer::streamSeed(const char* stream) const
+{
+    // FNV-1a of the name rather than qHash, so seeds don't move between Qt versions
+    quint32 name = 2166136261u;
+    for (const char* p = stream; *p; ++p)
+        name = (name ^ static_cast<quint8>(*p)) * 16777619u;
+    std::seed_seq seq{ static_cast<quint32>(m_config.getSeed()), name, m_rerollSalt };
+    quint32 seed = 0;
+    seq.generate(&seed, &seed + 1);
+    return seed;
+}
+
+void Randomizer::setRerollSalt(quint32 salt)
+{
+    m_rerollSalt = salt;
+    if (salt != 0)
+        m_rng.seed(streamSeed("shared"));
 }
 
 void Randomizer::initializeRandomizers()
 {
+    loadInstallNames();
+    learnBossScenes();
     m_enemyRandomizer = new EnemyRandomizer(this);
     m_shopRandomizer = new ShopRandomizer(this);
     m_fieldPickupRandomizer = new FieldPickupRandomizer_ff7tk(this);
     m_startingEquipmentRandomizer = new StartingEquipmentRandomizer(this);
+    m_armorRandomizer = new ArmorRandomizer(this);
     m_craterBarrierPatcher = new CraterBarrierPatcher(m_ff7Path, getOutputPath());
+    m_economyBalancer = new EconomyBalancer(this);
+}
+
+void Randomizer::loadInstallNames()
+{
+    if (!m_config.getUseInstallNames())
+        return;
+
+    // Read the untouched install copy, never our own output folder
+    const QString kernel2Path = GameFileLocator::findKernel2Bin(m_ff7Path, getOutputPath());
+    if (kernel2Path.isEmpty()) {
+        qDebug() << "kernel2.bin not found; using built-in item names";
+        return;
+    }
+    if (!m_installNames.load(kernel2Path))
+        qDebug() << "Warning:" << m_installNames.lastError() << "- using built-in item names";
+}
+
+void Randomizer::learnBossScenes()
+{
+    // Scene indices come from the untouched install, so a boss list that
+    // only names a boss still covers its scene (field tables, scripted fights)
+    const QString scenePath = GameFileLocator::findSceneBin(m_ff7Path, getOutputPath());
+    if (scenePath.isEmpty()) {
+        qDebug() << "scene.bin not found; boss scenes come from boss_list.json only";
+        return;
+    }
+    BossList::instance().learnScenes(EnemyRandomizer::sceneEnemyNames(scenePath));
+}
+
+const EconomyBalancer& Randomizer::economy()
+{
+    m_economyBalancer->analyze();
+    return *m_economyBalancer;
 }
 
 bool Randomizer::validateFF7Installation()
 {
-    QDir ff7Dir(m_ff7Path);
+    // Case-insensitive so Proton/Linux installs (DATA/, KERNEL.BIN, ...) pass too
+    const QString outputPath = getOutputPath();
+    
+    // PSX field DATs, KERNEL.BIN and disc images have their own layouts
+    if (GameFileLocator::looksLikePsxDisc(m_ff7Path)) {
+        qDebug() << "Error:" << m_ff7Path << "is a PlayStation disc; only the PC release is supported";
+        return false;
+    }
     
     // Check for essential directories and files
-    if (!ff7Dir.exists("data")) {
+    if (GameFileLocator::resolveCaseInsensitive(m_ff7Path, "data").isEmpty()) {
         qDebug() << "Error: data directory not found in FF7 installation";
         return false;
     }
     
-    if (!ff7Dir.exists("data/lang-en/battle")) {
-        qDebug() << "Error: data/lang-en/battle directory not found";
+    if (GameFileLocator::findSceneBin(m_ff7Path, outputPath).isEmpty()) {
+        qDebug() << "Error: scene.bin not found under" << m_ff7Path;
         return false;
     }
     
-    if (!ff7Dir.exists("data/lang-en/kernel") && !ff7Dir.exists("data/lang-en/kernel.bin")) {
-        qDebug() << "Error: data/lang-en/kernel directory or file not found";
+    if (GameFileLocator::findKernelBin(m_ff7Path, outputPath).isEmpty()) {
+        qDebug() << "Error: kernel.bin not found under" << m_ff7Path;
         return false;
     }
     
-    if (!ff7Dir.exists("data/field") && !ff7Dir.exists("data/flevel")) {
-        qDebug() << "Error: data/field or data/flevel directory not found";
+    if (GameFileLocator::findFlevelLgp(m_ff7Path, outputPath).isEmpty()) {
+        qDebug() << "Error: flevel.lgp not found under" << m_ff7Path;
         return false;
     }
     
@@ -98,6 +195,7 @@ bool Randomizer::createBackup(const QString& filePath)
 
 bool Randomizer::randomizeEnemyStats()
 {
+    PhaseTimer timer(*this, "enemy stats");
     if (!validateFF7Installation()) {
         return false;
     }
@@ -112,6 +210,7 @@ bool Randomizer::randomizeEnemyStats()
 
 bool Randomizer::randomizeEnemyEncounters()
 {
+    PhaseTimer timer(*this, "enemy encounters");
     if (!validateFF7Installation()) {
         return false;
     }
@@ -124,8 +223,114 @@ bool Randomizer::randomizeEnemyEncounters()
     return m_enemyRandomizer->randomizeEncounters();
 }
 
+bool Randomizer::renameEnemies()
+{
+    PhaseTimer timer(*this, "enemy names");
+    if (!validateFF7Installation()) {
+        return false;
+    }
+    
+    if (!m_enemyRandomizer) {
+        qDebug() << "Error: Enemy randomizer not initialized";
+        return false;
+    }
+    
+    return m_enemyRandomizer->renameEnemies();
+}
+
+bool Randomizer::randomizeEnemySteals()
+{
+    PhaseTimer timer(*this, "enemy steals");
+    if (!validateFF7Installation()) {
+        return false;
+    }
+    
+    if (!m_enemyRandomizer) {
+        qDebug() << "Error: Enemy randomizer not initialized";
+        return false;
+    }
+    
+    return m_enemyRandomizer->randomizeSteals();
+}
+
+bool Randomizer::randomizeEnemyMorphs()
+{
+    PhaseTimer timer(*this, "enemy morphs");
+    if (!validateFF7Installation()) {
+        return false;
+    }
+    
+    if (!m_enemyRandomizer) {
+        qDebug() << "Error: Enemy randomizer not initialized";
+        return false;
+    }
+    
+    return m_enemyRandomizer->randomizeMorphs();
+}
+
+bool Randomizer::randomizeEnemyAttacks()
+{
+    PhaseTimer timer(*this, "enemy attacks");
+    if (!validateFF7Installation()) {
+        return false;
+    }
+    
+    if (!m_enemyRandomizer) {
+        qDebug() << "Error: Enemy randomizer not initialized";
+        return false;
+    }
+    
+    return m_enemyRandomizer->randomizeAttacks();
+}
+
+bool Randomizer::applyBossDifficulty()
+{
+    PhaseTimer timer(*this, "boss difficulty");
+    if (!validateFF7Installation()) {
+        return false;
+    }
+
+    if (!m_enemyRandomizer) {
+        qDebug() << "Error: Enemy randomizer not initialized";
+        return false;
+    }
+
+    return m_enemyRandomizer->applyBossDifficulty();
+}
+
+bool Randomizer::applyRewardMultipliers()
+{
+    PhaseTimer timer(*this, "battle rewards");
+    if (!validateFF7Installation()) {
+        return false;
+    }
+
+    if (!m_enemyRandomizer) {
+        qDebug() << "Error: Enemy randomizer not initialized";
+        return false;
+    }
+
+    return m_enemyRandomizer->applyRewardMultipliers();
+}
+
+bool Randomizer::randomizeChocoboRatings()
+{
+    PhaseTimer timer(*this, "chocobo ratings");
+    if (!validateFF7Installation()) {
+        return false;
+    }
+    
+    if (!m_enemyRandomizer) {
+        qDebug() << "Error: Enemy randomizer not initialized";
+        return false;
+    }
+    
+    return m_enemyRandomizer->randomizeChocoboRatings();
+}
+
 bool Randomizer::randomizeShops()
 {
+    PhaseTimer timer(*this, "shops");
     // Shop randomizer only needs ff7.exe — skip full installation validation
     // (it finds and validates the exe on its own)
     if (!m_shopRandomizer) {
@@ -138,6 +343,7 @@ bool Randomizer::randomizeShops()
 
 bool Randomizer::randomizeFieldPickups()
 {
+    PhaseTimer timer(*this, "field pickups");
     if (!validateFF7Installation()) {
         return false;
     }
@@ -150,8 +356,77 @@ bool Randomizer::randomizeFieldPickups()
     return m_fieldPickupRandomizer->randomize();
 }
 
+QMap<QString, QString> Randomizer::moddedFields() const
+{
+    return m_fieldPickupRandomizer ? m_fieldPickupRandomizer->moddedFields()
+                                   : QMap<QString, QString>();
+}
+
+QMap<QString, QString> Randomizer::vanillaKeyItems() const
+{
+    return m_fieldPickupRandomizer ? m_fieldPickupRandomizer->vanillaKeyItems()
+                                   : QMap<QString, QString>();
+}
+
+QMap<QString, QStringList> Randomizer::keyItemSources() const
+{
+    return m_fieldPickupRandomizer ? m_fieldPickupRandomizer->keyItemSources()
+                                   : QMap<QString, QStringList>();
+}
+
+QMap<int, bool> Randomizer::kernelRoundtrip() const
+{
+    QMap<int, bool> sections = m_startingEquipmentRandomizer ? m_startingEquipmentRandomizer->kernelRoundtrip()
+                                                             : QMap<int, bool>();
+    if (m_armorRandomizer) {
+        const QMap<int, bool> armor = m_armorRandomizer->kernelRoundtrip();
+        for (auto it = armor.constBegin(); it != armor.constEnd(); ++it)
+            sections[it.key()] = sections.value(it.key(), true) && it.value();
+    }
+    return sections;
+}
+
+void Randomizer::reportInputError(InputError kind, const QString& source, const QString& detail)
+{
+    static const char* const KIND_NAMES[] = { "field", "scene", "kernel" };
+    const QString message = QString("%1 parse error in %2: %3")
+                                .arg(KIND_NAMES[static_cast<int>(kind)], source, detail);
+    qDebug() << "ERROR:" << message;
+    if (!m_inputErrors.contains(message))
+        m_inputErrors.append(message);
+}
+
+void Randomizer::reportPlanError(const QString& message)
+{
+    qDebug() << "ERROR:" << message;
+    m_planError = message;
+}
+
+QJsonObject Randomizer::performance() const
+{
+    QJsonArray phases;
+    qint64 totalMs = 0;
+    for (const PhaseTiming& phase : m_phaseTimings) {
+        QJsonObject entry;
+        entry["name"] = phase.name;
+        entry["ms"] = phase.ms;
+        phases.append(entry);
+        totalMs += phase.ms;
+    }
+    QJsonObject perf;
+    perf["phases"] = phases;
+    perf["totalMs"] = totalMs;
+    if (m_fieldPickupRandomizer) {
+        perf["fieldsScanned"] = m_fieldPickupRandomizer->fieldsScanned();
+        perf["bytesRecompressed"] = m_fieldPickupRandomizer->bytesRecompressed();
+    }
+    perf["peakMemoryBytes"] = SeedSummary::peakMemoryBytes();
+    return perf;
+}
+
 bool Randomizer::randomizeStartingEquipment()
 {
+    PhaseTimer timer(*this, "starting equipment");
     // Equipment randomizer finds and validates kernel.bin on its own
     if (!m_startingEquipmentRandomizer) {
         qDebug() << "Error: Starting equipment randomizer not initialized";
@@ -161,17 +436,66 @@ bool Randomizer::randomizeStartingEquipment()
     return m_startingEquipmentRandomizer->randomize();
 }
 
+bool Randomizer::randomizeArmor()
+{
+    PhaseTimer timer(*this, "armor");
+    if (!m_armorRandomizer) {
+        qDebug() << "Error: Armor randomizer not initialized";
+        return false;
+    }
+    return m_armorRandomizer->randomize();
+}
+
 bool Randomizer::applyCraterBarrier()
 {
+    PhaseTimer timer(*this, "crater barrier");
     if (!m_craterBarrierPatcher) {
         qDebug() << "Error: Crater barrier patcher not initialized";
         return false;
     }
+    m_craterBarrierPatcher->setBuildOnOutput(m_enemyRandomizer && m_enemyRandomizer->wroteWorldMap());
     return m_craterBarrierPatcher->patch();
 }
 
+bool Randomizer::writeDebugArtifacts()
+{
+    PhaseTimer timer(*this, "debug artifacts");
+    bool ok = true;
+    // Written once all enemy passes are done, so it shows the fights as played
+    if (m_enemyRandomizer
+        && (m_config.isFeatureEnabled(Config::EnemyStatsRandomization)
+            || m_config.isFeatureEnabled(Config::EnemyEncounterRandomization)))
+        ok = m_enemyRandomizer->writeDangerReport();
+    if (!m_config.isDebugOutputEnabled(Config::DebugSceneDump))
+        return ok;
+
+    // The enemy passes' scene.bin when one was written, else the install's
+    QString scenePath = QDir(getOutputPath()).filePath("data/lang-en/battle/scene.bin");
+    if (!QFile::exists(scenePath))
+        scenePath = GameFileLocator::findSceneBin(m_ff7Path, getOutputPath());
+    if (scenePath.isEmpty()) {
+        qDebug() << "Scene dump: scene.bin not found";
+        return false;
+    }
+
+    QFile dumpFile(QDir(getOutputPath()).filePath("scene_dump.csv"));
+    if (!dumpFile.open(QIODevice::WriteOnly | QIODevice::Text)) {
+        qDebug() << "Scene dump: cannot write" << dumpFile.fileName();
+        return false;
+    }
+    QTextStream out(&dumpFile);
+    QString error;
+    if (!EnemyRandomizer::exportEnemyTable(scenePath, "csv", out, &error)) {
+        qDebug() << "Scene dump failed:" << error;
+        return false;
+    }
+    return ok;
+}
+
 QString Randomizer::getOutputPath() const
 {
+    if (!m_outputPathOverride.isEmpty())
+        return m_outputPathOverride;
     QString outputFolder = m_config.getOutputFolder();
     if (QDir(outputFolder).isAbsolute()) {
         return outputFolder;
@@ -182,6 +506,52 @@ QString Randomizer::getOutputPath() const
     return ff7Dir.filePath(outputFolder);
 }
 
+bool Randomizer::claimOutputFolder(int policy, QString* error)
+{
+    const QString basePath = getOutputPath();
+    auto inUse = [](const QString& path) {
+        return QDir(path).exists()
+               && !QDir(path).isEmpty(QDir::AllEntries | QDir::NoDotAndDotDot);
+    };
+
+    QString path = basePath;
+    if (inUse(path)) {
+        switch (policy) {
+        case Config::OutputCollisionOverwrite:
+            qDebug() << "Overwriting existing output folder:" << path;
+            break;
+        case Config::OutputCollisionSuffix:
+            for (int n = 2; inUse(path); ++n)
+                path = QString("%1_%2").arg(basePath).arg(n);
+            qDebug() << "Output folder in use - writing to" << path;
+            break;
+        default:
+            if (error) *error = QString("Output folder %1 already contains files").arg(basePath);
+            return false;
+        }
+    }
+
+    // Steam Cloud or write-protected: say so before any pass runs
+    const QString writeProblem = GameFileLocator::writeTargetProblem(path);
+    if (!writeProblem.isEmpty()) {
+        if (error) *error = writeProblem;
+        return false;
+    }
+
+    if (path != basePath) {
+        m_outputPathOverride = path;
+        delete m_craterBarrierPatcher;
+        m_craterBarrierPatcher = new CraterBarrierPatcher(m_ff7Path, path);
+    }
+    m_createdOutputFolder = !QDir(path).exists();
+    if (!createOutputDirectory()) {
+        m_createdOutputFolder = false;
+        if (error) *error = QString("Could not create output directory %1").arg(path);
+        return false;
+    }
+    return true;
+}
+
 bool Randomizer::createOutputDirectory()
 {
     QString outputPath = getOutputPath();
@@ -200,109 +570,51 @@ bool Randomizer::createOutputDirectory()
 
 bool Randomizer::copyOriginalFiles()
 {
+    PhaseTimer timer(*this, "copy original files");
     if (!createOutputDirectory()) {
         return false;
     }
     
-    QString outputPath = getOutputPath();
-    QDir ff7Dir(m_ff7Path);
-    QDir outputDir(outputPath);
-    
+    QDir outputDir(getOutputPath());
     qDebug() << "Copying original files to output directory...";
-    
-    // Copy enemy data
-    QString enemySource = ff7Dir.filePath("data/lang-en/battle/scene.bin");
-    QString enemyDest = outputDir.filePath("data/lang-en/battle/scene.bin");
-    
-    QFileInfo enemyInfo(enemySource);
-    if (enemyInfo.exists()) {
-        QDir enemyOutputDir = QFileInfo(enemyDest).dir();
-        if (!enemyOutputDir.exists()) {
-            enemyOutputDir.mkpath(".");
-        }
-        if (QFile::exists(enemyDest)) {
-            QFile::remove(enemyDest);
-        }
-        if (QFile::copy(enemySource, enemyDest)) {
-            qDebug() << "Copied: data/lang-en/battle/scene.bin";
-        } else {
-            qDebug() << "Error: Could not copy enemy data";
-        }
-    } else {
-        qDebug() << "Warning: Enemy data not found:" << enemySource;
-    }
-    
-    // Copy kernel data (check if directory or file)
-    QString kernelDir = ff7Dir.filePath("data/lang-en/kernel");
-    QString kernelFile = ff7Dir.filePath("data/lang-en/kernel.bin");
-    
-    if (QDir(kernelDir).exists()) {
-        // kernel is a directory
-        QString kernelDest = outputDir.filePath("data/lang-en/kernel");
-        QDir sourceKernelDir(kernelDir);
-        QDir destKernelDir(kernelDest);
-        
-        if (!destKernelDir.exists()) {
-            destKernelDir.mkpath(".");
-        }
-        
-        // Copy all files in kernel directory
-        QStringList kernelFiles = sourceKernelDir.entryList(QDir::Files);
-        for (const QString& file : kernelFiles) {
-            QString srcFile = sourceKernelDir.filePath(file);
-            QString dstFile = destKernelDir.filePath(file);
-            if (QFile::exists(dstFile)) {
-                QFile::remove(dstFile);
-            }
-            if (QFile::copy(srcFile, dstFile)) {
-                qDebug() << "Copied: data/lang-en/kernel/" << file;
-            }
-        }
-    } else if (QFile::exists(kernelFile)) {
-        // kernel.bin is a file
-        QString kernelDest = outputDir.filePath("data/lang-en/kernel/kernel.bin");
-        QDir kernelOutputDir = QFileInfo(kernelDest).dir();
-        if (!kernelOutputDir.exists()) {
-            kernelOutputDir.mkpath(".");
-        }
-        if (QFile::exists(kernelDest)) {
-            QFile::remove(kernelDest);
+
+    auto prepare = [&](const QString& target) {
+        const QString dest = outputDir.filePath(target);
+        QDir().mkpath(QFileInfo(dest).path());
+        if (QFile::exists(dest)) {
+            QFile::remove(dest);
         }
-        if (QFile::copy(kernelFile, kernelDest)) {
-            qDebug() << "Copied: data/lang-en/kernel.bin";
+        return dest;
+    };
+
+    // With an input cache the files come from memory, not the install
+    QStringList copied;
+    if (m_inputCache) {
+        const QMap<QString, QByteArray>& files = m_inputCache->originalFiles();
+        for (auto it = files.constBegin(); it != files.constEnd(); ++it) {
+            QFile out(prepare(it.key()));
+            if (out.open(QIODevice::WriteOnly) && out.write(it.value()) == it.value().size())
+                copied << it.key();
+            else
+                qDebug() << "Error: Could not write" << it.key();
         }
     } else {
-        qDebug() << "Warning: Kernel data not found";
-    }
-    
-    // Copy field data (check both field and flevel directories)
-    QStringList fieldPaths = {"data/field/flevel.lgp", "data/flevel/flevel.lgp"};
-    bool fieldCopied = false;
-    
-    for (const QString& fieldPath : fieldPaths) {
-        QString fieldSource = ff7Dir.filePath(fieldPath);
-        QString fieldDest = outputDir.filePath(fieldPath);
-        
-        QFileInfo fieldInfo(fieldSource);
-        if (fieldInfo.exists()) {
-            QDir fieldOutputDir = QFileInfo(fieldDest).dir();
-            if (!fieldOutputDir.exists()) {
-                fieldOutputDir.mkpath(".");
-            }
-            if (QFile::exists(fieldDest)) {
-                QFile::remove(fieldDest);
-            }
-            if (QFile::copy(fieldSource, fieldDest)) {
-                qDebug() << "Copied:" << fieldPath;
-                fieldCopied = true;
-                break;
-            }
+        for (const GameFileLocator::OutputFile& file : GameFileLocator::originalFiles(m_ff7Path, getOutputPath())) {
+            if (QFile::copy(file.source, prepare(file.target)))
+                copied << file.target;
+            else
+                qDebug() << "Error: Could not copy" << file.target;
         }
     }
-    
-    if (!fieldCopied) {
+    for (const QString& target : copied)
+        qDebug() << "Copied:" << target;
+
+    if (!copied.contains("data/lang-en/battle/scene.bin"))
+        qDebug() << "Warning: Enemy data not found";
+    if (copied.filter("data/lang-en/kernel/").isEmpty())
+        qDebug() << "Warning: Kernel data not found";
+    if (copied.filter("flevel.lgp").isEmpty())
         qDebug() << "Warning: Field data not found in either data/field/ or data/flevel/";
-    }
     
     qDebug() << "File copying completed.";
     return true;
@@ -1,7 +1,10 @@
 #pragma once
 
 #include <QString>
+#include <QStringList>
 #include <QDir>
+#include <QJsonObject>
+#include <QVector>
 #include <random>
 #include "Config.h"
 
@@ -9,13 +12,18 @@
 #include "ShopRandomizer.h"
 #include "FieldPickupRandomizer_ff7tk.h"
 #include "StartingEquipmentRandomizer.h"
+#include "ArmorRandomizer.h"
 #include "CraterBarrierPatcher.h"
+#include "KernelNameTable.h"
+#include "EconomyBalancer.h"
 
 class EnemyRandomizer;
 class ShopRandomizer;
 class FieldPickupRandomizer_ff7tk;
 class StartingEquipmentRandomizer;
+class ArmorRandomizer;
 class CraterBarrierPatcher;
+class InputCache;
 
 class Randomizer
 {
@@ -23,22 +31,101 @@ class Randomizer
     friend class ShopRandomizer;
     friend class FieldPickupRandomizer_ff7tk;
     friend class StartingEquipmentRandomizer;
+    friend class ArmorRandomizer;
 public:
     Randomizer(const QString& ff7Path, const Config& config);
     ~Randomizer();
     
     bool randomizeEnemyStats();
     bool randomizeEnemyEncounters();
+    bool renameEnemies();
+    bool randomizeEnemySteals();
+    bool randomizeEnemyMorphs();
+    bool randomizeEnemyAttacks();
+    bool applyBossDifficulty();
+    bool applyRewardMultipliers();
+    bool randomizeChocoboRatings();
     bool randomizeShops();
     bool randomizeFieldPickups();
     bool randomizeStartingEquipment();
+    bool randomizeArmor();
     bool applyCraterBarrier();
+    // End-of-run reports not owned by one randomizer: danger_report.txt when
+    // the stat or encounter pass ran, and scene_dump.csv and the like, each
+    // skipped unless its Config::DebugOutput toggle is on
+    bool writeDebugArtifacts();
     
     bool createBackup(const QString& filePath);
     QString getFF7Path() const { return m_ff7Path; }
     QString getOutputPath() const;
     bool createOutputDirectory();
+
+    // Decide where this run writes before anything is copied. An output
+    // folder that already holds files is handled by `policy`
+    // (Config::OutputCollision): Error fails with *error set, Overwrite
+    // reuses it, Suffix moves the run to the first free "<folder>_N".
+    bool claimOutputFolder(int policy, QString* error = nullptr);
+    // Mark the run complete. A claimed folder this run created is deleted
+    // again on destruction unless the run got this far, so a failed run
+    // doesn't leave half an output behind.
+    void finishOutput() { m_outputFinished = true; }
     bool copyOriginalFiles();
+
+    // Names from the install's kernel2.bin; empty table if disabled or not found
+    const KernelNameTable& installNames() const { return m_installNames; }
+
+    // Gil income per tier; analyzed on first use (see EconomyBalancer)
+    const EconomyBalancer& economy();
+
+    // Fields the last field pass found modded (name -> reason)
+    QMap<QString, QString> moddedFields() const;
+
+    // Shuffled key items the field pass left on their vanilla grant (name -> reason)
+    QMap<QString, QString> vanillaKeyItems() const;
+
+    // Key items the field pass found granted in several places
+    // (name -> "<field> @<offset>: <status>" per source)
+    QMap<QString, QStringList> keyItemSources() const;
+
+    // kernel.bin sections the equipment and armor passes rebuilt
+    // (index -> round-trips exactly)
+    QMap<int, bool> kernelRoundtrip() const;
+
+    // Game files a pass could not parse, named so the user knows what to
+    // fix ("field parse error in md1stin: ..."). A pass that can't go on
+    // returns false after reporting; one that skips the file carries on
+    enum class InputError { FieldParse, SceneParse, KernelParse };
+    void reportInputError(InputError kind, const QString& source, const QString& detail);
+    QStringList inputErrors() const { return m_inputErrors; }
+
+    // Why a pass gave up on fine game files, e.g. key items the placement
+    // could not fit ("Key item placement: no slot for Keycard 62 ...")
+    void reportPlanError(const QString& message);
+    QString planError() const { return m_planError; }
+
+    // Wall time of each pass this run, in the order they ran. The passes
+    // above record themselves; callers add work done outside the
+    // Randomizer (the .iro export) with recordPhase.
+    struct PhaseTiming { QString name; qint64 ms; };
+    const QVector<PhaseTiming>& phaseTimings() const { return m_phaseTimings; }
+    void recordPhase(const QString& name, qint64 ms) { m_phaseTimings.append({name, ms}); }
+
+    // Timings, field pass counters and the process's peak memory, stored as
+    // "performance" in the seed report (SeedSummary::buildReport)
+    QJsonObject performance() const;
+
+    // Vanilla inputs already in memory (SeedGenerator); when set, flevel
+    // fields are read from it instead of the install. Not owned.
+    void setInputCache(const InputCache* cache) { m_inputCache = cache; }
+    const InputCache* inputCache() const { return m_inputCache; }
+
+    // Seed for one pass's own RNG, from the config seed, the pass name and
+    // the reroll salt. A salt of 0 is a normal run; a non-zero salt (set
+    // before the pass runs, see SeedGenerator::reroll) also reseeds the
+    // shared RNG so the pass draws a different, reproducible result.
+    quint32 streamSeed(const char* stream) const;
+    void setRerollSalt(quint32 salt);
+    quint32 rerollSalt() const { return m_rerollSalt; }
     
 private:
     QString m_ff7Path;
@@ -49,9 +136,24 @@ private:
     ShopRandomizer* m_shopRandomizer;
     FieldPickupRandomizer_ff7tk* m_fieldPickupRandomizer;
     StartingEquipmentRandomizer* m_startingEquipmentRandomizer;
+    ArmorRandomizer* m_armorRandomizer;
     CraterBarrierPatcher* m_craterBarrierPatcher;
+    EconomyBalancer* m_economyBalancer;
+    KernelNameTable m_installNames;
+    const InputCache* m_inputCache = nullptr;
+    quint32 m_rerollSalt = 0;
+
+    QVector<PhaseTiming> m_phaseTimings;
+    QStringList m_inputErrors;
+    QString m_planError;
+
+    QString m_outputPathOverride;    // set by claimOutputFolder (Suffix)
+    bool m_createdOutputFolder = false;
+    bool m_outputFinished = false;
 
     void initializeRandomizers();
+    void loadInstallNames();
+    void learnBossScenes();
     bool validateFF7Installation();
     // Resolve the effective FF7 data root from the user's selected path. The 2026
     // Steam re-release nests the engine + data under ff7/workingdir.
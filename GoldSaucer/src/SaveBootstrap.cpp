@@ -0,0 +1,121 @@
+#include "SaveBootstrap.h"
+#include "FieldPickupRandomizer_ff7tk.h"
+
+namespace {
+// Key items live in Var[1][0x40..0x46], savemap 0x0BE4..0x0BEA
+const int KEY_ITEM_FIRST = 0x0BE4;
+const int KEY_ITEM_LAST = 0x0BEA;
+// Game moment: Var[2][0], a 16-bit word at the start of bank 1
+const int GAME_MOMENT_OFFSET = 0x0BA4;
+
+int slotStart(int slot)
+{
+    return SaveBootstrap::FILE_HEADER_SIZE + slot * SaveBootstrap::SLOT_SIZE;
+}
+
+bool parseNumber(const QString& text, int& value)
+{
+    bool ok = false;
+    const QString t = text.trimmed();
+    value = t.startsWith("0x", Qt::CaseInsensitive) ? t.mid(2).toInt(&ok, 16) : t.toInt(&ok);
+    return ok;
+}
+} // namespace
+
+int SaveBootstrap::bankOffset(int bank)
+{
+    // Field banks come in 8-bit / 16-bit pairs over the same 256 bytes
+    switch (bank) {
+    case 1:  case 2:  return 0x0BA4;
+    case 3:  case 4:  return 0x0CA4;
+    case 11: case 12: return 0x0DA4;
+    case 13: case 14: return 0x0EA4;
+    case 7:  case 15: return 0x0FA4;
+    default:          return -1;
+    }
+}
+
+bool SaveBootstrap::parseKeyItems(const QString& list, QVector<QPair<int, int>>& bits, QString* error)
+{
+    for (const QString& entry : list.split(',', Qt::SkipEmptyParts)) {
+        const QString name = entry.trimmed();
+        const bool all = name.compare("all", Qt::CaseInsensitive) == 0;
+        bool found = false;
+        for (int offset = KEY_ITEM_FIRST; offset <= KEY_ITEM_LAST; ++offset) {
+            for (int bit = 0; bit < 8; ++bit) {
+                const QString itemName = FieldPickupRandomizer_ff7tk::getKeyItemName(offset, bit);
+                if (itemName == "Unknown") continue;
+                if (all || itemName.compare(name, Qt::CaseInsensitive) == 0) {
+                    bits.append(qMakePair(offset, bit));
+                    found = true;
+                }
+            }
+        }
+        if (!found) {
+            if (error) *error = QString("Unknown key item '%1'").arg(name);
+            return false;
+        }
+    }
+    return true;
+}
+
+bool SaveBootstrap::parseFlags(const QString& list, QVector<Flag>& flags, QString* error)
+{
+    for (const QString& entry : list.split(',', Qt::SkipEmptyParts)) {
+        const QStringList parts = entry.split(':');
+        Flag flag{};
+        if (parts.size() != 3 || !parseNumber(parts[0], flag.bank)
+            || !parseNumber(parts[1], flag.address) || !parseNumber(parts[2], flag.bit)
+            || bankOffset(flag.bank) < 0 || flag.address < 0 || flag.address > 0xFF
+            || flag.bit < 0 || flag.bit > 7) {
+            if (error) *error = QString("Bad flag '%1' (expected bank:address:bit)").arg(entry.trimmed());
+            return false;
+        }
+        flags.append(flag);
+    }
+    return true;
+}
+
+bool SaveBootstrap::apply(QByteArray& saveFile, int slot,
+                          const QVector<QPair<int, int>>& keyItemBits,
+                          const QVector<Flag>& flags, int gameMoment, QString* error)
+{
+    if (saveFile.size() != FILE_HEADER_SIZE + SLOT_COUNT * SLOT_SIZE) {
+        if (error) *error = QString("Not a PC save file (%1 bytes)").arg(saveFile.size());
+        return false;
+    }
+    if (slot < 0 || slot >= SLOT_COUNT) {
+        if (error) *error = QString("Slot must be 1-%1").arg(SLOT_COUNT);
+        return false;
+    }
+
+    char* data = saveFile.data() + slotStart(slot);
+    for (const auto& keyItem : keyItemBits)
+        data[keyItem.first] = static_cast<char>(data[keyItem.first] | (1 << keyItem.second));
+    for (const Flag& flag : flags) {
+        const int offset = bankOffset(flag.bank) + flag.address;
+        data[offset] = static_cast<char>(data[offset] | (1 << flag.bit));
+    }
+    if (gameMoment >= 0) {
+        data[GAME_MOMENT_OFFSET]     = static_cast<char>(gameMoment & 0xFF);
+        data[GAME_MOMENT_OFFSET + 1] = static_cast<char>((gameMoment >> 8) & 0xFF);
+    }
+
+    const quint16 checksum = slotChecksum(saveFile, slot);
+    data[0] = static_cast<char>(checksum & 0xFF);
+    data[1] = static_cast<char>(checksum >> 8);
+    return true;
+}
+
+quint16 SaveBootstrap::slotChecksum(const QByteArray& saveFile, int slot)
+{
+    const uchar* data = reinterpret_cast<const uchar*>(saveFile.constData()) + slotStart(slot);
+    quint32 r = 0xFFFF;
+    for (int i = 4; i < SLOT_SIZE; ++i) {
+        r ^= static_cast<quint32>(data[i]) << 8;
+        for (int b = 0; b < 8; ++b)
+            r = (r & 0x8000) ? (r << 1) ^ 0x1021 : (r << 1);
+        r &= 0xFFFF;
+    }
+    return static_cast<quint16>(~r & 0xFFFF);
+}
@@ -0,0 +1,51 @@
+#pragma once
+
+#include <QByteArray>
+#include <QPair>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+// SaveBootstrap — developer tool behind `--make-save`: patches one slot of an
+// existing PC save (save00.ff7 .. save09.ff7) so a tester can load straight
+// into the part of a seed they want to check, holding the key items and
+// story flags it needs.
+//
+// Only the savemap bytes asked for are touched and the slot checksum is
+// recomputed. Party, inventory and position come from the base save, so the
+// base should be a save made near the location being tested.
+class SaveBootstrap
+{
+public:
+    // A field script variable bit: Var[bank][address] bit, as written by
+    // BITON (banks 1-15, the same numbering as the pickup logs)
+    struct Flag {
+        int bank;
+        int address;
+        int bit;
+    };
+
+    static constexpr int FILE_HEADER_SIZE = 0x09;
+    static constexpr int SLOT_SIZE = 0x10F4;
+    static constexpr int SLOT_COUNT = 15;
+
+    // Savemap offset of a field variable bank, or -1 for an unknown bank
+    static int bankOffset(int bank);
+
+    // Parses "PHS, Keystone" (key item names, case-insensitive, or "all")
+    static bool parseKeyItems(const QString& list, QVector<QPair<int, int>>& bits,
+                              QString* error = nullptr);
+    // Parses "1:0x40:3,3:128:1" (bank:address:bit, decimal or 0x hex)
+    static bool parseFlags(const QString& list, QVector<Flag>& flags,
+                           QString* error = nullptr);
+
+    // Applies key items (savemap offset, bit), flags and, when >= 0, the game
+    // moment to slot (0-14) of a PC save file, then fixes its checksum
+    static bool apply(QByteArray& saveFile, int slot,
+                      const QVector<QPair<int, int>>& keyItemBits,
+                      const QVector<Flag>& flags, int gameMoment,
+                      QString* error = nullptr);
+
+    // FF7's CRC-16 over the slot data after the checksum word
+    static quint16 slotChecksum(const QByteArray& saveFile, int slot);
+};
@@ -0,0 +1,98 @@
+#include "SceneCatalog.h"
+
+#include <ff7tk/data/FF7Text.h>
+
+#include <cstring>
+
+SceneCatalog::SceneCatalog(const QVector<QByteArray>& scenes,
+                           const QMap<QString, QSet<int>>& areas)
+    : m_sceneEnemies(scenes.size())
+    , m_formationEnemies(scenes.size() * FORMATIONS_PER_SCENE)
+{
+    for (int s = 0; s < scenes.size(); ++s) {
+        const QByteArray& scene = scenes[s];
+        if (scene.size() != SCENE_SIZE) continue;
+
+        // Formations name enemies by ID; the scene's ID table says which
+        // record each one is
+        QVector<quint16> ids;
+        QStringList names;
+        for (int e = 0; e < ENEMIES_PER_SCENE; ++e) {
+            quint16 id;
+            memcpy(&id, scene.constData() + e * 2, 2);
+            QByteArray raw = scene.mid(ENEMY_DATA_BASE + e * ENEMY_RECORD_SIZE, ENEMY_NAME_SIZE);
+            QString name = raw.count(char(0xFF)) == ENEMY_NAME_SIZE ? QString()
+                                                                   : FF7Text::toPC(raw).trimmed();
+            ids.append(id);
+            names.append(name);
+            if (id != 0xFFFF && !name.isEmpty() && !m_sceneEnemies[s].contains(name))
+                m_sceneEnemies[s].append(name);
+        }
+
+        for (int f = 0; f < FORMATIONS_PER_SCENE; ++f) {
+            QStringList order;
+            QMap<QString, int> counts;
+            for (int pos = 0; pos < FORMATION_SIZE; pos += FORMATION_ENTRY_SIZE) {
+                quint16 id;
+                memcpy(&id, scene.constData() + FORMATION_BASE + f * FORMATION_SIZE + pos, 2);
+                int slot = id == 0xFFFF ? -1 : ids.indexOf(id);
+                if (slot < 0) continue;
+                QString name = names[slot].isEmpty() ? QStringLiteral("enemy %1").arg(id)
+                                                     : names[slot];
+                if (!counts.contains(name)) order.append(name);
+                ++counts[name];
+            }
+            QStringList& out = m_formationEnemies[s * FORMATIONS_PER_SCENE + f];
+            for (const QString& name : order)
+                out.append(counts[name] > 1 ? QStringLiteral("%1x %2").arg(counts[name]).arg(name)
+                                            : name);
+        }
+    }
+
+    for (auto it = areas.constBegin(); it != areas.constEnd(); ++it) {
+        for (int formation : it.value()) {
+            QStringList& where = m_formationAreas[formation];
+            if (!where.contains(it.key())) where.append(it.key());
+        }
+    }
+}
+
+QStringList SceneCatalog::sceneEnemies(int scene) const
+{
+    return scene >= 0 && scene < m_sceneEnemies.size() ? m_sceneEnemies[scene] : QStringList();
+}
+
+QStringList SceneCatalog::sceneAreas(int scene) const
+{
+    QStringList areas;
+    for (int f = 0; f < FORMATIONS_PER_SCENE; ++f) {
+        for (const QString& area : m_formationAreas.value(scene * FORMATIONS_PER_SCENE + f)) {
+            if (!areas.contains(area)) areas.append(area);
+        }
+    }
+    return areas;
+}
+
+QString SceneCatalog::describeScene(int scene) const
+{
+    QStringList enemies = sceneEnemies(scene);
+    QString text = QStringLiteral("scene %1").arg(scene);
+    if (!enemies.isEmpty())
+        text += QStringLiteral(" (%1)").arg(enemies.join(QStringLiteral(", ")));
+    return text + areaSuffix(sceneAreas(scene));
+}
+
+QString SceneCatalog::describeFormation(int formation) const
+{
+    QString text = QStringLiteral("formation %1").arg(formation);
+    if (formation >= 0 && formation < m_formationEnemies.size()
+        && !m_formationEnemies[formation].isEmpty())
+        text += QStringLiteral(" (%1)").arg(m_formationEnemies[formation].join(QStringLiteral(", ")));
+    return text + areaSuffix(m_formationAreas.value(formation));
+}
+
+QString SceneCatalog::areaSuffix(const QStringList& areas)
+{
+    return areas.isEmpty() ? QString()
+                           : QStringLiteral(" [%1]").arg(areas.join(QStringLiteral(", ")));
+}
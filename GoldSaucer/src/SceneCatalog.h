@@ -0,0 +1,58 @@
+#pragma once
+
+#include <QByteArray>
+#include <QMap>
+#include <QSet>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+/**
+ * SceneCatalog
+ *
+ * Human-readable names for scene.bin scenes and formations, so logs and
+ * reports can say "formation 48: 2x Guard Hound, Mono Drive [mds7st]"
+ * instead of a bare index. Enemy names come from the scenes themselves (so
+ * renamed or localized installs read right); areas come from the field and
+ * world map encounter tables that roll each formation.
+ *
+ * Build one from the vanilla scenes to describe what a slot originally held,
+ * or from the randomized ones to describe what it holds now.
+ */
+class SceneCatalog
+{
+public:
+    // scenes: decompressed 7808-byte scenes by index (others are skipped).
+    // areas:  area name -> formation IDs its encounter tables can roll.
+    SceneCatalog(const QVector<QByteArray>& scenes,
+                 const QMap<QString, QSet<int>>& areas = {});
+
+    // Distinct enemy names in a scene, in slot order
+    QStringList sceneEnemies(int scene) const;
+    // Areas whose tables roll any of the scene's formations
+    QStringList sceneAreas(int scene) const;
+
+    // "scene 12 (Guard Hound, Mono Drive) [mds7st, world region 0]"
+    QString describeScene(int scene) const;
+    // "formation 48 (2x Guard Hound, Mono Drive) [mds7st]"
+    QString describeFormation(int formation) const;
+
+    static const int FORMATIONS_PER_SCENE = 4;
+
+private:
+    QVector<QStringList>   m_sceneEnemies;
+    QVector<QStringList>   m_formationEnemies;
+    QMap<int, QStringList> m_formationAreas;
+
+    static QString areaSuffix(const QStringList& areas);
+
+    // ── scene.bin layout (see EnemyRandomizer.h) ────────────────────────
+    static const int SCENE_SIZE           = 7808;
+    static const int ENEMIES_PER_SCENE    = 3;
+    static const int ENEMY_DATA_BASE      = 0x0298;
+    static const int ENEMY_RECORD_SIZE    = 184;
+    static const int ENEMY_NAME_SIZE      = 32;
+    static const int FORMATION_BASE       = 0x0118;
+    static const int FORMATION_SIZE       = 96;
+    static const int FORMATION_ENTRY_SIZE = 16;
+};
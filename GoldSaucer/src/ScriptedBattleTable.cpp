@@ -0,0 +1,104 @@
+#include "ScriptedBattleTable.h"
+
+#include <QCoreApplication>
+#include <QFile>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QJsonObject>
+#include <QDebug>
+
+namespace {
+// scene.bin holds 256 scenes of 4 formations each
+const int FORMATION_COUNT = 1024;
+}
+
+ScriptedBattleTable& ScriptedBattleTable::instance()
+{
+    static ScriptedBattleTable table;
+    return table;
+}
+
+ScriptedBattleTable::ScriptedBattleTable()
+{
+    reload();
+}
+
+QString ScriptedBattleTable::overridePath()
+{
+    return QCoreApplication::applicationDirPath() + "/scripted_battles.json";
+}
+
+void ScriptedBattleTable::reload()
+{
+    m_equivalents.clear();
+    m_groupCount = 0;
+    m_warnings.clear();
+
+    QJsonObject builtIn;
+    if (readFile(":/data/scripted_battles.json", builtIn))
+        merge(builtIn, ":/data/scripted_battles.json");
+
+    const QString userPath = overridePath();
+    if (!QFile::exists(userPath))
+        return;
+
+    QJsonObject user;
+    if (!readFile(userPath, user))
+        return;
+    if (user["replace"].toBool(false)) {
+        m_equivalents.clear();
+        m_groupCount = 0;
+    }
+    merge(user, userPath);
+    qDebug() << "ScriptedBattleTable: applied override" << userPath << "-" << m_groupCount
+             << "groups";
+}
+
+bool ScriptedBattleTable::readFile(const QString& path, QJsonObject& root)
+{
+    QFile file(path);
+    if (!file.open(QIODevice::ReadOnly)) {
+        m_warnings << QString("Cannot open %1").arg(path);
+        return false;
+    }
+    QJsonParseError parseError;
+    QJsonDocument doc = QJsonDocument::fromJson(file.readAll(), &parseError);
+    if (parseError.error != QJsonParseError::NoError || !doc.isObject()) {
+        m_warnings << QString("%1: %2").arg(path, parseError.errorString());
+        return false;
+    }
+    root = doc.object();
+    return true;
+}
+
+void ScriptedBattleTable::merge(const QJsonObject& root, const QString& path)
+{
+    const QJsonArray groups = root["groups"].toArray();
+    for (int g = 0; g < groups.size(); ++g) {
+        QVector<int> members;
+        for (const QJsonValue& v : groups[g].toArray()) {
+            const int id = v.toInt(-1);
+            if (!v.isDouble() || id < 0 || id >= FORMATION_COUNT) {
+                m_warnings << QString("%1: group %2 has an invalid formation ID").arg(path).arg(g + 1);
+                continue;
+            }
+            if (!members.contains(id)) members.append(id);
+        }
+        if (members.size() < 2) {
+            m_warnings << QString("%1: group %2 needs at least two formations").arg(path).arg(g + 1);
+            continue;
+        }
+        for (int id : members) {
+            QVector<int>& others = m_equivalents[id];
+            for (int other : members) {
+                if (other != id && !others.contains(other)) others.append(other);
+            }
+        }
+        ++m_groupCount;
+    }
+}
+
+QVector<int> ScriptedBattleTable::equivalents(int formationId) const
+{
+    return m_equivalents.value(formationId);
+}
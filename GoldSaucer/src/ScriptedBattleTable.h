@@ -0,0 +1,53 @@
+#pragma once
+
+#include <QMap>
+#include <QStringList>
+#include <QVector>
+
+class QJsonObject;
+
+/**
+ * ScriptedBattleTable
+ *
+ * Which formations a scripted fight (field BATTLE opcode) may be swapped
+ * for. Two formations are only interchangeable when a group lists both, so
+ * a forced fight never turns into one from another part of the story, a
+ * different party setup or a fight the script's follow-up doesn't expect.
+ *
+ * The table ships as :/data/scripted_battles.json
+ * (src/data/scripted_battles.json). A scripted_battles.json next to the
+ * executable is merged on top:
+ *   "groups"           lists of formation IDs added to the built-in ones
+ *   "replace": true    drop the built-in groups first
+ */
+class ScriptedBattleTable
+{
+public:
+    static ScriptedBattleTable& instance();
+
+    // The other formations in formationId's groups (empty if it has none)
+    QVector<int> equivalents(int formationId) const;
+    int groupCount() const { return m_groupCount; }
+
+    // Path of the user override file (may not exist)
+    static QString overridePath();
+    // Problems found while reading the table (shown in debug logs)
+    QStringList warnings() const { return m_warnings; }
+
+    // Re-read the built-in table and override (e.g. after the user edits it)
+    void reload();
+
+private:
+    ScriptedBattleTable();
+    ~ScriptedBattleTable() = default;
+
+    ScriptedBattleTable(const ScriptedBattleTable&) = delete;
+    ScriptedBattleTable& operator=(const ScriptedBattleTable&) = delete;
+
+    bool readFile(const QString& path, QJsonObject& root);
+    void merge(const QJsonObject& root, const QString& path);
+
+    QMap<int, QVector<int>> m_equivalents;   // formation -> others in its groups
+    int                     m_groupCount = 0;
+    QStringList             m_warnings;
+};
@@ -0,0 +1,197 @@
+#include "SeedCard.h"
+#include "Config.h"
+#include <QCryptographicHash>
+#include <QPainter>
+#include <QSvgRenderer>
+#include <QLinearGradient>
+#include <QFont>
+#include <QFileInfo>
+#include <QDir>
+
+QString SeedCard::settingsHash(const Config& config)
+{
+    const QByteArray digest = QCryptographicHash::hash(config.toShareString().toUtf8(),
+                                                       QCryptographicHash::Sha256);
+    return QString::fromLatin1(digest.toHex().left(8)).toUpper();
+}
+
+QStringList SeedCard::settingsSummary(const Config& config)
+{
+    static const char* const RARITY_NAMES[] = { "Balanced", "Random", "High-tier Only" };
+    static const char* const EQUIPMENT_NAMES[] = { "Weak", "Balanced", "Strong" };
+    static const char* const ENEMY_NAME_MODES[] = { "Vanilla", "Shuffle", "Replace" };
+    static const char* const BOSS_DIFFICULTIES[] = { "Normal", "Easy", "Hard", "Scaled" };
+    static const char* const FIELD_ENCOUNTER_MODES[] = { "vanilla", "shuffled", "rerolled" };
+    static const char* const ARMOR_MODES[] = { "vanilla", "shuffled", "rerolled" };
+    auto pick = [](const char* const* names, int count, int index) {
+        return QString::fromLatin1(names[qBound(0, index, count - 1)]);
+    };
+    auto onOff = [](bool enabled) {
+        return enabled ? QStringLiteral("on") : QStringLiteral("off");
+    };
+
+    QStringList lines;
+    if (config.isFeatureEnabled(Config::ShopRandomization)) {
+        lines << QString("Shops: pool %1, prices %2, economy %3, pickup slot %4")
+                     .arg(config.getShopItemPoolSize())
+                     .arg(config.getRandomizeShopPrices()
+                              ? QString("+/-%1%").arg(qRound(config.getShopPriceVariance() * 100))
+                              : QStringLiteral("vanilla"))
+                     .arg(onOff(config.getBalanceEconomy()))
+                     .arg(onOff(config.getShopLocationSlot()));
+    } else {
+        lines << QStringLiteral("Shops: vanilla");
+    }
+    if (config.isFeatureEnabled(Config::FieldPickupRandomization)) {
+        lines << QString("Pickups: %1, key items %2, chocobo %3, caves %4, crater loot %5%")
+                     .arg(pick(RARITY_NAMES, 3, config.getPickupRarityMode()))
+                     .arg(config.getKeyItemRandomization() && config.getEarlyPhs()
+                              ? QStringLiteral("on (early PHS)")
+                              : onOff(config.getKeyItemRandomization()))
+                     .arg(onOff(config.getChocoboChecks()))
+                     .arg(onOff(config.getMateriaCaveChecks()))
+                     .arg(config.getFinalDungeonLootDensity());
+        static const char* const GROUP_NAMES[] = { "Wutai", "minigames", "missables" };
+        QStringList groupsOff;
+        for (int i = 0; i < Config::LocationGroupCount; ++i) {
+            if (!config.isLocationGroupEnabled(static_cast<Config::LocationGroup>(i)))
+                groupsOff << QString::fromLatin1(GROUP_NAMES[i]);
+        }
+        if (!groupsOff.isEmpty())
+            lines << QString("Vanilla locations: %1").arg(groupsOff.join(", "));
+        if (config.getMidgarPartsRequired() < Config::MIDGAR_PARTS_COUNT)
+            lines << QString("Midgar Parts needed: %1 of %2")
+                         .arg(config.getMidgarPartsRequired()).arg(Config::MIDGAR_PARTS_COUNT);
+        if (config.getHugeMateriaRequired() < Config::HUGE_MATERIA_COUNT)
+            lines << QString("Huge Materia needed: %1 of %2")
+                         .arg(config.getHugeMateriaRequired()).arg(Config::HUGE_MATERIA_COUNT);
+        if (config.getProgressiveKeycards())
+            lines << QStringLiteral("Keycards: progressive");
+        if (config.getTieredEquipment())
+            lines << QStringLiteral("Equipment: tiered by sphere");
+        if (config.getSilentPickups())
+            lines << QStringLiteral("Pickup messages: silent");
+        if (config.getFieldEncounterMode() != Config::FieldEncountersVanilla
+            || config.getRandomizeFieldEncounterRates())
+            lines << QString("Field encounters: %1, rates %2")
+                         .arg(pick(FIELD_ENCOUNTER_MODES, 3, config.getFieldEncounterMode()))
+                         .arg(config.getRandomizeFieldEncounterRates() ? "random" : "vanilla");
+        if (config.getRandomizeScriptedBattles())
+            lines << QStringLiteral("Scripted fights: rerolled");
+    } else {
+        lines << QStringLiteral("Pickups: vanilla");
+    }
+    lines << (config.isFeatureEnabled(Config::StartingEquipmentRandomization)
+                  ? QString("Starting equipment: %1")
+                        .arg(pick(EQUIPMENT_NAMES, 3, config.getStartingEquipmentTier()))
+                  : QStringLiteral("Starting equipment: vanilla"));
+    if (config.isFeatureEnabled(Config::StartingEquipmentRandomization)) {
+        static const char* const CHARACTER_NAMES[] = {
+            "Cloud", "Barret", "Tifa", "Aerith", "Red XIII", "Yuffie", "Cait Sith", "Vincent", "Cid"
+        };
+        QStringList kept;
+        for (int i = 0; i < Config::STARTING_CHARACTER_COUNT; ++i) {
+            if (Config::hasStartingCharacterToggle(i) && !config.isStartingCharacterRandomized(i))
+                kept << QString::fromLatin1(CHARACTER_NAMES[i]);
+        }
+        if (!kept.isEmpty())
+            lines << QString("Vanilla loadouts: %1").arg(kept.join(", "));
+    }
+    if (config.getRandomizeArmorStats() != Config::ArmorVanilla
+        || config.getRandomizeArmorSlots() != Config::ArmorVanilla)
+        lines << QString("Armor: stats %1, slots %2")
+                     .arg(pick(ARMOR_MODES, 3, config.getRandomizeArmorStats()))
+                     .arg(pick(ARMOR_MODES, 3, config.getRandomizeArmorSlots()));
+    if (config.isFeatureEnabled(Config::EnemyStatsRandomization))
+        lines << QString("Enemy stats: +/-%1%, boss protection %2")
+                     .arg(qRound(config.getEnemyStatsVariance() * 100))
+                     .arg(onOff(config.getBossProtectionEnabled()));
+    if (config.isFeatureEnabled(Config::EnemyEncounterRandomization))
+        lines << QString("Encounters: %1%2")
+                     .arg(config.getEncounterEnemySwap() ? "enemies swapped" : "shuffled")
+                     .arg(config.getScaleShuffledEncounters() ? ", scaled to slot" : "");
+    lines << QString("Enemy names: %1").arg(pick(ENEMY_NAME_MODES, 3, config.getEnemyNameMode()));
+    if (config.getRandomizeEnemySteals())
+        lines << QString("Enemy steals: shuffled%1")
+                     .arg(config.getStealDropConversion() ? ", mixed with drops" : "");
+    if (config.getRandomizeChocoboRatings())
+        lines << QStringLiteral("Chocobo ratings: shuffled");
+    if (config.getRandomizeEnemyMorphs())
+        lines << QString("Enemy morphs: %1 pool%2")
+                     .arg(config.getMorphItemPool().isEmpty() ? "curated" : "custom")
+                     .arg(config.getGuaranteeSourceMorphs() ? ", Sources kept" : "");
+    if (config.getShuffleEnemyAttacks())
+        lines << QStringLiteral("Enemy attacks: power/MP shuffled within tiers");
+    if (config.getBossDifficulty() != Config::BossDifficultyNormal)
+        lines << QString("Bosses: %1").arg(pick(BOSS_DIFFICULTIES, 4, config.getBossDifficulty()));
+    if (config.hasRewardMultipliers())
+        lines << QString("Rewards: EXP x%1, AP x%2, Gil x%3")
+                     .arg(config.getExpMultiplier()).arg(config.getApMultiplier()).arg(config.getGilMultiplier())
+                 + (config.getApJitter() > 0 ? QString(", AP +/-%1%").arg(config.getApJitter()) : QString());
+    if (config.isFeatureEnabled(Config::ArchipelagoIntegration))
+        lines << QString("Archipelago%1").arg(config.getFreeRoam() ? ", Free Roam" : "");
+    return lines;
+}
+
+QImage SeedCard::render(const Config& config)
+{
+    QImage image(CARD_WIDTH, CARD_HEIGHT, QImage::Format_ARGB32_Premultiplied);
+    QPainter painter(&image);
+    painter.setRenderHint(QPainter::Antialiasing);
+    painter.setRenderHint(QPainter::TextAntialiasing);
+
+    // Same dark theme as the GUI, with a gold rim
+    QLinearGradient background(0, 0, 0, CARD_HEIGHT);
+    background.setColorAt(0.0, QColor(45, 45, 45));
+    background.setColorAt(1.0, QColor(20, 20, 20));
+    painter.fillRect(image.rect(), background);
+    painter.setPen(QPen(QColor(212, 175, 55), 4));
+    painter.drawRect(image.rect().adjusted(2, 2, -2, -2));
+
+    const int margin = 24;
+    QSvgRenderer logo(QStringLiteral(":/data/goldsaucer_logo.svg"));
+    if (logo.isValid())
+        logo.render(&painter, QRectF(margin, margin, LOGO_SIZE, LOGO_SIZE));
+
+    const int textLeft = margin * 2 + LOGO_SIZE;
+    QFont titleFont(QStringLiteral("Sans Serif"), 24, QFont::Bold);
+    painter.setFont(titleFont);
+    painter.setPen(QColor(212, 175, 55));
+    painter.drawText(QRect(textLeft, margin, CARD_WIDTH - textLeft - margin, 40),
+                     Qt::AlignLeft | Qt::AlignVCenter, QStringLiteral("Gold Saucer"));
+
+    QFont hashFont(QStringLiteral("Monospace"), 28, QFont::Bold);
+    hashFont.setStyleHint(QFont::Monospace);
+    painter.setFont(hashFont);
+    painter.setPen(Qt::white);
+    painter.drawText(QRect(textLeft, margin + 44, CARD_WIDTH - textLeft - margin, 48),
+                     Qt::AlignLeft | Qt::AlignVCenter,
+                     QString("Hash %1").arg(settingsHash(config)));
+
+    QFont summaryFont(QStringLiteral("Sans Serif"), 11);
+    painter.setFont(summaryFont);
+    painter.setPen(QColor(220, 220, 220));
+    const int lineHeight = 26;
+    int y = margin * 2 + LOGO_SIZE;
+    for (const QString& line : settingsSummary(config)) {
+        painter.drawText(QRect(margin, y, CARD_WIDTH - margin * 2, lineHeight),
+                         Qt::AlignLeft | Qt::AlignVCenter, line);
+        y += lineHeight;
+    }
+
+    painter.setPen(QColor(150, 150, 150));
+    painter.drawText(QRect(margin, CARD_HEIGHT - margin - lineHeight, CARD_WIDTH - margin * 2, lineHeight),
+                     Qt::AlignRight | Qt::AlignVCenter,
+                     QStringLiteral("Matching hashes = same seed and settings"));
+    return image;
+}
+
+bool SeedCard::save(const Config& config, const QString& pngPath, QString* error)
+{
+    QDir().mkpath(QFileInfo(pngPath).absolutePath());
+    if (!render(config).save(pngPath, "PNG")) {
+        if (error) *error = QString("Cannot write %1").arg(pngPath);
+        return false;
+    }
+    return true;
+}
@@ -0,0 +1,35 @@
+#pragma once
+
+#include <QString>
+#include <QStringList>
+#include <QImage>
+
+class Config;
+
+// SeedCard — renders a shareable PNG "seed card" for race lobbies: the
+// GoldSaucer logo, a short settings hash and a one-line-per-area summary of
+// the options. The seed number and the settings string are deliberately left
+// off, so posting the card gives nothing away; racers compare hashes to make
+// sure everyone generated the same game. The share string carries the seed
+// salt (Config::getSeedSalt), so the hash can't be matched by trying seeds
+// against the summarised settings.
+class SeedCard
+{
+public:
+    // First 8 hex digits (upper case) of SHA-256 over the share string, which
+    // covers the seed, its salt and every shared setting
+    static QString settingsHash(const Config& config);
+
+    // Human-readable settings, one line per randomizer area
+    static QStringList settingsSummary(const Config& config);
+
+    static QImage render(const Config& config);
+
+    // Render and write a PNG; on failure *error says why
+    static bool save(const Config& config, const QString& pngPath, QString* error = nullptr);
+
+private:
+    static const int CARD_WIDTH  = 640;
+    static const int CARD_HEIGHT = 360;
+    static const int LOGO_SIZE   = 96;
+};
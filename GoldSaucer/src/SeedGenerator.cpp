@@ -0,0 +1,260 @@
+#include "SeedGenerator.h"
+#include "Config.h"
+#include "DiagnosticBundle.h"
+#include "IroExporter.h"
+#include "Randomizer.h"
+#include "SeedSummary.h"
+#include <QDateTime>
+#include <QDir>
+#include <QElapsedTimer>
+#include <QTextStream>
+
+SeedGenerator::SeedGenerator(const QString& ff7Path)
+    : m_ff7Path(ff7Path)
+{
+}
+
+QVector<SeedGenerator::Step> SeedGenerator::steps(const Config& config)
+{
+    return {
+        { true, "copy original files", "Original Files", 5, nullptr, &Randomizer::copyOriginalFiles },
+        // scene.bin rewrites first: gil balancing in the shop and pickup passes
+        // reads their drops, and the other enemy passes build on their output
+        { config.isFeatureEnabled(Config::EnemyStatsRandomization), "enemy stats", "Enemy Stats", 10,
+          nullptr, &Randomizer::randomizeEnemyStats },
+        { config.isFeatureEnabled(Config::EnemyEncounterRandomization), "encounters", "Encounters", 18,
+          nullptr, &Randomizer::randomizeEnemyEncounters },
+        { config.isFeatureEnabled(Config::ShopRandomization), "shops", "Shops", 25,
+          nullptr, &Randomizer::randomizeShops },
+        { config.isFeatureEnabled(Config::FieldPickupRandomization), "field pickups", "Field Pickups", 50,
+          nullptr, &Randomizer::randomizeFieldPickups },
+        { config.isFeatureEnabled(Config::StartingEquipmentRandomization), "starting equipment",
+          "Starting Equipment", 75, nullptr, &Randomizer::randomizeStartingEquipment },
+        // After the starting equipment pass, whose kernel.bin it builds on
+        { config.getRandomizeArmorStats() != Config::ArmorVanilla
+              || config.getRandomizeArmorSlots() != Config::ArmorVanilla, "armor", "Armor", 78,
+          nullptr, &Randomizer::randomizeArmor },
+        // Before renaming, so bosses are still found by name
+        { config.getBossDifficulty() != Config::BossDifficultyNormal, "boss difficulty", "Boss Difficulty", 80,
+          nullptr, &Randomizer::applyBossDifficulty },
+        { config.getEnemyNameMode() != Config::EnemyNamesVanilla, "enemy names", "Enemy Names", 85,
+          nullptr, &Randomizer::renameEnemies },
+        { config.getRandomizeEnemySteals(), "enemy steals", "Enemy Steals", 88,
+          nullptr, &Randomizer::randomizeEnemySteals },
+        { config.getRandomizeEnemyMorphs(), "enemy morphs", "Enemy Morphs", 89,
+          nullptr, &Randomizer::randomizeEnemyMorphs },
+        { config.getShuffleEnemyAttacks(), "enemy attacks", "Enemy Attacks", 89,
+          nullptr, &Randomizer::randomizeEnemyAttacks },
+        { config.hasRewardMultipliers(), "battle rewards", "Battle Rewards", 89,
+          nullptr, &Randomizer::applyRewardMultipliers },
+        { config.getRandomizeChocoboRatings(), "chocobo ratings", "Chocobo Ratings", 89,
+          nullptr, &Randomizer::randomizeChocoboRatings },
+        { config.getFreeRoam(), "crater barrier", "Crater Barrier", 90,
+          "world_us.lgp not found or unrecognised; the crater will remain open",
+          &Randomizer::applyCraterBarrier },
+    };
+}
+
+bool SeedGenerator::load(QString* error)
+{
+    return m_cache.load(m_ff7Path, error);
+}
+
+bool SeedGenerator::generate(const Config& config, QString* outputPath,
+                             QString* error, QTextStream* log)
+{
+    m_lastFailure = Failure();
+    QString stepError;
+    try {
+        if (runSteps(config, outputPath, &stepError, log)) {
+            m_lastFailure = Failure();
+            return true;
+        }
+    } catch (const std::exception& e) {
+        stepError = QString("%1 crashed: %2").arg(m_lastFailure.step, QString::fromLocal8Bit(e.what()));
+    } catch (...) {
+        stepError = QString("%1 crashed").arg(m_lastFailure.step);
+    }
+    m_lastFailure.message = stepError;
+    if (error) *error = stepError;
+    return false;
+}
+
+bool SeedGenerator::runSteps(const Config& config, QString* outputPath,
+                             QString* error, QTextStream* log)
+{
+    m_lastFailure.step = QStringLiteral("load inputs");
+    if (!isLoaded() && !load(error))
+        return false;
+
+    Randomizer randomizer(m_ff7Path, config);
+    randomizer.setInputCache(&m_cache);
+    m_lastFailure.step = QStringLiteral("output folder");
+    if (!randomizer.claimOutputFolder(config.getOutputCollision(), error))
+        return false;
+    m_lastFailure.outputPath = randomizer.getOutputPath();
+
+    for (const Step& step : steps(config)) {
+        if (!step.enabled) continue;
+        m_lastFailure.step = QString::fromLatin1(step.name);
+        bool ok = false;
+        try {
+            ok = (randomizer.*step.run)();
+        } catch (...) {
+            // Rethrown to generate(); the logs go with the randomizer
+            m_lastFailure.inputErrors = randomizer.inputErrors();
+            m_lastFailure.logs = DiagnosticBundle::collectLogs(randomizer.getOutputPath());
+            throw;
+        }
+        if (!ok && step.warning) {
+            if (log) *log << "WARNING: " << step.name << " failed - " << step.warning << "\n";
+            continue;
+        }
+        if (!ok) {
+            m_lastFailure.inputErrors = randomizer.inputErrors();
+            m_lastFailure.logs = DiagnosticBundle::collectLogs(randomizer.getOutputPath());
+            if (error) {
+                *error = QString("%1 failed").arg(step.name);
+                if (!randomizer.planError().isEmpty())
+                    *error += ": " + randomizer.planError();
+                if (!randomizer.inputErrors().isEmpty())
+                    *error += ": " + randomizer.inputErrors().join("; ");
+            }
+            return false;
+        }
+        if (log) *log << "Done: " << step.name << "\n";
+    }
+
+    // Files a pass skipped over still leave the seed usable, but the user
+    // should know their install has a damaged file
+    if (log) {
+        for (const QString& inputError : randomizer.inputErrors())
+            *log << "WARNING: " << inputError << "\n";
+    }
+
+    // Optional 7th Heaven archive, as in the GUI; a missing archive is only
+    // a warning since the loose output is complete
+    if (config.getExportIro()) {
+        m_lastFailure.step = QStringLiteral("iro export");
+        const QString iroPath = QDir(randomizer.getOutputPath())
+                                    .filePath(QString("FF7_AP_%1.iro").arg(config.getSeed()));
+        IroExporter iro(m_ff7Path, randomizer.getOutputPath());
+        QStringList iroLog;
+        QElapsedTimer iroTimer;
+        iroTimer.start();
+        const bool iroOk = iro.exportIro(iroPath, config, iroLog);
+        randomizer.recordPhase("iro export", iroTimer.elapsed());
+        if (log) {
+            for (const QString& line : iroLog)
+                *log << line << "\n";
+            *log << (iroOk ? "Done: .iro export " + iroPath
+                           : QStringLiteral("WARNING: .iro export produced no archive")) << "\n";
+        }
+    }
+
+    m_lastFailure.step = QStringLiteral("seed report");
+    if (!randomizer.writeDebugArtifacts() && log)
+        *log << "WARNING: some debug files could not be written\n";
+
+    const QJsonObject report = SeedSummary::buildReport(config, m_cache.inputHashes(),
+                                                        QDateTime::currentDateTime(),
+                                                        randomizer.moddedFields(),
+                                                        randomizer.kernelRoundtrip(),
+                                                        randomizer.performance(),
+                                                        randomizer.vanillaKeyItems(),
+                                                        randomizer.keyItemSources());
+    if (log) {
+        for (const QString& line : SeedSummary::performanceLines(report))
+            *log << line << "\n";
+        const QMap<QString, QString> vanillaKeyItems = randomizer.vanillaKeyItems();
+        for (auto it = vanillaKeyItems.constBegin(); it != vanillaKeyItems.constEnd(); ++it)
+            *log << "WARNING: key item " << it.key() << " left vanilla (" << it.value() << ")\n";
+    }
+    const QString reportPath = QDir(randomizer.getOutputPath()).filePath(SeedSummary::REPORT_FILE_NAME);
+    if (!SeedSummary::writeReport(report, reportPath, error))
+        return false;
+
+    randomizer.finishOutput();
+    if (outputPath) *outputPath = randomizer.getOutputPath();
+    return true;
+}
+
+namespace {
+struct RerollStep { const char* name; Config::Feature feature; bool (Randomizer::*run)(); };
+const RerollStep kRerollSteps[] = {
+    { "shops",     Config::ShopRandomization,              &Randomizer::randomizeShops },
+    { "pickups",   Config::FieldPickupRandomization,       &Randomizer::randomizeFieldPickups },
+    { "equipment", Config::StartingEquipmentRandomization, &Randomizer::randomizeStartingEquipment },
+};
+} // namespace
+
+QStringList SeedGenerator::rerollSubsystems()
+{
+    QStringList names;
+    for (const RerollStep& step : kRerollSteps)
+        names << QString::fromLatin1(step.name);
+    return names;
+}
+
+bool SeedGenerator::reroll(const Config& config, const QString& subsystem, quint32 salt,
+                           QString* error, QTextStream* log)
+{
+    const RerollStep* step = nullptr;
+    for (const RerollStep& s : kRerollSteps) {
+        if (subsystem == QLatin1String(s.name))
+            step = &s;
+    }
+    if (!step) {
+        if (error) *error = QString("Can't reroll \"%1\" - pick one of %2")
+                                .arg(subsystem, rerollSubsystems().join(", "));
+        return false;
+    }
+    if (!config.isFeatureEnabled(step->feature)) {
+        if (error) *error = QString("The %1 pass is off in this seed").arg(subsystem);
+        return false;
+    }
+    if (salt == 0) {
+        if (error) *error = QStringLiteral("The reroll salt must not be 0");
+        return false;
+    }
+    if (!isLoaded() && !load(error))
+        return false;
+
+    Randomizer randomizer(m_ff7Path, config);
+    randomizer.setInputCache(&m_cache);
+    if (!randomizer.claimOutputFolder(Config::OutputCollisionOverwrite, error))
+        return false;
+    randomizer.setRerollSalt(salt);
+    if (!(randomizer.*step->run)()) {
+        if (error) *error = QString("%1 reroll failed").arg(subsystem);
+        return false;
+    }
+    if (log) *log << "Done: " << subsystem << " (salt " << salt << ")\n";
+
+    // The equipment pass starts from a fresh kernel.bin; redo the seed's
+    // armor changes on top of it (own stream, so unsalted = as generated)
+    if (step->run == &Randomizer::randomizeStartingEquipment
+        && (config.getRandomizeArmorStats() != Config::ArmorVanilla
+            || config.getRandomizeArmorSlots() != Config::ArmorVanilla)) {
+        randomizer.setRerollSalt(0);
+        if (!randomizer.randomizeArmor()) {
+            if (error) *error = QStringLiteral("armor pass failed after the equipment reroll");
+            return false;
+        }
+    }
+
+    if (config.getExportIro()) {
+        const QString iroPath = QDir(randomizer.getOutputPath())
+                                    .filePath(QString("FF7_AP_%1.iro").arg(config.getSeed()));
+        IroExporter iro(m_ff7Path, randomizer.getOutputPath());
+        QStringList iroLog;
+        const bool iroOk = iro.exportIro(iroPath, config, iroLog);
+        if (log) {
+            *log << (iroOk ? "Done: .iro export " + iroPath
+                           : QStringLiteral("WARNING: .iro export produced no archive")) << "\n";
+        }
+    }
+
+    randomizer.finishOutput();
+    return true;
+}
@@ -0,0 +1,80 @@
+#pragma once
+
+#include "InputCache.h"
+#include <QMap>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+class Config;
+class QTextStream;
+class Randomizer;
+
+// SeedGenerator — a long-lived generator for running as a service (a race bot,
+// a web front end): the FF7 inputs are loaded once with load(), then every
+// generate() call produces a full output folder and seed report against the
+// cached data, without re-reading flevel.lgp per seed.
+//
+// Each generate() is an independent run with its own Randomizer, so configs
+// may differ between calls; the GUI runs the same steps() in the same order.
+class SeedGenerator
+{
+public:
+    explicit SeedGenerator(const QString& ff7Path);
+
+    // One pass of a seed. A step with a warning only reports it when it
+    // fails and the seed carries on without it.
+    struct Step {
+        bool enabled;
+        const char* name;       // "enemy stats": Failure::step, log lines
+        const char* title;      // "Enemy Stats": GUI console subsystem
+        int progress;           // GUI progress bar value as the step starts
+        const char* warning;    // nullptr: a failure stops the seed
+        bool (Randomizer::*run)();
+    };
+    // The passes config turns on, in run order (disabled ones included)
+    static QVector<Step> steps(const Config& config);
+
+    bool load(QString* error = nullptr);
+    bool isLoaded() const { return m_cache.isLoaded(); }
+    QString ff7Path() const { return m_ff7Path; }
+    const InputCache& inputCache() const { return m_cache; }
+
+    // Generates one seed into config's output folder (claimed with its
+    // collision policy). outputPath receives the folder actually written;
+    // progress lines go to log when given.
+    bool generate(const Config& config, QString* outputPath = nullptr,
+                  QString* error = nullptr, QTextStream* log = nullptr);
+
+    // Why the last generate() failed, for error dialogs and bug reports: the
+    // step that stopped ("enemy attacks", "seed report"...), its message,
+    // the game files it could not parse, the output folder it was writing and
+    // the debug logs in it (read before the partial folder is removed). A step
+    // that throws fails the seed the same way instead of taking the caller
+    // down. Empty after a successful run.
+    struct Failure {
+        QString step;
+        QString message;
+        QStringList inputErrors;
+        QString outputPath;
+        QMap<QString, QString> logs;    // file name -> text (DiagnosticBundle)
+        bool isEmpty() const { return step.isEmpty(); }
+    };
+    Failure lastFailure() const { return m_lastFailure; }
+
+    // Re-runs one pass ("shops", "pickups" or "equipment") in place over an
+    // existing output folder (config's output folder) with a non-zero salt.
+    // Only the files that pass owns are rewritten; the enemy passes share
+    // scene.bin and can't be rerolled on their own. The .iro is rebuilt when
+    // the config exports one.
+    static QStringList rerollSubsystems();
+    bool reroll(const Config& config, const QString& subsystem, quint32 salt,
+                QString* error = nullptr, QTextStream* log = nullptr);
+
+private:
+    bool runSteps(const Config& config, QString* outputPath, QString* error, QTextStream* log);
+
+    QString m_ff7Path;
+    InputCache m_cache;
+    Failure m_lastFailure;
+};
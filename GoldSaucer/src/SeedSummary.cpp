@@ -0,0 +1,251 @@
+#include "SeedSummary.h"
+#include "SeedCard.h"
+#include "Config.h"
+#include "GameFileLocator.h"
+#include "LogicOverrides.h"
+#include <QCryptographicHash>
+#include <QSysInfo>
+#include <QPair>
+#include <QCoreApplication>
+#include <QJsonDocument>
+#include <QJsonArray>
+#include <QFile>
+#include <QSaveFile>
+#include <QFileInfo>
+#include <QDir>
+
+#if defined(Q_OS_WIN)
+#ifndef NOMINMAX
+#define NOMINMAX
+#endif
+#include <windows.h>
+#include <psapi.h>
+#elif defined(Q_OS_UNIX)
+#include <sys/resource.h>
+#endif
+
+#ifndef GOLDSAUCER_GIT_HASH
+#define GOLDSAUCER_GIT_HASH "unknown"
+#endif
+
+const char* const SeedSummary::REPORT_FILE_NAME = "seed_report.json";
+
+QString SeedSummary::gitHash()
+{
+    return QStringLiteral(GOLDSAUCER_GIT_HASH);
+}
+
+QString SeedSummary::platform()
+{
+    return QString("%1 (%2)").arg(QSysInfo::prettyProductName(),
+                                  QSysInfo::currentCpuArchitecture());
+}
+
+qint64 SeedSummary::peakMemoryBytes()
+{
+#if defined(Q_OS_WIN)
+    // K32 entry point lives in kernel32, so no extra psapi.lib link
+    PROCESS_MEMORY_COUNTERS counters;
+    if (K32GetProcessMemoryInfo(GetCurrentProcess(), &counters, sizeof(counters)))
+        return static_cast<qint64>(counters.PeakWorkingSetSize);
+    return 0;
+#elif defined(Q_OS_UNIX)
+    struct rusage usage;
+    if (getrusage(RUSAGE_SELF, &usage) != 0) return 0;
+#if defined(Q_OS_MACOS)
+    return static_cast<qint64>(usage.ru_maxrss);          // bytes
+#else
+    return static_cast<qint64>(usage.ru_maxrss) * 1024;   // kilobytes
+#endif
+#else
+    return 0;
+#endif
+}
+
+QJsonObject SeedSummary::inputHashes(const QString& ff7Path)
+{
+    const QPair<QString, QString> inputs[] = {
+        { "flevel.lgp",  GameFileLocator::findFlevelLgp(ff7Path) },
+        { "scene.bin",   GameFileLocator::findSceneBin(ff7Path) },
+        { "kernel.bin",  GameFileLocator::findKernelBin(ff7Path) },
+        { "kernel2.bin", GameFileLocator::findKernel2Bin(ff7Path) },
+    };
+    QJsonObject hashes;
+    for (const auto& input : inputs) {
+        QFile file(input.second);
+        if (input.second.isEmpty() || !file.open(QIODevice::ReadOnly)) continue;
+        QCryptographicHash hash(QCryptographicHash::Sha256);
+        hash.addData(&file);
+        hashes[input.first] = QString::fromLatin1(hash.result().toHex());
+    }
+    return hashes;
+}
+
+QStringList SeedSummary::inputMismatches(const QJsonObject& report, const QString& ff7Path)
+{
+    const QJsonObject expected = report["inputs"].toObject();
+    const QJsonObject actual = inputHashes(ff7Path);
+    QStringList mismatches;
+    for (auto it = expected.constBegin(); it != expected.constEnd(); ++it) {
+        if (!actual.contains(it.key()))
+            mismatches << QString("%1 missing").arg(it.key());
+        else if (actual[it.key()].toString() != it.value().toString())
+            mismatches << QString("%1 differs").arg(it.key());
+    }
+    return mismatches;
+}
+
+QJsonObject SeedSummary::buildReport(const Config& config, const QString& ff7Path,
+                                     const QDateTime& generated,
+                                     const QMap<QString, QString>& moddedFields,
+                                     const QMap<int, bool>& kernelRoundtrip,
+                                     const QJsonObject& performance,
+                                     const QMap<QString, QString>& vanillaKeyItems,
+                                     const QMap<QString, QStringList>& keyItemSources)
+{
+    return buildReport(config, inputHashes(ff7Path), generated, moddedFields, kernelRoundtrip,
+                       performance, vanillaKeyItems, keyItemSources);
+}
+
+QJsonObject SeedSummary::buildReport(const Config& config, const QJsonObject& inputs,
+                                     const QDateTime& generated,
+                                     const QMap<QString, QString>& moddedFields,
+                                     const QMap<int, bool>& kernelRoundtrip,
+                                     const QJsonObject& performance,
+                                     const QMap<QString, QString>& vanillaKeyItems,
+                                     const QMap<QString, QStringList>& keyItemSources)
+{
+    QJsonObject report;
+    report["seed"] = static_cast<qint64>(config.getSeed());
+    report["settingsString"] = config.toShareString();
+    report["hash"] = SeedCard::settingsHash(config);
+    report["flags"] = QJsonArray::fromStringList(SeedCard::settingsSummary(config));
+    report["generated"] = generated.toUTC().toString(Qt::ISODate);
+    report["version"] = QCoreApplication::applicationVersion();
+    report["gitHash"] = gitHash();
+    report["platform"] = platform();
+    report["inputs"] = inputs;
+    if (!moddedFields.isEmpty()) {
+        QJsonObject modded;
+        for (auto it = moddedFields.constBegin(); it != moddedFields.constEnd(); ++it)
+            modded[it.key()] = it.value();
+        report["moddedFields"] = modded;
+    }
+    if (!kernelRoundtrip.isEmpty()) {
+        QJsonObject sections;
+        for (auto it = kernelRoundtrip.constBegin(); it != kernelRoundtrip.constEnd(); ++it)
+            sections[QString::number(it.key())] = it.value();
+        report["kernelRoundtrip"] = sections;
+    }
+    if (!performance.isEmpty())
+        report["performance"] = performance;
+    if (!vanillaKeyItems.isEmpty()) {
+        QJsonObject fallbacks;
+        for (auto it = vanillaKeyItems.constBegin(); it != vanillaKeyItems.constEnd(); ++it)
+            fallbacks[it.key()] = it.value();
+        report["vanillaKeyItems"] = fallbacks;
+    }
+    if (!keyItemSources.isEmpty()) {
+        QJsonObject sources;
+        for (auto it = keyItemSources.constBegin(); it != keyItemSources.constEnd(); ++it)
+            sources[it.key()] = QJsonArray::fromStringList(it.value());
+        report["keyItemSources"] = sources;
+    }
+    // Logic overrides are not part of the settings string, so a seed built
+    // with them says so here
+    if (config.isFeatureEnabled(Config::FieldPickupRandomization) && config.getKeyItemRandomization()
+        && !LogicOverrides::instance().isEmpty())
+        report["logicOverrides"] = LogicOverrides::instance().toJson();
+    return report;
+}
+
+QStringList SeedSummary::performanceLines(const QJsonObject& report)
+{
+    const QJsonObject perf = report["performance"].toObject();
+    if (perf.isEmpty()) return {};
+
+    auto seconds = [](const QJsonValue& ms) {
+        return QString::number(ms.toVariant().toLongLong() / 1000.0, 'f', 1) + "s";
+    };
+    QStringList phases;
+    for (const QJsonValue& value : perf["phases"].toArray()) {
+        const QJsonObject phase = value.toObject();
+        phases << QString("%1 %2").arg(phase["name"].toString(), seconds(phase["ms"]));
+    }
+    QStringList lines;
+    lines << QString("Timing: %1 total (%2)").arg(seconds(perf["totalMs"]), phases.join(", "));
+    if (perf.contains("fieldsScanned"))
+        lines << QString("Fields scanned: %1, recompressed: %2 KB")
+                     .arg(perf["fieldsScanned"].toInt())
+                     .arg(perf["bytesRecompressed"].toVariant().toLongLong() / 1024);
+    const qint64 peak = perf["peakMemoryBytes"].toVariant().toLongLong();
+    if (peak > 0)
+        lines << QString("Peak memory: %1 MB").arg(peak / (1024 * 1024));
+    return lines;
+}
+
+bool SeedSummary::writeReport(const QJsonObject& report, const QString& path, QString* error)
+{
+    QSaveFile file(path);
+    if (!file.open(QIODevice::WriteOnly)) {
+        if (error) *error = QString("Cannot write %1").arg(path);
+        return false;
+    }
+    file.write(QJsonDocument(report).toJson(QJsonDocument::Indented));
+    if (!file.commit()) {
+        if (error) *error = QString("Cannot write %1").arg(path);
+        return false;
+    }
+    return true;
+}
+
+bool SeedSummary::readReport(const QString& path, QJsonObject& report, QString* error)
+{
+    const QString reportPath = QFileInfo(path).isDir()
+        ? QDir(path).filePath(REPORT_FILE_NAME) : path;
+    QFile file(reportPath);
+    if (!file.open(QIODevice::ReadOnly)) {
+        if (error) *error = QString("Cannot read %1").arg(reportPath);
+        return false;
+    }
+    QJsonParseError parseError;
+    const QJsonDocument doc = QJsonDocument::fromJson(file.readAll(), &parseError);
+    if (!doc.isObject() || !doc.object().contains("settingsString")) {
+        if (error) *error = QString("%1 is not a seed report: %2")
+                                .arg(reportPath, parseError.errorString());
+        return false;
+    }
+    report = doc.object();
+    return true;
+}
+
+QString SeedSummary::markdown(const QJsonObject& report)
+{
+    QStringList flags;
+    for (const QJsonValue& flag : report["flags"].toArray())
+        flags << flag.toString();
+
+    QString text;
+    text += QString("**Gold Saucer seed %1** (hash `%2`)\n")
+                .arg(report["seed"].toVariant().toLongLong())
+                .arg(report["hash"].toString());
+    text += "```\n" + report["settingsString"].toString() + "\n```\n";
+    for (const QString& flag : flags)
+        text += "- " + flag + "\n";
+    text += QString("Generated %1 with Gold Saucer %2 (%3)\n")
+                .arg(report["generated"].toString(), report["version"].toString(),
+                     report["gitHash"].toString("unknown"));
+    const int modded = report["moddedFields"].toObject().size();
+    if (modded > 0)
+        text += QString("%1 modded field(s) kept their vanilla pickup texts\n").arg(modded);
+    const QStringList vanillaKeyItems = report["vanillaKeyItems"].toObject().keys();
+    if (!vanillaKeyItems.isEmpty())
+        text += QString("Key items left vanilla: %1\n").arg(vanillaKeyItems.join(", "));
+    const QStringList duplicateSources = report["keyItemSources"].toObject().keys();
+    if (!duplicateSources.isEmpty())
+        text += QString("Key items with several vanilla grants: %1\n").arg(duplicateSources.join(", "));
+    const int overrides = report["logicOverrides"].toArray().size();
+    if (overrides > 0)
+        text += QString("Key items placed with %1 logic override rule(s)\n").arg(overrides);
+    return text;
+}
@@ -0,0 +1,76 @@
+#pragma once
+
+#include <QString>
+#include <QJsonObject>
+#include <QDateTime>
+#include <QMap>
+#include <QStringList>
+
+class Config;
+
+// SeedSummary — the structured record of one generation (seed_report.json in
+// the output folder) and the short markdown block built from it for race
+// threads. The report holds the settings string, hash, seed, settings summary,
+// generation time and Gold Saucer version; `--summary` re-renders the markdown
+// from a saved report, so the block can be produced again later.
+//
+// For bug reports the record is also a reproduction manifest: it carries the
+// build (version, git hash), the platform and SHA-256 hashes of the input game
+// files, and `--reproduce` re-runs a generation from it with identical settings.
+class SeedSummary
+{
+public:
+    // ff7Path locates the input files to hash (see inputHashes). moddedFields
+    // lists fields with a non-vanilla layout (Randomizer::moddedFields) and is
+    // stored as "moddedFields" when not empty. kernelRoundtrip is the per-section
+    // check of a rebuilt kernel.bin (Randomizer::kernelRoundtrip), stored as
+    // "kernelRoundtrip" {"<section>": exact} when a kernel.bin was written.
+    // performance (Randomizer::performance) is stored as is when not empty.
+    // vanillaKeyItems (Randomizer::vanillaKeyItems) is stored as
+    // "vanillaKeyItems" {"<key item>": reason} when not empty. keyItemSources
+    // (Randomizer::keyItemSources) is stored as "keyItemSources"
+    // {"<key item>": ["<field> @<offset>: <status>", ...]} when not empty.
+    static QJsonObject buildReport(const Config& config, const QString& ff7Path,
+                                   const QDateTime& generated,
+                                   const QMap<QString, QString>& moddedFields = {},
+                                   const QMap<int, bool>& kernelRoundtrip = {},
+                                   const QJsonObject& performance = {},
+                                   const QMap<QString, QString>& vanillaKeyItems = {},
+                                   const QMap<QString, QStringList>& keyItemSources = {});
+    // Same, with input hashes already computed (InputCache::inputHashes)
+    static QJsonObject buildReport(const Config& config, const QJsonObject& inputs,
+                                   const QDateTime& generated,
+                                   const QMap<QString, QString>& moddedFields = {},
+                                   const QMap<int, bool>& kernelRoundtrip = {},
+                                   const QJsonObject& performance = {},
+                                   const QMap<QString, QString>& vanillaKeyItems = {},
+                                   const QMap<QString, QStringList>& keyItemSources = {});
+
+    static bool writeReport(const QJsonObject& report, const QString& path,
+                            QString* error = nullptr);
+    // Accepts the report file itself or the output folder containing it
+    static bool readReport(const QString& path, QJsonObject& report,
+                           QString* error = nullptr);
+
+    // Discord-friendly markdown: a heading, the settings string in a code
+    // block, then hash / flags / time / version lines
+    static QString markdown(const QJsonObject& report);
+
+    // SHA-256 of the vanilla files the randomizers read, keyed by file name
+    // (flevel.lgp, scene.bin, kernel.bin, kernel2.bin). Missing files are left out.
+    static QJsonObject inputHashes(const QString& ff7Path);
+    // Input files whose hash differs from (or is missing compared to) a report
+    static QStringList inputMismatches(const QJsonObject& report, const QString& ff7Path);
+
+    // Console lines for a report's "performance" block: time per phase,
+    // fields scanned / bytes recompressed and peak memory. Empty without one.
+    static QStringList performanceLines(const QJsonObject& report);
+
+    static QString gitHash();
+    static QString platform();
+    // Peak resident memory of this process so far, in bytes (0 if unknown).
+    // A batch run reports the peak across every seed generated before it.
+    static qint64 peakMemoryBytes();
+
+    static const char* const REPORT_FILE_NAME;   // "seed_report.json"
+};
@@ -1,6 +1,7 @@
 #include "ShopRandomizer.h"
 #include "Randomizer.h"
 #include "Config.h"
+#include "FieldPickupRandomizer_ff7tk.h"
 #include <QFile>
 #include <QDir>
 #include <QDebug>
@@ -8,7 +9,10 @@
 #include <QJsonDocument>
 #include <QJsonObject>
 #include <QJsonArray>
+#include <QHash>
+#include <QMap>
 #include <algorithm>
+#include <cmath>
 #include <cstring>
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -89,6 +93,9 @@ bool ShopRandomizer::randomize()
         return false;
     }
     buildTieredPools(log);
+    if (m_parent->m_config.getBalanceEconomy())
+        balancePrices(log);
+    logApShopPrices(log);
 
     // --- randomize -----------------------------------------------------------
     int modified = 0;
@@ -118,6 +125,18 @@ bool ShopRandomizer::randomize()
 
     if (logOk) log << "\nShops randomized: " << modified << " / " << shops.size() << "\n";
 
+    // --- shop location slots (AP mode places its own checks) -----------------
+    if (m_parent->m_config.getShopLocationSlot()) {
+        if (m_parent->m_config.isFeatureEnabled(Config::ArchipelagoIntegration))
+            log << "Shop location slots: skipped in Archipelago mode (AP shop slots are the checks)\n";
+        else
+            placeLocationSlots(shops, log);
+    }
+
+    // --- price randomization (after all normal stock is known) ---------------
+    if (m_parent->m_config.getRandomizeShopPrices())
+        randomizePrices(shops, log);
+
     // --- inject Archipelago shop slots (token items) -------------------------
     applyApShops(shops, log);
 
@@ -133,6 +152,7 @@ bool ShopRandomizer::randomize()
         if (logOk) log << "ERROR: Failed to generate hext patch\n";
         return false;
     }
+    writeSpoiler(outputPath, shops, log);
 
     if (logOk) {
         log << "SUCCESS: Hext patch generated in hext/ff7/en/\n";
@@ -324,24 +344,34 @@ bool ShopRandomizer::generateHextPatch(const QString& outputPath, const QVector<
 
     // AP tokens use ids whose price-table entry may be 0 (free) — materia gap-ids and
     // item placeholder/never-sold ids alike. A free purchase deducts no gil and may
-    // not be sellable at all, so write a fixed price for every AP token. (Item tokens
+    // not be sellable at all, so write a price for every AP token (see apSlotPrice()). (Item tokens
     // were reworked 2026-06-23 to ids NOT sold in ANY shop — placeholder ids 0x69-0x7F
     // + never-sold real items — so the shophook can't mislabel a real shop item; many
     // of those ids have no/garbage price, hence they now need this write too.)
     // Price tables: materia @ SHOP_INVENTORY_VA + MATERIA_PRICE_DELTA, items @
     // SHOP_INVENTORY_VA + ITEM_PRICE_DELTA (constant VAs across builds).
-    const quint32 AP_TOKEN_PRICE = 100;           // nonzero + affordable anywhere
-    auto writePrice = [&](qint64 priceAddr) {
+    auto writePrice = [&](qint64 priceAddr, quint32 price) {
         QString priceBytes;
         for (int b = 0; b < 4; ++b)
-            priceBytes += QString("%1 ").arg((AP_TOKEN_PRICE >> (b * 8)) & 0xFF,
+            priceBytes += QString("%1 ").arg((price >> (b * 8)) & 0xFF,
                                              2, 16, QChar('0')).toUpper();
         hext << QString::number(priceAddr, 16).toUpper() << " = " << priceBytes.trimmed() << "\n";
     };
+    // Economy-balanced prices first; AP token ids are never in the pools, so
+    // the token prices below can't be overridden by them.
+    for (const auto& p : m_priceOverrides)
+        writePrice(p.first, p.second);
+    // Prices are per token id; a token sold in several shops takes the lowest.
+    QMap<qint64, quint32> tokenPrices;
     for (const ApShopSlot& e : m_apShops) {
         const qint64 delta = e.isMateria ? MATERIA_PRICE_DELTA : ITEM_PRICE_DELTA;
-        writePrice(SHOP_INVENTORY_VA + delta + static_cast<qint64>(e.token) * 4);
+        const qint64 addr  = SHOP_INVENTORY_VA + delta + static_cast<qint64>(e.token) * 4;
+        const quint32 price = apSlotPrice(e);
+        if (!tokenPrices.contains(addr) || price < tokenPrices[addr])
+            tokenPrices[addr] = price;
     }
+    for (auto it = tokenPrices.constBegin(); it != tokenPrices.constEnd(); ++it)
+        writePrice(it.key(), it.value());
 
     hextFile.close();
     qDebug() << "ShopRandomizer: Hext patch written to" << hextPath;
@@ -381,15 +411,164 @@ void ShopRandomizer::loadApShops(QTextStream& log)
         const int maxToken = isMateria ? MATERIA_MAX_ID : (COMPOSITE_COUNT - 1);
         if (shopId < 0 || token < 0 || token > maxToken)
             continue;
-        m_apShops.append({ shopId, static_cast<quint16>(token), isMateria });
+        const quint32 price = o.contains("price")
+                                  ? static_cast<quint32>(qBound(1, o.value("price").toInt(1), 999999))
+                                  : 0;
+        const bool progression = o.value("progression").toBool(false);
+        m_apShops.append({ shopId, static_cast<quint16>(token), isMateria, price, progression });
         if (isMateria) m_reservedMateria.insert(static_cast<quint16>(token));
         else           m_reservedTokens.insert(static_cast<quint16>(token));
     }
-    log << "AP shops: " << m_apShops.size() << " slot(s), "
+"    log << "AP shops: " << m_apShops.size() << " slot(s), "
         << m_reservedTokens.size() << " item + " << m_reservedMateria.size()
         << " materia reserved token id(s)\n";
 }
 
+int ShopRandomizer::apItemTier(const ApShopSlot& slot) const
+{
+    int cat = CatMateria;
+    if (!slot.isMateria) {
+        cat = slot.token >= ACCESSORY_START ? CatAccessory
+            : slot.token >= ARMOR_START     ? CatArmor
+            : slot.token >= WEAPON_START    ? CatWeapon
+                                            : CatItem;
+        if (cat == CatItem && slot.token >= ITEM_COUNT)
+            return shopTier(slot.shopId);   // placeholder id
+    }
+    const QVector<quint32>& table = slot.isMateria ? m_materiaPrices : m_itemPrices;
+    const quint32 price = slot.token < table.size() ? table[slot.token] : 0;
+    if (price < SELLABLE_MIN)
+        return shopTier(slot.shopId);
+
+    // Above the dearest item of a tier = a later tier
+    int tier = 0;
+    for (int t = 0; t < NUM_TIERS - 1; ++t) {
+        quint32 top = 0;
+        for (quint16 id : m_pool[cat][t])
+            top = std::max(top, table[id]);
+        if (price > top) tier = t + 1;
+    }
+    return std::max(tier, progressionFloor(cat, slot.token));
+}
+
+quint32 ShopRandomizer::apSlotPrice(const ApShopSlot& slot) const
+{
+    const bool balance = m_parent->m_config.getBalanceEconomy();
+    quint32 price = slot.price;
+    if (price == 0) {
+        const int tier = apItemTier(slot);
+        double base = LOCATION_SLOT_PRICE[tier];
+        if (balance)
+            base *= m_parent->economy().priceFactor(tier);
+        price = std::max<quint32>(1, static_cast<quint32>(std::lround(base)));
+    }
+    if (!slot.progression) return price;
+
+    // Logic expects a progression purchase as soon as its shop is reachable,
+    // so it must not cost more than a player has at that point. With economy
+    // balancing the cap follows the tier's income.
+    const int tier = shopTier(slot.shopId);
+    double cap = PROGRESSION_PRICE_CAP[tier];
+    if (balance)
+        cap *= m_parent->economy().priceFactor(tier);
+    return std::min(price, std::max<quint32>(1, static_cast<quint32>(std::lround(cap))));
+}
+
+void ShopRandomizer::logApShopPrices(QTextStream& log) const
+{
+    for (const ApShopSlot& e : m_apShops) {
+        log << "AP shop " << e.shopId << " (" << shopName(e.shopId) << "): token 0x"
+            << QString::number(e.token, 16) << " item tier " << apItemTier(e) << ", "
+            << (e.price ? QString("price %1").arg(e.price) : QStringLiteral("tier price"))
+            << (e.progression ? " (progression)" : "") << " -> " << apSlotPrice(e) << " gil\n";
+    }
+}
+
+void ShopRandomizer::placeLocationSlots(QVector<ExeShopRecord>& shops, QTextStream& log)
+{
+    log << "\n=== Shop location slots ===\n";
+    m_locationSlots.clear();
+    QMap<quint16, quint32> addedPrices;   // unpriced id -> lowest tier price
+    int placed = 0;
+    for (int i = 0; i < shops.size() && i < NUM_SHOPS; ++i) {
+        ExeShopRecord& s = shops[i];
+        const ExeShopType t = s.shopType;
+        if (t == ExeShopType::Hotel || t == ExeShopType::Vegetable
+            || t == ExeShopType::Materia || s.itemCount == 0)
+            continue;
+        // Only added alongside the normal stock, never in place of it
+        if (s.itemCount >= ExeShopRecord::SLOT_COUNT) {
+            log << "Shop " << i << " (" << shopName(i) << "): SKIP (stock full)\n";
+            continue;
+        }
+
+        // Re-draw a few times rather than duplicate the shop's own stock
+        quint16 item = drawPoolItem();
+        for (int attempt = 0; attempt < 8; ++attempt) {
+            bool duplicate = false;
+            for (int k = 0; k < s.itemCount && k < ExeShopRecord::SLOT_COUNT; ++k)
+                duplicate |= (s.entries[k].type == 0 && s.entries[k].index == item);
+            if (!duplicate) break;
+            item = drawPoolItem();
+        }
+
+        const int slot = s.itemCount;
+        s.entries[slot].type    = 0;
+        s.entries[slot].index   = item;
+        s.entries[slot].padding = 0;
+        s.itemCount = static_cast<quint8>(slot + 1);
+        m_locationSlots.insert(i * ExeShopRecord::SLOT_COUNT + slot);
+
+        QString priceNote;
+        if (item < COMPOSITE_COUNT && m_itemPrices[item] < SELLABLE_MIN) {
+            // Never sold in vanilla: without a price it would be free
+            const quint32 price = LOCATION_SLOT_PRICE[shopTier(i)];
+            if (!addedPrices.contains(item) || price < addedPrices[item])
+                addedPrices[item] = price;
+            priceNote = QString(" (unpriced -> %1 gil)").arg(price);
+        }
+        log << "Shop " << i << " (" << shopName(i) << "): slot " << slot
+            << " -> item 0x" << QString::number(item, 16) << priceNote << "\n";
+        placed++;
+    }
+
+    for (auto it = addedPrices.constBegin(); it != addedPrices.constEnd(); ++it)
+        setPriceOverride(SHOP_INVENTORY_VA + ITEM_PRICE_DELTA + static_cast<qint64>(it.key()) * 4,
+                         it.value());
+    log << "Location slots placed: " << placed << "\n\n";
+}
+
+quint16 ShopRandomizer::drawPoolItem()
+{
+    // Same weighted categories and exclusions as
+    // FieldPickupRandomizer_ff7tk::getWeightedItem, drawn with the seeded shop RNG
+    const Config& config = m_parent->m_config;
+    QVector<QVector<quint16>> pools(Config::PickupCategoryCount);
+    int totalWeight = 0;
+    for (int c = 0; c < Config::PickupCategoryCount; ++c) {
+        auto category = static_cast<Config::PickupCategory>(c);
+        pools[c] = FieldPickupRandomizer_ff7tk::pickupCategoryItems(c);
+        if (config.isPickupCategoryEnabled(category) && !pools[c].isEmpty())
+            totalWeight += config.getPickupCategoryWeight(category);
+    }
+
+    if (totalWeight > 0) {
+        int roll = std::uniform_int_distribution<int>(0, totalWeight - 1)(m_rng);
+        for (int c = 0; c < Config::PickupCategoryCount; ++c) {
+            auto category = static_cast<Config::PickupCategory>(c);
+            if (!config.isPickupCategoryEnabled(category) || pools[c].isEmpty()) continue;
+            roll -= config.getPickupCategoryWeight(category);
+            if (roll < 0)
+                return pools[c][std::uniform_int_distribution<int>(0, pools[c].size() - 1)(m_rng)];
+        }
+    }
+
+    // Every category excluded: the pickups' fallback pool (getRandomItem(1),
+    // consumables + battle items)
+    const QVector<quint16> fallback = pools[Config::PickupConsumables] + pools[Config::PickupBattleItems];
+    return fallback[std::uniform_int_distribution<int>(0, fallback.size() - 1)(m_rng)];
+}
+
 void ShopRandomizer::applyApShops(QVector<ExeShopRecord>& shops, QTextStream& log)
 {
     for (const ApShopSlot& e : m_apShops) {
@@ -581,12 +760,19 @@ void ShopRandomizer::buildTieredPools(QTextStream& log)
                       return a.first < b.first;  // ascending by price
                   });
         const int n = priced.size();
-        for (int t = 0; t < NUM_TIERS; ++t) {
+        for (int t = 0; t < NUM_TIERS; ++t)
             m_pool[cat][t].clear();
+        for (int t = 0; t < NUM_TIERS; ++t) {
             const int lo = n * t / NUM_TIERS;
             const int hi = n * (t + 1) / NUM_TIERS;
-            for (int k = lo; k < hi; ++k)
-                m_pool[cat][t].append(priced[k].second);
+            for (int k = lo; k < hi; ++k) {
+                const quint16 id = priced[k].second;
+                const int floor = progressionFloor(cat, id);
+                if (floor > t)
+                    log << "  cat " << cat << " id " << id << ": price tier " << t
+                        << " raised to progression tier " << floor << "\n";
+                m_pool[cat][std::max(t, floor)].append(id);
+            }
         }
         log << "  pool cat " << cat << ": " << n << " sellable -> tiers ["
             << m_pool[cat][0].size() << "," << m_pool[cat][1].size() << ","
@@ -627,6 +813,93 @@ void ShopRandomizer::buildTieredPools(QTextStream& log)
     }
 }
 
+void ShopRandomizer::balancePrices(QTextStream& log)
+{
+    m_priceOverrides.clear();
+    const EconomyBalancer& economy = m_parent->economy();
+
+    log << "Economy balancing (price factor per tier):";
+    for (int t = 0; t < NUM_TIERS; ++t)
+        log << " " << QString::number(economy.priceFactor(t), 'f', 2);
+    log << "\n";
+
+    // Price tiers come from the pools: the cheapest third of each category is
+    // what early shops stock, so it follows the early-game income.
+    for (int cat = 0; cat < CatCOUNT; ++cat) {
+        const bool materia = (cat == CatMateria);
+        const qint64 delta = materia ? MATERIA_PRICE_DELTA : ITEM_PRICE_DELTA;
+        for (int t = 0; t < NUM_TIERS; ++t) {
+            const double factor = economy.priceFactor(t);
+            if (factor == 1.0) continue;
+            for (quint16 id : m_pool[cat][t]) {
+                const quint32 price = materia ? m_materiaPrices[id] : m_itemPrices[id];
+                const quint32 scaled = std::max<quint32>(SELLABLE_MIN + 1,
+                    static_cast<quint32>(std::lround(price * factor)));
+                if (scaled == price) continue;
+                m_priceOverrides.append(qMakePair(SHOP_INVENTORY_VA + delta + static_cast<qint64>(id) * 4,
+                                                  scaled));
+            }
+        }
+    }
+    log << "  " << m_priceOverrides.size() << " prices rescaled\n\n";
+}
+
+void ShopRandomizer::setPriceOverride(qint64 priceVa, quint32 price)
+{
+    // The hext writes overrides in order, so the last entry for an address wins
+    for (auto it = m_priceOverrides.rbegin(); it != m_priceOverrides.rend(); ++it) {
+        if (it->first == priceVa) {
+            it->second = price;
+            return;
+        }
+    }
+    m_priceOverrides.append(qMakePair(priceVa, price));
+}
+
+void ShopRandomizer::randomizePrices(const QVector<ExeShopRecord>& shops, QTextStream& log)
+{
+    const double variance = qBound(0.0, m_parent->m_config.getShopPriceVariance(), 1.0);
+    log << "=== Price randomization (+/-" << qRound(variance * 100) << "%) ===\n";
+
+    // (slot type, id) -> tier of the earliest shop that stocks it
+    QMap<QPair<qint32, quint16>, int> stocked;
+    for (int i = 0; i < shops.size() && i < NUM_SHOPS; ++i) {
+        const ExeShopRecord& s = shops[i];
+        if (s.shopType == ExeShopType::Hotel || s.shopType == ExeShopType::Vegetable)
+            continue;
+        for (int k = 0; k < s.itemCount && k < ExeShopRecord::SLOT_COUNT; ++k) {
+            const ExeShopSlot& slot = s.entries[k];
+            if (isApToken(slot)) continue;
+            const QPair<qint32, quint16> key(slot.type, slot.index);
+            const int tier = shopTier(i);
+            if (!stocked.contains(key) || tier < stocked[key])
+                stocked[key] = tier;
+        }
+    }
+
+    std::uniform_real_distribution<double> roll(1.0 - variance, 1.0 + variance);
+    int changed = 0;
+    for (auto it = stocked.constBegin(); it != stocked.constEnd(); ++it) {
+        const bool materia = (it.key().first == 1);
+        const quint16 id = it.key().second;
+        const quint32 base = finalPrice(ExeShopSlot{ it.key().first, id, 0 });
+        if (base < SELLABLE_MIN) continue;
+
+        quint32 price = static_cast<quint32>(std::lround(base * roll(m_rng) / 10.0)) * 10;
+        price = std::max(price, PRICE_FLOOR);
+        if (!materia && id < ITEM_COUNT)
+            price = std::min(price, std::max(base, CONSUMABLE_PRICE_CAP[it.value()]));
+        if (price == base) continue;
+
+        setPriceOverride(SHOP_INVENTORY_VA + (materia ? MATERIA_PRICE_DELTA : ITEM_PRICE_DELTA)
+                             + static_cast<qint64>(id) * 4, price);
+        log << "  " << (materia ? "materia" : "item") << " 0x" << QString::number(id, 16)
+            << ": " << base << " -> " << price << " (tier " << it.value() << ")\n";
+        ++changed;
+    }
+    log << "Prices randomized: " << changed << " / " << stocked.size() << "\n\n";
+}
+
 int ShopRandomizer::shopTier(int id) const
 {
     // World-progression tiers (see shopName()): 0 = early, 1 = mid, 2 = late.
@@ -637,6 +910,37 @@ int ShopRandomizer::shopTier(int id) const
     return 2;                                               // Wutai, Temple, Icicle, Mideel, Bone Village
 }
 
+int ShopRandomizer::progressionFloor(int category, quint16 id)
+{
+    static const QHash<quint16, int> kCompositeFloors = {
+        { 0x05, 1 },   // Elixir
+        { 0x06, 2 },   // Megalixir
+        { 0x126, 2 },  // Champion Belt
+        { 0x130, 2 },  // Sprint Shoes
+        { 0x132, 2 },  // Ribbon
+        { 0x136, 2 },  // Tetra Elemental
+        { 0x13A, 2 },  // Protect Ring
+        { 0x13B, 1 },  // Cat's Bell
+    };
+    static const QHash<quint16, int> kMateriaFloors = {
+        { 0x0C, 2 },   // Mega All
+        { 0x12, 1 },   // HP <-> MP
+        { 0x13, 2 },   // W-Magic
+        { 0x14, 2 },   // W-Summon
+        { 0x15, 2 },   // W-Item
+        { 0x23, 2 },   // Quadra Magic
+        { 0x2B, 2 },   // Mime
+        { 0x46, 2 },   // Full Cure
+        { 0x48, 2 },   // Ultima
+        { 0x55, 2 },   // Neo Bahamut
+        { 0x56, 2 },   // Hades
+        { 0x57, 2 },   // Typhon
+        { 0x58, 2 },   // Bahamut ZERO
+    };
+    const QHash<quint16, int>& floors = (category == CatMateria) ? kMateriaFloors : kCompositeFloors;
+    return floors.value(id, 0);
+}
+
 quint16 ShopRandomizer::pickTiered(int category, int tier) const
 {
     if (category < 0 || category >= CatCOUNT)
@@ -649,6 +953,27 @@ quint16 ShopRandomizer::pickTiered(int category, int tier) const
     if (category == CatWeapon || category == CatArmor || category == CatAccessory)
         maxTier = EQUIP_MAX_TIER;
     if (tier > maxTier) tier = maxTier;
+
+    // Loose draws from this tier and every earlier one, Off from any tier; the
+    // equipment cap still applies to both.
+    const int strictness = m_parent->m_config.getShopTierStrictness();
+    if (strictness < 2) {
+        const int top = (strictness == 0) ? maxTier : tier;
+        int total = 0;
+        for (int t = 0; t <= top; ++t)
+            total += m_pool[category][t].size();
+        if (total > 0) {
+            int idx = std::uniform_int_distribution<int>(0, total - 1)(
+                const_cast<std::mt19937&>(m_rng));
+            for (int t = 0; t <= top; ++t) {
+                const QVector<quint16>& pool = m_pool[category][t];
+                if (idx < pool.size())
+                    return pool[idx];
+                idx -= pool.size();
+            }
+        }
+    }
+
     // Prefer the requested tier; fall back to the nearest non-empty tier (within
     // [0, maxTier]) so a sparse category (e.g. armor) never leaves a slot unfilled.
     for (int d = 0; d < NUM_TIERS; ++d) {
@@ -688,6 +1013,112 @@ quint16 ShopRandomizer::randomFromCategory(ExeShopType shopType, int tier) const
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Spoiler
+// ─────────────────────────────────────────────────────────────────────────────
+
+bool ShopRandomizer::isApToken(const ExeShopSlot& slot) const
+{
+    return slot.type == 1 ? m_reservedMateria.contains(slot.index)
+                          : m_reservedTokens.contains(slot.index);
+}
+
+QString ShopRandomizer::stockName(const ExeShopSlot& slot) const
+{
+    if (isApToken(slot))
+        return QStringLiteral("Archipelago item");
+    QString name = slot.type == 1
+        ? m_parent->installNames().materiaName(static_cast<quint8>(slot.index))
+        : m_parent->installNames().itemName(slot.index);
+    if (!name.isEmpty()) return name;
+    return slot.type == 1
+        ? FieldPickupRandomizer_ff7tk::getCanonicalMateriaName(static_cast<quint8>(slot.index))
+        : FieldPickupRandomizer_ff7tk::getCanonicalItemName(slot.index);
+}
+
+quint32 ShopRandomizer::finalPrice(const ExeShopSlot& slot) const
+{
+    const bool materia = (slot.type == 1);
+    if (isApToken(slot)) {
+        // Same rule as the hext: the lowest price of any slot selling the token
+        quint32 best = 0;
+        for (const ApShopSlot& e : m_apShops) {
+            if (e.isMateria != materia || e.token != slot.index) continue;
+            const quint32 p = apSlotPrice(e);
+            if (best == 0 || p < best) best = p;
+        }
+        return best;
+    }
+    const qint64 addr = SHOP_INVENTORY_VA + (materia ? MATERIA_PRICE_DELTA : ITEM_PRICE_DELTA)
+                      + static_cast<qint64>(slot.index) * 4;
+    // Last match, as the hext writes them in order
+    for (auto it = m_priceOverrides.crbegin(); it != m_priceOverrides.crend(); ++it) {
+        if (it->first == addr) return it->second;
+    }
+    const QVector<quint32>& table = materia ? m_materiaPrices : m_itemPrices;
+    return slot.index < table.size() ? table[slot.index] : 0;
+}
+
+bool ShopRandomizer::writeSpoiler(const QString& outputPath, const QVector<ExeShopRecord>& shops,
+                                  QTextStream& log) const
+{
+    QFile textFile(QDir(outputPath).filePath("shop_spoiler.txt"));
+    if (!textFile.open(QIODevice::WriteOnly | QIODevice::Text)) {
+        log << "Shop spoiler: cannot write " << textFile.fileName() << "\n";
+        return false;
+    }
+    QTextStream out(&textFile);
+    out << "=== Shop Spoiler ===\n"
+        << QDateTime::currentDateTime().toString() << "\n"
+        << "Seed: " << m_parent->m_config.getSeed() << "\n\n";
+
+    QJsonArray shopArray;
+    for (int i = 0; i < shops.size() && i < NUM_SHOPS; ++i) {
+        const ExeShopRecord& s = shops[i];
+        if (s.shopType == ExeShopType::Hotel || s.itemCount == 0) continue;
+
+        out << "[" << i << "] " << shopName(i) << " (tier " << shopTier(i) << ")\n";
+        QJsonArray stock;
+        for (int k = 0; k < s.itemCount && k < ExeShopRecord::SLOT_COUNT; ++k) {
+            const ExeShopSlot& e = s.entries[k];
+            const quint32 price = finalPrice(e);
+            QString tag;
+            if (isApToken(e))                                              tag = "  [AP]";
+            else if (m_locationSlots.contains(i * ExeShopRecord::SLOT_COUNT + k)) tag = "  [pickup slot]";
+            out << QString("    %1 %2 gil%3\n").arg(stockName(e), -24).arg(price, 7).arg(tag);
+
+            QJsonObject entry;
+            entry["slot"]    = k;
+            entry["type"]    = e.type == 1 ? "materia" : "item";
+            entry["id"]      = e.index;
+            entry["name"]    = stockName(e);
+            entry["price"]   = static_cast<qint64>(price);
+            entry["ap"]      = isApToken(e);
+            entry["pickupSlot"] = m_locationSlots.contains(i * ExeShopRecord::SLOT_COUNT + k);
+            stock.append(entry);
+        }
+        out << "\n";
+
+        QJsonObject shop;
+        shop["shop_id"] = i;
+        shop["name"]    = shopName(i);
+        shop["tier"]    = shopTier(i);
+        shop["stock"]   = stock;
+        shopArray.append(shop);
+    }
+
+    // Machine-readable copy for trackers
+    QJsonObject root;
+    root["seed"]  = static_cast<qint64>(m_parent->m_config.getSeed());
+    root["shops"] = shopArray;
+    QFile jsonFile(QDir(outputPath).filePath("shop_spoiler.json"));
+    if (jsonFile.open(QIODevice::WriteOnly))
+        jsonFile.write(QJsonDocument(root).toJson(QJsonDocument::Indented));
+
+    log << "Shop spoiler written: " << textFile.fileName() << " (" << shopArray.size() << " shops)\n";
+    return true;
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Shop name table (for debug log readability)
 // ─────────────────────────────────────────────────────────────────────────────
@@ -91,11 +91,31 @@ private:
     // forced game_moment, so the AP token slots survive the story-branch swap.
     void mirrorFreeRoamStoryShops(QVector<ExeShopRecord>& shops, QTextStream& log);
 
-    struct ApShopSlot { int shopId; quint16 token; bool isMateria; };
+    // The base price follows the token's price tier (apItemTier), unless the
+    // slot gives its own "price" (0 = none); slots flagged "progression" are
+    // then capped by the shop's tier so logic can expect the player to afford
+    // them when that shop opens. Needs the tiered pools (buildTieredPools).
+    struct ApShopSlot { int shopId; quint16 token; bool isMateria; quint32 price; bool progression; };
+    quint32 apSlotPrice(const ApShopSlot& slot) const;
+    // Tier the token's own price falls in among its category's pools; ids
+    // with no real price (placeholders, never-sold items) take the shop's tier
+    int     apItemTier(const ApShopSlot& slot) const;
+    void    logApShopPrices(QTextStream& log) const;
+
+    static constexpr quint32 PROGRESSION_PRICE_CAP[3] = { 500, 2000, 5000 };  // per shopTier()
     QVector<ApShopSlot> m_apShops;
     QSet<quint16>       m_reservedTokens;   // composite item tokens (slot type 0)
     QSet<quint16>       m_reservedMateria;  // materia tokens        (slot type 1)
 
+    // ── location slots (non-AP) ─────────────────────────────────────────
+    // One extra slot per item-type shop stocked from the field pickup pool
+    // (Item Pool tab weights), so shops hold pickup-quality items that their
+    // category pools never would. Ids with no real price get a tiered one.
+    void    placeLocationSlots(QVector<ExeShopRecord>& shops, QTextStream& log);
+    quint16 drawPoolItem();
+    // Per tier: shopTier() for location slots, apItemTier() for AP slots
+    static constexpr quint32 LOCATION_SLOT_PRICE[3] = { 300, 1500, 4000 };
+
     // ── randomization logic (price-tiered pools) ────────────────────────
     void    randomizeShop(int shopId, ExeShopRecord& shop, QTextStream& log);
     quint16 randomFromCategory(ExeShopType shopType, int tier) const;
@@ -105,8 +125,26 @@ private:
     bool readPrices(const QString& exePath, QTextStream& log);
     void buildTieredPools(QTextStream& log);
     int  shopTier(int shopId) const;            // 0 = early, 1 = mid, 2 = late
+    // Lowest tier an id may be stocked at, whatever its price: strong gear and
+    // materia that vanilla prices cheaply (or not at all) stay out of early shops.
+    static int progressionFloor(int category, quint16 id);
     quint16 pickTiered(int category, int tier) const;
 
+    // Economy balancing: scale each pooled item's price by its tier's
+    // EconomyBalancer::priceFactor(). Written to the hext as price-table lines.
+    void balancePrices(QTextStream& log);
+    QVector<QPair<qint64, quint32>> m_priceOverrides;  // price VA -> new price
+
+    // Price randomization: every id stocked after the stock pass gets its
+    // (balanced) price scaled by a roll within the configured variance,
+    // rounded to 10 gil and kept at PRICE_FLOOR or more. Consumables are also
+    // capped by the tier of the earliest shop selling them, unless vanilla
+    // already charged more.
+    void randomizePrices(const QVector<ExeShopRecord>& shops, QTextStream& log);
+    void setPriceOverride(qint64 priceVa, quint32 price);
+    static constexpr quint32 PRICE_FLOOR = 20;
+    static constexpr quint32 CONSUMABLE_PRICE_CAP[3] = { 300, 1500, 5000 };  // per shopTier()
+
     // ── composite item-ID ranges (non-materia) ──────────────────────────
     static const quint16 ITEM_COUNT       = 105;  // 0x00 – 0x68
     static const quint16 WEAPON_START     = 128;   // 0x80
@@ -136,4 +174,13 @@ private:
 
     // ── shop names for debug log ────────────────────────────────────────
     static QString shopName(int shopId);
+
+    // ── spoiler (shop_spoiler.txt / .json) ──────────────────────────────
+    // Final stock and prices of every shop, after AP slots and price changes.
+    bool    writeSpoiler(const QString& outputPath, const QVector<ExeShopRecord>& shops,
+                         QTextStream& log) const;
+    QString stockName(const ExeShopSlot& slot) const;
+    quint32 finalPrice(const ExeShopSlot& slot) const;
+    bool    isApToken(const ExeShopSlot& slot) const;
+    QSet<int> m_locationSlots;   // shopId * SLOT_COUNT + slot
 };
@@ -3,7 +3,11 @@
 #include "TextEncoder.h"
 #include "Randomizer.h"
 #include "Config.h"
+#include "GameFileLocator.h"
+#include "InitSaveData.h"
+#include "KernelBinValidator.h"
 #include <QFile>
+#include <QSaveFile>
 #include <QDir>
 #include <QDebug>
 #include <QByteArray>
@@ -91,6 +95,20 @@ static void closeLog() {
     }
 }
 
+// Equip masks (bit n = character n in savemap order) of every record in a
+// kernel armor (36-byte records, mask at 0x12) or accessory (16-byte records,
+// mask at 0x0C) table
+static QVector<quint16> readEquipMasks(const QByteArray& table, int recordSize, int maskOffset)
+{
+    QVector<quint16> masks;
+    for (int pos = 0; pos + recordSize <= table.size(); pos += recordSize) {
+        quint16 mask;
+        memcpy(&mask, table.constData() + pos + maskOffset, 2);
+        masks.append(mask);
+    }
+    return masks;
+}
+
 // kernel.bin format: 27 GZIP-compressed sections
 // Each section has 6-byte header: 2 bytes compressed size, 2 bytes decompressed size, 2 bytes file type
 // Section 4 (index 3) contains initialization/starting equipment data
@@ -189,6 +207,8 @@ bool StartingEquipmentRandomizer::randomize()
     if (sections.size() < 4) {
         log("ERROR: Not enough sections in kernel.bin (need at least 4, found "
             + QString::number(sections.size()) + ")");
+        m_parent->reportInputError(Randomizer::InputError::KernelParse, "kernel.bin",
+                                   QString("only %1 sections, need at least 4").arg(sections.size()));
         closeLog();
         return false;
     }
@@ -200,22 +220,52 @@ bool StartingEquipmentRandomizer::randomize()
     QByteArray initData = GZIP::decompress(sec3Gzip, sec3.decSize);
     if (initData.isEmpty()) {
         log("ERROR: Failed to decompress section 3");
+        m_parent->reportInputError(Randomizer::InputError::KernelParse, "kernel.bin",
+                                   "section 3 (initial data) does not decompress");
         closeLog();
         return false;
     }
     log("Section 3 decompressed: " + QString::number(initData.size()) + " bytes");
 
+    // --- armor / accessory equip masks (sections 6 and 7) ---------------------
+    // Without them any armor or accessory may be handed to anyone; log it
+    m_armorEquipMasks.clear();
+    m_accessoryEquipMasks.clear();
+    if (sections.size() > 7) {
+        auto sectionData = [&](int index) {
+            const KSection& sec = sections[index];
+            return GZIP::decompress(raw.mid(sec.offset + SECTION_HEADER_SIZE, sec.compSize), sec.decSize);
+        };
+        m_armorEquipMasks = readEquipMasks(sectionData(6), 36, 0x12);
+        m_accessoryEquipMasks = readEquipMasks(sectionData(7), 16, 0x0C);
+    }
+    log(QString("Equip masks: %1 armor, %2 accessories")
+        .arg(m_armorEquipMasks.size()).arg(m_accessoryEquipMasks.size()));
+    if (m_armorEquipMasks.isEmpty() || m_accessoryEquipMasks.isEmpty())
+        log("WARNING: armor/accessory tables unreadable, equipment is not checked against equip masks");
+
     // --- randomize character equipment ---------------------------------------
     randomizeStartingEquipment(initData);
 
     // --- recompress section 3 ------------------------------------------------
-    QByteArray sec3Recompressed = GZIP::compress(initData);
+    QByteArray sec3Recompressed = GZIP::compress(initData, m_parent->m_config.getGzipLevel());
     if (sec3Recompressed.isEmpty()) {
         log("ERROR: Failed to recompress section 3");
         closeLog();
         return false;
     }
     log("Section 3 recompressed: " + QString::number(sec3Recompressed.size()) + " bytes");
+    // The header stores the size as a u16; a larger section can't be written
+    if (sec3Recompressed.size() > KernelBinValidator::MAX_KERNEL_SECTION_SIZE) {
+        const QString message = QString("kernel.bin: %1 recompresses to %2 bytes, the limit is %3")
+                                    .arg(KernelBinValidator::kernelSectionName(3))
+                                    .arg(sec3Recompressed.size())
+                                    .arg(KernelBinValidator::MAX_KERNEL_SECTION_SIZE);
+        log("ERROR: " + message);
+        m_parent->reportPlanError(message);
+        closeLog();
+        return false;
+    }
 
     // --- rebuild kernel.bin --------------------------------------------------
     // Update the 6-byte header for section 3 with the new compressed size,
@@ -243,15 +293,62 @@ bool StartingEquipmentRandomizer::randomize()
     if (lastEnd < raw.size())
         rebuilt.append(raw.mid(lastEnd));
 
+    // --- check sizes and layout against what the game loads ------------------
+    const QStringList layoutErrors = KernelBinValidator::checkRebuiltKernel(raw, rebuilt, sections.size());
+    if (!layoutErrors.isEmpty()) {
+        for (const QString& error : layoutErrors)
+            log("ERROR: " + error);
+        m_parent->reportPlanError("kernel.bin: " + layoutErrors.join("; "));
+        closeLog();
+        return false;
+    }
+
+    // --- verify every section round-trips ------------------------------------
+    // Re-walk the rebuilt file and decompress each section: section 3 must give
+    // back exactly the init data written, the others exactly the vanilla bytes.
+    // A mismatch means the rebuild or the compressor is broken, so nothing is
+    // written.
+    m_kernelRoundtrip.clear();
+    bool roundtripOk = true;
+    int rpos = 0;
+    for (int i = 0; i < sections.size(); ++i) {
+        bool exact = false;
+        if (rpos + SECTION_HEADER_SIZE <= rebuilt.size()) {
+            quint16 compSize, decSize;
+            memcpy(&compSize, rebuilt.constData() + rpos, 2);
+            memcpy(&decSize,  rebuilt.constData() + rpos + 2, 2);
+            const QByteArray expected = i == 3 ? initData
+                : GZIP::decompress(raw.mid(sections[i].offset + SECTION_HEADER_SIZE, sections[i].compSize),
+                                   sections[i].decSize);
+            const QByteArray actual = GZIP::decompress(rebuilt.mid(rpos + SECTION_HEADER_SIZE, compSize), decSize);
+            exact = !actual.isEmpty() && actual == expected;
+            rpos += SECTION_HEADER_SIZE + compSize;
+        }
+        m_kernelRoundtrip[i] = exact;
+        if (!exact) {
+            log("ERROR: section " + QString::number(i) + " does not round-trip");
+            roundtripOk = false;
+        }
+    }
+    if (!roundtripOk) {
+        closeLog();
+        return false;
+    }
+    log("All " + QString::number(sections.size()) + " sections round-trip exactly");
+
     // --- write back ----------------------------------------------------------
-    QFile out(outKernel);
+    QSaveFile out(outKernel);
     if (!out.open(QIODevice::WriteOnly)) {
         log("ERROR: Cannot open kernel.bin for writing");
         closeLog();
         return false;
     }
     out.write(rebuilt);
-    out.close();
+    if (!out.commit()) {
+        log("ERROR: Cannot finish writing kernel.bin");
+        closeLog();
+        return false;
+    }
 
     log("SUCCESS: kernel.bin written (" + QString::number(rebuilt.size()) + " bytes)");
     closeLog();
@@ -260,19 +357,8 @@ bool StartingEquipmentRandomizer::randomize()
 
 QString StartingEquipmentRandomizer::findKernelBin() const
 {
-    QString ff7Path = m_parent->getFF7Path();
-    QStringList candidates = {
-        ff7Path + "/data/lang-en/kernel/kernel.bin",   // Steam English
-        ff7Path + "/data/lang-fr/kernel/kernel.bin",   // Steam French
-        ff7Path + "/data/lang-de/kernel/kernel.bin",   // Steam German
-        ff7Path + "/data/lang-es/kernel/kernel.bin",   // Steam Spanish
-        ff7Path + "/data/kernel.bin",                   // 1998 PC
-        ff7Path + "/kernel.bin",                        // fallback
-    };
-    for (const QString& p : candidates) {
-        if (QFile::exists(p)) return p;
-    }
-    return QString();
+    // Case-insensitive lookup (KERNEL.BIN on Proton/Linux installs)
+    return GameFileLocator::findKernelBin(m_parent->getFF7Path(), m_parent->getOutputPath());
 }
 
 bool StartingEquipmentRandomizer::randomizeAll()
@@ -307,16 +393,14 @@ bool StartingEquipmentRandomizer::loadInitialData(const QString& filePath, QByte
 
 bool StartingEquipmentRandomizer::saveInitialData(const QString& filePath, const QByteArray& data)
 {
-    QFile file(filePath);
+    QSaveFile file(filePath);
     if (!file.open(QIODevice::WriteOnly)) {
         qDebug() << "Error: Could not open kernel.bin for writing:" << filePath;
         return false;
     }
     
     qint64 bytesWritten = file.write(data);
-    file.close();
-    
-    if (bytesWritten != data.size()) {
+    if (bytesWritten != data.size() || !file.commit()) {
         qDebug() << "Error: Could not write all data to kernel.bin";
         return false;
     }
@@ -326,17 +410,11 @@ bool StartingEquipmentRandomizer::saveInitialData(const QString& filePath, const
 
 void StartingEquipmentRandomizer::randomizeStartingEquipment(QByteArray& data)
 {
-    // Section 4 contains initialization data copied to savemap on New Game
-    // Character records are 132 bytes each, starting at the beginning of section 4
+    // Kernel section 3 is the initialization data copied to the savemap on
+    // New Game; InitSaveData maps the character records within it
     // Characters: Cloud(0), Barret(1), Tifa(2), Aerith(3), Red(4), Yuffie(5), CaitSith(6), Vincent(7), Cid(8)
+    InitSaveData init(data);
     
-    const int CHAR_RECORD_SIZE = 132;
-    const int WEAPON_OFFSET = 0x1C;    // 28
-    const int ARMOR_OFFSET = 0x1D;     // 29
-    const int ACCESSORY_OFFSET = 0x1E; // 30
-    const int MATERIA_OFFSET = 0x40;   // 64 — 16 slots × 4 bytes (id + 3 bytes AP)
-    const int MATERIA_SLOT_SIZE = 4;
-    const int TOTAL_MATERIA_SLOTS = 16;
     const int MAX_WEAPON_MATERIA = 3;    // cap to avoid exceeding actual weapon slots
     const int MAX_ARMOR_MATERIA  = 2;    // cap to avoid exceeding actual armor slots
 
@@ -406,12 +484,26 @@ void StartingEquipmentRandomizer::randomizeStartingEquipment(QByteArray& data)
     };
     
     for (int charId : charactersToRandomize) {
-        int charOffset = charId * CHAR_RECORD_SIZE;
-        
-        if (charOffset + CHAR_RECORD_SIZE > data.size()) {
+        if (init.weapon(charId) < 0) {
             qDebug() << "Character" << charId << "offset out of bounds, skipping";
             continue;
         }
+        // Young Cloud and Sephiroth borrow Cait Sith's and Vincent's slots for
+        // the Kalm flashback, where their vanilla equipment is expected; another
+        // character's weapon (or any armor swap) breaks the flashback battles
+        if (init.isFlashbackRecord(charId)) {
+            log(QString("Character slot %1: %2 record (id %3), kept vanilla")
+                .arg(charId)
+                .arg(init.characterId(charId) == InitSaveData::YOUNG_CLOUD_ID ? "Young Cloud" : "Sephiroth")
+                .arg(init.characterId(charId)));
+            continue;
+        }
+        // The record is left byte-for-byte vanilla, materia included
+        if (!m_parent->m_config.isStartingCharacterRandomized(charId)) {
+            log(QString("Character %1 (%2): kept vanilla loadout")
+                .arg(charId).arg(FF7Char::defaultName(charId)));
+            continue;
+        }
         
         // Valid weapon range for this character. The FF7CHAR.weapon byte stores the
         // WEAPON NUMBER (= weapon item id - 0x80). The per-character ranges are NOT
@@ -449,24 +541,40 @@ void StartingEquipmentRandomizer::randomizeStartingEquipment(QByteArray& data)
         // Randomize weapon (pick from character's valid weapons)
         std::uniform_int_distribution<int> weaponDist(0, numWeapons - 1);
         quint8 newWeapon = static_cast<quint8>(weaponStart + weaponDist(m_rng));
-        data[charOffset + WEAPON_OFFSET] = static_cast<char>(newWeapon);
+        init.setWeapon(charId, newWeapon);
         
+        // Armor and accessories the character may equip (kernel equip masks);
+        // all 32 when the tables couldn't be read
+        auto equippable = [charId](const QVector<quint16>& masks) {
+            QVector<int> ids;
+            for (int id = 0; id < 32; ++id) {
+                if (masks.isEmpty() || (id < masks.size() && (masks[id] & (1u << charId))))
+                    ids.append(id);
+            }
+            return ids;
+        };
+        const QVector<int> armorIds = equippable(m_armorEquipMasks);
+        const QVector<int> accessoryIds = equippable(m_accessoryEquipMasks);
+
         // Randomize armor (0-31 for armor IDs, game adds 256 internally)
-        std::uniform_int_distribution<int> armorDist(0, 31);
-        quint8 newArmor = static_cast<quint8>(armorDist(m_rng));
-        data[charOffset + ARMOR_OFFSET] = static_cast<char>(newArmor);
+        quint8 newArmor = static_cast<quint8>(init.armor(charId));   // vanilla if nothing fits
+        if (!armorIds.isEmpty()) {
+            std::uniform_int_distribution<int> armorDist(0, armorIds.size() - 1);
+            newArmor = static_cast<quint8>(armorIds[armorDist(m_rng)]);
+        }
+        init.setArmor(charId, newArmor);
         
         // Randomize accessory (0-31 for accessory IDs, or 255 for none)
         // 20% chance of no accessory
         std::uniform_real_distribution<double> chanceDist(0.0, 1.0);
         quint8 newAccessory;
-        if (chanceDist(m_rng) < 0.2) {
-            newAccessory = 255; // No accessory
+        if (accessoryIds.isEmpty() || chanceDist(m_rng) < 0.2) {
+            newAccessory = InitSaveData::NO_ACCESSORY;
         } else {
-            std::uniform_int_distribution<int> accessoryDist(0, 31);
-            newAccessory = static_cast<quint8>(accessoryDist(m_rng));
+            std::uniform_int_distribution<int> accessoryDist(0, accessoryIds.size() - 1);
+            newAccessory = static_cast<quint8>(accessoryIds[accessoryDist(m_rng)]);
         }
-        data[charOffset + ACCESSORY_OFFSET] = static_cast<char>(newAccessory);
+        init.setAccessory(charId, newAccessory);
         
         // Randomize materia slots (record slots 0-7 = weapon, 8-15 = armor).
         // CRITICAL: only fill slots that the equipped item ACTUALLY has, else the
@@ -488,10 +596,7 @@ void StartingEquipmentRandomizer::randomizeStartingEquipment(QByteArray& data)
         QStringList materiaLog;
         int weaponMateriaCount = 0;
         int armorMateriaCount  = 0;
-        for (int slot = 0; slot < TOTAL_MATERIA_SLOTS; ++slot) {
-            int slotOffset = charOffset + MATERIA_OFFSET + (slot * MATERIA_SLOT_SIZE);
-            if (slotOffset + MATERIA_SLOT_SIZE > data.size()) break;
-
+        for (int slot = 0; slot < InitSaveData::CHARACTER_MATERIA_SLOTS; ++slot) {
             bool isWeaponSlot = (slot < 8);
             int  physIdx   = isWeaponSlot ? slot : (slot - 8);  // slot index on the item
             int  slotCount = isWeaponSlot ? weaponSlots : armorSlots;
@@ -506,16 +611,10 @@ void StartingEquipmentRandomizer::randomizeStartingEquipment(QByteArray& data)
                 quint8 matId = selectableMateria[materiaDist(m_rng)];
                 if (isWeaponSlot) ++weaponMateriaCount;
                 else ++armorMateriaCount;
-                data[slotOffset]     = static_cast<char>(matId);
-                data[slotOffset + 1] = 0; // AP byte 0
-                data[slotOffset + 2] = 0; // AP byte 1
-                data[slotOffset + 3] = 0; // AP byte 2
+                init.setCharacterMateria(charId, slot, matId, 0);
                 materiaLog.append(QString("%1:%2").arg(slot).arg(matId, 0, 16));
             } else {
-                data[slotOffset]     = static_cast<char>(0xFF); // empty / no slot
-                data[slotOffset + 1] = static_cast<char>(0xFF);
-                data[slotOffset + 2] = static_cast<char>(0xFF);
-                data[slotOffset + 3] = static_cast<char>(0xFF);
+                init.clearCharacterMateria(charId, slot); // empty / no slot
             }
         }
 
@@ -524,7 +623,7 @@ void StartingEquipmentRandomizer::randomizeStartingEquipment(QByteArray& data)
             .arg(FF7Char::defaultName(charId))
             .arg(newWeapon)
             .arg(newArmor)
-            .arg(newAccessory == 255 ? "None" : QString::number(newAccessory))
+            .arg(newAccessory == InitSaveData::NO_ACCESSORY ? "None" : QString::number(newAccessory))
             .arg(materiaLog.isEmpty() ? "none" : materiaLog.join(", ")));
     }
 }
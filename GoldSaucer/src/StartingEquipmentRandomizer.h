@@ -17,6 +17,11 @@ public:
     
     bool randomize();
     
+    // Per-section result of the last randomize()'s verification: section
+    // index -> whether the rebuilt section decompressed to exactly the bytes
+    // intended for it. Empty until a kernel.bin has been rebuilt.
+    QMap<int, bool> kernelRoundtrip() const { return m_kernelRoundtrip; }
+    
 private:
     Randomizer* m_parent;
     std::mt19937& m_rng;
@@ -60,6 +65,13 @@ private:
     QMap<int, quint16> m_randomizedAccessories;
     QMap<int, QVector<quint16>> m_randomizedMateria;
     
+    QMap<int, bool> m_kernelRoundtrip;
+    
+    // Per armor / accessory number: kernel equip mask (bit n = character n);
+    // empty if the kernel tables couldn't be read
+    QVector<quint16> m_armorEquipMasks;
+    QVector<quint16> m_accessoryEquipMasks;
+    
     void initializeEquipmentPools();
     
     enum Character {
@@ -2,6 +2,391 @@
 #include <QDir>
 #include <QDebug>
 #include "GUI/SimpleMainWindow.h"
+#include "GUI/SettingsParityCheck.h"
+#include "EnemyRandomizer.h"
+#include "GameFileLocator.h"
+#include "Config.h"
+#include "SeedCard.h"
+#include "SeedSummary.h"
+#include "SeedGenerator.h"
+#include "SaveBootstrap.h"
+#include "FieldPickupRandomizer_ff7tk.h"
+#include "LogicOverrides.h"
+#include <QMessageBox>
+#include <QDateTime>
+#include <QTextStream>
+#include <QFile>
+#include <QSaveFile>
+#include <QFileInfo>
+#include <QJsonArray>
+
+// `--inspect-enemies <FF7 folder | scene.bin> [--format csv|json] [--output file]`
+// Dumps every scene.bin enemy (stats, drops, steals, morph) without randomizing.
+static int runInspectEnemies(const QStringList& args)
+{
+    QTextStream err(stderr);
+    auto valueOf = [&](const QString& flag, const QString& fallback) {
+        int i = args.indexOf(flag);
+        return (i >= 0 && i + 1 < args.size()) ? args[i + 1] : fallback;
+    };
+
+    QString target = valueOf("--inspect-enemies", QString());
+    if (target.isEmpty() || target.startsWith("--")) {
+        err << "Usage: --inspect-enemies <FF7 folder | scene.bin> [--format csv|json] [--output file]\n";
+        return 2;
+    }
+    QString format = valueOf("--format", "csv");
+    QString outputPath = valueOf("--output", QString());
+
+    QString scenePath = target;
+    if (QFileInfo(target).isDir()) {
+        scenePath = GameFileLocator::findSceneBin(target);
+        if (scenePath.isEmpty())
+            scenePath = GameFileLocator::findSceneBin(QDir(target).filePath("ff7/workingdir"));
+        if (scenePath.isEmpty()) {
+            err << "scene.bin not found under " << target << "\n";
+            return 1;
+        }
+    }
+
+    QFile outFile;
+    QTextStream out(stdout);
+    if (!outputPath.isEmpty()) {
+        outFile.setFileName(outputPath);
+        if (!outFile.open(QIODevice::WriteOnly | QIODevice::Text)) {
+            err << "Cannot write " << outputPath << "\n";
+            return 1;
+        }
+        out.setDevice(&outFile);
+    }
+
+    QString error;
+    if (!EnemyRandomizer::exportEnemyTable(scenePath, format, out, &error)) {
+        err << error << "\n";
+        return 1;
+    }
+    if (!outputPath.isEmpty())
+        err << "Wrote " << outputPath << " from " << scenePath << "\n";
+    return 0;
+}
+
+// `--seed-card <config.json | GS1 string | seed> [--output file]`
+// Renders the spoiler-free seed card PNG for an existing configuration.
+static int runSeedCard(const QStringList& args)
+{
+    QTextStream err(stderr);
+    auto valueOf = [&](const QString& flag, const QString& fallback) {
+        int i = args.indexOf(flag);
+        return (i >= 0 && i + 1 < args.size()) ? args[i + 1] : fallback;
+    };
+
+    QString source = valueOf("--seed-card", QString());
+    if (source.isEmpty() || source.startsWith("--")) {
+        err << "Usage: --seed-card <config.json | GS1 string | seed> [--output file]\n";
+        return 2;
+    }
+    QString outputPath = valueOf("--output", "seed_card.png");
+
+    Config config;
+    QString error;
+    if (QFileInfo(source).isFile()) {
+        if (!config.loadFromFile(source)) {
+            err << "Cannot read config " << source << "\n";
+            return 1;
+        }
+    } else if (!config.loadFromShareString(source, &error)) {
+        err << error << "\n";
+        return 1;
+    }
+
+    if (!SeedCard::save(config, outputPath, &error)) {
+        err << error << "\n";
+        return 1;
+    }
+    err << "Wrote " << outputPath << " (hash " << SeedCard::settingsHash(config) << ")\n";
+    return 0;
+}
+
+// `--summary <seed_report.json | output folder> [--output file]`
+// Prints the markdown race-thread block for a finished generation.
+static int runSummary(const QStringList& args)
+{
+    QTextStream err(stderr);
+    auto valueOf = [&](const QString& flag, const QString& fallback) {
+        int i = args.indexOf(flag);
+        return (i >= 0 && i + 1 < args.size()) ? args[i + 1] : fallback;
+    };
+
+    QString source = valueOf("--summary", QString());
+    if (source.isEmpty() || source.startsWith("--")) {
+        err << "Usage: --summary <seed_report.json | output folder> [--output file]\n";
+        return 2;
+    }
+    QString outputPath = valueOf("--output", QString());
+
+    QJsonObject report;
+    QString error;
+    if (!SeedSummary::readReport(source, report, &error)) {
+        err << error << "\n";
+        return 1;
+    }
+
+    QFile outFile;
+    QTextStream out(stdout);
+    if (!outputPath.isEmpty()) {
+        outFile.setFileName(outputPath);
+        if (!outFile.open(QIODevice::WriteOnly | QIODevice::Text)) {
+            err << "Cannot write " << outputPath << "\n";
+            return 1;
+        }
+        out.setDevice(&outFile);
+    }
+    out << SeedSummary::markdown(report);
+    return 0;
+}
+
+// `--reproduce <seed_report.json | output folder> [--ff7 folder] [--output folder]
+//              [--debug pickupsLog,stitmIndex,keyBitScan,sceneDump,scriptDumps,patchLog|all|none]
+//              [--validate-logic]`
+// Re-runs a generation headlessly from a seed report's settings string, in the
+// same order as the GUI, and warns when the input files differ from the report.
+// --debug picks the debug artifacts to write (default: the pickup log only).
+// --validate-logic fails the run when the key item plan isn't beatable, for
+// reports saved with validateLogic off.
+static int runReproduce(const QStringList& args)
+{
+    QTextStream err(stderr);
+    auto valueOf = [&](const QString& flag, const QString& fallback) {
+        int i = args.indexOf(flag);
+        return (i >= 0 && i + 1 < args.size()) ? args[i + 1] : fallback;
+    };
+
+    QString source = valueOf("--reproduce", QString());
+    if (source.isEmpty() || source.startsWith("--")) {
+        err << "Usage: --reproduce <seed_report.json | output folder> [--ff7 folder] [--output folder]"
+               " [--debug <outputs>] [--validate-logic]\n";
+        return 2;
+    }
+
+    QJsonObject report;
+    QString error;
+    if (!SeedSummary::readReport(source, report, &error)) {
+        err << error << "\n";
+        return 1;
+    }
+    Config config;
+    if (!config.loadFromShareString(report["settingsString"].toString(), &error)) {
+        err << error << "\n";
+        return 1;
+    }
+    const QString ff7Path = valueOf("--ff7", GameFileLocator::detectFF7Install());
+    if (ff7Path.isEmpty()) {
+        err << "FF7 install not found - pass --ff7 <folder>\n";
+        return 1;
+    }
+    const QString outputPath = valueOf("--output", QString());
+    if (!outputPath.isEmpty())
+        config.setOutputFolder(outputPath);
+    if (args.contains("--debug")
+        && !config.setDebugOutputsFromList(valueOf("--debug", QString()), &error)) {
+        err << error << "\n";
+        return 2;
+    }
+    if (args.contains("--validate-logic"))
+        config.setValidateLogic(true);
+
+    err << "Reproducing hash " << report["hash"].toString() << " (built "
+        << report["version"].toString() << " " << report["gitHash"].toString("unknown")
+        << ", running " << QCoreApplication::applicationVersion() << " "
+        << SeedSummary::gitHash() << ")\n";
+    for (const QString& mismatch : SeedSummary::inputMismatches(report, ff7Path))
+        err << "WARNING: input " << mismatch << " - output may not match the report\n";
+    if (config.isFeatureEnabled(Config::FieldPickupRandomization) && config.getKeyItemRandomization()) {
+        LogicOverrides& overrides = LogicOverrides::instance();
+        overrides.reload(ff7Path);
+        if (report["logicOverrides"].toArray() != overrides.toJson())
+            err << "WARNING: logic overrides (" << LogicOverrides::tomlPath(ff7Path) << ", "
+                << LogicOverrides::overridePath()
+                << ") differ from the report's - key items may not match\n";
+    }
+
+    SeedGenerator generator(ff7Path);
+    QString writtenPath;
+    if (!generator.generate(config, &writtenPath, &error, &err)) {
+        err << "ERROR: " << error << "\n";
+        return 1;
+    }
+
+    // Passes rerolled after the original generation, replayed in order
+    const QJsonArray rerolls = report["rerolls"].toArray();
+    if (!rerolls.isEmpty()) {
+        config.setOutputFolder(writtenPath);
+        for (const QJsonValue& value : rerolls) {
+            const QJsonObject reroll = value.toObject();
+            if (!generator.reroll(config, reroll["subsystem"].toString(),
+                                  static_cast<quint32>(reroll["salt"].toInteger()), &error, &err)) {
+                err << "ERROR: " << error << "\n";
+                return 1;
+            }
+        }
+        const QString reportPath = QDir(writtenPath).filePath(SeedSummary::REPORT_FILE_NAME);
+        QJsonObject written;
+        if (!SeedSummary::readReport(reportPath, written, &error)) {
+            err << "ERROR: " << error << "\n";
+            return 1;
+        }
+        written["rerolls"] = rerolls;
+        if (!SeedSummary::writeReport(written, reportPath, &error)) {
+            err << "ERROR: " << error << "\n";
+            return 1;
+        }
+    }
+    err << "Wrote " << writtenPath << "\n";
+    return 0;
+}
+
+// `--reroll <output folder | seed_report.json> --only shops|pickups|equipment
+//           [--ff7 folder] [--salt n]`
+// Re-rolls one pass of a finished generation in place; every other output
+// file is left byte-identical. The salt (default: one more than that pass's
+// earlier rerolls) is added to "rerolls" in seed_report.json so --reproduce
+// replays it.
+static int runReroll(const QStringList& args)
+{
+    QTextStream err(stderr);
+    auto valueOf = [&](const QString& flag, const QString& fallback) {
+        int i = args.indexOf(flag);
+        return (i >= 0 && i + 1 < args.size()) ? args[i + 1] : fallback;
+    };
+
+    QString folder = valueOf("--reroll", QString());
+    const QString only = valueOf("--only", QString());
+    if (folder.isEmpty() || folder.startsWith("--") || only.isEmpty() || only.startsWith("--")) {
+        err << "Usage: --reroll <output folder | seed_report.json> --only "
+            << SeedGenerator::rerollSubsystems().join("|") << " [--ff7 folder] [--salt n]\n";
+        return 2;
+    }
+
+    QJsonObject report;
+    QString error;
+    if (!SeedSummary::readReport(folder, report, &error)) {
+        err << error << "\n";
+        return 1;
+    }
+    if (QFileInfo(folder).isFile())
+        folder = QFileInfo(folder).absolutePath();
+    Config config;
+    if (!config.loadFromShareString(report["settingsString"].toString(), &error)) {
+        err << error << "\n";
+        return 1;
+    }
+    config.setOutputFolder(folder);
+    const QString ff7Path = valueOf("--ff7", GameFileLocator::detectFF7Install());
+    if (ff7Path.isEmpty()) {
+        err << "FF7 install not found - pass --ff7 <folder>\n";
+        return 1;
+    }
+
+    QJsonArray rerolls = report["rerolls"].toArray();
+    quint32 salt = 1;
+    for (const QJsonValue& value : rerolls) {
+        const QJsonObject reroll = value.toObject();
+        if (reroll["subsystem"].toString() == only)
+            salt = qMax(salt, static_cast<quint32>(reroll["salt"].toInteger()) + 1);
+    }
+    if (args.contains("--salt")) {
+        bool ok = false;
+        salt = valueOf("--salt", QString()).toUInt(&ok);
+        if (!ok || salt == 0) {
+            err << "--salt takes a number above 0\n";
+            return 2;
+        }
+    }
+    for (const QString& mismatch : SeedSummary::inputMismatches(report, ff7Path))
+        err << "WARNING: input " << mismatch << " - the reroll may not match the rest of the seed\n";
+
+    SeedGenerator generator(ff7Path);
+    if (!generator.reroll(config, only, salt, &error, &err)) {
+        err << "ERROR: " << error << "\n";
+        return 1;
+    }
+
+    QJsonObject entry;
+    entry["subsystem"] = only;
+    entry["salt"] = static_cast<qint64>(salt);
+    rerolls.append(entry);
+    report["rerolls"] = rerolls;
+    if (!SeedSummary::writeReport(report, QDir(folder).filePath(SeedSummary::REPORT_FILE_NAME), &error)) {
+        err << "ERROR: " << error << "\n";
+        return 1;
+    }
+    err << "Rerolled " << only << " in " << folder << " (salt " << salt << ")\n";
+    return 0;
+}
+
+// `--make-save <save file> --output file [--slot 1-15] [--key-items names|all]
+//              [--flags bank:addr:bit,...] [--moment n]`
+// Developer tool: copies a PC save with key items / story flags set so a
+// tester can load straight into a suspect location of a seed.
+static int runMakeSave(const QStringList& args)
+{
+    QTextStream err(stderr);
+    auto valueOf = [&](const QString& flag, const QString& fallback) {
+        int i = args.indexOf(flag);
+        return (i >= 0 && i + 1 < args.size()) ? args[i + 1] : fallback;
+    };
+
+    const QString source = valueOf("--make-save", QString());
+    const QString outputPath = valueOf("--output", QString());
+    if (source.isEmpty() || source.startsWith("--") || outputPath.isEmpty()) {
+        err << "Usage: --make-save <save file> --output file [--slot 1-15] [--key-items names|all]"
+               " [--flags bank:addr:bit,...] [--moment n]\n";
+        return 2;
+    }
+
+    QString error;
+    QVector<QPair<int, int>> keyItems;
+    QVector<SaveBootstrap::Flag> flags;
+    if (!SaveBootstrap::parseKeyItems(valueOf("--key-items", QString()), keyItems, &error)
+        || !SaveBootstrap::parseFlags(valueOf("--flags", QString()), flags, &error)) {
+        err << error << "\n";
+        return 2;
+    }
+    bool slotOk = false, momentOk = true;
+    const int slot = valueOf("--slot", "1").toInt(&slotOk) - 1;
+    const int moment = args.contains("--moment") ? valueOf("--moment", QString()).toInt(&momentOk) : -1;
+    if (!slotOk || !momentOk) {
+        err << "--slot and --moment take a number\n";
+        return 2;
+    }
+
+    QFile in(source);
+    if (!in.open(QIODevice::ReadOnly)) {
+        err << "Cannot read " << source << "\n";
+        return 1;
+    }
+    QByteArray save = in.readAll();
+    in.close();
+    if (!SaveBootstrap::apply(save, slot, keyItems, flags, moment, &error)) {
+        err << error << "\n";
+        return 1;
+    }
+
+    // Writing into the game's save folder is the point, but Steam may put
+    // the cloud copy back over it
+    if (GameFileLocator::isSteamCloudPath(QFileInfo(outputPath).path()))
+        err << "WARNING: " << QFileInfo(outputPath).path() << " is synced by Steam Cloud; launch the game "
+               "with cloud sync off for FF7, or Steam may replace this save with the cloud copy\n";
+    QSaveFile out(outputPath);
+    if (!out.open(QIODevice::WriteOnly) || out.write(save) != save.size() || !out.commit()) {
+        err << "Cannot write " << outputPath << "\n";
+        return 1;
+    }
+    err << "Wrote " << outputPath << " (slot " << slot + 1 << ": " << keyItems.size()
+        << " key item bit(s), " << flags.size() << " flag(s)"
+        << (moment >= 0 ? QString(", game moment %1").arg(moment) : QString()) << ")\n";
+    return 0;
+}
 
 int main(int argc, char *argv[])
 {
@@ -32,6 +417,53 @@ int main(int argc, char *argv[])
     
     app.setPalette(darkPalette);
     
+    // Built-in field patch bytes are checked before anything can use them
+    const QStringList helperProblems = FieldPickupRandomizer_ff7tk::validateHelperScripts();
+    if (!helperProblems.isEmpty()) {
+        QTextStream err(stderr);
+        for (const QString& problem : helperProblems)
+            err << problem << "\n";
+        if (app.arguments().size() == 1)
+            QMessageBox::critical(nullptr, "Gold Saucer", "Built-in field patches are malformed:\n\n" + helperProblems.join("\n"));
+        return 1;
+    }
+    
+    // Headless settings parity check (GUI <-> config schema), no window shown
+    if (app.arguments().contains("--check-settings")) {
+        QTextStream out(stdout);
+        return runSettingsParityCheck(out);
+    }
+    
+    // Headless enemy dump for balance reports, no window shown
+    if (app.arguments().contains("--inspect-enemies")) {
+        return runInspectEnemies(app.arguments());
+    }
+    
+    // Headless seed card render, no window shown
+    if (app.arguments().contains("--seed-card")) {
+        return runSeedCard(app.arguments());
+    }
+    
+    // Headless race-thread summary from a saved seed report, no window shown
+    if (app.arguments().contains("--summary")) {
+        return runSummary(app.arguments());
+    }
+    
+    // Headless re-run of a bug report's seed, no window shown
+    if (app.arguments().contains("--reproduce")) {
+        return runReproduce(app.arguments());
+    }
+    
+    // Headless reroll of one pass of a finished seed, no window shown
+    if (app.arguments().contains("--reroll")) {
+        return runReroll(app.arguments());
+    }
+    
+    // Headless save file for testers, no window shown
+    if (app.arguments().contains("--make-save")) {
+        return runMakeSave(app.arguments());
+    }
+    
     // Create and show main window
     SimpleMainWindow window;
     window.show();